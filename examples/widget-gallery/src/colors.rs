@@ -0,0 +1,37 @@
+use floem::{peniko::color::palette::css, prelude::*};
+
+use crate::form::{form, form_item};
+
+pub fn colors_view() -> impl IntoView {
+    let color = RwSignal::new(css::ROYAL_BLUE);
+    let recent = RwSignal::new(Vec::new());
+
+    form((
+        form_item(
+            "Color Picker:",
+            color_picker(color)
+                .swatches([
+                    css::TOMATO,
+                    css::ORANGE,
+                    css::GOLD,
+                    css::SEA_GREEN,
+                    css::TEAL,
+                    css::ROYAL_BLUE,
+                    css::REBECCA_PURPLE,
+                    css::DIM_GRAY,
+                ])
+                .recent_colors(recent),
+        ),
+        form_item(
+            "Opaque, in OKLCH:",
+            color_picker(color)
+                .format(ColorFormat::Oklch)
+                .show_alpha(false)
+                .recent_colors(recent),
+        ),
+        form_item(
+            "Preview:",
+            empty().style(move |s| s.size(100, 40).border_radius(8).background(color.get())),
+        ),
+    ))
+}
@@ -0,0 +1,54 @@
+use floem::prelude::*;
+
+use crate::form::{form, form_item};
+
+pub fn dates_view() -> impl IntoView {
+    let today = Date::today();
+    let date = RwSignal::new(Some(today));
+    let range = RwSignal::new(None);
+    let due = RwSignal::new(None);
+    let time = RwSignal::new(Time::new(9, 30, 0).unwrap());
+
+    form((
+        form_item(
+            "Calendar:",
+            v_stack((
+                calendar(date),
+                label(move || match date.get() {
+                    Some(date) => format!("Selected: {date}"),
+                    None => "Nothing selected".to_string(),
+                }),
+            ))
+            .style(|s| s.gap(5)),
+        ),
+        form_item(
+            "Range Calendar:",
+            v_stack((
+                range_calendar(range)
+                    .first_day_of_week(Weekday::Sunday)
+                    .disabled_dates(|date| {
+                        matches!(date.weekday(), Weekday::Saturday | Weekday::Sunday)
+                    }),
+                label(move || match range.get() {
+                    Some(DateRange { start, end }) => format!("{start} to {end}"),
+                    None => "Pick two weekdays".to_string(),
+                }),
+            ))
+            .style(|s| s.gap(5)),
+        ),
+        form_item(
+            "Date Picker:",
+            date_picker(due)
+                .disabled_dates(move |date| date < today)
+                .style(|s| s.width(200.)),
+        ),
+        form_item(
+            "Time Picker:",
+            v_stack((
+                time_picker(time),
+                time_picker(time).twelve_hour(true).show_seconds(true),
+            ))
+            .style(|s| s.gap(5)),
+        ),
+    ))
+}
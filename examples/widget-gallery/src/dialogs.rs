@@ -0,0 +1,61 @@
+use floem::prelude::*;
+
+use crate::form::{form, form_item};
+
+pub fn dialogs_view() -> impl IntoView {
+    let result = RwSignal::new(String::from("No dialog shown yet"));
+    let open_dialog: RwSignal<Option<DialogHandle>> = RwSignal::new(None);
+
+    let settings = move |sheet: bool| {
+        let name = RwSignal::new(String::new());
+        let close = move || {
+            if let Some(handle) = open_dialog.get_untracked() {
+                handle.close();
+            }
+        };
+        let handle = Dialog::new(
+            v_stack((
+                text("Rename").class(DialogTitleClass),
+                text_input(name).placeholder("New name"),
+                h_stack((
+                    button("Cancel").action(close),
+                    button("Save").action(move || {
+                        result.set(format!("Renamed to {:?}", name.get_untracked()));
+                        close();
+                    }),
+                ))
+                .class(DialogActionsClass),
+            ))
+            .style(|s| s.gap(12)),
+        )
+        .sheet(sheet)
+        .close_on_backdrop_click(!sheet)
+        .show();
+        open_dialog.set(Some(handle));
+    };
+
+    form((
+        form_item(
+            "Dialog:",
+            button("Rename...").action(move || settings(false)),
+        ),
+        form_item("Sheet:", button("Rename...").action(move || settings(true))),
+        form_item(
+            "Alert:",
+            button("Show Alert").action(move || {
+                alert("Saved", "Your changes have been saved.", move || {
+                    result.set("Alert dismissed".to_string())
+                });
+            }),
+        ),
+        form_item(
+            "Confirm:",
+            button("Delete...").action(move || {
+                confirm("Delete file?", "This can't be undone.", move |confirmed| {
+                    result.set(if confirmed { "Deleted" } else { "Kept" }.to_string())
+                });
+            }),
+        ),
+        form_item("Result:", label(move || result.get())),
+    ))
+}
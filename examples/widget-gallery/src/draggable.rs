@@ -58,6 +58,7 @@ fn sortable_item(
             }
             floem::event::EventPropagation::Continue
         })
+        .draggable_with(move || item_id)
         .dragging_style(|s| {
             s.box_shadow_blur(3)
                 .box_shadow_color(Color::from_rgb8(100, 100, 100))
@@ -81,11 +82,29 @@ pub fn draggable_view() -> impl IntoView {
     let sortable_items = RwSignal::new((0..items.len()).collect::<Vec<usize>>());
     let dragger_id = RwSignal::new(0);
 
-    dyn_stack(
+    let list = dyn_stack(
         move || sortable_items.get(),
         move |item_id| *item_id,
         move |item_id| sortable_item(items[item_id], sortable_items, dragger_id, item_id),
     )
-    .style(|s| s.flex_col().row_gap(5).padding(10))
-    .into_view()
+    .style(|s| s.flex_col().row_gap(5));
+
+    // Receives the item id carried by the drag, and only accepts even items
+    let trash = "Drop even items here to remove them"
+        .on_drop_if(
+            |item_id: &usize, _| item_id % 2 == 0,
+            move |item_id: &usize, _| {
+                sortable_items.update(|items| items.retain(|id| id != item_id))
+            },
+        )
+        .style(|s| {
+            s.padding(10)
+                .border(2)
+                .border_color(palette::css::GRAY)
+                .drag_over(|s| s.border_color(palette::css::GREEN))
+        });
+
+    (list, trash)
+        .style(|s| s.flex_col().row_gap(10).padding(10))
+        .into_view()
 }
@@ -12,6 +12,8 @@ use crate::form::{form, form_item};
 
 pub fn text_input_view() -> impl IntoView {
     let text = RwSignal::new(String::new());
+    let comment = RwSignal::new(String::new());
+    let opacity = RwSignal::new(100.0);
 
     const LIGHT_GRAY_224: Color = Color::from_rgb8(224, 224, 224);
     const MEDIUM_GRAY_189: Color = Color::from_rgb8(189, 189, 189);
@@ -59,5 +61,21 @@ pub fn text_input_view() -> impl IntoView {
                 .placeholder("Disabled input")
                 .style(|s| s.set_disabled(true)),
         ),
+        form_item(
+            "Text Area:",
+            text_area(comment)
+                .placeholder("Leave a comment")
+                .max_length(500)
+                .max_rows(6)
+                .style(|s| s.width(250.)),
+        ),
+        form_item(
+            "Number Input:",
+            number_input(opacity)
+                .range(0.0..=100.0)
+                .step(0.5)
+                .label("Opacity")
+                .style(|s| s.width(250.)),
+        ),
     ))
 }
@@ -3,7 +3,10 @@ pub mod buttons;
 pub mod canvas;
 pub mod checkbox;
 pub mod clipboard;
+pub mod colors;
 pub mod context_menu;
+pub mod dates;
+pub mod dialogs;
 pub mod draggable;
 pub mod dropdown;
 pub mod dropped_file;
@@ -15,8 +18,11 @@ pub mod lists;
 pub mod radio_buttons;
 pub mod rich_text;
 pub mod slider;
+pub mod table;
 pub mod tabs;
 pub mod texteditor;
+pub mod toasts;
+pub mod tree;
 
 use floem::{
     action::{add_overlay, set_theme, set_window_menu, toggle_global_theme, toggle_window_theme},
@@ -39,12 +45,18 @@ fn app_view(window_id: WindowId) -> impl IntoView {
         "Input",
         "Text Editor",
         "Lists",
+        "Table",
+        "Tree",
         "Image",
         "Dropdown",
         "Checkbox",
         "Radio",
         "Tabs",
         "Slider",
+        "Date & Time",
+        "Color",
+        "Dialog",
+        "Toast",
         "Canvas",
         "Menu",
         "Rich Text",
@@ -64,12 +76,18 @@ fn app_view(window_id: WindowId) -> impl IntoView {
             "Input" => inputs::text_input_view().into_any(),
             "Canvas" => canvas::canvas_view().into_any(),
             "Lists" => lists::list_view().into_any(),
+            "Table" => table::table_view().into_any(),
+            "Tree" => tree::tree_view_example().into_any(),
             "Tabs" => tabs::tab_view().into_any(),
             "Menu" => context_menu::menu_view().into_any(),
             "Rich Text" => rich_text::rich_text_view().into_any(),
             "Image" => images::img_view().into_any(),
             "Clipboard" => clipboard::clipboard_view().into_any(),
             "Slider" => slider::slider_view().into_any(),
+            "Date & Time" => dates::dates_view().into_any(),
+            "Color" => colors::colors_view().into_any(),
+            "Dialog" => dialogs::dialogs_view().into_any(),
+            "Toast" => toasts::toasts_view().into_any(),
             "Dropdown" => dropdown::dropdown_view().into_any(),
             "Animation" => animation::animation_view().into_any(),
             "Draggable" => draggable::draggable_view().into_any(),
@@ -0,0 +1,51 @@
+use floem::{imbl, prelude::*};
+
+#[derive(Clone)]
+struct Order {
+    id: usize,
+    customer: String,
+    quantity: usize,
+    price: f64,
+}
+
+pub fn table_view() -> impl IntoView {
+    let orders: imbl::Vector<Order> = (0..1_000_000)
+        .map(|id| Order {
+            id,
+            customer: format!("Customer {}", id * 7919 % 1000),
+            quantity: id * 31 % 50 + 1,
+            price: (id * 17 % 10_000) as f64 / 100.,
+        })
+        .collect();
+
+    let table = table(
+        move || orders.clone(),
+        [
+            TableColumn::new("#", |o: &Order| o.id)
+                .width(80.)
+                .sort_by_key(|o: &Order| o.id),
+            TableColumn::new("Customer", |o: &Order| o.customer.clone())
+                .min_width(120.)
+                .sort_by_key(|o: &Order| o.customer.clone()),
+            TableColumn::new("Quantity", |o: &Order| o.quantity)
+                .width(100.)
+                .sort_by_key(|o: &Order| o.quantity),
+            TableColumn::new("Price", |o: &Order| format!("{:.2}", o.price))
+                .width(100.)
+                .sort_by(|a: &Order, b: &Order| a.price.total_cmp(&b.price)),
+            TableColumn::new("Total", |o: &Order| {
+                format!("{:.2}", o.price * o.quantity as f64)
+            })
+            .width(120.),
+        ],
+    )
+    .frozen_columns(1)
+    .selection_mode(TableSelectionMode::Multiple);
+    let selection = table.selection();
+
+    (
+        label(move || format!("{} orders selected", selection.with(|s| s.len()))),
+        table.scroll().style(|s| s.width_full().height(400.)),
+    )
+        .style(|s| s.flex_col().gap(10).padding(10).width_full())
+}
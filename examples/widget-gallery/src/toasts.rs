@@ -0,0 +1,76 @@
+use floem::prelude::*;
+
+use crate::form::{form, form_item};
+
+pub fn toasts_view() -> impl IntoView {
+    let count = RwSignal::new(0);
+    let corner = RwSignal::new(ToastCorner::BottomRight);
+
+    let kind_button = |name: &'static str, kind: ToastKind| {
+        button(name).action(move || {
+            count.update(|count| *count += 1);
+            notify(
+                Toast::new(format!("Notification #{}", count.get_untracked()))
+                    .title(name)
+                    .kind(kind),
+            );
+        })
+    };
+
+    form((
+        form_item(
+            "Kinds:",
+            h_stack((
+                kind_button("Info", ToastKind::Info),
+                kind_button("Success", ToastKind::Success),
+                kind_button("Warning", ToastKind::Warning),
+                kind_button("Danger", ToastKind::Danger),
+            ))
+            .style(|s| s.gap(8)),
+        ),
+        form_item(
+            "With Action:",
+            button("Delete").action(|| {
+                notify(
+                    Toast::new("File deleted")
+                        .kind(ToastKind::Success)
+                        .timeout(8.seconds())
+                        .action("Undo", || {
+                            notify(Toast::new("File restored"));
+                        }),
+                );
+            }),
+        ),
+        form_item(
+            "Persistent:",
+            button("Show").action(|| {
+                notify(
+                    Toast::new("Connection lost")
+                        .kind(ToastKind::Danger)
+                        .persistent(),
+                );
+            }),
+        ),
+        form_item(
+            "Many:",
+            button("Show 10").action(|| {
+                for i in 1..=10 {
+                    notify(Toast::new(format!("Queued notification {i}")));
+                }
+            }),
+        ),
+        form_item(
+            "Corner:",
+            button(label(move || format!("{:?}", corner.get()))).action(move || {
+                let next = match corner.get_untracked() {
+                    ToastCorner::TopLeft => ToastCorner::TopRight,
+                    ToastCorner::TopRight => ToastCorner::BottomRight,
+                    ToastCorner::BottomRight => ToastCorner::BottomLeft,
+                    ToastCorner::BottomLeft => ToastCorner::TopLeft,
+                };
+                corner.set(next);
+                set_toast_corner(next);
+            }),
+        ),
+    ))
+}
@@ -0,0 +1,41 @@
+use floem::prelude::*;
+
+#[derive(Clone)]
+struct Node {
+    path: String,
+    depth: usize,
+}
+
+pub fn tree_view_example() -> impl IntoView {
+    let tree = tree_view(
+        || Node {
+            path: "root".to_string(),
+            depth: 0,
+        },
+        |node: &Node| {
+            if node.depth == 4 {
+                return TreeChildren::Leaf;
+            }
+            // Children are only generated once a node is expanded
+            let node = node.clone();
+            TreeChildren::lazy(move || {
+                (0..20)
+                    .map(|i| Node {
+                        path: format!("{}/{i}", node.path),
+                        depth: node.depth + 1,
+                    })
+                    .collect()
+            })
+        },
+        |node| node.path.clone(),
+        |node| node.path.rsplit('/').next().unwrap_or_default().to_string(),
+    )
+    .multi_select(true);
+    let selection = tree.selection();
+
+    (
+        label(move || format!("{} nodes selected", selection.with(|s| s.len()))),
+        tree.scroll().style(|s| s.width(300.).height(400.)),
+    )
+        .style(|s| s.flex_col().gap(10).padding(10))
+}
@@ -1,10 +1,13 @@
 use std::any::{Any, TypeId};
 
-use crate::runtime::RUNTIME;
+use crate::{runtime::RUNTIME, scope::Scope};
 
 /// Try to retrieve a stored Context value in the reactive system.
 /// You can store a Context value anywhere, and retrieve it from anywhere afterwards.
 ///
+/// Values provided with [`Scope::provide_context`] by the current Scope or one of its
+/// ancestors take precedence over the ones stored with [`provide_context`].
+///
 /// # Example
 /// In a parent component:
 /// ```rust
@@ -23,6 +26,10 @@ pub fn use_context<T>() -> Option<T>
 where
     T: Clone + 'static,
 {
+    if let Some(value) = Scope::current().use_context::<T>() {
+        return Some(value);
+    }
+
     let ty = TypeId::of::<T>();
     RUNTIME.with(|runtime| {
         let contexts = runtime.contexts.borrow();
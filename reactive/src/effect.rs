@@ -125,24 +125,37 @@ pub fn untrack<T>(f: impl FnOnce() -> T) -> T {
 }
 
 pub fn batch<T>(f: impl FnOnce() -> T) -> T {
+    /// Ends the batch when dropped, so a panic in `f` doesn't leave the runtime batching.
+    struct EndBatch;
+
+    impl Drop for EndBatch {
+        fn drop(&mut self) {
+            RUNTIME.with(|runtime| {
+                runtime.batching.set(false);
+                // While unwinding, the pending effects are left to run after the next batch
+                if !std::thread::panicking() {
+                    runtime.run_pending_effects();
+                }
+            });
+        }
+    }
+
     let already_batching = RUNTIME.with(|runtime| {
         let batching = runtime.batching.get();
         if !batching {
             runtime.batching.set(true);
+            runtime.batch_id.set(runtime.batch_id.get().wrapping_add(1));
         }
 
         batching
     });
 
-    let result = f();
-    if !already_batching {
-        RUNTIME.with(|runtime| {
-            runtime.batching.set(false);
-            runtime.run_pending_effects();
-        });
-    }
-
-    result
+    let _end = if already_batching {
+        None
+    } else {
+        Some(EndBatch)
+    };
+    f()
 }
 
 pub(crate) fn run_initial_effect(effect: Rc<dyn EffectTrait>) {
@@ -163,7 +176,7 @@ pub(crate) fn run_initial_effect(effect: Rc<dyn EffectTrait>) {
 
 pub(crate) fn run_effect(effect: Rc<dyn EffectTrait>) {
     let effect_id = effect.id();
-    effect_id.dispose();
+    effect_id.clean_up();
 
     observer_clean_up(&effect);
 
@@ -306,7 +319,7 @@ impl SignalTracker {
     /// Updates the tracking function used for [SignalTracker].
     pub fn track<T: 'static>(&self, f: impl FnOnce() -> T) -> T {
         // Clear any previous tracking by disposing the old effect
-        self.id.dispose();
+        self.id.clean_up();
 
         let prev_effect = RUNTIME.with(|runtime| runtime.current_effect.borrow_mut().take());
 
@@ -30,18 +30,42 @@ impl Id {
             let mut children = runtime.children.borrow_mut();
             let children = children.entry(*scope).or_default();
             children.insert(*self);
+            runtime.parents.borrow_mut().insert(*self, *scope);
         });
     }
 
+    /// The Scope this Id was created in, if any
+    pub(crate) fn parent(&self) -> Option<Id> {
+        RUNTIME.with(|runtime| runtime.parents.borrow().get(self).copied())
+    }
+
     /// Dispose the relevant resources that's linking to this Id, and the all the children
-    /// and grandchildren.
+    /// and grandchildren, and remove this Id from its parent Scope.
     pub(crate) fn dispose(&self) {
-        if let Ok((children, signal)) = RUNTIME.try_with(|runtime| {
+        let _ = RUNTIME.try_with(|runtime| {
+            if let Some(parent) = runtime.parents.borrow_mut().remove(self) {
+                if let Some(siblings) = runtime.children.borrow_mut().get_mut(&parent) {
+                    siblings.remove(self);
+                }
+            }
+        });
+        self.clean_up();
+    }
+
+    /// Dispose the relevant resources that's linking to this Id, and the all the children
+    /// and grandchildren, but keep this Id in its parent Scope, as an effect does every
+    /// time it runs again.
+    pub(crate) fn clean_up(&self) {
+        if let Ok((children, signal, contexts)) = RUNTIME.try_with(|runtime| {
             (
                 runtime.children.borrow_mut().remove(self),
                 runtime.signals.borrow_mut().remove(self),
+                runtime.scope_contexts.borrow_mut().remove(self),
             )
         }) {
+            // Contexts are dropped outside of the runtime borrow, as they may own signals
+            drop(contexts);
+
             if let Some(children) = children {
                 for child in children {
                     child.dispose();
@@ -15,7 +15,11 @@ mod read;
 mod runtime;
 mod scope;
 mod signal;
+mod sync_signal;
+#[cfg(feature = "testing")]
+pub mod testing;
 mod trigger;
+mod undo;
 mod write;
 
 pub use base::{create_base_signal, BaseSignal};
@@ -30,5 +34,7 @@ pub use memo::{create_memo, Memo};
 pub use read::{ReadSignalValue, SignalGet, SignalRead, SignalTrack, SignalWith};
 pub use scope::{as_child_of_current_scope, with_scope, Scope};
 pub use signal::{create_rw_signal, create_signal, ReadSignal, RwSignal, WriteSignal};
+pub use sync_signal::{create_sync_signal, flush_sync_signals, set_sync_signal_waker, SyncSignal};
 pub use trigger::{create_trigger, Trigger};
+pub use undo::{create_history, create_undoable_signal, History, UndoableSignal};
 pub use write::{SignalUpdate, SignalWrite, WriteSignalValue};
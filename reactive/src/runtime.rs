@@ -17,15 +17,20 @@ thread_local! {
     pub(crate) static RUNTIME: Runtime = Runtime::new();
 }
 
+pub(crate) type Contexts = HashMap<TypeId, Box<dyn Any>>;
+
 /// The internal reactive Runtime which stores all the reactive system states in a
 /// thread local
 pub(crate) struct Runtime {
     pub(crate) current_effect: RefCell<Option<Rc<dyn EffectTrait>>>,
     pub(crate) current_scope: RefCell<Id>,
     pub(crate) children: RefCell<HashMap<Id, HashSet<Id>>>,
+    pub(crate) parents: RefCell<HashMap<Id, Id>>,
     pub(crate) signals: RefCell<HashMap<Id, Signal>>,
-    pub(crate) contexts: RefCell<HashMap<TypeId, Box<dyn Any>>>,
+    pub(crate) contexts: RefCell<Contexts>,
+    pub(crate) scope_contexts: RefCell<HashMap<Id, Contexts>>,
     pub(crate) batching: Cell<bool>,
+    pub(crate) batch_id: Cell<u64>,
     pub(crate) pending_effects: RefCell<SmallVec<[Rc<dyn EffectTrait>; 10]>>,
 }
 
@@ -41,9 +46,12 @@ impl Runtime {
             current_effect: RefCell::new(None),
             current_scope: RefCell::new(Id::next()),
             children: RefCell::new(HashMap::new()),
+            parents: RefCell::new(HashMap::new()),
             signals: Default::default(),
             contexts: Default::default(),
+            scope_contexts: Default::default(),
             batching: Cell::new(false),
+            batch_id: Cell::new(0),
             pending_effects: RefCell::new(SmallVec::new()),
         }
     }
@@ -59,6 +67,11 @@ impl Runtime {
         }
     }
 
+    /// The id of the outermost `batch` that is currently running, if any
+    pub(crate) fn current_batch(&self) -> Option<u64> {
+        self.batching.get().then(|| self.batch_id.get())
+    }
+
     pub(crate) fn run_pending_effects(&self) {
         let pending_effects = self.pending_effects.take();
         for effect in pending_effects {
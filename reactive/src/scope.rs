@@ -1,4 +1,11 @@
-use std::{any::Any, cell::RefCell, collections::HashMap, fmt, marker::PhantomData, rc::Rc};
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::HashMap,
+    fmt,
+    marker::PhantomData,
+    rc::Rc,
+};
 
 use crate::{
     create_effect, create_updater,
@@ -52,10 +59,64 @@ impl Scope {
             let mut children = runtime.children.borrow_mut();
             let children = children.entry(self.0).or_default();
             children.insert(child);
+            runtime.parents.borrow_mut().insert(child, self.0);
         });
         Scope(child, PhantomData)
     }
 
+    /// The Scope this Scope was created as a child of, if any
+    pub fn parent(&self) -> Option<Scope> {
+        self.0.parent().map(|id| Scope(id, PhantomData))
+    }
+
+    /// Store a Context value that is only visible from this Scope and its descendants.
+    ///
+    /// [`use_context`](crate::use_context) looks up the closest Scope providing a value of
+    /// the requested type, starting from the current one, before falling back to the values
+    /// stored with [`provide_context`](crate::provide_context). The value is dropped when
+    /// this Scope is disposed.
+    pub fn provide_context<T>(&self, value: T)
+    where
+        T: Clone + 'static,
+    {
+        let ty = TypeId::of::<T>();
+        let prev = RUNTIME.with(|runtime| {
+            runtime
+                .scope_contexts
+                .borrow_mut()
+                .entry(self.0)
+                .or_default()
+                .insert(ty, Box::new(value) as Box<dyn Any>)
+        });
+        drop(prev);
+    }
+
+    /// Retrieve the Context value of type `T` provided by this Scope or its closest
+    /// ancestor, without falling back to the global contexts.
+    pub fn use_context<T>(&self) -> Option<T>
+    where
+        T: Clone + 'static,
+    {
+        let ty = TypeId::of::<T>();
+        let mut id = Some(self.0);
+        while let Some(current) = id {
+            let value = RUNTIME.with(|runtime| {
+                runtime
+                    .scope_contexts
+                    .borrow()
+                    .get(&current)
+                    .and_then(|contexts| contexts.get(&ty))
+                    .and_then(|val| val.downcast_ref::<T>())
+                    .cloned()
+            });
+            if value.is_some() {
+                return value;
+            }
+            id = current.parent();
+        }
+        None
+    }
+
     /// Create a new Signal under this Scope
     pub fn create_signal<T>(self, value: T) -> (ReadSignal<T>, WriteSignal<T>)
     where
@@ -0,0 +1,298 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{channel, Receiver, Sender},
+        Arc, Mutex, RwLock,
+    },
+    thread::{self, ThreadId},
+};
+
+use crate::{
+    effect::batch, id::Id, read::SignalTrack, signal::create_rw_signal, SignalGet, SignalUpdate,
+    SignalWith,
+};
+
+type Waker = Arc<dyn Fn() + Send + Sync>;
+
+static WAKER: RwLock<Option<Waker>> = RwLock::new(None);
+
+static PENDING: Mutex<Vec<PendingNotify>> = Mutex::new(Vec::new());
+
+/// A [`SyncSignal`] that was written from another thread and still has to notify
+/// its subscribers on the thread that owns it.
+struct PendingNotify {
+    thread: ThreadId,
+    trigger: Id,
+    dirty: Arc<AtomicBool>,
+}
+
+/// Sets the function that is called to wake up the thread owning a [`SyncSignal`] after
+/// the signal was written from another thread.
+///
+/// The woken thread is expected to call [`flush_sync_signals`]. Wakeups are coalesced: the
+/// waker is only called when there were no pending notifications for that thread yet.
+pub fn set_sync_signal_waker(waker: impl Fn() + Send + Sync + 'static) {
+    *WAKER.write().unwrap() = Some(Arc::new(waker));
+}
+
+/// Notifies the subscribers of every [`SyncSignal`] owned by the current thread that was
+/// written from another thread since the last flush.
+///
+/// All the notifications are batched, so an effect depending on several of them runs once.
+pub fn flush_sync_signals() {
+    let current = thread::current().id();
+    let pending: Vec<PendingNotify> = {
+        let mut pending = PENDING.lock().unwrap();
+        let (mine, others) = pending.drain(..).partition(|p| p.thread == current);
+        *pending = others;
+        mine
+    };
+    if pending.is_empty() {
+        return;
+    }
+
+    batch(|| {
+        for p in pending {
+            // Clear the flag first, so a write that happens while effects run queues again
+            p.dirty.store(false, Ordering::Release);
+            if let Some(signal) = p.trigger.signal() {
+                signal.run_effects();
+            }
+        }
+    });
+}
+
+struct SyncSignalInner<T> {
+    value: RwLock<T>,
+    /// The thread the signal was created on, which is the only one with a reactive runtime
+    /// that knows about `trigger`
+    thread: ThreadId,
+    trigger: Id,
+    dirty: Arc<AtomicBool>,
+    senders: Mutex<Vec<Sender<T>>>,
+}
+
+/// A signal that can be shared with and written from any thread.
+///
+/// On the thread that created it, a `SyncSignal` behaves like a regular [`RwSignal`](crate::RwSignal):
+/// it implements [`SignalGet`], [`SignalWith`], [`SignalTrack`] and [`SignalUpdate`], and can be
+/// used in effects and memos.
+///
+/// Writes from other threads update the value immediately, but the effects on the owning
+/// thread only run once that thread calls [`flush_sync_signals`]. Floem does that on its own
+/// when the event loop is woken up through the waker set with [`set_sync_signal_waker`].
+///
+/// Worker threads can observe changes with [`SyncSignal::subscribe`].
+pub struct SyncSignal<T> {
+    inner: Arc<SyncSignalInner<T>>,
+}
+
+impl<T> Clone for SyncSignal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> PartialEq for SyncSignal<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<T> Eq for SyncSignal<T> {}
+
+impl<T: Send + Sync + 'static> SyncSignal<T> {
+    /// Create a new `SyncSignal` owned by the current thread, in the current Scope
+    pub fn new(value: T) -> Self {
+        create_sync_signal(value)
+    }
+}
+
+impl<T> SyncSignal<T> {
+    fn is_owner_thread(&self) -> bool {
+        thread::current().id() == self.inner.thread
+    }
+
+    /// Run the effects subscribed to this signal, or queue them to run on the owning
+    /// thread if this is called from another thread.
+    pub fn notify(&self) {
+        if self.is_owner_thread() {
+            if let Some(signal) = self.inner.trigger.signal() {
+                signal.run_effects();
+            }
+            return;
+        }
+
+        if self.inner.dirty.swap(true, Ordering::AcqRel) {
+            // Already queued and not yet flushed
+            return;
+        }
+
+        let should_wake = {
+            let mut pending = PENDING.lock().unwrap();
+            let should_wake = !pending.iter().any(|p| p.thread == self.inner.thread);
+            pending.push(PendingNotify {
+                thread: self.inner.thread,
+                trigger: self.inner.trigger,
+                dirty: self.inner.dirty.clone(),
+            });
+            should_wake
+        };
+        if should_wake {
+            let waker = WAKER.read().unwrap().clone();
+            if let Some(waker) = waker {
+                waker();
+            }
+        }
+    }
+}
+
+impl<T: Clone> SyncSignal<T> {
+    /// Returns a channel that receives the new value every time the signal is written,
+    /// from any thread. The subscription ends when the receiver is dropped.
+    pub fn subscribe(&self) -> Receiver<T> {
+        let (tx, rx) = channel();
+        self.inner.senders.lock().unwrap().push(tx);
+        rx
+    }
+
+    fn write<O>(&self, f: impl FnOnce(&mut T) -> O) -> O {
+        let (result, value) = {
+            let mut value = self.inner.value.write().unwrap();
+            let result = f(&mut value);
+            (result, value.clone())
+        };
+
+        let mut senders = self.inner.senders.lock().unwrap();
+        if !senders.is_empty() {
+            senders.retain(|tx| tx.send(value.clone()).is_ok());
+        }
+        drop(senders);
+
+        self.notify();
+        result
+    }
+}
+
+impl<T: Clone> SignalGet<T> for SyncSignal<T> {
+    fn id(&self) -> Id {
+        self.inner.trigger
+    }
+
+    fn try_get(&self) -> Option<T>
+    where
+        T: 'static,
+    {
+        self.try_track();
+        self.try_get_untracked()
+    }
+
+    fn try_get_untracked(&self) -> Option<T>
+    where
+        T: 'static,
+    {
+        Some(self.inner.value.read().unwrap().clone())
+    }
+}
+
+impl<T> SignalWith<T> for SyncSignal<T> {
+    fn id(&self) -> Id {
+        self.inner.trigger
+    }
+
+    fn with<O>(&self, f: impl FnOnce(&T) -> O) -> O
+    where
+        T: 'static,
+    {
+        self.try_track();
+        self.with_untracked(f)
+    }
+
+    fn with_untracked<O>(&self, f: impl FnOnce(&T) -> O) -> O
+    where
+        T: 'static,
+    {
+        f(&self.inner.value.read().unwrap())
+    }
+
+    fn try_with<O>(&self, f: impl FnOnce(Option<&T>) -> O) -> O
+    where
+        T: 'static,
+    {
+        self.with(|v| f(Some(v)))
+    }
+
+    fn try_with_untracked<O>(&self, f: impl FnOnce(Option<&T>) -> O) -> O
+    where
+        T: 'static,
+    {
+        self.with_untracked(|v| f(Some(v)))
+    }
+}
+
+impl<T> SignalTrack<T> for SyncSignal<T> {
+    fn id(&self) -> Id {
+        self.inner.trigger
+    }
+
+    /// Subscribes the current running effect to this signal. Effects only exist on the
+    /// owning thread, so this does nothing anywhere else.
+    fn track(&self) {
+        self.try_track();
+    }
+
+    fn try_track(&self) {
+        if !self.is_owner_thread() {
+            return;
+        }
+        if let Some(signal) = self.inner.trigger.signal() {
+            signal.subscribe();
+        }
+    }
+}
+
+impl<T: Clone> SignalUpdate<T> for SyncSignal<T> {
+    fn id(&self) -> Id {
+        self.inner.trigger
+    }
+
+    fn set(&self, new_value: T)
+    where
+        T: 'static,
+    {
+        self.write(|v| *v = new_value);
+    }
+
+    fn update(&self, f: impl FnOnce(&mut T))
+    where
+        T: 'static,
+    {
+        self.write(f);
+    }
+
+    fn try_update<O>(&self, f: impl FnOnce(&mut T) -> O) -> Option<O>
+    where
+        T: 'static,
+    {
+        Some(self.write(f))
+    }
+}
+
+/// Creates a new [`SyncSignal`] owned by the current thread, in the current Scope.
+///
+/// Disposing the Scope stops effects from being notified, but the value itself lives
+/// as long as any clone of the signal.
+pub fn create_sync_signal<T: Send + Sync + 'static>(value: T) -> SyncSignal<T> {
+    let trigger = create_rw_signal(()).id;
+    SyncSignal {
+        inner: Arc::new(SyncSignalInner {
+            value: RwLock::new(value),
+            thread: thread::current().id(),
+            trigger,
+            dirty: Arc::new(AtomicBool::new(false)),
+            senders: Mutex::new(Vec::new()),
+        }),
+    }
+}
@@ -0,0 +1,428 @@
+//! Utilities to unit test code built on signals, without a window or an event loop.
+//!
+//! Enabled with the `testing` feature.
+//!
+//! # Example
+//! ```rust
+//! # use std::time::Duration;
+//! # use floem_reactive::{testing::*, RwSignal, SignalGet, SignalUpdate};
+//! let rt = TestRuntime::new();
+//! let count = RwSignal::new(0);
+//! let changes = record(count);
+//!
+//! rt.clock().set_timeout(Duration::from_millis(100), move || count.set(1));
+//! rt.advance(Duration::from_millis(99));
+//! assert!(changes.is_empty());
+//! rt.advance(Duration::from_millis(1));
+//! assert_eq!(changes.values(), vec![1]);
+//! ```
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashSet,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    create_effect, flush_sync_signals, id::Id, runtime::RUNTIME, scope::with_scope, Scope,
+    SignalGet,
+};
+
+thread_local! {
+    static CLOCK: RefCell<Option<VirtualClock>> = const { RefCell::new(None) };
+}
+
+/// Keys handed out by [`VirtualClock::set_timeout`] start here, so they never collide with
+/// the keys of timers scheduled by the UI code.
+const FIRST_TIMEOUT_KEY: u64 = 1 << 63;
+
+struct VirtualTimer {
+    key: u64,
+    deadline: Duration,
+    action: Box<dyn FnOnce()>,
+}
+
+struct ClockState {
+    start: Instant,
+    elapsed: Cell<Duration>,
+    timers: RefCell<Vec<VirtualTimer>>,
+    next_key: Cell<u64>,
+}
+
+/// A clock that only moves forward when told to, with timers that fire when it does.
+///
+/// While a clock is installed on the current thread, Floem schedules the timers of
+/// `exec_after` and `debounce_action` on it instead of the event loop.
+#[derive(Clone)]
+pub struct VirtualClock {
+    state: Rc<ClockState>,
+}
+
+impl VirtualClock {
+    /// Installs a new clock on the current thread, replacing the previous one if any.
+    pub fn install() -> VirtualClock {
+        let clock = VirtualClock {
+            state: Rc::new(ClockState {
+                start: Instant::now(),
+                elapsed: Cell::new(Duration::ZERO),
+                timers: RefCell::new(Vec::new()),
+                next_key: Cell::new(FIRST_TIMEOUT_KEY),
+            }),
+        };
+        CLOCK.with(|c| *c.borrow_mut() = Some(clock.clone()));
+        clock
+    }
+
+    /// The clock installed on the current thread, if any.
+    pub fn current() -> Option<VirtualClock> {
+        CLOCK.with(|c| c.borrow().clone())
+    }
+
+    /// Removes the clock installed on the current thread. Its pending timers never fire.
+    pub fn uninstall() {
+        let clock = CLOCK.with(|c| c.borrow_mut().take());
+        if let Some(clock) = clock {
+            clock.state.timers.take();
+        }
+    }
+
+    /// The current virtual time.
+    pub fn now(&self) -> Instant {
+        self.state.start + self.state.elapsed.get()
+    }
+
+    /// How much virtual time passed since the clock was installed.
+    pub fn elapsed(&self) -> Duration {
+        self.state.elapsed.get()
+    }
+
+    /// Schedules `action` to run once the clock moved forward by `delay`, returning a key
+    /// that can be passed to [`VirtualClock::cancel`].
+    pub fn set_timeout(&self, delay: Duration, action: impl FnOnce() + 'static) -> u64 {
+        let key = self.state.next_key.get();
+        self.state.next_key.set(key + 1);
+        self.schedule(key, delay, action);
+        key
+    }
+
+    /// Schedules `action` under a key chosen by the caller, such as the raw value of a
+    /// timer token.
+    pub fn schedule(&self, key: u64, delay: Duration, action: impl FnOnce() + 'static) {
+        self.state.timers.borrow_mut().push(VirtualTimer {
+            key,
+            deadline: self.elapsed() + delay,
+            action: Box::new(action),
+        });
+    }
+
+    /// Cancels a timer, returning `false` if it already fired or doesn't exist.
+    pub fn cancel(&self, key: u64) -> bool {
+        let mut timers = self.state.timers.borrow_mut();
+        let len = timers.len();
+        timers.retain(|t| t.key != key);
+        timers.len() != len
+    }
+
+    /// The number of timers that didn't fire yet.
+    pub fn pending_timers(&self) -> usize {
+        self.state.timers.borrow().len()
+    }
+
+    /// The time left until the next timer fires.
+    pub fn next_deadline(&self) -> Option<Duration> {
+        self.state
+            .timers
+            .borrow()
+            .iter()
+            .map(|t| t.deadline)
+            .min()
+            .map(|deadline| deadline.saturating_sub(self.elapsed()))
+    }
+
+    /// Moves the clock forward, firing the timers that are due in deadline order, with the
+    /// clock set to each deadline in turn. Returns the number of timers that fired.
+    ///
+    /// Timers scheduled while advancing fire too if they are due before the end.
+    pub fn advance(&self, by: Duration) -> usize {
+        let end = self.elapsed() + by;
+        let mut fired = 0;
+        while let Some(timer) = self.take_next_due(end) {
+            self.state.elapsed.set(timer.deadline.max(self.elapsed()));
+            (timer.action)();
+            fired += 1;
+        }
+        self.state.elapsed.set(end);
+        fired
+    }
+
+    /// Fires the timers that are due without moving the clock.
+    pub fn run_due(&self) -> usize {
+        self.advance(Duration::ZERO)
+    }
+
+    fn take_next_due(&self, end: Duration) -> Option<VirtualTimer> {
+        let mut timers = self.state.timers.borrow_mut();
+        // The earliest deadline fires first, and timers with the same deadline fire in the
+        // order they were scheduled
+        let (index, _) = timers
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.deadline <= end)
+            .min_by_key(|(i, t)| (t.deadline, *i))?;
+        Some(timers.remove(index))
+    }
+}
+
+/// A headless reactive runtime for tests.
+///
+/// It installs a [`VirtualClock`] on the current thread and owns a root [`Scope`], which are
+/// both removed when it is dropped.
+pub struct TestRuntime {
+    clock: VirtualClock,
+    scope: Scope,
+}
+
+impl Default for TestRuntime {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestRuntime {
+    /// Creates the runtime, and installs its clock on the current thread.
+    pub fn new() -> Self {
+        Self {
+            clock: VirtualClock::install(),
+            scope: Scope::new(),
+        }
+    }
+
+    /// The virtual clock used by the timers.
+    pub fn clock(&self) -> &VirtualClock {
+        &self.clock
+    }
+
+    /// The root Scope of the runtime, disposed when it is dropped.
+    pub fn scope(&self) -> Scope {
+        self.scope
+    }
+
+    /// Runs `f` in the root Scope, so everything it creates is disposed with the runtime.
+    pub fn run<T: 'static>(&self, f: impl FnOnce() -> T) -> T {
+        with_scope(self.scope, f)
+    }
+
+    /// Runs everything that is ready without moving the clock: effects queued by signals
+    /// written from other threads, and the timers that are due. Repeats until nothing is
+    /// left, and returns how many timers fired.
+    pub fn run_pending(&self) -> usize {
+        let mut fired = 0;
+        loop {
+            flush_sync_signals();
+            RUNTIME.with(|runtime| {
+                if !runtime.batching.get() {
+                    runtime.run_pending_effects();
+                }
+            });
+            let due = self.clock.run_due();
+            if due == 0 {
+                return fired;
+            }
+            fired += due;
+        }
+    }
+
+    /// Moves the clock forward, then runs everything that is pending.
+    pub fn advance(&self, by: Duration) -> usize {
+        self.run_pending() + self.clock.advance(by) + self.run_pending()
+    }
+
+    /// Moves the clock forward until no timer is left, and returns how many fired.
+    ///
+    /// # Panics
+    /// If timers keep scheduling new ones after `max_timers` fired.
+    pub fn run_all_timers(&self, max_timers: usize) -> usize {
+        let mut fired = self.run_pending();
+        while let Some(next) = self.clock.next_deadline() {
+            fired += self.advance(next);
+            assert!(
+                fired <= max_timers,
+                "more than {max_timers} timers fired, they are probably rescheduling themselves"
+            );
+        }
+        fired
+    }
+}
+
+impl Drop for TestRuntime {
+    fn drop(&mut self) {
+        self.scope.dispose();
+        let installed = VirtualClock::current()
+            .is_some_and(|clock| Rc::ptr_eq(&clock.state, &self.clock.state));
+        if installed {
+            VirtualClock::uninstall();
+        }
+    }
+}
+
+/// The values a signal was written with, captured by [`record`].
+pub struct SignalRecorder<T> {
+    values: Rc<RefCell<Vec<T>>>,
+}
+
+impl<T> Clone for SignalRecorder<T> {
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+        }
+    }
+}
+
+impl<T: Clone> SignalRecorder<T> {
+    /// Every recorded value, oldest first.
+    pub fn values(&self) -> Vec<T> {
+        self.values.borrow().clone()
+    }
+
+    /// The last recorded value.
+    pub fn last(&self) -> Option<T> {
+        self.values.borrow().last().cloned()
+    }
+
+    /// Returns the recorded values, and forgets them.
+    pub fn take(&self) -> Vec<T> {
+        self.values.take()
+    }
+}
+
+impl<T> SignalRecorder<T> {
+    /// How many values were recorded.
+    pub fn len(&self) -> usize {
+        self.values.borrow().len()
+    }
+
+    /// Returns `true` if the signal wasn't written.
+    pub fn is_empty(&self) -> bool {
+        self.values.borrow().is_empty()
+    }
+}
+
+/// Records the value of `signal` every time it is written from now on, in the current Scope.
+///
+/// The current value isn't recorded. A signal that is written several times in a `batch`
+/// is only recorded once, with its final value.
+pub fn record<T: Clone + 'static>(signal: impl SignalGet<T> + 'static) -> SignalRecorder<T> {
+    let recorder = SignalRecorder {
+        values: Rc::new(RefCell::new(Vec::new())),
+    };
+    let values = recorder.values.clone();
+    create_effect(move |initialized: Option<()>| {
+        let value = signal.get();
+        if initialized.is_some() {
+            values.borrow_mut().push(value);
+        }
+    });
+    recorder
+}
+
+/// Counts how many times an effect ran. See [`create_counted_effect`].
+#[derive(Clone)]
+pub struct RunCounter {
+    runs: Rc<Cell<usize>>,
+}
+
+impl RunCounter {
+    /// How many times the effect ran, including the initial run.
+    pub fn get(&self) -> usize {
+        self.runs.get()
+    }
+
+    /// Returns how many times the effect ran, and starts counting from zero again.
+    pub fn reset(&self) -> usize {
+        self.runs.replace(0)
+    }
+}
+
+/// Creates an effect in the current Scope, and counts how many times it runs.
+pub fn create_counted_effect(f: impl Fn() + 'static) -> RunCounter {
+    let counter = RunCounter {
+        runs: Rc::new(Cell::new(0)),
+    };
+    let runs = counter.runs.clone();
+    create_effect(move |_| {
+        runs.set(runs.get() + 1);
+        f();
+    });
+    counter
+}
+
+/// The number of signals, effects and child Scopes that are still alive under `scope`,
+/// at any depth.
+pub fn live_descendants(scope: Scope) -> usize {
+    RUNTIME.with(|runtime| {
+        let children = runtime.children.borrow();
+        let signals = runtime.signals.borrow();
+        let mut seen = HashSet::new();
+        let mut stack = vec![scope.0];
+        while let Some(id) = stack.pop() {
+            if let Some(c) = children.get(&id) {
+                stack.extend(c.iter().copied().filter(|child| seen.insert(*child)));
+            }
+        }
+        seen.iter()
+            .filter(|id| signals.contains_key(id) || children.contains_key(id))
+            .count()
+    })
+}
+
+/// The number of signals, effects and Scopes that are linked to the Scope they were
+/// created in, on this thread. Disposing them removes the link.
+pub fn scope_links() -> usize {
+    RUNTIME.with(|runtime| runtime.parents.borrow().len())
+}
+
+/// Asserts that a disposed Scope left nothing behind: no signal, effect or child Scope
+/// created under it is still alive, and it holds no context values.
+///
+/// # Panics
+/// With the number of leaked items if the Scope, or one of its descendants, wasn't fully
+/// disposed.
+#[track_caller]
+pub fn assert_scope_disposed(scope: Scope) {
+    let leaked = RUNTIME.with(|runtime| {
+        let parents = runtime.parents.borrow();
+        let signals = runtime.signals.borrow();
+        let children = runtime.children.borrow();
+        let contexts = runtime.scope_contexts.borrow();
+        let is_alive = |id: &Id| {
+            signals.contains_key(id) || children.contains_key(id) || contexts.contains_key(id)
+        };
+        let descends_from_scope = |mut id: Id| {
+            while let Some(parent) = parents.get(&id) {
+                if *parent == scope.0 {
+                    return true;
+                }
+                id = *parent;
+            }
+            false
+        };
+
+        let own = usize::from(is_alive(&scope.0));
+        own + parents
+            .keys()
+            .filter(|id| is_alive(id) && descends_from_scope(**id))
+            .count()
+    });
+    assert!(
+        leaked == 0,
+        "{scope:?} was not fully disposed: {leaked} signals, effects or scopes are still alive"
+    );
+}
+
+/// Disposes `scope`, then checks it with [`assert_scope_disposed`].
+#[track_caller]
+pub fn dispose_and_assert(scope: Scope) {
+    scope.dispose();
+    assert_scope_disposed(scope);
+}
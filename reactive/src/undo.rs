@@ -0,0 +1,499 @@
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    marker::PhantomData,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    effect::batch,
+    id::Id,
+    read::{SignalRead, SignalTrack},
+    runtime::RUNTIME,
+    signal::{create_rw_signal, NotThreadSafe, ReadSignal, RwSignal},
+    SignalGet, SignalUpdate, SignalWith,
+};
+
+/// A single recorded change, which knows how to revert and re-apply itself.
+struct Change {
+    /// The signal this change snapshots, if it is a snapshot change.
+    /// Consecutive snapshots of the same signal within a step are coalesced.
+    target: Option<Id>,
+    undo: Rc<dyn Fn()>,
+    redo: Rc<dyn Fn()>,
+}
+
+/// One undo step, made of all the changes that were merged together.
+struct Step {
+    changes: Vec<Change>,
+    batch: Option<u64>,
+    last_change: Option<Instant>,
+}
+
+impl Step {
+    fn new() -> Self {
+        Self {
+            changes: Vec::new(),
+            batch: None,
+            last_change: None,
+        }
+    }
+
+    fn push(&mut self, change: Change) {
+        if let (Some(target), Some(last)) = (change.target, self.changes.last_mut()) {
+            if last.target == Some(target) {
+                last.redo = change.redo;
+                return;
+            }
+        }
+        self.changes.push(change);
+    }
+}
+
+#[derive(Default)]
+struct HistoryState {
+    undo_stack: VecDeque<Step>,
+    redo_stack: Vec<Step>,
+    limit: Option<usize>,
+    merge_window: Option<Duration>,
+    /// Whether the next change is allowed to merge into the top of the undo stack
+    merge_open: bool,
+    transaction: Option<Step>,
+    transaction_depth: usize,
+    /// Set while undoing or redoing so the writes done by that aren't recorded
+    applying: bool,
+}
+
+impl HistoryState {
+    fn record(&mut self, change: Change) {
+        if self.applying {
+            return;
+        }
+        self.redo_stack.clear();
+
+        if let Some(step) = self.transaction.as_mut() {
+            step.push(change);
+            return;
+        }
+
+        let batch = RUNTIME.with(|runtime| runtime.current_batch());
+        let now = self.merge_window.map(|_| Instant::now());
+
+        if self.merge_open {
+            if let Some(top) = self.undo_stack.back_mut() {
+                let same_batch = batch.is_some() && top.batch == batch;
+                let in_window = match (self.merge_window, top.last_change, now) {
+                    (Some(window), Some(last), Some(now)) => now.duration_since(last) <= window,
+                    _ => false,
+                };
+                if same_batch || in_window {
+                    top.push(change);
+                    top.batch = batch;
+                    top.last_change = now;
+                    return;
+                }
+            }
+        }
+
+        let mut step = Step::new();
+        step.push(change);
+        step.batch = batch;
+        step.last_change = now;
+        self.push_step(step);
+    }
+
+    fn push_step(&mut self, step: Step) {
+        if step.changes.is_empty() {
+            return;
+        }
+        self.undo_stack.push_back(step);
+        if let Some(limit) = self.limit {
+            while self.undo_stack.len() > limit {
+                self.undo_stack.pop_front();
+            }
+        }
+        self.merge_open = true;
+    }
+}
+
+/// An undo/redo history that [`UndoableSignal`]s record their changes into.
+///
+/// Each [`undo`](History::undo) reverts one step. Changes are merged into a single step
+/// when they happen inside the same [`batch`], inside a [`History::transaction`], or
+/// within the [`merge_window`](History::merge_window) of each other.
+///
+/// Several signals can share one `History`, which makes a change spanning all of them
+/// undoable at once:
+/// ```rust
+/// # use floem_reactive::{History, SignalGet, SignalUpdate};
+/// let history = History::new();
+/// let x = history.signal(0);
+/// let y = history.signal(0);
+///
+/// history.transaction(|| {
+///     x.set(10);
+///     y.set(20);
+/// });
+/// assert!(history.undo());
+/// assert_eq!((x.get(), y.get()), (0, 0));
+/// ```
+pub struct History {
+    state: RwSignal<HistoryState>,
+    can_undo: RwSignal<bool>,
+    can_redo: RwSignal<bool>,
+}
+
+impl Copy for History {}
+
+impl Clone for History {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl History {
+    /// Create a new, empty history in the current Scope
+    pub fn new() -> Self {
+        create_history()
+    }
+
+    /// Limit the number of undo steps that are kept. The oldest steps are dropped first.
+    pub fn limit(self, limit: usize) -> Self {
+        self.state.update(|s| s.limit = Some(limit));
+        self
+    }
+
+    /// Merge changes that happen within `window` of the previous change into the same
+    /// undo step, for example to treat a burst of keystrokes as one edit.
+    pub fn merge_window(self, window: Duration) -> Self {
+        self.state.update(|s| s.merge_window = Some(window));
+        self
+    }
+
+    /// Create a new [`UndoableSignal`] that records into this history
+    pub fn signal<T: 'static>(&self, value: T) -> UndoableSignal<T> {
+        UndoableSignal {
+            signal: create_rw_signal(value),
+            history: *self,
+            ts: PhantomData,
+        }
+    }
+
+    /// Whether there is a step that can be undone
+    pub fn can_undo(&self) -> ReadSignal<bool> {
+        self.can_undo.read_only()
+    }
+
+    /// Whether there is a step that can be redone
+    pub fn can_redo(&self) -> ReadSignal<bool> {
+        self.can_redo.read_only()
+    }
+
+    /// Record a custom change, described by a pair of functions that revert and
+    /// re-apply it. The change is expected to have already been applied.
+    pub fn record(&self, undo: impl Fn() + 'static, redo: impl Fn() + 'static) {
+        self.push(Change {
+            target: None,
+            undo: Rc::new(undo),
+            redo: Rc::new(redo),
+        });
+    }
+
+    /// Run `f`, recording every change made in it as a single undo step.
+    ///
+    /// The updates are also batched, so effects only run once at the end of the
+    /// outermost transaction.
+    ///
+    /// If `f` panics, the changes made before the panic are still recorded as one step.
+    pub fn transaction<R>(&self, f: impl FnOnce() -> R) -> R {
+        /// Ends the transaction when dropped, even if `f` panicked.
+        struct EndTransaction(History);
+
+        impl Drop for EndTransaction {
+            fn drop(&mut self) {
+                self.0.state.update(|s| {
+                    s.transaction_depth -= 1;
+                    if s.transaction_depth == 0 {
+                        if let Some(step) = s.transaction.take() {
+                            s.push_step(step);
+                            // A transaction is always its own step
+                            s.merge_open = false;
+                        }
+                    }
+                });
+                self.0.refresh();
+            }
+        }
+
+        self.state.update(|s| {
+            if s.transaction_depth == 0 {
+                s.transaction = Some(Step::new());
+            }
+            s.transaction_depth += 1;
+        });
+
+        batch(|| {
+            let _end = EndTransaction(*self);
+            f()
+        })
+    }
+
+    /// Make sure the next change starts a new undo step, even if it would otherwise be
+    /// merged into the previous one.
+    pub fn break_merge(&self) {
+        self.state.update(|s| s.merge_open = false);
+    }
+
+    /// Revert the most recent step. Returns `false` if there was nothing to undo.
+    pub fn undo(&self) -> bool {
+        let Some(step) = self.state.try_update(|s| s.undo_stack.pop_back()).flatten() else {
+            return false;
+        };
+        self.apply(|| {
+            for change in step.changes.iter().rev() {
+                (change.undo)();
+            }
+        });
+        self.state.update(|s| {
+            s.redo_stack.push(step);
+            s.merge_open = false;
+        });
+        self.refresh();
+        true
+    }
+
+    /// Re-apply the most recently undone step. Returns `false` if there was nothing to redo.
+    pub fn redo(&self) -> bool {
+        let Some(step) = self.state.try_update(|s| s.redo_stack.pop()).flatten() else {
+            return false;
+        };
+        self.apply(|| {
+            for change in step.changes.iter() {
+                (change.redo)();
+            }
+        });
+        self.state.update(|s| {
+            s.undo_stack.push_back(step);
+            s.merge_open = false;
+        });
+        self.refresh();
+        true
+    }
+
+    /// Forget all undo and redo steps
+    pub fn clear(&self) {
+        self.state.update(|s| {
+            s.undo_stack.clear();
+            s.redo_stack.clear();
+            s.merge_open = false;
+        });
+        self.refresh();
+    }
+
+    fn push(&self, change: Change) {
+        self.state.update(|s| s.record(change));
+        self.refresh();
+    }
+
+    fn apply(&self, f: impl FnOnce()) {
+        self.state.update(|s| s.applying = true);
+        batch(f);
+        self.state.update(|s| s.applying = false);
+    }
+
+    /// Sync `can_undo` and `can_redo` with the stacks, only notifying on change
+    fn refresh(&self) {
+        let Some((can_undo, can_redo)) = self.state.try_with_untracked(|s| {
+            s.map(|s| (!s.undo_stack.is_empty(), !s.redo_stack.is_empty()))
+        }) else {
+            return;
+        };
+        if self.can_undo.get_untracked() != can_undo {
+            self.can_undo.set(can_undo);
+        }
+        if self.can_redo.get_untracked() != can_redo {
+            self.can_redo.set(can_redo);
+        }
+    }
+}
+
+/// Creates a new, empty [`History`] in the current Scope
+pub fn create_history() -> History {
+    History {
+        state: create_rw_signal(HistoryState::default()),
+        can_undo: create_rw_signal(false),
+        can_redo: create_rw_signal(false),
+    }
+}
+
+/// A [`RwSignal`] whose updates are recorded into a [`History`] so they can be undone and
+/// redone.
+///
+/// [`SignalUpdate::set`] and [`SignalUpdate::update`] record a snapshot of the value from
+/// before and after the change, unless the value didn't change. For values that are expensive to clone, use
+/// [`UndoableSignal::update_with_inverse`] to record an operation and its inverse instead.
+pub struct UndoableSignal<T> {
+    signal: RwSignal<T>,
+    history: History,
+    ts: PhantomData<NotThreadSafe>,
+}
+
+impl<T> Copy for UndoableSignal<T> {}
+
+impl<T> Clone for UndoableSignal<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Eq for UndoableSignal<T> {}
+
+impl<T> PartialEq for UndoableSignal<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.signal == other.signal
+    }
+}
+
+impl<T: 'static> UndoableSignal<T> {
+    /// Create a new `UndoableSignal` with its own [`History`]
+    pub fn new(value: T) -> Self {
+        create_undoable_signal(value)
+    }
+
+    /// The [`History`] this signal records into
+    pub fn history(&self) -> History {
+        self.history
+    }
+
+    /// The underlying signal. Writes through it are not recorded.
+    pub fn signal(&self) -> RwSignal<T> {
+        self.signal
+    }
+
+    /// Create a Getter of this Signal
+    pub fn read_only(&self) -> ReadSignal<T> {
+        self.signal.read_only()
+    }
+
+    /// Revert the most recent step of this signal's history
+    pub fn undo(&self) -> bool {
+        self.history.undo()
+    }
+
+    /// Re-apply the most recently undone step of this signal's history
+    pub fn redo(&self) -> bool {
+        self.history.redo()
+    }
+
+    /// Whether this signal's history has a step that can be undone
+    pub fn can_undo(&self) -> ReadSignal<bool> {
+        self.history.can_undo()
+    }
+
+    /// Whether this signal's history has a step that can be redone
+    pub fn can_redo(&self) -> ReadSignal<bool> {
+        self.history.can_redo()
+    }
+
+    /// Update the value with `apply` and record `inverse` as the way to undo it.
+    /// Redoing runs `apply` again.
+    pub fn update_with_inverse(
+        &self,
+        apply: impl Fn(&mut T) + 'static,
+        inverse: impl Fn(&mut T) + 'static,
+    ) {
+        let signal = self.signal;
+        if signal.id.signal().is_none() {
+            return;
+        }
+        let apply = Rc::new(apply);
+        self.history.push(Change {
+            target: None,
+            undo: Rc::new(move || signal.update(&inverse)),
+            redo: Rc::new({
+                let apply = apply.clone();
+                move || signal.update(&*apply)
+            }),
+        });
+        signal.update(&*apply);
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> SignalUpdate<T> for UndoableSignal<T> {
+    fn id(&self) -> Id {
+        self.signal.id
+    }
+
+    fn set(&self, new_value: T) {
+        self.try_update(|v| *v = new_value);
+    }
+
+    fn update(&self, f: impl FnOnce(&mut T)) {
+        self.try_update(f);
+    }
+
+    fn try_update<O>(&self, f: impl FnOnce(&mut T) -> O) -> Option<O> {
+        let signal = self.signal.id.signal()?;
+        let (before, after, result) = {
+            let value = signal
+                .value
+                .downcast_ref::<RefCell<T>>()
+                .expect("to downcast signal type");
+            let mut value = value.borrow_mut();
+            let before = value.clone();
+            let result = f(&mut value);
+            (before, value.clone(), result)
+        };
+
+        // An update that leaves the value unchanged has nothing to undo
+        if before != after {
+            // Record before notifying so changes made by effects are ordered after this one
+            let target = self.signal;
+            self.history.push(Change {
+                target: Some(target.id),
+                undo: Rc::new(move || target.set(before.clone())),
+                redo: Rc::new(move || target.set(after.clone())),
+            });
+        }
+        signal.run_effects();
+        Some(result)
+    }
+}
+
+impl<T: Clone> SignalGet<T> for UndoableSignal<T> {
+    fn id(&self) -> Id {
+        self.signal.id
+    }
+}
+
+impl<T> SignalWith<T> for UndoableSignal<T> {
+    fn id(&self) -> Id {
+        self.signal.id
+    }
+}
+
+impl<T> SignalTrack<T> for UndoableSignal<T> {
+    fn id(&self) -> Id {
+        self.signal.id
+    }
+}
+
+impl<T> SignalRead<T> for UndoableSignal<T> {
+    fn id(&self) -> Id {
+        self.signal.id
+    }
+}
+
+/// Creates a new [`UndoableSignal`] with its own [`History`].
+///
+/// Use [`History::signal`] to create signals that share a history instead.
+pub fn create_undoable_signal<T: 'static>(value: T) -> UndoableSignal<T> {
+    create_history().signal(value)
+}
@@ -0,0 +1,65 @@
+use floem_reactive::{
+    create_effect, provide_context, use_context, with_scope, RwSignal, Scope, SignalGet,
+    SignalUpdate,
+};
+
+#[test]
+fn scope_context_is_visible_to_descendants() {
+    let root = Scope::new();
+    let child = root.create_child();
+    let sibling = Scope::new();
+
+    root.provide_context(1u8);
+    child.provide_context(2u16);
+
+    assert_eq!(child.use_context::<u8>(), Some(1));
+    assert_eq!(child.use_context::<u16>(), Some(2));
+    assert_eq!(root.use_context::<u16>(), None);
+    assert_eq!(sibling.use_context::<u8>(), None);
+    assert_eq!(
+        child.parent().map(|s| format!("{s:?}")),
+        Some(format!("{root:?}"))
+    );
+}
+
+#[test]
+fn scope_context_takes_precedence_over_global() {
+    provide_context(String::from("global"));
+    let scope = Scope::new();
+    scope.provide_context(String::from("scoped"));
+
+    assert_eq!(use_context::<String>().as_deref(), Some("global"));
+    let found = with_scope(scope.create_child(), use_context::<String>);
+    assert_eq!(found.as_deref(), Some("scoped"));
+}
+
+#[test]
+fn scope_context_is_found_from_rerun_effects() {
+    let scope = Scope::new();
+    scope.provide_context(7i32);
+    let trigger = RwSignal::new(0);
+    let seen = RwSignal::new(Vec::new());
+
+    with_scope(scope, || {
+        create_effect(move |_| {
+            trigger.get();
+            let value = use_context::<i32>();
+            seen.update(|seen| seen.push(value));
+        });
+    });
+    trigger.set(1);
+    trigger.set(2);
+
+    assert_eq!(seen.get_untracked(), vec![Some(7); 3]);
+}
+
+#[test]
+fn scope_context_is_dropped_on_dispose() {
+    let scope = Scope::new();
+    let child = scope.create_child();
+    scope.provide_context(3u32);
+    scope.dispose();
+
+    assert_eq!(scope.use_context::<u32>(), None);
+    assert_eq!(child.use_context::<u32>(), None);
+}
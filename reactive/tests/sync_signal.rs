@@ -0,0 +1,107 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use floem_reactive::{
+    create_effect, create_memo, create_sync_signal, flush_sync_signals, set_sync_signal_waker,
+    SignalGet, SignalTrack, SignalUpdate, SignalWith,
+};
+
+#[test]
+fn behaves_like_a_signal_on_owner_thread() {
+    let count = create_sync_signal(1);
+    let doubled = create_memo({
+        let count = count.clone();
+        move |_| count.get() * 2
+    });
+
+    let runs = Rc::new(Cell::new(0));
+    create_effect({
+        let runs = runs.clone();
+        move |_| {
+            doubled.track();
+            runs.set(runs.get() + 1);
+        }
+    });
+    assert_eq!(runs.get(), 1);
+
+    count.set(2);
+    assert_eq!(doubled.get(), 4);
+    assert_eq!(runs.get(), 2);
+
+    count.update(|c| *c += 1);
+    assert_eq!(count.with(|c| *c), 3);
+    assert_eq!(doubled.get(), 6);
+}
+
+#[test]
+fn writes_from_other_threads_are_flushed_and_coalesced() {
+    let wakes = Arc::new(AtomicUsize::new(0));
+    set_sync_signal_waker({
+        let wakes = wakes.clone();
+        move || {
+            wakes.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+
+    let value = create_sync_signal(0);
+    let runs = Rc::new(Cell::new(0));
+    create_effect({
+        let runs = runs.clone();
+        let value = value.clone();
+        move |_| {
+            value.get();
+            runs.set(runs.get() + 1);
+        }
+    });
+
+    let worker = value.clone();
+    thread::spawn(move || {
+        for i in 1..=10 {
+            worker.set(i);
+        }
+    })
+    .join()
+    .unwrap();
+
+    // The value is visible right away, but effects wait for the flush
+    assert_eq!(value.get_untracked(), 10);
+    assert_eq!(runs.get(), 1);
+    assert_eq!(wakes.load(Ordering::SeqCst), 1);
+
+    flush_sync_signals();
+    assert_eq!(runs.get(), 2);
+
+    // Flushing again has nothing to do
+    flush_sync_signals();
+    assert_eq!(runs.get(), 2);
+}
+
+#[test]
+fn workers_can_subscribe() {
+    let value = create_sync_signal(String::new());
+    let rx = value.subscribe();
+
+    let handle = thread::spawn(move || {
+        let mut received = Vec::new();
+        while let Ok(v) = rx.recv_timeout(Duration::from_secs(5)) {
+            received.push(v);
+            if received.len() == 2 {
+                break;
+            }
+        }
+        received
+    });
+
+    value.set("a".to_string());
+    value.update(|s| s.push('b'));
+
+    assert_eq!(handle.join().unwrap(), vec!["a", "ab"]);
+}
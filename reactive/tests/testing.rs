@@ -0,0 +1,142 @@
+#![cfg(feature = "testing")]
+
+use std::time::Duration;
+
+use floem_reactive::{
+    batch, create_memo, create_rw_signal,
+    testing::{
+        assert_scope_disposed, create_counted_effect, dispose_and_assert, live_descendants, record,
+        scope_links, TestRuntime, VirtualClock,
+    },
+    RwSignal, Scope, SignalGet, SignalUpdate,
+};
+
+#[test]
+fn timers_fire_in_deadline_order() {
+    let rt = TestRuntime::new();
+    let log = RwSignal::new(Vec::new());
+    let clock = rt.clock();
+
+    clock.set_timeout(Duration::from_millis(20), move || {
+        log.update(|l| l.push(20))
+    });
+    let cancelled = clock.set_timeout(Duration::from_millis(15), move || {
+        log.update(|l| l.push(15))
+    });
+    clock.set_timeout(Duration::from_millis(10), move || {
+        log.update(|l| l.push(10));
+        // Scheduled while advancing, and due before the end
+        VirtualClock::current()
+            .unwrap()
+            .set_timeout(Duration::from_millis(5), move || log.update(|l| l.push(11)));
+    });
+    assert!(clock.cancel(cancelled));
+
+    assert_eq!(rt.advance(Duration::from_millis(9)), 0);
+    assert_eq!(rt.advance(Duration::from_millis(11)), 3);
+    assert_eq!(log.get_untracked(), vec![10, 11, 20]);
+    assert_eq!(clock.elapsed(), Duration::from_millis(20));
+    assert_eq!(clock.pending_timers(), 0);
+}
+
+#[test]
+fn run_all_timers_follows_chains() {
+    let rt = TestRuntime::new();
+    let count = RwSignal::new(0);
+
+    fn tick(count: RwSignal<i32>) {
+        count.update(|c| *c += 1);
+        if count.get_untracked() < 5 {
+            VirtualClock::current()
+                .unwrap()
+                .set_timeout(Duration::from_secs(1), move || tick(count));
+        }
+    }
+    rt.clock()
+        .set_timeout(Duration::from_secs(1), move || tick(count));
+
+    assert_eq!(rt.run_all_timers(100), 5);
+    assert_eq!(count.get_untracked(), 5);
+    assert_eq!(rt.clock().elapsed(), Duration::from_secs(5));
+}
+
+#[test]
+fn recorder_and_counter() {
+    let rt = TestRuntime::new();
+    let (recorder, runs, a, b) = rt.run(|| {
+        let a = RwSignal::new(1);
+        let b = RwSignal::new(2);
+        let sum = create_memo(move |_| a.get() + b.get());
+        (
+            record(sum),
+            create_counted_effect(move || {
+                sum.get();
+            }),
+            a,
+            b,
+        )
+    });
+    assert!(recorder.is_empty());
+    assert_eq!(runs.get(), 1);
+
+    a.set(2);
+    batch(|| {
+        a.set(3);
+        b.set(3);
+    });
+    assert_eq!(recorder.values(), vec![4, 6]);
+    assert_eq!(runs.reset(), 3);
+
+    // The memo doesn't notify when its value doesn't change
+    a.set(3);
+    assert_eq!(recorder.len(), 2);
+    assert_eq!(runs.get(), 0);
+}
+
+#[test]
+fn scope_disposal_assertions() {
+    let rt = TestRuntime::new();
+    let scope = rt.scope().create_child();
+    let trigger = create_rw_signal(0);
+    floem_reactive::with_scope(scope, || {
+        let inner = create_rw_signal(0);
+        let child = Scope::current().create_child();
+        child.provide_context(1u8);
+        child.create_effect(move |_| inner.get() + trigger.get());
+    });
+    assert_eq!(live_descendants(scope), 3);
+
+    dispose_and_assert(scope);
+    assert_eq!(live_descendants(scope), 0);
+    // Writing a signal the disposed effect used to depend on doesn't revive anything
+    trigger.set(1);
+    assert_scope_disposed(scope);
+}
+
+#[test]
+fn disposed_scopes_are_unlinked_from_their_parent() {
+    let root = Scope::new();
+    for i in 0..100 {
+        let child = root.create_child();
+        let signal = child.create_rw_signal(i);
+        child.create_effect(move |_| signal.get());
+        child.dispose();
+    }
+    assert_eq!(live_descendants(root), 0);
+    assert_eq!(scope_links(), 0);
+
+    for i in 0..100 {
+        let scope = Scope::new();
+        scope.create_rw_signal(i);
+        scope.dispose();
+    }
+    assert_eq!(scope_links(), 0);
+}
+
+#[test]
+#[should_panic(expected = "was not fully disposed")]
+fn undisposed_scope_is_reported() {
+    let scope = Scope::new();
+    scope.create_rw_signal(0);
+    assert_scope_disposed(scope);
+}
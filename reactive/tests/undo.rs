@@ -0,0 +1,182 @@
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+use floem_reactive::{
+    batch, create_effect, create_undoable_signal, History, SignalGet, SignalTrack, SignalUpdate,
+};
+
+#[test]
+fn undo_redo_snapshots() {
+    let value = create_undoable_signal(0);
+    assert!(!value.can_undo().get());
+
+    value.set(1);
+    value.set(2);
+    assert!(value.can_undo().get());
+    assert!(!value.can_redo().get());
+
+    assert!(value.undo());
+    assert_eq!(value.get(), 1);
+    assert!(value.can_redo().get());
+
+    assert!(value.undo());
+    assert_eq!(value.get(), 0);
+    assert!(!value.undo());
+    assert!(!value.can_undo().get());
+
+    assert!(value.redo());
+    assert!(value.redo());
+    assert_eq!(value.get(), 2);
+    assert!(!value.redo());
+}
+
+#[test]
+fn new_change_clears_redo() {
+    let value = create_undoable_signal(0);
+    value.set(1);
+    value.undo();
+    assert!(value.can_redo().get());
+
+    value.set(5);
+    assert!(!value.can_redo().get());
+    assert!(!value.redo());
+    assert_eq!(value.get(), 5);
+}
+
+#[test]
+fn batch_merges_into_one_step() {
+    let value = create_undoable_signal(0);
+    batch(|| {
+        value.set(1);
+        value.set(2);
+        value.set(3);
+    });
+    value.set(4);
+
+    value.undo();
+    assert_eq!(value.get(), 3);
+    value.undo();
+    assert_eq!(value.get(), 0);
+    assert!(!value.can_undo().get());
+}
+
+#[test]
+fn merge_window() {
+    let history = History::new().merge_window(Duration::from_secs(60));
+    let value = history.signal(String::new());
+    value.update(|s| s.push('a'));
+    value.update(|s| s.push('b'));
+    history.break_merge();
+    value.update(|s| s.push('c'));
+
+    history.undo();
+    assert_eq!(value.get(), "ab");
+    history.undo();
+    assert_eq!(value.get(), "");
+}
+
+#[test]
+fn transaction_groups_signals() {
+    let history = History::new();
+    let x = history.signal(0);
+    let y = history.signal(0);
+
+    let runs = Rc::new(Cell::new(0));
+    create_effect({
+        let runs = runs.clone();
+        move |_| {
+            x.track();
+            y.track();
+            runs.set(runs.get() + 1);
+        }
+    });
+
+    history.transaction(|| {
+        x.set(1);
+        y.set(2);
+    });
+    assert_eq!(runs.get(), 2);
+
+    history.undo();
+    assert_eq!((x.get(), y.get()), (0, 0));
+    assert_eq!(runs.get(), 3);
+
+    history.redo();
+    assert_eq!((x.get(), y.get()), (1, 2));
+}
+
+#[test]
+fn inverse_operations() {
+    let list = create_undoable_signal(Vec::new());
+    list.update_with_inverse(|v| v.push(1), |v| _ = v.pop());
+    list.update_with_inverse(|v| v.push(2), |v| _ = v.pop());
+    assert_eq!(list.get(), vec![1, 2]);
+
+    list.undo();
+    assert_eq!(list.get(), vec![1]);
+    list.redo();
+    assert_eq!(list.get(), vec![1, 2]);
+}
+
+#[test]
+fn limit_drops_oldest() {
+    let history = History::new().limit(2);
+    let value = history.signal(0);
+    value.set(1);
+    value.set(2);
+    value.set(3);
+
+    assert!(history.undo());
+    assert!(history.undo());
+    assert!(!history.undo());
+    assert_eq!(value.get(), 1);
+}
+
+#[test]
+fn unchanged_snapshots_are_not_recorded() {
+    let value = create_undoable_signal(0);
+    value.set(1);
+    value.undo();
+    assert!(value.can_redo().get());
+
+    value.set(0);
+    value.update(|_| {});
+    // The redo step is kept as nothing changed
+    assert!(value.can_redo().get());
+    assert!(!value.can_undo().get());
+
+    assert!(value.redo());
+    assert_eq!(value.get(), 1);
+}
+
+#[test]
+fn panicking_transaction_ends() {
+    let history = History::new();
+    let x = history.signal(0);
+    let y = history.signal(0);
+    let runs = Rc::new(Cell::new(0));
+    create_effect({
+        let runs = runs.clone();
+        move |_| {
+            y.track();
+            runs.set(runs.get() + 1);
+        }
+    });
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        history.transaction(|| {
+            x.set(1);
+            panic!("failed halfway");
+        })
+    }));
+    assert!(result.is_err());
+    assert!(history.can_undo().get());
+
+    // Later changes are their own steps, and aren't batched anymore
+    y.set(1);
+    assert_eq!(runs.get(), 2);
+    assert!(history.undo());
+    assert_eq!((x.get(), y.get()), (1, 0));
+    assert!(history.undo());
+    assert_eq!(x.get(), 0);
+    assert!(!history.can_undo().get());
+}
@@ -22,6 +22,36 @@ pub struct Img<'a> {
     pub hash: &'a [u8],
 }
 
+/// Filters applied to the contents of a layer, like the CSS `filter` property.
+///
+/// They are applied in order: blur, then brightness, then grayscale.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Filter {
+    /// The standard deviation of a gaussian blur, in logical pixels.
+    pub blur: f64,
+    /// A multiplier of the colors. `0.0` is black and `1.0` leaves them unchanged.
+    pub brightness: f64,
+    /// How much the colors are converted to grayscale, from `0.0` to `1.0`.
+    pub grayscale: f64,
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Self {
+            blur: 0.0,
+            brightness: 1.0,
+            grayscale: 0.0,
+        }
+    }
+}
+
+impl Filter {
+    /// Returns `true` if the filter leaves the contents unchanged.
+    pub fn is_identity(&self) -> bool {
+        self.blur <= 0.0 && self.brightness == 1.0 && self.grayscale <= 0.0
+    }
+}
+
 pub trait Renderer {
     fn begin(&mut self, capture: bool);
 
@@ -47,7 +77,7 @@ pub trait Renderer {
     /// [non-zero fill rule]: https://en.wikipedia.org/wiki/Nonzero-rule
     fn fill<'b>(&mut self, path: &impl Shape, brush: impl Into<BrushRef<'b>>, blur_radius: f64);
 
-    /// Push a layer (This is not supported with Vger)
+    /// Push a layer (Vger only applies `alpha`, to each color drawn in the layer)
     fn push_layer(
         &mut self,
         blend: impl Into<BlendMode>,
@@ -56,9 +86,23 @@ pub trait Renderer {
         clip: &impl Shape,
     );
 
-    /// Pop a layer (This is not supported with Vger)
+    /// Push a layer that is composited with `alpha` and `filter` when it is popped with
+    /// [`Renderer::pop_layer`]. Drawing outside of `clip` is discarded.
+    ///
+    /// Vello doesn't blur layers, and Vger only applies `alpha`. By default, this pushes a
+    /// layer with [`Renderer::push_layer`] and ignores `filter`.
+    fn push_filter_layer(&mut self, alpha: f32, _filter: Filter, clip: &impl Shape) {
+        self.push_layer(BlendMode::default(), alpha, Affine::IDENTITY, clip);
+    }
+
+    /// Pop a layer
     fn pop_layer(&mut self);
 
+    /// Blur what has already been drawn inside `shape`, like the CSS `backdrop-filter` property.
+    ///
+    /// This is only supported with tiny-skia. By default, this does nothing.
+    fn blur_backdrop(&mut self, _shape: &impl Shape, _radius: f64) {}
+
     /// Draw a [`TextLayout`].
     ///
     /// The `pos` parameter specifies the upper-left corner of the layout object
@@ -1,6 +1,7 @@
 use std::ops::Range;
 
-use crate::text::{fontdb, Family, Stretch, Style, Weight};
+use crate::text::decoration::{SpanStyle, SpanStyles};
+use crate::text::{fontdb, Family, Stretch, Style, TextDecoration, TextShadow, Weight};
 use peniko::Color;
 
 /// An owned version of [`Family`]
@@ -58,6 +59,8 @@ pub struct AttrsOwned {
     attrs: cosmic_text::AttrsOwned,
     pub font_size: f32,
     line_height: LineHeightValue,
+    letter_spacing: f32,
+    span: SpanStyle,
 }
 impl AttrsOwned {
     pub fn new(attrs: Attrs) -> Self {
@@ -65,6 +68,8 @@ impl AttrsOwned {
             attrs: cosmic_text::AttrsOwned::new(&attrs.attrs),
             font_size: attrs.font_size,
             line_height: attrs.line_height,
+            letter_spacing: attrs.letter_spacing,
+            span: attrs.span,
         }
     }
 
@@ -73,6 +78,8 @@ impl AttrsOwned {
             attrs: self.attrs.as_attrs(),
             font_size: self.font_size,
             line_height: self.line_height,
+            letter_spacing: self.letter_spacing,
+            span: self.span,
         }
     }
 }
@@ -83,6 +90,9 @@ pub struct Attrs<'a> {
     attrs: cosmic_text::Attrs<'a>,
     pub font_size: f32,
     line_height: LineHeightValue,
+    /// In pixels, converted to EM for cosmic-text
+    letter_spacing: f32,
+    span: SpanStyle,
 }
 
 impl Default for Attrs<'_> {
@@ -100,6 +110,8 @@ impl<'a> Attrs<'a> {
             attrs: cosmic_text::Attrs::new(),
             font_size: 16.0,
             line_height: LineHeightValue::Normal(1.0),
+            letter_spacing: 0.0,
+            span: SpanStyle::default(),
         }
     }
 
@@ -157,6 +169,37 @@ impl<'a> Attrs<'a> {
         self.font_size = font_size;
         let metrics = self.get_metrics();
         self.attrs = self.attrs.metrics(metrics);
+        if self.letter_spacing != 0.0 {
+            self = self.letter_spacing(self.letter_spacing);
+        }
+        self
+    }
+
+    /// Set the space added after each character, in pixels
+    pub fn letter_spacing(mut self, letter_spacing: f32) -> Self {
+        self.letter_spacing = letter_spacing;
+        if self.font_size > 0.0 {
+            self.attrs = self.attrs.letter_spacing(letter_spacing / self.font_size);
+        }
+        self
+    }
+
+    /// Set the space added after each space character, in addition to the letter spacing,
+    /// in pixels
+    pub fn word_spacing(mut self, word_spacing: f32) -> Self {
+        self.span.word_spacing = word_spacing;
+        self
+    }
+
+    /// Set [TextDecoration]
+    pub fn decoration(mut self, decoration: TextDecoration) -> Self {
+        self.span.decoration = Some(decoration);
+        self
+    }
+
+    /// Set [TextShadow]
+    pub fn shadow(mut self, shadow: TextShadow) -> Self {
+        self.span.shadow = Some(shadow);
         self
     }
 
@@ -186,49 +229,75 @@ impl<'a> Attrs<'a> {
 }
 
 #[derive(PartialEq, Clone)]
-pub struct AttrsList(pub cosmic_text::AttrsList);
+pub struct AttrsList(
+    /// The attributes handled by cosmic-text. Spans added to it directly have no
+    /// decorations, shadows or word spacing.
+    pub cosmic_text::AttrsList,
+    /// The attributes cosmic-text doesn't handle
+    pub(crate) SpanStyles,
+);
 
 impl AttrsList {
     /// Create a new attributes list with a set of default [Attrs]
     pub fn new(defaults: Attrs) -> Self {
-        Self(cosmic_text::AttrsList::new(&defaults.attrs))
+        Self(
+            cosmic_text::AttrsList::new(&defaults.attrs),
+            SpanStyles::new(defaults.span),
+        )
     }
 
     /// Get the default [Attrs]
     pub fn defaults(&self) -> Attrs<'_> {
-        self.0.defaults().into()
+        Attrs::from(self.0.defaults()).with_span(self.1.defaults())
     }
 
     /// Clear the current attribute spans
     pub fn clear_spans(&mut self) {
         self.0.clear_spans();
+        self.1.clear_spans();
     }
 
     /// Add an attribute span, removes any previous matching parts of spans
     pub fn add_span(&mut self, range: Range<usize>, attrs: Attrs) {
-        self.0.add_span(range, &attrs.attrs);
+        self.0.add_span(range.clone(), &attrs.attrs);
+        self.1.add_span(range, attrs.span);
     }
 
     /// Get the attribute span for an index
     ///
     /// This returns a span that contains the index
     pub fn get_span(&self, index: usize) -> Attrs<'_> {
-        self.0.get_span(index).into()
+        Attrs::from(self.0.get_span(index)).with_span(self.1.get(index))
     }
 
     /// Split attributes list at an offset
     pub fn split_off(&mut self, index: usize) -> Self {
-        let new = self.0.split_off(index);
-        Self(new)
+        Self(self.0.split_off(index), self.1.split_off(index))
+    }
+}
+
+impl Attrs<'_> {
+    fn with_span(mut self, span: SpanStyle) -> Self {
+        self.span = span;
+        self
     }
 }
 
 impl<'a> From<cosmic_text::Attrs<'a>> for Attrs<'a> {
     fn from(attrs: cosmic_text::Attrs<'a>) -> Self {
+        let metrics = attrs.metrics_opt.map(cosmic_text::Metrics::from);
+        let font_size = metrics.map_or(1.0, |metrics| metrics.font_size);
         Self {
+            // cosmic-text's letter spacing is in EM
+            letter_spacing: attrs
+                .letter_spacing_opt
+                .map_or(0.0, |spacing| spacing.0 * font_size),
             attrs,
-            font_size: 1.0,
-            line_height: LineHeightValue::Normal(1.0),
+            font_size,
+            line_height: metrics.map_or(LineHeightValue::Normal(1.0), |metrics| {
+                LineHeightValue::Px(metrics.line_height)
+            }),
+            span: SpanStyle::default(),
         }
     }
 }
@@ -310,3 +379,30 @@ impl Iterator for ParseList<'_> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letter_spacing_stays_in_pixels_through_cosmic_text() {
+        let attrs = Attrs::new().font_size(20.0).letter_spacing(4.0);
+        assert_eq!(
+            attrs.attrs.letter_spacing_opt.map(|spacing| spacing.0),
+            Some(0.2)
+        );
+
+        let list = AttrsList::new(attrs);
+        let defaults = list.defaults();
+        assert_eq!(defaults.font_size, 20.0);
+        assert_eq!(defaults.letter_spacing, 4.0);
+
+        // Changing the font size keeps the spacing in pixels
+        let resized = defaults.font_size(40.0);
+        assert_eq!(resized.letter_spacing, 4.0);
+        assert_eq!(
+            resized.attrs.letter_spacing_opt.map(|spacing| spacing.0),
+            Some(0.1)
+        );
+    }
+}
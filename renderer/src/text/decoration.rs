@@ -0,0 +1,550 @@
+use std::ops::Range;
+
+use cosmic_text::LayoutGlyph;
+use peniko::{
+    color::palette,
+    kurbo::{BezPath, Cap, Stroke},
+    Color,
+};
+
+/// The lines drawn by a [`TextDecoration`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TextDecorationLine {
+    pub underline: bool,
+    pub overline: bool,
+    pub line_through: bool,
+}
+
+impl TextDecorationLine {
+    pub const NONE: Self = Self {
+        underline: false,
+        overline: false,
+        line_through: false,
+    };
+    pub const UNDERLINE: Self = Self {
+        underline: true,
+        ..Self::NONE
+    };
+    pub const OVERLINE: Self = Self {
+        overline: true,
+        ..Self::NONE
+    };
+    pub const LINE_THROUGH: Self = Self {
+        line_through: true,
+        ..Self::NONE
+    };
+
+    /// The lines of both `self` and `other`
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            underline: self.underline || other.underline,
+            overline: self.overline || other.overline,
+            line_through: self.line_through || other.line_through,
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        *self == Self::NONE
+    }
+}
+
+/// How the lines of a [`TextDecoration`] are drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TextDecorationStyle {
+    #[default]
+    Solid,
+    Double,
+    Dotted,
+    Dashed,
+    Wavy,
+}
+
+/// Lines drawn under, over or through text, like the CSS `text-decoration` property.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TextDecoration {
+    pub line: TextDecorationLine,
+    pub style: TextDecorationStyle,
+    /// The color of the lines, the color of the text if `None`
+    pub color: Option<Color>,
+    /// The thickness of the lines in pixels, derived from the font size if `None`
+    pub thickness: Option<f32>,
+}
+
+impl TextDecoration {
+    pub fn new(line: TextDecorationLine) -> Self {
+        Self {
+            line,
+            ..Default::default()
+        }
+    }
+
+    pub fn style(mut self, style: TextDecorationStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = Some(thickness);
+        self
+    }
+}
+
+/// A shadow drawn behind text, like the CSS `text-shadow` property.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextShadow {
+    pub color: Color,
+    pub offset_x: f32,
+    pub offset_y: f32,
+    /// The standard deviation of the blur in pixels. Only tiny-skia blurs text shadows.
+    pub blur: f32,
+}
+
+impl Default for TextShadow {
+    fn default() -> Self {
+        Self {
+            color: palette::css::BLACK,
+            offset_x: 0.0,
+            offset_y: 0.0,
+            blur: 0.0,
+        }
+    }
+}
+
+/// The text attributes that are not handled by cosmic-text.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct SpanStyle {
+    pub decoration: Option<TextDecoration>,
+    pub shadow: Option<TextShadow>,
+    /// In pixels, added to the advance of spaces when shaping
+    pub word_spacing: f32,
+}
+
+/// [`SpanStyle`]s by byte range, with the same semantics as [`cosmic_text::AttrsList`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct SpanStyles {
+    defaults: SpanStyle,
+    spans: Vec<(Range<usize>, SpanStyle)>,
+}
+
+impl SpanStyles {
+    pub fn new(defaults: SpanStyle) -> Self {
+        Self {
+            defaults,
+            spans: Vec::new(),
+        }
+    }
+
+    pub fn defaults(&self) -> SpanStyle {
+        self.defaults
+    }
+
+    /// Returns `true` if no text has a decoration or a shadow.
+    pub fn is_plain(&self) -> bool {
+        let plain = |style: &SpanStyle| style.decoration.is_none() && style.shadow.is_none();
+        plain(&self.defaults) && self.spans.iter().all(|(_, style)| plain(style))
+    }
+
+    /// Returns `true` if any text has word spacing.
+    pub fn has_word_spacing(&self) -> bool {
+        self.defaults.word_spacing != 0.0
+            || self
+                .spans
+                .iter()
+                .any(|(_, style)| style.word_spacing != 0.0)
+    }
+
+    pub fn clear_spans(&mut self) {
+        self.spans.clear();
+    }
+
+    /// Sets the style of `range`, replacing the parts of previous spans in it.
+    pub fn add_span(&mut self, range: Range<usize>, style: SpanStyle) {
+        if range.is_empty() {
+            return;
+        }
+        let mut spans = Vec::with_capacity(self.spans.len() + 2);
+        for (span, span_style) in self.spans.drain(..) {
+            if span.end <= range.start || span.start >= range.end {
+                spans.push((span, span_style));
+                continue;
+            }
+            if span.start < range.start {
+                spans.push((span.start..range.start, span_style));
+            }
+            if span.end > range.end {
+                spans.push((range.end..span.end, span_style));
+            }
+        }
+        if style != self.defaults {
+            spans.push((range, style));
+        }
+        spans.sort_by_key(|(span, _)| span.start);
+        self.spans = spans;
+    }
+
+    pub fn get(&self, index: usize) -> SpanStyle {
+        self.spans
+            .iter()
+            .find(|(span, _)| span.contains(&index))
+            .map_or(self.defaults, |(_, style)| *style)
+    }
+
+    /// Splits off the styles from `index`, which start at 0 in the returned styles.
+    pub fn split_off(&mut self, index: usize) -> Self {
+        let mut new = Self::new(self.defaults);
+        let mut kept = Vec::with_capacity(self.spans.len());
+        for (span, style) in self.spans.drain(..) {
+            if span.start < index {
+                kept.push((span.start..span.end.min(index), style));
+            }
+            if span.end > index {
+                new.spans
+                    .push((span.start.max(index) - index..span.end - index, style));
+            }
+        }
+        self.spans = kept;
+        new
+    }
+}
+
+/// A line of a [`TextDecoration`] under, over or through a range of glyphs.
+///
+/// The coordinates are relative to the position the text is drawn at.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecorationLine {
+    pub x0: f64,
+    pub x1: f64,
+    /// The center of the line
+    pub y: f64,
+    pub thickness: f64,
+    pub style: TextDecorationStyle,
+    pub color: Color,
+}
+
+impl DecorationLine {
+    /// The path to stroke with [`DecorationLine::stroke`].
+    pub fn path(&self) -> BezPath {
+        let mut path = BezPath::new();
+        match self.style {
+            TextDecorationStyle::Double => {
+                let gap = self.thickness;
+                for y in [self.y - gap, self.y + gap] {
+                    path.move_to((self.x0, y));
+                    path.line_to((self.x1, y));
+                }
+            }
+            TextDecorationStyle::Wavy => {
+                let amplitude = self.thickness * 1.5;
+                let half_period = (self.thickness * 3.0).max(2.0);
+                let mut x = self.x0;
+                let mut up = true;
+                path.move_to((x, self.y));
+                while x < self.x1 {
+                    let next = (x + half_period).min(self.x1);
+                    let control_y = if up {
+                        self.y - amplitude
+                    } else {
+                        self.y + amplitude
+                    };
+                    path.quad_to(((x + next) / 2.0, control_y), (next, self.y));
+                    x = next;
+                    up = !up;
+                }
+            }
+            TextDecorationStyle::Solid
+            | TextDecorationStyle::Dotted
+            | TextDecorationStyle::Dashed => {
+                path.move_to((self.x0, self.y));
+                path.line_to((self.x1, self.y));
+            }
+        }
+        path
+    }
+
+    pub fn stroke(&self) -> Stroke {
+        let stroke = Stroke::new(self.thickness).with_caps(Cap::Butt);
+        match self.style {
+            TextDecorationStyle::Dotted => {
+                stroke.with_dashes(0.0, [self.thickness, self.thickness])
+            }
+            TextDecorationStyle::Dashed => {
+                stroke.with_dashes(0.0, [self.thickness * 3.0, self.thickness * 3.0])
+            }
+            _ => stroke,
+        }
+    }
+}
+
+/// The color glyphs are drawn with.
+pub(crate) fn glyph_color(glyph: &LayoutGlyph) -> Color {
+    glyph.color_opt.map_or(palette::css::BLACK, |c| {
+        Color::from_rgba8(c.r(), c.g(), c.b(), c.a())
+    })
+}
+
+/// Builds the decoration lines of a line of glyphs, merging consecutive glyphs with the same
+/// decoration, color and font size.
+pub(crate) fn decoration_lines(
+    glyphs: &[LayoutGlyph],
+    styles: &SpanStyles,
+    line_y: f32,
+    max_ascent: f32,
+) -> Vec<DecorationLine> {
+    let mut lines: Vec<DecorationLine> = Vec::new();
+    let mut last: Option<(TextDecoration, Color, f32, usize)> = None;
+    for glyph in glyphs {
+        let Some(decoration) = styles.get(glyph.start).decoration else {
+            last = None;
+            continue;
+        };
+        if decoration.line.is_none() {
+            last = None;
+            continue;
+        }
+        let color = decoration.color.unwrap_or_else(|| glyph_color(glyph));
+        let x0 = glyph.x as f64;
+        let x1 = (glyph.x + glyph.w) as f64;
+        if let Some((last_decoration, last_color, font_size, count)) = last {
+            if last_decoration == decoration && last_color == color && font_size == glyph.font_size
+            {
+                for line in &mut lines[lines.len() - count..] {
+                    line.x0 = line.x0.min(x0);
+                    line.x1 = line.x1.max(x1);
+                }
+                continue;
+            }
+        }
+
+        let font_size = glyph.font_size;
+        let thickness = decoration.thickness.unwrap_or((font_size / 14.0).max(1.0)) as f64;
+        let line_y = line_y as f64;
+        let mut ys = Vec::new();
+        if decoration.line.underline {
+            ys.push(line_y + (font_size * 0.1) as f64 + thickness / 2.0);
+        }
+        if decoration.line.overline {
+            ys.push(line_y - max_ascent as f64 + thickness / 2.0);
+        }
+        if decoration.line.line_through {
+            ys.push(line_y - (font_size * 0.3) as f64);
+        }
+        let count = ys.len();
+        lines.extend(ys.into_iter().map(|y| DecorationLine {
+            x0,
+            x1,
+            y,
+            thickness,
+            style: decoration.style,
+            color,
+        }));
+        last = Some((decoration, color, font_size, count));
+    }
+    lines
+}
+
+/// Groups consecutive glyphs with the same shadow.
+pub(crate) fn shadow_runs<'a>(
+    glyphs: &'a [LayoutGlyph],
+    styles: &SpanStyles,
+) -> Vec<(TextShadow, &'a [LayoutGlyph])> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    let mut current: Option<TextShadow> = None;
+    for (i, glyph) in glyphs.iter().enumerate() {
+        let shadow = styles.get(glyph.start).shadow;
+        if shadow != current {
+            if let Some(shadow) = current {
+                runs.push((shadow, &glyphs[start..i]));
+            }
+            start = i;
+            current = shadow;
+        }
+    }
+    if let Some(shadow) = current {
+        runs.push((shadow, &glyphs[start..]));
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmic_text::{fontdb, CacheKeyFlags};
+    use peniko::kurbo::PathEl;
+
+    use super::*;
+
+    /// A glyph for the byte at `index`, 10 pixels wide.
+    fn glyph(index: usize) -> LayoutGlyph {
+        LayoutGlyph {
+            start: index,
+            end: index + 1,
+            font_size: 14.0,
+            line_height_opt: None,
+            font_id: fontdb::ID::dummy(),
+            glyph_id: 0,
+            x: index as f32 * 10.0,
+            y: 0.0,
+            w: 10.0,
+            level: unicode_bidi::Level::ltr(),
+            x_offset: 0.0,
+            y_offset: 0.0,
+            color_opt: None,
+            metadata: 0,
+            cache_key_flags: CacheKeyFlags::empty(),
+        }
+    }
+
+    fn decorated(decoration: TextDecoration) -> SpanStyle {
+        SpanStyle {
+            decoration: Some(decoration),
+            ..SpanStyle::default()
+        }
+    }
+
+    #[test]
+    fn spans_replace_the_parts_of_previous_spans() {
+        let underline = decorated(TextDecoration::new(TextDecorationLine::UNDERLINE));
+        let overline = decorated(TextDecoration::new(TextDecorationLine::OVERLINE));
+        let mut styles = SpanStyles::default();
+        styles.add_span(0..10, underline);
+        styles.add_span(3..5, overline);
+        assert_eq!(styles.get(2), underline);
+        assert_eq!(styles.get(3), overline);
+        assert_eq!(styles.get(5), underline);
+        assert_eq!(styles.get(10), SpanStyle::default());
+        assert!(!styles.is_plain());
+
+        let mut split = styles.clone();
+        let end = split.split_off(4);
+        assert_eq!(split.get(3), overline);
+        assert_eq!(split.get(4), SpanStyle::default());
+        assert_eq!(end.get(0), overline);
+        assert_eq!(end.get(1), underline);
+        assert_eq!(end.get(6), SpanStyle::default());
+
+        // Setting the defaults removes the spans
+        styles.add_span(0..10, SpanStyle::default());
+        assert!(styles.is_plain());
+        assert!(!styles.has_word_spacing());
+    }
+
+    #[test]
+    fn consecutive_glyphs_share_decoration_lines() {
+        let red = palette::css::RED;
+        let mut styles = SpanStyles::default();
+        styles.add_span(
+            0..2,
+            decorated(TextDecoration::new(TextDecorationLine::UNDERLINE)),
+        );
+        styles.add_span(
+            2..3,
+            decorated(TextDecoration::new(TextDecorationLine::UNDERLINE).color(red)),
+        );
+        let glyphs: Vec<_> = (0..4).map(glyph).collect();
+
+        let lines = decoration_lines(&glyphs, &styles, 10.0, 12.0);
+        assert_eq!(lines.len(), 2);
+        assert_eq!((lines[0].x0, lines[0].x1), (0.0, 20.0));
+        assert_eq!(lines[0].color, palette::css::BLACK);
+        assert_eq!((lines[1].x0, lines[1].x1), (20.0, 30.0));
+        assert_eq!(lines[1].color, red);
+        // The thickness is derived from the font size, and underlines are below the baseline
+        assert_eq!(lines[0].thickness, 1.0);
+        assert!(lines[0].y > 10.0);
+    }
+
+    #[test]
+    fn each_line_of_a_decoration_is_placed_on_the_text() {
+        let mut styles = SpanStyles::default();
+        let line = TextDecorationLine::UNDERLINE
+            .union(TextDecorationLine::OVERLINE)
+            .union(TextDecorationLine::LINE_THROUGH);
+        styles.add_span(0..1, decorated(TextDecoration::new(line).thickness(2.0)));
+
+        let lines = decoration_lines(&[glyph(0)], &styles, 20.0, 12.0);
+        let ys: Vec<f64> = lines.iter().map(|line| line.y).collect();
+        assert_eq!(ys.len(), 3);
+        let (underline, overline, line_through) = (ys[0], ys[1], ys[2]);
+        assert!(overline < line_through && line_through < 20.0 && 20.0 < underline);
+        assert_eq!(overline, 20.0 - 12.0 + 1.0);
+        assert!(lines.iter().all(|line| line.thickness == 2.0));
+    }
+
+    #[test]
+    fn shadows_are_grouped_by_runs_of_glyphs() {
+        let shadow = TextShadow {
+            offset_x: 1.0,
+            ..TextShadow::default()
+        };
+        let mut styles = SpanStyles::default();
+        styles.add_span(
+            1..3,
+            SpanStyle {
+                shadow: Some(shadow),
+                ..SpanStyle::default()
+            },
+        );
+        let glyphs: Vec<_> = (0..4).map(glyph).collect();
+
+        let runs = shadow_runs(&glyphs, &styles);
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].0, shadow);
+        let starts: Vec<usize> = runs[0].1.iter().map(|glyph| glyph.start).collect();
+        assert_eq!(starts, vec![1, 2]);
+    }
+
+    #[test]
+    fn line_styles_have_matching_paths_and_strokes() {
+        let line = |style| DecorationLine {
+            x0: 0.0,
+            x1: 30.0,
+            y: 5.0,
+            thickness: 2.0,
+            style,
+            color: palette::css::BLACK,
+        };
+        let move_tos = |path: &BezPath| {
+            path.elements()
+                .iter()
+                .filter(|el| matches!(el, PathEl::MoveTo(_)))
+                .count()
+        };
+
+        let double = line(TextDecorationStyle::Double).path();
+        assert_eq!(move_tos(&double), 2);
+
+        let wavy = line(TextDecorationStyle::Wavy).path();
+        assert!(wavy
+            .elements()
+            .iter()
+            .any(|el| matches!(el, PathEl::QuadTo(..))));
+        assert!(matches!(
+            wavy.elements().last(),
+            Some(PathEl::QuadTo(_, end)) if end.x == 30.0 && end.y == 5.0
+        ));
+
+        assert!(line(TextDecorationStyle::Solid)
+            .stroke()
+            .dash_pattern
+            .is_empty());
+        assert_eq!(
+            line(TextDecorationStyle::Dotted)
+                .stroke()
+                .dash_pattern
+                .as_slice(),
+            &[2.0, 2.0]
+        );
+        assert_eq!(
+            line(TextDecorationStyle::Dashed)
+                .stroke()
+                .dash_pattern
+                .as_slice(),
+            &[6.0, 6.0]
+        );
+    }
+}
@@ -1,9 +1,10 @@
 use std::{ops::Range, sync::LazyLock};
 
-use crate::text::AttrsList;
+use crate::text::decoration::{decoration_lines, shadow_runs, SpanStyles};
+use crate::text::{AttrsList, DecorationLine, TextShadow};
 use cosmic_text::{
-    Affinity, Align, Buffer, BufferLine, Cursor, FontSystem, LayoutCursor, LayoutGlyph, LineEnding,
-    LineIter, Metrics, Scroll, Shaping, Wrap,
+    Affinity, Align, AttrsOwned, Buffer, BufferLine, Cursor, FontSystem, LayoutCursor, LayoutGlyph,
+    LineEnding, LineIter, Metrics, Scroll, Shaping, Wrap,
 };
 use parking_lot::Mutex;
 use peniko::kurbo::{Point, Size};
@@ -43,9 +44,28 @@ pub struct LayoutRun<'a> {
     pub line_height: f32,
     /// Width of line
     pub line_w: f32,
+    /// The decorations and shadows of the original text line
+    styles: &'a SpanStyles,
 }
 
-impl LayoutRun<'_> {
+impl<'a> LayoutRun<'a> {
+    /// The lines to draw under, over and through the glyphs, relative to the position of the
+    /// text
+    pub fn decorations(&self) -> Vec<DecorationLine> {
+        if self.styles.is_plain() {
+            return Vec::new();
+        }
+        decoration_lines(self.glyphs, self.styles, self.line_y, self.max_ascent)
+    }
+
+    /// The glyphs with a shadow, grouped by consecutive glyphs with the same shadow
+    pub fn shadows(&self) -> Vec<(TextShadow, &'a [LayoutGlyph])> {
+        if self.styles.is_plain() {
+            return Vec::new();
+        }
+        shadow_runs(self.glyphs, self.styles)
+    }
+
     /// Return the pixel span `Some((x_left, x_width))` of the highlighted area between `cursor_start`
     /// and `cursor_end` within this run, or None if the cursor range does not intersect this run.
     /// This may return widths of zero if `cursor_start == cursor_end`, if the run is empty, or if the
@@ -163,6 +183,7 @@ impl<'b> Iterator for LayoutRunIter<'b> {
                     line_top,
                     line_height,
                     line_w: layout_line.w,
+                    styles: &self.text_layout.line_styles[self.line_i],
                 });
             }
             self.line_i += 1;
@@ -203,6 +224,8 @@ pub struct HitPoint {
 pub struct TextLayout {
     buffer: Buffer,
     lines_range: Vec<Range<usize>>,
+    /// The decorations and shadows of each line
+    line_styles: Vec<SpanStyles>,
     width_opt: Option<f32>,
     height_opt: Option<f32>,
 }
@@ -218,6 +241,7 @@ impl TextLayout {
         TextLayout {
             buffer: Buffer::new_empty(Metrics::new(16.0, 16.0)),
             lines_range: Vec::new(),
+            line_styles: Vec::new(),
             width_opt: None,
             height_opt: None,
         }
@@ -232,25 +256,31 @@ impl TextLayout {
     pub fn set_text(&mut self, text: &str, attrs_list: AttrsList, align: Option<Align>) {
         self.buffer.lines.clear();
         self.lines_range.clear();
-        let mut attrs_list = attrs_list.0;
+        self.line_styles.clear();
+        let AttrsList(mut attrs_list, mut styles) = attrs_list;
+        let font_size = self.buffer.metrics().font_size;
         for (range, ending) in LineIter::new(text) {
             self.lines_range.push(range.clone());
             let line_text = &text[range];
-            let new_attrs = attrs_list
-                .clone()
-                .split_off(line_text.len() + ending.as_str().len());
-            let mut line =
-                BufferLine::new(line_text, ending, attrs_list.clone(), Shaping::Advanced);
+            let line_len = line_text.len() + ending.as_str().len();
+            let new_attrs = attrs_list.clone().split_off(line_len);
+            let new_styles = styles.split_off(line_len);
+            let mut line_attrs = attrs_list.clone();
+            apply_word_spacing(line_text, &mut line_attrs, &styles, font_size);
+            let mut line = BufferLine::new(line_text, ending, line_attrs, Shaping::Advanced);
             line.set_align(align);
             self.buffer.lines.push(line);
+            self.line_styles.push(styles);
             attrs_list = new_attrs;
+            styles = new_styles;
         }
         if self.buffer.lines.is_empty() {
             let mut line =
                 BufferLine::new("", LineEnding::default(), attrs_list, Shaping::Advanced);
             line.set_align(align);
             self.buffer.lines.push(line);
-            self.lines_range.push(0..0)
+            self.lines_range.push(0..0);
+            self.line_styles.push(styles);
         }
         self.buffer.set_scroll(Scroll::default());
 
@@ -541,3 +571,33 @@ impl TextLayout {
             })
     }
 }
+
+/// cosmic-text has no word spacing, so it is added to the letter spacing of spaces.
+fn apply_word_spacing(
+    text: &str,
+    attrs_list: &mut cosmic_text::AttrsList,
+    styles: &SpanStyles,
+    default_font_size: f32,
+) {
+    if !styles.has_word_spacing() {
+        return;
+    }
+    for (i, c) in text.char_indices() {
+        if c != ' ' && c != '\u{a0}' {
+            continue;
+        }
+        let word_spacing = styles.get(i).word_spacing;
+        if word_spacing == 0.0 {
+            continue;
+        }
+        let attrs = AttrsOwned::new(&attrs_list.get_span(i));
+        let font_size = attrs.metrics_opt.map_or(default_font_size, |metrics| {
+            Metrics::from(metrics).font_size
+        });
+        let letter_spacing = attrs.letter_spacing_opt.map_or(0.0, |spacing| spacing.0);
+        let attrs = attrs
+            .as_attrs()
+            .letter_spacing(letter_spacing + word_spacing / font_size);
+        attrs_list.add_span(i..i + c.len_utf8(), &attrs);
+    }
+}
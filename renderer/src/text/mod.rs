@@ -1,4 +1,5 @@
 mod attrs;
+mod decoration;
 mod layout;
 
 pub use attrs::{Attrs, AttrsList, AttrsOwned, FamilyOwned, LineHeightValue};
@@ -6,4 +7,7 @@ pub use cosmic_text::{
     fontdb, Affinity, Align, CacheKey, Cursor, Family, LayoutGlyph, LayoutLine, LineEnding,
     Stretch, Style, SubpixelBin, SwashCache, SwashContent, Weight, Wrap,
 };
+pub use decoration::{
+    DecorationLine, TextDecoration, TextDecorationLine, TextDecorationStyle, TextShadow,
+};
 pub use layout::{HitPoint, HitPosition, LayoutRun, TextLayout, FONT_SYSTEM};
@@ -0,0 +1,672 @@
+#![deny(missing_docs)]
+//! # Accessibility
+//!
+//! Views describe themselves to assistive technologies such as screen readers with an
+//! [`Accessibility`], returned by [`View::accessibility`](crate::View::accessibility) and
+//! refined with [`Decorators::accessibility`](crate::views::Decorators::accessibility).
+//!
+//! Every window keeps an [AccessKit](https://accesskit.dev) tree of its views. Each view
+//! is a node whose id is derived from its [`ViewId`], with its window bounds, children,
+//! focus and disabled state filled in from the view tree. After the first update, only the
+//! nodes that changed are sent again.
+//!
+//! Actions requested by assistive technologies are dispatched to their view as an
+//! [`Event::Accessibility`]:
+//! - [`Action::Focus`] focuses views that are focusable,
+//! - [`Action::Click`] runs the view's [`EventListener::Click`](crate::event::EventListener::Click)
+//!   handlers,
+//! - [`Action::ScrollIntoView`] scrolls the view into view,
+//! - other actions, such as [`Action::SetValue`], are handled by the view itself or by
+//!   [`EventListener::Accessibility`](crate::event::EventListener::Accessibility) handlers.
+
+mod adapter;
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+};
+
+pub use accesskit::{Action, ActionData, ActionRequest, NodeId, Role, Toggled, TreeUpdate};
+use accesskit::{Node, Tree};
+use slotmap::{Key, KeyData};
+
+pub(crate) use adapter::{AccessibilityRequest, Adapter};
+
+use crate::{
+    ViewId,
+    style::{Disabled, Focusable},
+    view_storage::VIEW_STORAGE,
+};
+
+thread_local! {
+    /// The roots of the windows whose tree was sent to an assistive technology.
+    static ACTIVE_ROOTS: RefCell<HashSet<ViewId>> = RefCell::new(HashSet::new());
+}
+
+/// Returns `true` if the window of `id` keeps an accessibility tree, so that changes to the
+/// view need to be sent.
+pub(crate) fn is_active(id: ViewId) -> bool {
+    ACTIVE_ROOTS.with_borrow(|roots| {
+        !roots.is_empty()
+            && VIEW_STORAGE
+                .with_borrow(|s| s.root_view_id(id))
+                .is_some_and(|root| roots.contains(&root))
+    })
+}
+
+/// A numeric value, such as the value of a slider.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NumericValue {
+    /// The current value.
+    pub value: f64,
+    /// The smallest value.
+    pub min: Option<f64>,
+    /// The largest value.
+    pub max: Option<f64>,
+    /// The amount the value changes by when incremented or decremented.
+    pub step: Option<f64>,
+}
+
+/// How a view is described to assistive technologies.
+///
+/// Every property is optional. When a view's [`View::accessibility`](crate::View::accessibility)
+/// is refined with [`Decorators::accessibility`](crate::views::Decorators::accessibility),
+/// the properties set by the decorator replace the ones of the view.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Accessibility {
+    role: Option<Role>,
+    label: Option<String>,
+    labelled_by: Option<ViewId>,
+    description: Option<String>,
+    value: Option<String>,
+    placeholder: Option<String>,
+    numeric: Option<NumericValue>,
+    toggled: Option<Toggled>,
+    selected: Option<bool>,
+    expanded: Option<bool>,
+    read_only: Option<bool>,
+    modal: Option<bool>,
+    scroll: Option<(f64, f64)>,
+    actions: Vec<Action>,
+}
+
+impl Accessibility {
+    /// A description with the given role.
+    pub fn new(role: Role) -> Self {
+        Self::default().role(role)
+    }
+
+    /// Sets what kind of control the view is. Views without a role are generic containers.
+    pub fn role(mut self, role: Role) -> Self {
+        self.role = Some(role);
+        self
+    }
+
+    /// Sets the name read for the view.
+    ///
+    /// Buttons, checkboxes, radio buttons, switches and list items without a label are
+    /// named after the text of their descendants.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Names the view after the text of another view, such as the label next to a checkbox.
+    pub fn labelled_by(mut self, id: ViewId) -> Self {
+        self.labelled_by = Some(id);
+        self
+    }
+
+    /// Sets a longer description, read after the label.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the text value, such as the text of a label or of a text input.
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Sets the hint shown in an empty text input.
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = Some(placeholder.into());
+        self
+    }
+
+    /// Sets the numeric value, such as the value of a slider.
+    pub fn numeric_value(mut self, value: NumericValue) -> Self {
+        self.numeric = Some(value);
+        self
+    }
+
+    /// Sets whether a checkbox, radio button or switch is checked.
+    pub fn toggled(mut self, toggled: bool) -> Self {
+        self.toggled = Some(if toggled {
+            Toggled::True
+        } else {
+            Toggled::False
+        });
+        self
+    }
+
+    /// Sets whether the view is selected, such as the selected item of a list.
+    pub fn selected(mut self, selected: bool) -> Self {
+        self.selected = Some(selected);
+        self
+    }
+
+    /// Sets whether the view is expanded, such as an open dropdown.
+    pub fn expanded(mut self, expanded: bool) -> Self {
+        self.expanded = Some(expanded);
+        self
+    }
+
+    /// Sets whether the value of the view can be changed by the user.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = Some(read_only);
+        self
+    }
+
+    /// Sets whether a dialog is modal, so assistive technologies keep to it and ignore the
+    /// views behind it.
+    pub fn modal(mut self, modal: bool) -> Self {
+        self.modal = Some(modal);
+        self
+    }
+
+    /// Sets the scroll offset of a scroll view.
+    pub fn scroll_offset(mut self, x: f64, y: f64) -> Self {
+        self.scroll = Some((x, y));
+        self
+    }
+
+    /// Adds an action the view supports, in addition to the ones derived from its state.
+    pub fn action(mut self, action: Action) -> Self {
+        if !self.actions.contains(&action) {
+            self.actions.push(action);
+        }
+        self
+    }
+
+    /// Replaces the properties of `self` with the ones set in `other`.
+    pub(crate) fn apply(mut self, other: Accessibility) -> Self {
+        fn merge<T>(a: &mut Option<T>, b: Option<T>) {
+            if b.is_some() {
+                *a = b;
+            }
+        }
+        merge(&mut self.role, other.role);
+        merge(&mut self.label, other.label);
+        merge(&mut self.labelled_by, other.labelled_by);
+        merge(&mut self.description, other.description);
+        merge(&mut self.value, other.value);
+        merge(&mut self.placeholder, other.placeholder);
+        merge(&mut self.numeric, other.numeric);
+        merge(&mut self.toggled, other.toggled);
+        merge(&mut self.selected, other.selected);
+        merge(&mut self.expanded, other.expanded);
+        merge(&mut self.read_only, other.read_only);
+        merge(&mut self.modal, other.modal);
+        merge(&mut self.scroll, other.scroll);
+        for action in other.actions {
+            self = self.action(action);
+        }
+        self
+    }
+
+    /// Returns `true` if the view takes its label from its descendants when it has none.
+    fn label_from_contents(&self) -> bool {
+        matches!(
+            self.role,
+            Some(
+                Role::Button
+                    | Role::CheckBox
+                    | Role::RadioButton
+                    | Role::Switch
+                    | Role::ListItem
+                    | Role::Tab
+                    | Role::Link
+                    | Role::MenuItem
+            )
+        )
+    }
+}
+
+/// The AccessKit node id of a view.
+pub fn node_id(id: ViewId) -> NodeId {
+    NodeId(id.data().as_ffi())
+}
+
+fn view_id(node: NodeId) -> ViewId {
+    ViewId::from(KeyData::from_ffi(node.0))
+}
+
+/// The description of a view, combining [`View::accessibility`](crate::View::accessibility)
+/// with the one set by [`Decorators::accessibility`](crate::views::Decorators::accessibility).
+fn view_accessibility(id: ViewId) -> Accessibility {
+    let base = id.view().borrow().accessibility().unwrap_or_default();
+    let decorated = id.state().borrow().accessibility.clone();
+    decorated.into_iter().fold(base, Accessibility::apply)
+}
+
+/// What a node is built from besides the description of its view, to rebuild it only when
+/// it changes.
+#[derive(PartialEq)]
+struct NodeInputs {
+    children: Vec<NodeId>,
+    /// The text of the descendants, only collected for views labelled by their contents.
+    contents: String,
+    bounds: accesskit::Rect,
+    disabled: bool,
+    focusable: bool,
+    /// The scale of the window, set on the root node.
+    scale: Option<f64>,
+}
+
+/// A node as it was last sent.
+struct CachedNode {
+    /// The description of the view, rebuilt when requested by
+    /// [`ViewId::request_accessibility`].
+    description: Accessibility,
+    inputs: NodeInputs,
+    node: Node,
+}
+
+/// The AccessKit tree of a window, remembering what was last sent to only send changes.
+#[derive(Default)]
+pub(crate) struct AccessibilityTree {
+    nodes: HashMap<NodeId, CachedNode>,
+    root: Option<NodeId>,
+}
+
+impl AccessibilityTree {
+    /// Returns the view of a node that is in the tree.
+    pub(crate) fn view_for_node(&self, node: NodeId) -> Option<ViewId> {
+        self.nodes.contains_key(&node).then(|| view_id(node))
+    }
+
+    /// Builds the nodes that changed since the last update, or the whole tree on the first
+    /// update or when the root changed.
+    ///
+    /// Only the nodes of views whose description was invalidated, or whose bounds, children,
+    /// state or contained text changed, are rebuilt.
+    pub(crate) fn update(&mut self, root: ViewId, scale: f64, focus: Option<ViewId>) -> TreeUpdate {
+        let root_node = node_id(root);
+        let full = self.root != Some(root_node);
+        if full {
+            self.deactivate();
+            self.nodes.clear();
+            self.root = Some(root_node);
+            ACTIVE_ROOTS.with_borrow_mut(|roots| roots.insert(root));
+        }
+
+        let mut visited = HashSet::new();
+        let mut changed = Vec::new();
+        self.build(root, Some(scale), None, &mut visited, &mut changed);
+        self.nodes.retain(|id, _| visited.contains(id));
+        // Keeps updates deterministic
+        changed.sort_by_key(|(id, _)| id.0);
+
+        let focus = focus
+            .map(node_id)
+            .filter(|focus| self.nodes.contains_key(focus))
+            .unwrap_or(root_node);
+        TreeUpdate {
+            nodes: changed,
+            tree: full.then(|| Tree::new(root_node)),
+            focus,
+        }
+    }
+
+    /// Updates the node of `id` and its visible descendants. The text of the view and its
+    /// descendants is appended to `text` if it is given.
+    fn build(
+        &mut self,
+        id: ViewId,
+        scale: Option<f64>,
+        text: Option<&mut String>,
+        visited: &mut HashSet<NodeId>,
+        changed: &mut Vec<(NodeId, Node)>,
+    ) {
+        let this = node_id(id);
+        visited.insert(this);
+        let dirty = std::mem::take(&mut id.state().borrow_mut().accessibility_dirty);
+        let (description, cached) = match self.nodes.remove(&this) {
+            Some(mut cached) if !dirty => (std::mem::take(&mut cached.description), Some(cached)),
+            cached => (view_accessibility(id), cached),
+        };
+
+        // The text of a label is its value, other views contain the text of their descendants
+        let value_text = description
+            .value
+            .as_deref()
+            .filter(|_| description.role == Some(Role::Label));
+        let label_from_contents = description.label.is_none() && description.label_from_contents();
+        let collect = label_from_contents || (text.is_some() && value_text.is_none());
+        let mut contents = String::new();
+        let mut children = Vec::new();
+        for child in id.children() {
+            if child.is_hidden() {
+                continue;
+            }
+            self.build(
+                child,
+                None,
+                collect.then_some(&mut contents),
+                visited,
+                changed,
+            );
+            children.push(node_id(child));
+        }
+        if let Some(text) = text {
+            let own = value_text.unwrap_or(&contents);
+            if !own.is_empty() {
+                if !text.is_empty() {
+                    text.push(' ');
+                }
+                text.push_str(own);
+            }
+        }
+
+        let inputs = {
+            let state = id.state();
+            let state = state.borrow();
+            let size = id.get_size().unwrap_or_default();
+            let origin = state.window_origin;
+            NodeInputs {
+                children,
+                contents: if label_from_contents {
+                    contents
+                } else {
+                    String::new()
+                },
+                bounds: accesskit::Rect::new(
+                    origin.x,
+                    origin.y,
+                    origin.x + size.width,
+                    origin.y + size.height,
+                ),
+                disabled: state.computed_style.get(Disabled),
+                focusable: state.computed_style.get(Focusable),
+                scale,
+            }
+        };
+
+        let previous = match cached {
+            Some(mut cached) if !dirty && cached.inputs == inputs => {
+                cached.description = description;
+                self.nodes.insert(this, cached);
+                return;
+            }
+            cached => cached.map(|cached| cached.node),
+        };
+        let node = build_node(&description, &inputs);
+        // A rebuilt description doesn't always change the node
+        if previous.as_ref() != Some(&node) {
+            changed.push((this, node.clone()));
+        }
+        self.nodes.insert(
+            this,
+            CachedNode {
+                description,
+                inputs,
+                node,
+            },
+        );
+    }
+
+    /// Forgets that the root of the tree is followed by an assistive technology.
+    fn deactivate(&mut self) {
+        if let Some(root) = self.root.take() {
+            ACTIVE_ROOTS.with_borrow_mut(|roots| roots.remove(&view_id(root)));
+        }
+    }
+}
+
+impl Drop for AccessibilityTree {
+    fn drop(&mut self) {
+        self.deactivate();
+    }
+}
+
+/// Builds the node of a view.
+fn build_node(description: &Accessibility, inputs: &NodeInputs) -> Node {
+    let mut node = Node::new(description.role.unwrap_or(Role::GenericContainer));
+    match &description.label {
+        Some(label) => node.set_label(label.as_str()),
+        None if !inputs.contents.is_empty() => node.set_label(inputs.contents.as_str()),
+        None => {}
+    }
+    if let Some(labelled_by) = description.labelled_by {
+        node.set_labelled_by(vec![node_id(labelled_by)]);
+    }
+    if let Some(value) = &description.description {
+        node.set_description(value.as_str());
+    }
+    if let Some(value) = &description.value {
+        node.set_value(value.as_str());
+    }
+    if let Some(placeholder) = &description.placeholder {
+        node.set_placeholder(placeholder.as_str());
+    }
+    if let Some(numeric) = description.numeric {
+        node.set_numeric_value(numeric.value);
+        if let Some(min) = numeric.min {
+            node.set_min_numeric_value(min);
+        }
+        if let Some(max) = numeric.max {
+            node.set_max_numeric_value(max);
+        }
+        if let Some(step) = numeric.step {
+            node.set_numeric_value_step(step);
+        }
+    }
+    if let Some(toggled) = description.toggled {
+        node.set_toggled(toggled);
+    }
+    if let Some(selected) = description.selected {
+        node.set_selected(selected);
+    }
+    if let Some(expanded) = description.expanded {
+        node.set_expanded(expanded);
+    }
+    if description.read_only == Some(true) {
+        node.set_read_only();
+    }
+    if description.modal == Some(true) {
+        node.set_modal();
+    }
+    if let Some((x, y)) = description.scroll {
+        node.set_scroll_x(x);
+        node.set_scroll_y(y);
+    }
+    for action in &description.actions {
+        node.add_action(*action);
+    }
+
+    if inputs.disabled {
+        node.set_disabled();
+    } else if inputs.focusable {
+        node.add_action(Action::Focus);
+    }
+    node.set_bounds(inputs.bounds);
+    node.set_children(inputs.children.clone());
+    if let Some(scale) = inputs.scale {
+        node.set_transform(accesskit::Affine::scale(scale));
+    }
+    node
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decorator_properties_replace_view_properties() {
+        let view = Accessibility::new(Role::Button)
+            .label("OK")
+            .action(Action::Click);
+        let decorated = view.apply(Accessibility::default().label("Save").action(Action::Click));
+        assert_eq!(decorated.role, Some(Role::Button));
+        assert_eq!(decorated.label.as_deref(), Some("Save"));
+        assert_eq!(decorated.actions, vec![Action::Click]);
+    }
+
+    #[cfg(feature = "testing")]
+    mod window {
+        use accesskit::{ActionData, ActionRequest, Node, NodeId, Role, Toggled, TreeUpdate};
+
+        use crate::{
+            accessibility::{is_active, node_id},
+            prelude::*,
+            testing::TestWindow,
+            views::{Checkbox, slider::Slider},
+        };
+
+        fn node(update: &TreeUpdate, id: NodeId) -> Option<&Node> {
+            update
+                .nodes
+                .iter()
+                .find(|(node, _)| *node == id)
+                .map(|(_, node)| node)
+        }
+
+        fn action(
+            target: ViewId,
+            action: accesskit::Action,
+            data: Option<ActionData>,
+        ) -> ActionRequest {
+            ActionRequest {
+                action,
+                target: node_id(target),
+                data,
+            }
+        }
+
+        #[test]
+        fn nodes_describe_the_views() {
+            let checked = RwSignal::new(false);
+            let mut window = TestWindow::new((300.0, 300.0), move || {
+                v_stack((
+                    button("Save").debug_name("save"),
+                    Checkbox::new_rw(checked).debug_name("check"),
+                    Slider::new_ranged(|| 25.0, 0.0..=50.0).debug_name("slider"),
+                    label(|| "Hello").debug_name("hello"),
+                ))
+            });
+
+            let update = window.accessibility_update();
+            assert!(update.tree.is_some());
+            let save = node(&update, node_id(window.find("save").unwrap())).unwrap();
+            assert_eq!(save.role(), Role::Button);
+            assert_eq!(save.label(), Some("Save"));
+            let check = node(&update, node_id(window.find("check").unwrap())).unwrap();
+            assert_eq!(check.role(), Role::CheckBox);
+            assert_eq!(check.toggled(), Some(Toggled::False));
+            let slider = node(&update, node_id(window.find("slider").unwrap())).unwrap();
+            assert_eq!(slider.role(), Role::Slider);
+            assert_eq!(slider.numeric_value(), Some(25.0));
+            assert_eq!(slider.max_numeric_value(), Some(50.0));
+            let hello = node(&update, node_id(window.find("hello").unwrap())).unwrap();
+            assert_eq!(hello.role(), Role::Label);
+            assert_eq!(hello.value(), Some("Hello"));
+        }
+
+        #[test]
+        fn only_changed_nodes_are_sent_again() {
+            let checked = RwSignal::new(false);
+            let mut window = TestWindow::new((300.0, 300.0), move || {
+                v_stack((
+                    button("Save").debug_name("save"),
+                    Checkbox::new_rw(checked).debug_name("check"),
+                ))
+            });
+            let save = node_id(window.find("save").unwrap());
+            let check = node_id(window.find("check").unwrap());
+
+            window.accessibility_update();
+            let update = window.accessibility_update();
+            assert!(update.tree.is_none());
+            assert!(update.nodes.is_empty());
+
+            checked.set(true);
+            let update = window.accessibility_update();
+            assert_eq!(node(&update, check).unwrap().toggled(), Some(Toggled::True));
+            assert!(node(&update, save).is_none());
+        }
+
+        #[test]
+        fn buttons_are_labelled_by_their_current_text() {
+            let text = RwSignal::new("Save".to_string());
+            let mut window = TestWindow::new((300.0, 300.0), move || {
+                button(label(move || text.get())).debug_name("save")
+            });
+            let save = node_id(window.find("save").unwrap());
+            let update = window.accessibility_update();
+            assert_eq!(node(&update, save).unwrap().label(), Some("Save"));
+
+            text.set("Saved".to_string());
+            let update = window.accessibility_update();
+            assert_eq!(node(&update, save).unwrap().label(), Some("Saved"));
+        }
+
+        #[test]
+        fn views_are_invalidated_only_once_the_tree_is_built() {
+            let mut window =
+                TestWindow::new((300.0, 300.0), || label(|| "Hello").debug_name("hello"));
+            let hello = window.find("hello").unwrap();
+            assert!(!is_active(hello));
+
+            window.accessibility_update();
+            assert!(is_active(hello));
+            hello.request_layout();
+            assert!(hello.state().borrow().accessibility_dirty);
+
+            drop(window);
+            assert!(!is_active(hello));
+        }
+
+        #[test]
+        fn actions_are_routed_to_their_view() {
+            let clicks = RwSignal::new(0);
+            let value = RwSignal::new(0.0);
+            let text = RwSignal::new(String::new());
+            let mut window = TestWindow::new((300.0, 300.0), move || {
+                v_stack((
+                    button("Save")
+                        .debug_name("save")
+                        .action(move || clicks.update(|clicks| *clicks += 1)),
+                    Slider::new_ranged(move || value.get(), 0.0..=50.0)
+                        .on_change_value(move |new| value.set(new))
+                        .debug_name("slider"),
+                    text_input(text).debug_name("input"),
+                ))
+            });
+            let save = window.find("save").unwrap();
+            let slider = window.find("slider").unwrap();
+            let input = window.find("input").unwrap();
+            window.accessibility_update();
+
+            window.accessibility_action(action(save, accesskit::Action::Click, None));
+            assert_eq!(clicks.get_untracked(), 1);
+
+            window.accessibility_action(action(
+                slider,
+                accesskit::Action::SetValue,
+                Some(ActionData::NumericValue(40.0)),
+            ));
+            assert_eq!(value.get_untracked(), 40.0);
+
+            window.accessibility_action(action(input, accesskit::Action::Focus, None));
+            assert_eq!(window.focused(), Some(input));
+            window.accessibility_action(action(
+                input,
+                accesskit::Action::SetValue,
+                Some(ActionData::Value("typed".into())),
+            ));
+            assert_eq!(text.get_untracked(), "typed");
+        }
+    }
+}
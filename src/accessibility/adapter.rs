@@ -0,0 +1,186 @@
+//! Connects the accessibility tree of a window to the assistive technologies of the platform
+//! with the AccessKit adapters.
+//!
+//! The adapters may call their handlers from other threads, so requests are sent to the event
+//! loop as [`UserEvent::Accessibility`] and answered by the window on the main thread.
+#![cfg_attr(
+    not(any(target_os = "windows", target_os = "macos", target_os = "linux")),
+    allow(dead_code)
+)]
+
+use accesskit::{ActionHandler, ActionRequest, ActivationHandler, DeactivationHandler, TreeUpdate};
+use winit::window::{Window, WindowId};
+
+use crate::app::{Application, UserEvent};
+
+/// A request of an assistive technology for a window.
+pub(crate) enum AccessibilityRequest {
+    /// An assistive technology started following the window and needs the whole tree.
+    InitialTree,
+    /// An action to dispatch to a view.
+    Action(ActionRequest),
+    /// No assistive technology follows the window anymore.
+    Deactivate,
+}
+
+struct Handler {
+    window_id: WindowId,
+}
+
+impl Handler {
+    fn send(&self, request: AccessibilityRequest) {
+        Application::send_proxy_event(UserEvent::Accessibility {
+            window_id: self.window_id,
+            request,
+        });
+    }
+}
+
+impl ActivationHandler for Handler {
+    fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+        // The tree is sent by the window once it gets the request
+        self.send(AccessibilityRequest::InitialTree);
+        None
+    }
+}
+
+impl ActionHandler for Handler {
+    fn do_action(&mut self, request: ActionRequest) {
+        self.send(AccessibilityRequest::Action(request));
+    }
+}
+
+impl DeactivationHandler for Handler {
+    fn deactivate_accessibility(&mut self) {
+        self.send(AccessibilityRequest::Deactivate);
+    }
+}
+
+/// The AccessKit adapter of a window. It does nothing on platforms without one.
+pub(crate) struct Adapter {
+    #[cfg(target_os = "windows")]
+    inner: accesskit_windows::SubclassingAdapter,
+    #[cfg(target_os = "macos")]
+    inner: accesskit_macos::SubclassingAdapter,
+    #[cfg(target_os = "linux")]
+    inner: accesskit_unix::Adapter,
+}
+
+impl Adapter {
+    /// Creates the adapter of `window`, which must not have been shown yet.
+    #[cfg(target_os = "windows")]
+    pub(crate) fn new(window: &dyn Window) -> Option<Self> {
+        use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+        let RawWindowHandle::Win32(handle) = window.window_handle().ok()?.as_raw() else {
+            return None;
+        };
+        let handler = || Handler {
+            window_id: window.id(),
+        };
+        Some(Self {
+            inner: accesskit_windows::SubclassingAdapter::new(
+                accesskit_windows::HWND(handle.hwnd.get() as *mut _),
+                handler(),
+                handler(),
+            ),
+        })
+    }
+
+    /// Creates the adapter of `window`, which must not have been shown yet.
+    #[cfg(target_os = "macos")]
+    pub(crate) fn new(window: &dyn Window) -> Option<Self> {
+        use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+        let RawWindowHandle::AppKit(handle) = window.window_handle().ok()?.as_raw() else {
+            return None;
+        };
+        let handler = || Handler {
+            window_id: window.id(),
+        };
+        Some(Self {
+            inner: unsafe {
+                accesskit_macos::SubclassingAdapter::new(
+                    handle.ns_view.as_ptr(),
+                    handler(),
+                    handler(),
+                )
+            },
+        })
+    }
+
+    /// Creates the adapter of `window`, which must not have been shown yet.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn new(window: &dyn Window) -> Option<Self> {
+        let handler = || Handler {
+            window_id: window.id(),
+        };
+        let mut adapter = Self {
+            inner: accesskit_unix::Adapter::new(handler(), handler(), handler()),
+        };
+        adapter.bounds_changed(window);
+        Some(adapter)
+    }
+
+    /// Creates the adapter of `window`, which must not have been shown yet.
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+    pub(crate) fn new(_window: &dyn Window) -> Option<Self> {
+        Some(Self {})
+    }
+
+    /// Sends the update built by `update` if an assistive technology follows the window.
+    #[cfg_attr(
+        not(any(target_os = "windows", target_os = "macos", target_os = "linux")),
+        allow(unused_variables)
+    )]
+    pub(crate) fn update_if_active(&mut self, update: impl FnOnce() -> TreeUpdate) {
+        #[cfg(any(target_os = "windows", target_os = "macos"))]
+        if let Some(events) = self.inner.update_if_active(update) {
+            events.raise();
+        }
+        #[cfg(target_os = "linux")]
+        self.inner.update_if_active(update);
+    }
+
+    /// Tells the assistive technologies whether the window has the focus.
+    #[cfg_attr(
+        not(any(target_os = "macos", target_os = "linux")),
+        allow(unused_variables)
+    )]
+    pub(crate) fn focused(&mut self, focused: bool) {
+        #[cfg(target_os = "macos")]
+        if let Some(events) = self.inner.update_view_focus_state(focused) {
+            events.raise();
+        }
+        #[cfg(target_os = "linux")]
+        self.inner.update_window_focus_state(focused);
+    }
+
+    /// Tells the assistive technologies where the window is on the screen, which they can't
+    /// find out by themselves on Linux.
+    #[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+    pub(crate) fn bounds_changed(&mut self, window: &dyn Window) {
+        #[cfg(target_os = "linux")]
+        {
+            let outer_position = window.outer_position().unwrap_or_default().cast::<f64>();
+            let outer_size = window.outer_size().cast::<f64>();
+            let surface_position = window.surface_position().cast::<f64>();
+            let surface_size = window.surface_size().cast::<f64>();
+            let outer = accesskit::Rect::new(
+                outer_position.x,
+                outer_position.y,
+                outer_position.x + outer_size.width,
+                outer_position.y + outer_size.height,
+            );
+            let inner_x = outer_position.x + surface_position.x;
+            let inner_y = outer_position.y + surface_position.y;
+            let inner = accesskit::Rect::new(
+                inner_x,
+                inner_y,
+                inner_x + surface_size.width,
+                inner_y + surface_size.height,
+            );
+            self.inner.set_root_window_bounds(outer, inner);
+        }
+    }
+}
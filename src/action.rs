@@ -155,11 +155,20 @@ impl TimerToken {
 
     /// Cancel a timer.
     pub fn cancel(self) {
+        #[cfg(feature = "testing")]
+        if let Some(clock) = floem_reactive::testing::VirtualClock::current() {
+            clock.cancel(self.0);
+            return;
+        }
         add_app_update_event(AppUpdateEvent::CancelTimer { timer: self });
     }
 }
 
 /// Execute a callback after a specified duration.
+///
+/// With the `testing` feature, the callback is scheduled on the
+/// [`VirtualClock`](floem_reactive::testing::VirtualClock) of the current thread if one is
+/// installed.
 pub fn exec_after(duration: Duration, action: impl FnOnce(TimerToken) + 'static) -> TimerToken {
     let view = get_current_view();
     let action = move |token| {
@@ -170,6 +179,13 @@ pub fn exec_after(duration: Duration, action: impl FnOnce(TimerToken) + 'static)
     };
 
     let token = TimerToken::next();
+
+    #[cfg(feature = "testing")]
+    if let Some(clock) = floem_reactive::testing::VirtualClock::current() {
+        clock.schedule(token.0, duration, move || action(token));
+        return token;
+    }
+
     let deadline = Instant::now() + duration;
     add_app_update_event(AppUpdateEvent::RequestTimer {
         timer: Timer {
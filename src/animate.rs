@@ -20,6 +20,19 @@ use std::time::{Duration, Instant};
 #[cfg(target_arch = "wasm32")]
 use web_time::{Duration, Instant};
 
+/// The current time for animations and transitions.
+///
+/// With the `testing` feature, this is the time of the
+/// [`VirtualClock`](floem_reactive::testing::VirtualClock) of the current thread if one is
+/// installed, so tests can step animations deterministically.
+pub(crate) fn now() -> Instant {
+    #[cfg(all(feature = "testing", not(target_arch = "wasm32")))]
+    if let Some(clock) = floem_reactive::testing::VirtualClock::current() {
+        return clock.now();
+    }
+    Instant::now()
+}
+
 /// Holds a resolved prop, along with the associated frame id and easing function
 #[derive(Clone, Debug)]
 pub struct KeyFrameProp {
@@ -803,7 +816,7 @@ impl Animation {
                 started_on,
                 elapsed,
             } => {
-                let duration = Instant::now() - *started_on;
+                let duration = now() - *started_on;
                 Some(*elapsed + duration)
             }
             AnimState::PassFinished { elapsed, .. } => Some(*elapsed),
@@ -824,8 +837,7 @@ impl Animation {
                 started_on,
                 elapsed,
             } => {
-                let now = Instant::now();
-                let duration = now - *started_on;
+                let duration = now() - *started_on;
                 let og_elapsed = *elapsed;
                 *elapsed = duration;
 
@@ -857,8 +869,7 @@ impl Animation {
                 started_on,
                 elapsed,
             } => {
-                let now = Instant::now();
-                let duration = now - *started_on;
+                let duration = now() - *started_on;
                 *elapsed = duration;
 
                 if self.props_in_ext_progress.is_empty() {
@@ -879,7 +890,7 @@ impl Animation {
                         self.reverse_once.set(true);
                     }
                     self.state = AnimState::PassInProgress {
-                        started_on: Instant::now(),
+                        started_on: now(),
                         elapsed: Duration::ZERO,
                     }
                 }
@@ -898,7 +909,7 @@ impl Animation {
                         }
                     } else {
                         self.state = AnimState::PassInProgress {
-                            started_on: Instant::now(),
+                            started_on: now(),
                             elapsed: Duration::ZERO,
                         }
                     }
@@ -930,7 +941,7 @@ impl Animation {
             AnimStateCommand::Resume => {
                 if let AnimState::Paused { elapsed } = &self.state {
                     self.state = AnimState::PassInProgress {
-                        started_on: Instant::now(),
+                        started_on: now(),
                         elapsed: elapsed.unwrap_or(Duration::ZERO),
                     }
                 }
@@ -940,7 +951,7 @@ impl Animation {
                 self.folded_style.map.clear();
                 self.repeat_count = 0;
                 self.state = AnimState::PassInProgress {
-                    started_on: Instant::now(),
+                    started_on: now(),
                     elapsed: Duration::ZERO,
                 }
             }
@@ -949,7 +960,7 @@ impl Animation {
                 self.folded_style.map.clear();
                 self.repeat_count = 0;
                 self.state = AnimState::PassInProgress {
-                    started_on: Instant::now(),
+                    started_on: now(),
                     elapsed: Duration::ZERO,
                 }
             }
@@ -17,6 +17,7 @@ use winit::{
 };
 
 use crate::{
+    accessibility::AccessibilityRequest,
     action::{Timer, TimerToken},
     app_handle::ApplicationHandle,
     clipboard::Clipboard,
@@ -106,6 +107,10 @@ pub(crate) enum UserEvent {
     GpuResourcesUpdate {
         window_id: WindowId,
     },
+    Accessibility {
+        window_id: WindowId,
+        request: AccessibilityRequest,
+    },
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -216,6 +221,7 @@ impl Application {
         let (sender, receiver) = channel();
 
         *EVENT_LOOP_PROXY.lock() = Some((event_loop_proxy.clone(), sender));
+        crate::ext_event::register_sync_signal_waker();
         unsafe {
             Clipboard::init(event_loop.display_handle().unwrap().as_raw());
         }
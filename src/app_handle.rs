@@ -99,6 +99,11 @@ impl ApplicationHandle {
                     panic!("Sent a gpu resource update after it had already been initialized");
                 }
             }
+            UserEvent::Accessibility { window_id, request } => {
+                if let Some(handle) = self.window_handles.get_mut(&window_id) {
+                    handle.accessibility_request(request);
+                }
+            }
         }
     }
 
@@ -574,6 +579,7 @@ impl ApplicationHandle {
         for trigger in ext_events {
             trigger.notify();
         }
+        floem_reactive::flush_sync_signals();
 
         self.handle_updates_for_all_windows();
     }
@@ -1,8 +1,10 @@
-use floem_reactive::Scope;
+use accesskit::Action;
+use floem_reactive::{RwSignal, Scope, SignalGet, SignalUpdate, with_scope};
 use floem_renderer::Renderer as FloemRenderer;
 use floem_renderer::gpu_resources::{GpuResourceError, GpuResources};
 use peniko::kurbo::{Affine, Point, Rect, RoundedRect, Shape, Size, Vec2};
 use std::{
+    any::Any,
     ops::{Deref, DerefMut},
     rc::Rc,
     sync::Arc,
@@ -28,16 +30,20 @@ use crate::dropped_file::FileDragEvent;
 use crate::easing::{Easing, Linear};
 use crate::menu::Menu;
 use crate::renderer::Renderer;
-use crate::style::{Disabled, DisplayProp, Focusable, Hidden, PointerEvents, PointerEventsProp};
-use crate::view_state::IsHiddenState;
+use crate::style::{
+    CursorStyle, Disabled, DisplayProp, Focusable, Hidden, PointerEvents, PointerEventsProp,
+};
+use crate::view_state::{IsHiddenState, ViewStyleProps};
 use crate::{
-    action::{exec_after, show_context_menu},
+    AnyView,
+    action::{add_overlay, exec_after, show_context_menu},
     event::{Event, EventListener, EventPropagation},
     id::ViewId,
     inspector::CaptureState,
     style::{Style, StyleProp, ZIndex},
-    view::{View, paint_bg, paint_border, paint_outline},
+    view::{View, paint_backdrop_blur, paint_bg, paint_border, paint_outline},
     view_state::ChangeFlags,
+    views::Decorators,
     window_state::WindowState,
 };
 
@@ -60,11 +66,82 @@ pub(crate) struct MoveListeners {
 
 pub(crate) type CleanupListeners = Vec<Rc<dyn Fn()>>;
 
+pub(crate) type DragPayloadFn = dyn Fn() -> Rc<dyn Any>;
+pub(crate) type DragPreviewFn = dyn Fn() -> AnyView;
+
+/// A typed drop handler added with [`Decorators::on_drop_if`].
+pub(crate) struct DropHandler {
+    /// Whether the handler accepts the payload dropped at a point in the view.
+    pub(crate) accepts: Box<dyn Fn(&dyn Any, Point) -> bool>,
+    pub(crate) on_drop: Box<dyn Fn(&dyn Any, Point)>,
+}
+
 pub struct DragState {
     pub(crate) id: ViewId,
     pub(crate) offset: Vec2,
     pub(crate) released_at: Option<Instant>,
     pub(crate) release_location: Option<Point>,
+    /// The value returned by the payload function of the dragged view, if it has one.
+    pub(crate) payload: Option<Rc<dyn Any>>,
+    /// The overlay showing the drag preview, painted instead of the dragged view.
+    pub(crate) preview: Option<DragPreview>,
+}
+
+pub(crate) struct DragPreview {
+    pub(crate) overlay: ViewId,
+    /// The position of the overlay in the window.
+    pub(crate) position: RwSignal<Point>,
+    pub(crate) scope: Scope,
+}
+
+impl DragState {
+    /// Starts dragging `id`, grabbed at `offset` from its origin, with the pointer at
+    /// `window_point` in window coordinates.
+    fn start(id: ViewId, offset: Vec2, window_point: Point) -> Self {
+        let payload = id.state().borrow().drag_payload.clone();
+        let preview_fn = id.state().borrow().drag_preview.clone();
+        let preview = preview_fn.map(|preview_fn| {
+            let scope = Scope::new();
+            with_scope(scope, || {
+                let position = RwSignal::new(window_point - offset);
+                let overlay = add_overlay(preview_fn().style(move |s| {
+                    let position = position.get();
+                    s.inset_left(position.x)
+                        .inset_top(position.y)
+                        .pointer_events_none()
+                        .z_index(1000)
+                }));
+                DragPreview {
+                    overlay,
+                    position,
+                    scope,
+                }
+            })
+        });
+        DragState {
+            id,
+            offset,
+            released_at: None,
+            release_location: None,
+            payload: payload.map(|payload| payload()),
+            preview,
+        }
+    }
+
+    /// The handler of `target` accepting the payload of this drag at `point`, if any, with
+    /// the payload.
+    pub(crate) fn drop_handler(
+        &self,
+        target: ViewId,
+        point: Point,
+    ) -> Option<(Rc<DropHandler>, Rc<dyn Any>)> {
+        let payload = self.payload.clone()?;
+        let handlers = target.state().borrow().drop_handlers.clone();
+        handlers
+            .into_iter()
+            .find(|handler| (handler.accepts)(&*payload, point))
+            .map(|handler| (handler, payload))
+    }
 }
 
 pub(crate) enum FrameUpdate {
@@ -98,6 +175,31 @@ impl EventCx<'_> {
         self.window_state.update_focus(id, keyboard_navigation);
     }
 
+    /// Makes `view_id` the drop target if it's the innermost view under the pointer accepting
+    /// the dragged payload, or shows the drop isn't allowed if it rejects it.
+    fn update_drop_target(&mut self, view_id: ViewId, point: Point) {
+        if self.window_state.drop_target.is_some()
+            || view_id.state().borrow().drop_handlers.is_empty()
+        {
+            return;
+        }
+        let accepted = self
+            .window_state
+            .dragging
+            .as_ref()
+            .and_then(|dragging| dragging.drop_handler(view_id, point))
+            .is_some();
+        if accepted {
+            self.window_state.drop_target = Some(view_id);
+            // An inner view rejecting the payload doesn't matter when an outer view accepts it
+            if self.window_state.cursor == Some(CursorStyle::NotAllowed) {
+                self.window_state.cursor = None;
+            }
+        } else if self.window_state.cursor.is_none() {
+            self.window_state.cursor = Some(CursorStyle::NotAllowed);
+        }
+    }
+
     /// Internal method used by Floem. This can be called from parent `View`s to propagate an event to the child `View`.
     pub(crate) fn unconditional_view_event(
         &mut self,
@@ -298,6 +400,7 @@ impl EventCx<'_> {
                     if rect.contains(current.logical_point()) {
                         if self.window_state.is_dragging() {
                             self.window_state.dragging_over.insert(view_id);
+                            self.update_drop_target(view_id, current.logical_point());
                             view_id.apply_event(&EventListener::DragOver, &event);
                         } else {
                             self.window_state.hovered.insert(view_id);
@@ -326,16 +429,20 @@ impl EventCx<'_> {
                             {
                                 // update the mouse position if the view is dragging and not released
                                 dragging.offset = drag_start.to_vec2();
+                                if let Some(preview) = &dragging.preview {
+                                    preview.position.set(
+                                        self.window_state.last_cursor_location - dragging.offset,
+                                    );
+                                }
                                 self.window_state.request_paint(view_id);
                             } else if offset.x.abs() + offset.y.abs() > 1.0 {
                                 // start dragging when moved 1 px
                                 self.window_state.active = None;
-                                self.window_state.dragging = Some(DragState {
-                                    id: view_id,
-                                    offset: drag_start.to_vec2(),
-                                    released_at: None,
-                                    release_location: None,
-                                });
+                                self.window_state.dragging = Some(DragState::start(
+                                    view_id,
+                                    drag_start.to_vec2(),
+                                    self.window_state.last_cursor_location,
+                                ));
                                 self.update_active(view_id);
                                 self.window_state.request_paint(view_id);
                                 view_id.apply_event(&EventListener::DragStart, &event);
@@ -368,32 +475,41 @@ impl EventCx<'_> {
                         }
 
                         if !directed {
-                            if on_view {
-                                if let Some(dragging) = self.window_state.dragging.as_mut() {
-                                    let dragging_id = dragging.id;
-                                    if view_id
+                            if on_view && self.window_state.is_dragging() {
+                                let point = state.logical_point();
+                                let handler = self
+                                    .window_state
+                                    .dragging
+                                    .as_ref()
+                                    .and_then(|dragging| dragging.drop_handler(view_id, point));
+                                let dropped = if let Some((handler, payload)) = handler {
+                                    (handler.on_drop)(&*payload, point);
+                                    true
+                                } else {
+                                    view_id
                                         .apply_event(&EventListener::Drop, &event)
                                         .is_some_and(|prop| prop.is_processed())
-                                    {
-                                        // if the drop is processed, we set dragging to none so that the animation
-                                        // for the dragged view back to its original position isn't played.
-                                        self.window_state.dragging = None;
-                                        self.window_state.request_paint(view_id);
+                                };
+                                if dropped {
+                                    // if the drop is processed, we end the drag without the animation
+                                    // for the dragged view back to its original position.
+                                    self.window_state.request_paint(view_id);
+                                    if let Some(dragging_id) = self.window_state.end_drag(None) {
                                         dragging_id.apply_event(&EventListener::DragEnd, &event);
                                     }
                                 }
                             }
-                        } else if let Some(dragging) = self
+                        } else if self
                             .window_state
                             .dragging
-                            .as_mut()
-                            .filter(|d| d.id == view_id)
+                            .as_ref()
+                            .is_some_and(|d| d.id == view_id && d.released_at.is_none())
                         {
-                            let dragging_id = dragging.id;
-                            dragging.released_at = Some(Instant::now());
-                            dragging.release_location = Some(state.logical_point());
-                            self.window_state.request_paint(view_id);
-                            dragging_id.apply_event(&EventListener::DragEnd, &event);
+                            if let Some(dragging_id) =
+                                self.window_state.end_drag(Some(state.logical_point()))
+                            {
+                                dragging_id.apply_event(&EventListener::DragEnd, &event);
+                            }
                         }
 
                         let last_pointer_down = view_state.borrow_mut().last_pointer_down.take();
@@ -473,6 +589,27 @@ impl EventCx<'_> {
                         view_id.apply_event(&EventListener::Click, &event);
                     }
                 }
+                Event::Accessibility(request) => match request.action {
+                    Action::Focus => {
+                        if view_state.borrow().computed_style.get(Focusable) {
+                            self.window_state.update_focus(view_id, true);
+                            return (EventPropagation::Stop, PointerEventConsumed::Yes);
+                        }
+                    }
+                    Action::Click => {
+                        if view_id
+                            .apply_event(&EventListener::Click, &event)
+                            .is_some_and(|prop| prop.is_processed())
+                        {
+                            return (EventPropagation::Stop, PointerEventConsumed::Yes);
+                        }
+                    }
+                    Action::ScrollIntoView => {
+                        view_id.scroll_to(None);
+                        return (EventPropagation::Stop, PointerEventConsumed::Yes);
+                    }
+                    _ => {}
+                },
                 Event::WindowResized(_) => {
                     if view_state.borrow().has_style_selectors.has_responsive() {
                         view_id.request_style();
@@ -576,6 +713,7 @@ pub struct InteractionState {
     pub(crate) is_clicking: bool,
     pub(crate) is_dark_mode: bool,
     pub(crate) is_file_hover: bool,
+    pub(crate) is_drag_over: bool,
     pub(crate) using_keyboard_navigation: bool,
 }
 
@@ -603,7 +741,7 @@ impl<'a> StyleCx<'a> {
             current: Default::default(),
             direct: Default::default(),
             saved: Default::default(),
-            now: Instant::now(),
+            now: crate::animate::now(),
             saved_disabled: Default::default(),
             saved_selected: Default::default(),
             saved_hidden: Default::default(),
@@ -631,6 +769,7 @@ impl<'a> StyleCx<'a> {
             is_clicking: self.window_state.is_clicking(id),
             is_dark_mode: self.window_state.is_dark_mode(),
             is_file_hover: self.window_state.is_file_hover(id),
+            is_drag_over: self.window_state.is_drag_over(id),
             using_keyboard_navigation: self.window_state.keyboard_navigation,
         }
     }
@@ -1075,7 +1214,8 @@ pub struct PaintCx<'a> {
     pub(crate) saved_clips: Vec<Option<RoundedRect>>,
     pub(crate) saved_z_indexes: Vec<Option<i32>>,
     pub gpu_resources: Option<GpuResources>,
-    pub window: Arc<dyn Window>,
+    /// The window being painted, or `None` when painting headless.
+    pub window: Option<Arc<dyn Window>>,
     #[cfg(feature = "vello")]
     pub layer_count: usize,
     #[cfg(feature = "vello")]
@@ -1174,22 +1314,32 @@ impl PaintCx<'_> {
                 self.set_z_index(z_index);
             }
 
+            paint_backdrop_blur(self, &view_style_props, size);
+            let effects = self.push_effects_layer(id, &view_style_props, size);
+
             paint_bg(self, &view_style_props, size);
 
             view.borrow_mut().paint(self);
             paint_border(self, &layout_props, &view_style_props, size);
-            paint_outline(self, &view_style_props, size)
+            paint_outline(self, &view_style_props, size);
+
+            if effects {
+                self.pop_effects_layer();
+            }
         }
         let mut drag_set_to_none = false;
 
         if let Some(dragging) = self.window_state.dragging.as_ref() {
-            if dragging.id == id {
+            // A drag preview is painted as an overlay instead of the dragged view
+            if dragging.id == id && dragging.preview.is_none() {
                 let transform = if let Some((released_at, release_location)) =
                     dragging.released_at.zip(dragging.release_location)
                 {
                     let easing = Linear;
                     const ANIMATION_DURATION_MS: f64 = 300.0;
-                    let elapsed = released_at.elapsed().as_millis() as f64;
+                    let elapsed = crate::animate::now()
+                        .saturating_duration_since(released_at)
+                        .as_millis() as f64;
                     let progress = elapsed / ANIMATION_DURATION_MS;
 
                     if !(easing.finished(progress)) {
@@ -1232,7 +1382,7 @@ impl PaintCx<'_> {
                         view_style_props.read_explicit(
                             &style,
                             &style,
-                            &Instant::now(),
+                            &crate::animate::now(),
                             &mut _new_frame,
                         );
                     }
@@ -1246,10 +1396,14 @@ impl PaintCx<'_> {
 
                     CURRENT_DRAG_PAINTING_ID.set(Some(id));
 
+                    let effects = self.push_effects_layer(id, &view_style_props, size);
                     paint_bg(self, &view_style_props, size);
                     view.borrow_mut().paint(self);
                     paint_border(self, &layout_props, &view_style_props, size);
                     paint_outline(self, &view_style_props, size);
+                    if effects {
+                        self.pop_effects_layer();
+                    }
 
                     self.restore();
 
@@ -1264,6 +1418,38 @@ impl PaintCx<'_> {
         self.restore();
     }
 
+    /// Pushes a layer for the opacity and filter of a view, covering the view and its
+    /// children. Returns `true` if a layer was pushed, which must then be popped with
+    /// [`PaintCx::pop_effects_layer`].
+    fn push_effects_layer(&mut self, id: ViewId, style: &ViewStyleProps, size: Size) -> bool {
+        let opacity = style.opacity().clamp(0.0, 1.0);
+        let filter = style.filter();
+        if opacity >= 1.0 && filter.is_identity() {
+            return false;
+        }
+        // The layout rect includes the children, in window coordinates
+        let bounds = self
+            .transform
+            .inverse()
+            .transform_rect_bbox(id.layout_rect())
+            .union(size.to_rect());
+        // Pixels up to three standard deviations away are changed by the blur
+        let blur = filter.blur.max(0.0) * 3.0;
+        let bounds = bounds.inflate(blur, blur);
+        if bounds.is_zero_area() {
+            return false;
+        }
+        self.push_filter_layer(opacity, filter, &bounds);
+        // Layers pushed by the view for clipping are popped before the effects layer
+        self.save();
+        true
+    }
+
+    fn pop_effects_layer(&mut self) {
+        self.restore();
+        self.pop_layer();
+    }
+
     /// Clip the drawing area to the given shape.
     pub fn clip(&mut self, shape: &impl Shape) {
         #[cfg(feature = "vello")]
@@ -1434,6 +1620,13 @@ impl PaintState {
     pub(crate) fn set_scale(&mut self, scale: f64) {
         self.renderer_mut().set_scale(scale);
     }
+
+    /// A paint state for a window without a surface, painting into an offscreen pixmap.
+    pub(crate) fn new_headless(scale: f64, size: Size) -> Self {
+        Self::Initialized {
+            renderer: Renderer::new_offscreen(scale, size),
+        }
+    }
 }
 
 pub struct UpdateCx<'a> {
@@ -1527,3 +1720,175 @@ fn animations_on_create(id: ViewId) {
 
     id.children().into_iter().for_each(animations_on_create);
 }
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use floem_renderer::tiny_skia::Pixmap;
+    use peniko::kurbo::Vec2;
+    use ui_events::pointer::PointerButton;
+
+    use crate::{event::EventListener, prelude::*, style::CursorStyle, testing::TestWindow};
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Card(usize);
+
+    /// Drops of cards by the name of the view they were dropped on.
+    type Drops = Rc<RefCell<Vec<(&'static str, usize)>>>;
+
+    fn card(number: usize) -> Empty {
+        empty()
+            .debug_name("card")
+            .style(|s| s.size(40.0, 40.0))
+            .draggable_with(move || Card(number))
+            .drag_preview(|| empty().debug_name("preview").style(|s| s.size(10.0, 10.0)))
+    }
+
+    fn drop_zone(
+        name: &'static str,
+        drops: &Drops,
+        accepts: fn(&Card) -> bool,
+        child: impl IntoView + 'static,
+    ) -> Container {
+        let drops = drops.clone();
+        container(child).debug_name(name).on_drop_if(
+            move |card: &Card, _| accepts(card),
+            move |card: &Card, _| drops.borrow_mut().push((name, card.0)),
+        )
+    }
+
+    fn start_drag(window: &mut TestWindow) {
+        let card = window.rect(window.find("card").unwrap()).center();
+        window.pointer_down(card, PointerButton::Primary, 1);
+        window.pointer_move(card + Vec2::new(5.0, 5.0));
+    }
+
+    #[test]
+    fn the_innermost_accepting_view_gets_the_drop() {
+        let drops = Drops::default();
+        let mut window = TestWindow::new((300.0, 200.0), {
+            let drops = drops.clone();
+            move || {
+                h_stack((
+                    card(7),
+                    drop_zone(
+                        "outer",
+                        &drops,
+                        |_| true,
+                        drop_zone("inner", &drops, |_| true, empty()).style(|s| s.size_full()),
+                    )
+                    .style(|s| s.size(150.0, 150.0).padding(25.0)),
+                ))
+            }
+        });
+        let inner = window.rect(window.find("inner").unwrap()).center();
+
+        start_drag(&mut window);
+        assert!(window.find("preview").is_some());
+        window.pointer_move(inner);
+        assert_eq!(window.cursor(), None);
+        window.pointer_up(inner, PointerButton::Primary, 1);
+
+        assert_eq!(*drops.borrow(), vec![("inner", 7)]);
+        assert!(window.find("preview").is_none());
+    }
+
+    #[test]
+    fn a_rejecting_view_shows_a_not_allowed_cursor() {
+        let drops = Drops::default();
+        let mut window = TestWindow::new((300.0, 200.0), {
+            let drops = drops.clone();
+            move || {
+                h_stack((
+                    card(7),
+                    drop_zone("odd", &drops, |card| card.0 % 2 == 1, empty())
+                        .style(|s| s.size(60.0, 60.0)),
+                    drop_zone("even", &drops, |card| card.0 % 2 == 0, empty())
+                        .style(|s| s.size(60.0, 60.0)),
+                ))
+            }
+        });
+        let odd = window.rect(window.find("odd").unwrap()).center();
+        let even = window.rect(window.find("even").unwrap()).center();
+
+        start_drag(&mut window);
+        window.pointer_move(even);
+        assert_eq!(window.cursor(), Some(CursorStyle::NotAllowed));
+        window.pointer_move(odd);
+        assert_eq!(window.cursor(), None);
+
+        window.pointer_move(even);
+        window.pointer_up(even, PointerButton::Primary, 1);
+        assert!(drops.borrow().is_empty());
+    }
+
+    #[test]
+    fn escape_cancels_the_drag() {
+        let drops = Drops::default();
+        let drag_ends = RwSignal::new(0);
+        let mut window = TestWindow::new((300.0, 200.0), {
+            let drops = drops.clone();
+            move || {
+                h_stack((
+                    card(7).on_event_stop(EventListener::DragEnd, move |_| {
+                        drag_ends.update(|ends| *ends += 1)
+                    }),
+                    drop_zone("zone", &drops, |_| true, empty()).style(|s| s.size(60.0, 60.0)),
+                ))
+            }
+        });
+        let zone = window.rect(window.find("zone").unwrap()).center();
+
+        start_drag(&mut window);
+        window.pointer_move(zone);
+        assert!(window.find("preview").is_some());
+
+        window.key_press(Key::Named(NamedKey::Escape), Modifiers::empty());
+        assert!(window.find("preview").is_none());
+        assert_eq!(drag_ends.get_untracked(), 1);
+
+        // Releasing the button afterwards doesn't drop the card
+        window.pointer_up(zone, PointerButton::Primary, 1);
+        assert!(drops.borrow().is_empty());
+        assert_eq!(drag_ends.get_untracked(), 1);
+    }
+    fn rgb(pixmap: &Pixmap, x: u32, y: u32) -> (u8, u8, u8) {
+        let pixel = pixmap.pixel(x, y).unwrap();
+        (pixel.red(), pixel.green(), pixel.blue())
+    }
+
+    #[test]
+    fn opacity_fades_the_view_and_its_children() {
+        let mut window = TestWindow::new((100.0, 100.0), || {
+            container(empty().style(|s| s.size(20.0, 20.0).background(palette::css::RED)))
+                .style(|s| s.padding(40.0).opacity(0.5))
+        });
+        let pixels = window.render();
+
+        let (red, green, blue) = rgb(&pixels, 50, 50);
+        assert_eq!(red, 255);
+        assert!(green.abs_diff(128) <= 1 && blue.abs_diff(128) <= 1);
+        assert_eq!(rgb(&pixels, 30, 50), rgb(&pixels, 5, 5));
+    }
+
+    #[test]
+    fn blur_spreads_past_the_clip_of_the_view() {
+        let mut window = TestWindow::new((100.0, 100.0), || {
+            container(
+                clip(empty().style(|s| s.size(60.0, 60.0).background(palette::css::RED)))
+                    .style(|s| s.size(20.0, 20.0).blur(3.0)),
+            )
+            .style(|s| s.padding(40.0))
+        });
+        let pixels = window.render();
+        let background = rgb(&pixels, 5, 5);
+
+        assert_eq!(rgb(&pixels, 50, 50), (255, 0, 0));
+        // the blur reaches past the 40..60 square clipped by the view
+        let (_, green, _) = rgb(&pixels, 37, 50);
+        assert!(green < background.1 - 20, "{green}");
+        // but the child is cut at the clip before it's blurred
+        assert_eq!(rgb(&pixels, 75, 75), background);
+    }
+}
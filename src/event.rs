@@ -1,3 +1,4 @@
+use accesskit::ActionRequest;
 use peniko::kurbo::{Affine, Point, Size, Vec2};
 use ui_events::{
     ScrollDelta,
@@ -102,6 +103,8 @@ pub enum EventListener {
     WindowScaleChanged,
     /// Receives [`Event::DroppedFile`]
     DroppedFiles,
+    /// Receives [`Event::Accessibility`]
+    Accessibility,
 }
 
 #[derive(Debug, Clone)]
@@ -126,6 +129,8 @@ pub enum Event {
     FocusGained,
     FocusLost,
     WindowScaleChanged(f64),
+    /// An action requested by an assistive technology, sent to its target view.
+    Accessibility(ActionRequest),
 }
 
 impl Event {
@@ -176,6 +181,7 @@ impl Event {
             | Event::ImeDisabled
             | Event::ImePreedit { .. }
             | Event::ImeCommit(_)
+            | Event::Accessibility(_)
             | Event::FileDrag(
                 FileDragEvent::DragEntered { .. }
                 | FileDragEvent::DragMoved { .. }
@@ -299,7 +305,8 @@ impl Event {
             | Event::WindowMaximizeChanged(_)
             | Event::WindowScaleChanged(_)
             | Event::WindowGotFocus
-            | Event::WindowLostFocus => {}
+            | Event::WindowLostFocus
+            | Event::Accessibility(_) => {}
         }
         self
     }
@@ -337,6 +344,7 @@ impl Event {
             Event::FocusGained => Some(EventListener::FocusGained),
             Event::ThemeChanged(_) => Some(EventListener::ThemeChanged),
             Event::FileDrag(FileDragEvent::DragDropped { .. }) => Some(EventListener::DroppedFiles),
+            Event::Accessibility(_) => Some(EventListener::Accessibility),
             _ => None, // TODO
         }
     }
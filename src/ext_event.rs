@@ -83,10 +83,22 @@ impl ExtEventHandler {
             // causes another trigger to be registered
             EXT_EVENT_HANDLER.queue.lock().push_back(trigger);
         }
+        self.wake();
+    }
+
+    /// Wake up the event loop so it processes the queued triggers and any
+    /// [`SyncSignal`](floem_reactive::SyncSignal) written from another thread
+    pub fn wake(&self) {
         Application::send_proxy_event(UserEvent::Idle);
     }
 }
 
+/// Route the wakeups of [`SyncSignal`](floem_reactive::SyncSignal)s written from other threads
+/// through the [`ExtEventHandler`]
+pub(crate) fn register_sync_signal_waker() {
+    floem_reactive::set_sync_signal_waker(|| EXT_EVENT_HANDLER.wake());
+}
+
 pub fn register_ext_trigger(trigger: ExtSendTrigger) {
     EXT_EVENT_HANDLER.add_trigger(trigger);
 }
@@ -196,6 +196,18 @@ impl ViewId {
         VIEW_STORAGE.with_borrow(|s| s.parent.get(*self).cloned().flatten())
     }
 
+    /// Returns `true` if this view is `ancestor` or one of its direct or indirect children.
+    pub fn is_descendant_of(&self, ancestor: ViewId) -> bool {
+        let mut id = Some(*self);
+        while let Some(current) = id {
+            if current == ancestor {
+                return true;
+            }
+            id = current.parent();
+        }
+        false
+    }
+
     /// Get the root view of the window that the given view is in
     pub fn root(&self) -> Option<ViewId> {
         VIEW_STORAGE.with_borrow_mut(|s| {
@@ -362,6 +374,10 @@ impl ViewId {
 
     /// Request that this view have it's layout pass run
     pub fn request_layout(&self) {
+        // Layout changes usually come with a change of the content of the view
+        if crate::accessibility::is_active(*self) {
+            self.request_accessibility();
+        }
         self.request_changes(ChangeFlags::LAYOUT)
     }
 
@@ -370,6 +386,12 @@ impl ViewId {
         self.root().and_then(window_id_for_root)
     }
 
+    /// Request that the description of this view for assistive technologies be built again,
+    /// after a change to the state reported by [`View::accessibility`].
+    pub fn request_accessibility(&self) {
+        self.state().borrow_mut().accessibility_dirty = true;
+    }
+
     /// Request that this view have it's paint pass run
     pub fn request_paint(&self) {
         self.add_update_message(UpdateMessage::RequestPaint);
@@ -414,6 +436,21 @@ impl ViewId {
         self.add_update_message(UpdateMessage::ClearFocus(*self));
     }
 
+    /// Keeps the keyboard focus inside of this view, such as for a modal dialog.
+    ///
+    /// Tab navigation only cycles through the views inside of this view, and key events that
+    /// no focused view handles are sent to this view instead of the window. The focus is
+    /// restored to the view focused before when the trap is released with
+    /// [`release_focus`](Self::release_focus) or this view is removed as an overlay.
+    pub fn trap_focus(&self) {
+        self.add_update_message(UpdateMessage::TrapFocus(*self));
+    }
+
+    /// Releases the focus trap set by [`trap_focus`](Self::trap_focus).
+    pub fn release_focus(&self) {
+        self.add_update_message(UpdateMessage::ReleaseFocus(*self));
+    }
+
     /// Set the system context menu that should be shown when this view is right-clicked
     pub fn update_context_menu(&self, menu: impl Fn() -> Menu + 'static) {
         self.state().borrow_mut().context_menu = Some(Rc::new(menu));
@@ -445,6 +482,24 @@ impl ViewId {
         self.add_update_message(UpdateMessage::Inspect);
     }
 
+    /// Renders this view and its children offscreen, and calls `on_image` with the result.
+    ///
+    /// The view is painted with tiny-skia into an image of its laid out size times `scale`,
+    /// in premultiplied RGBA, over the window background. Rendering happens once pending
+    /// style and layout changes are processed, so this can be called right after building
+    /// or changing the view. Use [`render_view_to_image`](crate::render_view_to_image) for
+    /// a view that isn't in a window.
+    ///
+    /// If the view is removed, or its window is closed, before the image is rendered,
+    /// `on_image` is dropped without being called.
+    pub fn render_to_image(&self, scale: f64, on_image: impl FnOnce(peniko::ImageBrush) + 'static) {
+        self.add_update_message(UpdateMessage::RenderToImage(
+            *self,
+            scale,
+            Box::new(on_image),
+        ));
+    }
+
     /// Scrolls the view and all direct and indirect children to bring the view to be
     /// visible. The optional rectangle can be used to add an additional offset and intersection.
     pub fn scroll_to(&self, rect: Option<Rect>) {
@@ -176,6 +176,7 @@
 //!
 //! For additional information about animation, [see here](crate::animate::Animation).
 
+pub mod accessibility;
 pub mod action;
 pub mod animate;
 mod app;
@@ -202,6 +203,8 @@ mod renderer;
 pub mod responsive;
 mod screen_layout;
 pub mod style;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod theme;
 pub mod unit;
 mod update;
@@ -217,11 +220,13 @@ pub(crate) mod window_state;
 mod window_tracking;
 pub mod receiver_signal {
     //! Signals from Channels, Futures, and Streams.
+    mod async_memo;
     mod channel_signal;
     mod common;
     mod future_signal;
     mod resource;
     mod stream_signal;
+    pub use async_memo::*;
     pub use channel_signal::*;
     pub use common::*;
     pub use future_signal::*;
@@ -246,7 +251,7 @@ pub use taffy;
 pub use ui_events;
 pub use view::{AnyView, IntoView, View, default_compute_layout, recursively_layout_view};
 pub use view_state::{Stack, StackOffset};
-pub use window::{close_window, new_window};
+pub use window::{close_window, new_window, render_view_to_image};
 pub use window_id::{Urgency, WindowIdExt};
 pub use window_state::WindowState;
 
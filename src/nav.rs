@@ -46,7 +46,7 @@ pub(crate) fn view_arrow_navigation(key: NamedKey, window_state: &mut WindowStat
     let mut focusable: Vec<ViewId> = window_state.focusable.iter().copied().collect();
     focusable.retain(|id| {
         let layout = id.layout_rect();
-        direction_target.contains(layout.center()) && *id != focused
+        direction_target.contains(layout.center()) && *id != focused && id.is_descendant_of(view)
     });
 
     // Find the best target in a single pass with a priority scoring system
@@ -0,0 +1,246 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use floem_reactive::{
+    Scope, SignalGet, SignalTracker, SignalUpdate, SignalWith, WriteSignal, batch,
+    create_rw_signal, create_tracker, untrack,
+};
+use futures::{
+    future::{AbortHandle, Abortable},
+    task::{ArcWake, waker},
+};
+
+use crate::ext_event::{ExtSendTrigger, register_ext_trigger};
+
+use super::{
+    super::common::{CustomExecutor, EventLoopExecutor, executors::*},
+    AsyncMemo,
+};
+
+#[cfg(feature = "tokio")]
+use super::super::common::TokioExecutor;
+
+/// The in-flight part of an [`AsyncMemo`] run, handed to the executor after the first poll.
+///
+/// It resolves to `None` if the run was cancelled because the dependencies changed.
+/// Signals read while polling it are not tracked.
+pub struct AsyncTask<Fut> {
+    inner: Abortable<Pin<Box<Fut>>>,
+}
+
+impl<Fut: Future> Future for AsyncTask<Fut> {
+    type Output = Option<Fut::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        untrack(|| Pin::new(&mut this.inner).poll(cx)).map(Result::ok)
+    }
+}
+
+/// A builder for creating customized `AsyncMemo` instances.
+///
+/// Created via `AsyncMemo::custom()`. Allows fine-grained control over:
+/// - Executor type (event loop, tokio, custom)
+pub struct AsyncMemoBuilder<Fut, E> {
+    f: Box<dyn Fn() -> Fut + 'static>,
+    executor: E,
+}
+
+impl<Fut> AsyncMemoBuilder<Fut, EventLoopExecutor>
+where
+    Fut: Future + 'static,
+{
+    pub(super) fn new(f: impl Fn() -> Fut + 'static) -> Self {
+        Self {
+            f: Box::new(f),
+            executor: EventLoopExecutor,
+        }
+    }
+}
+
+// Builder methods for customization
+impl<Fut, E> AsyncMemoBuilder<Fut, E>
+where
+    Fut: Future + 'static,
+{
+    /// Use the main event loop as the executor.
+    ///
+    /// The future does not need to be `Send`. This is the default.
+    pub fn event_loop(self) -> AsyncMemoBuilder<Fut, EventLoopExecutor> {
+        AsyncMemoBuilder {
+            f: self.f,
+            executor: EventLoopExecutor,
+        }
+    }
+
+    /// Use tokio::spawn as the executor.
+    ///
+    /// The future must be `Send + 'static`. It is still polled once on the main thread so
+    /// the signals read before the first `.await` are tracked. Requires the `tokio` feature.
+    #[cfg(feature = "tokio")]
+    pub fn tokio_spawn(self) -> AsyncMemoBuilder<Fut, TokioExecutor>
+    where
+        Fut: Send,
+        Fut::Output: Send,
+    {
+        AsyncMemoBuilder {
+            f: self.f,
+            executor: TokioExecutor,
+        }
+    }
+
+    /// Use a custom executor function.
+    ///
+    /// The executor receives the task and signals to update when it completes. Cancelled
+    /// tasks resolve to `None` as soon as they are polled again.
+    pub fn executor<F>(self, executor: F) -> AsyncMemoBuilder<Fut, CustomExecutor<F>>
+    where
+        F: Fn(AsyncTask<Fut>, WriteSignal<Option<Fut::Output>>, WriteSignal<bool>, ExtSendTrigger)
+            + 'static,
+    {
+        AsyncMemoBuilder {
+            f: self.f,
+            executor: CustomExecutor(executor),
+        }
+    }
+}
+
+impl<Fut, T, E> AsyncMemoBuilder<Fut, EventLoopExecutor>
+where
+    T: 'static,
+    E: 'static,
+    Fut: Future<Output = Result<T, E>> + 'static,
+{
+    pub fn build(self) -> AsyncMemo<T, E> {
+        build_async_memo(self.f, event_loop_future)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<Fut, T, E> AsyncMemoBuilder<Fut, TokioExecutor>
+where
+    T: Send + 'static,
+    E: Send + 'static,
+    Fut: Future<Output = Result<T, E>> + Send + 'static,
+{
+    pub fn build(self) -> AsyncMemo<T, E> {
+        build_async_memo(self.f, tokio_spawn_future)
+    }
+}
+
+impl<Fut, T, E, F> AsyncMemoBuilder<Fut, CustomExecutor<F>>
+where
+    T: 'static,
+    E: 'static,
+    Fut: Future<Output = Result<T, E>> + 'static,
+    F: Fn(AsyncTask<Fut>, WriteSignal<Option<Result<T, E>>>, WriteSignal<bool>, ExtSendTrigger)
+        + 'static,
+{
+    pub fn build(self) -> AsyncMemo<T, E> {
+        build_async_memo(self.f, self.executor.0)
+    }
+}
+
+struct TriggerWake(ExtSendTrigger);
+
+impl ArcWake for TriggerWake {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        register_ext_trigger(arc_self.0);
+    }
+}
+
+/// Everything kept alive for one run. Dropping it cancels the run.
+struct AsyncRun {
+    abort: AbortHandle,
+    _tracker: Option<SignalTracker>,
+}
+
+impl Drop for AsyncRun {
+    fn drop(&mut self) {
+        self.abort.abort();
+    }
+}
+
+fn build_async_memo<Fut, T, E, F>(f: Box<dyn Fn() -> Fut + 'static>, executor: F) -> AsyncMemo<T, E>
+where
+    T: 'static,
+    E: 'static,
+    Fut: Future<Output = Result<T, E>> + 'static,
+    F: Fn(AsyncTask<Fut>, WriteSignal<Option<Result<T, E>>>, WriteSignal<bool>, ExtSendTrigger)
+        + 'static,
+{
+    let cx = Scope::current();
+    let (result_read, result_write) = cx.create_signal(None);
+    let (finished_read, finished_write) = cx.create_signal(false);
+
+    let complete = move |result: Result<T, E>| {
+        batch(|| {
+            result_write.set(Some(result));
+            finished_write.set(true);
+        });
+    };
+
+    cx.create_effect(move |prev: Option<AsyncRun>| {
+        // Cancel the previous run before starting a new one. Its trigger and signals
+        // were created in this effect's scope, so they are already disposed.
+        drop(prev);
+
+        let (abort, registration) = AbortHandle::new_pair();
+        // Calling `f` and the first poll happen inside the effect, so the signals read
+        // before the first `.await` are tracked.
+        let mut inner = Abortable::new(Box::pin(f()), registration);
+
+        let trigger = ExtSendTrigger::new();
+        let waker = waker(Arc::new(TriggerWake(trigger)));
+        let mut context = Context::from_waker(&waker);
+
+        match Pin::new(&mut inner).poll(&mut context) {
+            Poll::Ready(result) => {
+                if let Ok(result) = result {
+                    complete(result);
+                }
+                return AsyncRun {
+                    abort,
+                    _tracker: None,
+                };
+            }
+            Poll::Pending => {}
+        }
+
+        if finished_read.get_untracked() {
+            finished_write.set(false);
+        }
+
+        // The executor writes into signals owned by this run, which are forwarded to
+        // the memo. They are disposed with the run, so a cancelled run never writes.
+        let run_result = create_rw_signal(None);
+        let run_finished = create_rw_signal(false);
+        let tracker = create_tracker(move || {
+            if let Some(Some(result)) = run_result.try_update(Option::take) {
+                complete(result);
+            }
+        });
+        tracker.track(|| run_result.with(|_| {}));
+
+        executor(
+            AsyncTask { inner },
+            run_result.write_only(),
+            run_finished.write_only(),
+            trigger,
+        );
+
+        AsyncRun {
+            abort,
+            _tracker: Some(tracker),
+        }
+    });
+
+    AsyncMemo {
+        result: result_read,
+        finished: finished_read,
+    }
+}
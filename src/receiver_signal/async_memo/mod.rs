@@ -0,0 +1,382 @@
+//! Async effects and memos that rerun when the signals they read change.
+
+mod builder;
+
+use super::common::EventLoopExecutor;
+pub use builder::{AsyncMemoBuilder, AsyncTask};
+use floem_reactive::{ReadSignal, SignalGet, SignalWith};
+
+/// The state of an [`AsyncMemo`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsyncState<T, E> {
+    /// The future for the current dependencies hasn't completed yet.
+    Pending,
+    /// The future completed successfully.
+    Ready(T),
+    /// The future completed with an error.
+    Error(E),
+}
+
+impl<T, E> AsyncState<T, E> {
+    pub fn is_pending(&self) -> bool {
+        matches!(self, AsyncState::Pending)
+    }
+
+    pub fn is_ready(&self) -> bool {
+        matches!(self, AsyncState::Ready(_))
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self, AsyncState::Error(_))
+    }
+
+    pub fn as_ref(&self) -> AsyncState<&T, &E> {
+        match self {
+            AsyncState::Pending => AsyncState::Pending,
+            AsyncState::Ready(v) => AsyncState::Ready(v),
+            AsyncState::Error(e) => AsyncState::Error(e),
+        }
+    }
+}
+
+/// A value computed by an async function that reruns whenever the signals it reads change.
+///
+/// The function is called inside an effect and the returned future is polled once right
+/// away, so every signal read before the first `.await` is tracked. When one of them
+/// changes, the in-flight future is cancelled before the function runs again:
+/// - it is dropped without being polled again on the event loop executor,
+/// - it is aborted on tokio and custom executors, which then resolve it to `None`,
+/// - its result is never written, whatever the executor does with it.
+///
+/// While a new future is in flight the memo is [`Pending`](AsyncState::Pending), but the
+/// last successful value stays available through [`AsyncMemo::with_value`].
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let user = create_async_memo(move || {
+///     let id = user_id.get();
+///     async move { fetch_user(id).await }
+/// });
+///
+/// // Full customization
+/// let user = AsyncMemo::custom(move || {
+///     let id = user_id.get();
+///     async move { fetch_user(id).await }
+/// })
+/// .tokio_spawn()
+/// .build();
+/// ```
+pub struct AsyncMemo<T, E> {
+    pub(super) result: ReadSignal<Option<Result<T, E>>>,
+    pub(super) finished: ReadSignal<bool>,
+}
+
+/// An [`AsyncMemo`] run only for its side effects.
+pub type AsyncEffect<E> = AsyncMemo<(), E>;
+
+impl<T, E> Copy for AsyncMemo<T, E> {}
+
+impl<T, E> Clone for AsyncMemo<T, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static, E: 'static> AsyncMemo<T, E> {
+    /// Creates a new async memo that runs its futures on the main event loop.
+    ///
+    /// See [`create_async_memo`].
+    pub fn new<Fut>(f: impl Fn() -> Fut + 'static) -> Self
+    where
+        Fut: std::future::Future<Output = Result<T, E>> + 'static,
+    {
+        create_async_memo(f)
+    }
+
+    /// Creates an async memo builder for full customization.
+    ///
+    /// Use this when you need a different executor (tokio, custom, etc.)
+    pub fn custom<Fut>(f: impl Fn() -> Fut + 'static) -> AsyncMemoBuilder<Fut, EventLoopExecutor>
+    where
+        Fut: std::future::Future<Output = Result<T, E>> + 'static,
+    {
+        AsyncMemoBuilder::new(f)
+    }
+
+    /// Returns `true` while the future for the current dependencies is in flight.
+    pub fn is_pending(&self) -> bool {
+        !self.finished.get()
+    }
+
+    /// Applies a closure to the current state, and subscribes to it.
+    pub fn with_state<O>(&self, f: impl FnOnce(AsyncState<&T, &E>) -> O) -> O {
+        let finished = self.finished.get();
+        self.result.with(|result| match result {
+            _ if !finished => f(AsyncState::Pending),
+            Some(Ok(v)) => f(AsyncState::Ready(v)),
+            Some(Err(e)) => f(AsyncState::Error(e)),
+            None => f(AsyncState::Pending),
+        })
+    }
+
+    /// Returns a clone of the current state, and subscribes to it.
+    pub fn state(&self) -> AsyncState<T, E>
+    where
+        T: Clone,
+        E: Clone,
+    {
+        self.with_state(|state| match state {
+            AsyncState::Pending => AsyncState::Pending,
+            AsyncState::Ready(v) => AsyncState::Ready(v.clone()),
+            AsyncState::Error(e) => AsyncState::Error(e.clone()),
+        })
+    }
+
+    /// Applies a closure to the value of the last future that completed successfully,
+    /// even if a newer one is pending.
+    pub fn with_value<O>(&self, f: impl FnOnce(Option<&T>) -> O) -> O {
+        self.result
+            .with(|result| f(result.as_ref().and_then(|r| r.as_ref().ok())))
+    }
+
+    /// Returns the value of the last future that completed successfully, even if a newer
+    /// one is pending.
+    pub fn value(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.with_value(|v| v.cloned())
+    }
+
+    /// Returns the error of the last future, if it failed.
+    pub fn error(&self) -> Option<E>
+    where
+        E: Clone,
+    {
+        self.result
+            .with(|result| result.as_ref().and_then(|r| r.as_ref().err()).cloned())
+    }
+}
+
+/// Creates an [`AsyncMemo`] from an async function, run on the main event loop.
+///
+/// The future does not need to be `Send`. The signals read before its first `.await` are
+/// tracked, and a change to any of them cancels the in-flight future and runs `f` again.
+pub fn create_async_memo<T, E, Fut>(f: impl Fn() -> Fut + 'static) -> AsyncMemo<T, E>
+where
+    T: 'static,
+    E: 'static,
+    Fut: std::future::Future<Output = Result<T, E>> + 'static,
+{
+    AsyncMemoBuilder::new(f).build()
+}
+
+/// Creates an [`AsyncEffect`], an async function run on the main event loop whenever the
+/// signals read before its first `.await` change.
+///
+/// A change cancels the previous run if it hasn't completed yet. The returned handle
+/// exposes whether the latest run is pending, succeeded or failed.
+pub fn create_async_effect<E, Fut>(f: impl Fn() -> Fut + 'static) -> AsyncEffect<E>
+where
+    E: 'static,
+    Fut: std::future::Future<Output = Result<(), E>> + 'static,
+{
+    AsyncMemoBuilder::new(f).build()
+}
+
+impl<T, E> AsyncMemo<T, E> {
+    /// Lets the closest suspense know that the memo was read in a tracked context.
+    /// Untracked reads don't wait for the memo.
+    fn register_read(&self) {
+        crate::views::register_async_read(self.finished, None);
+    }
+}
+
+impl<T, E> SignalWith<Option<Result<T, E>>> for AsyncMemo<T, E> {
+    fn id(&self) -> floem_reactive::ReactiveId {
+        floem_reactive::SignalWith::id(&self.result)
+    }
+
+    fn with<O>(&self, f: impl FnOnce(&Option<Result<T, E>>) -> O) -> O
+    where
+        Option<Result<T, E>>: 'static,
+    {
+        self.register_read();
+        floem_reactive::SignalWith::with(&self.result, f)
+    }
+
+    fn try_with<O>(&self, f: impl FnOnce(Option<&Option<Result<T, E>>>) -> O) -> O
+    where
+        Option<Result<T, E>>: 'static,
+    {
+        self.register_read();
+        floem_reactive::SignalWith::try_with(&self.result, f)
+    }
+}
+
+impl<T, E> floem_reactive::SignalTrack<Option<Result<T, E>>> for AsyncMemo<T, E> {
+    fn id(&self) -> floem_reactive::ReactiveId {
+        floem_reactive::SignalTrack::id(&self.result)
+    }
+
+    fn track(&self) {
+        self.register_read();
+        floem_reactive::SignalTrack::track(&self.result);
+    }
+
+    fn try_track(&self) {
+        self.register_read();
+        floem_reactive::SignalTrack::try_track(&self.result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::{Cell, RefCell},
+        future::Future,
+        pin::Pin,
+        rc::Rc,
+        task::{Context, Poll},
+    };
+
+    use floem_reactive::{RwSignal, SignalGet, SignalUpdate, WriteSignal};
+    use futures::task::noop_waker;
+
+    use super::*;
+
+    type Task = (
+        Pin<Box<dyn Future<Output = Option<Result<i32, String>>>>>,
+        WriteSignal<Option<Result<i32, String>>>,
+        WriteSignal<bool>,
+    );
+
+    /// An executor that only polls when told to
+    #[derive(Clone, Default)]
+    struct ManualExecutor {
+        tasks: Rc<RefCell<Vec<Task>>>,
+    }
+
+    impl ManualExecutor {
+        fn run(&self) {
+            let waker = noop_waker();
+            let mut cx = Context::from_waker(&waker);
+            let tasks = std::mem::take(&mut *self.tasks.borrow_mut());
+            for (mut task, write, finished) in tasks {
+                match task.as_mut().poll(&mut cx) {
+                    Poll::Ready(v) => {
+                        write.set(v);
+                        finished.set(true);
+                    }
+                    Poll::Pending => self.tasks.borrow_mut().push((task, write, finished)),
+                }
+            }
+        }
+
+        fn len(&self) -> usize {
+            self.tasks.borrow().len()
+        }
+    }
+
+    /// A future that stays pending until the gate is opened
+    struct Gate(Rc<Cell<bool>>);
+
+    impl Future for Gate {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+            if self.0.get() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    fn doubled(
+        dep: RwSignal<i32>,
+        gate: Rc<Cell<bool>>,
+        executor: ManualExecutor,
+    ) -> AsyncMemo<i32, String> {
+        AsyncMemo::custom(move || {
+            let value = dep.get();
+            let gate = Gate(gate.clone());
+            async move {
+                gate.await;
+                if value < 0 {
+                    Err(format!("{value} is negative"))
+                } else {
+                    Ok(value * 2)
+                }
+            }
+        })
+        .executor(move |task, write, finished, _trigger| {
+            executor.tasks.borrow_mut().push((
+                Box::pin(task) as Pin<Box<dyn Future<Output = _>>>,
+                write,
+                finished,
+            ));
+        })
+        .build()
+    }
+
+    #[test]
+    fn pending_then_ready() {
+        let dep = RwSignal::new(1);
+        let gate = Rc::new(Cell::new(false));
+        let executor = ManualExecutor::default();
+        let memo = doubled(dep, gate.clone(), executor.clone());
+
+        assert!(memo.is_pending());
+        assert_eq!(memo.state(), AsyncState::Pending);
+        assert_eq!(executor.len(), 1);
+
+        gate.set(true);
+        executor.run();
+        assert_eq!(memo.state(), AsyncState::Ready(2));
+
+        dep.set(-1);
+        executor.run();
+        assert_eq!(
+            memo.state(),
+            AsyncState::Error("-1 is negative".to_string())
+        );
+    }
+
+    #[test]
+    fn completes_on_first_poll() {
+        let dep = RwSignal::new(4);
+        let executor = ManualExecutor::default();
+        let memo = doubled(dep, Rc::new(Cell::new(true)), executor.clone());
+
+        // Nothing is handed to the executor if the future is ready right away
+        assert_eq!(executor.len(), 0);
+        assert_eq!(memo.state(), AsyncState::Ready(8));
+    }
+
+    #[test]
+    fn dependency_change_cancels_in_flight() {
+        let dep = RwSignal::new(1);
+        let gate = Rc::new(Cell::new(false));
+        let executor = ManualExecutor::default();
+        let memo = doubled(dep, gate.clone(), executor.clone());
+
+        gate.set(true);
+        executor.run();
+        gate.set(false);
+
+        dep.set(2);
+        dep.set(3);
+        assert_eq!(executor.len(), 2);
+        assert!(memo.is_pending());
+        // The last value stays available while the new one is pending
+        assert_eq!(memo.value(), Some(2));
+
+        gate.set(true);
+        executor.run();
+        // The cancelled future for `2` never writes its result
+        assert_eq!(memo.state(), AsyncState::Ready(6));
+        assert_eq!(executor.len(), 0);
+    }
+}
@@ -80,30 +80,80 @@ impl<T: 'static> FutureSignal<T> {
     }
 }
 
+impl<T> FutureSignal<T> {
+    /// Lets the closest suspense know that the future was read in a tracked context.
+    /// Untracked reads don't wait for the future.
+    fn register_read(&self) {
+        crate::views::register_async_read(self.finished, None);
+    }
+}
+
 impl<T> floem_reactive::SignalGet<Option<T>> for FutureSignal<T>
 where
     T: Clone,
 {
     fn id(&self) -> floem_reactive::ReactiveId {
-        self.value.id()
+        floem_reactive::SignalGet::id(&self.value)
+    }
+
+    fn try_get(&self) -> Option<Option<T>>
+    where
+        Option<T>: 'static,
+    {
+        self.register_read();
+        floem_reactive::SignalGet::try_get(&self.value)
     }
 }
 
 impl<T> floem_reactive::SignalWith<Option<T>> for FutureSignal<T> {
     fn id(&self) -> floem_reactive::ReactiveId {
-        self.value.id()
+        floem_reactive::SignalWith::id(&self.value)
+    }
+
+    fn with<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> O
+    where
+        Option<T>: 'static,
+    {
+        self.register_read();
+        floem_reactive::SignalWith::with(&self.value, f)
+    }
+
+    fn try_with<O>(&self, f: impl FnOnce(Option<&Option<T>>) -> O) -> O
+    where
+        Option<T>: 'static,
+    {
+        self.register_read();
+        floem_reactive::SignalWith::try_with(&self.value, f)
     }
 }
 
 impl<T> floem_reactive::SignalRead<Option<T>> for FutureSignal<T> {
     fn id(&self) -> floem_reactive::ReactiveId {
-        self.value.id()
+        floem_reactive::SignalRead::id(&self.value)
+    }
+
+    fn try_read(&self) -> Option<floem_reactive::ReadSignalValue<Option<T>>>
+    where
+        Option<T>: 'static,
+    {
+        self.register_read();
+        floem_reactive::SignalRead::try_read(&self.value)
     }
 }
 
 impl<T> floem_reactive::SignalTrack<Option<T>> for FutureSignal<T> {
     fn id(&self) -> floem_reactive::ReactiveId {
-        self.value.id()
+        floem_reactive::SignalTrack::id(&self.value)
+    }
+
+    fn track(&self) {
+        self.register_read();
+        floem_reactive::SignalTrack::track(&self.value);
+    }
+
+    fn try_track(&self) {
+        self.register_read();
+        floem_reactive::SignalTrack::try_track(&self.value);
     }
 }
 
@@ -194,26 +194,76 @@ impl<T: 'static> Resource<T> {
     }
 }
 
+impl<T> Resource<T> {
+    /// Lets the closest suspense and error boundary know that the resource was read in a
+    /// tracked context. Untracked reads don't wait for the resource.
+    fn register_read(&self) {
+        crate::views::register_async_read(self.finished, Some(self.refetch_trigger));
+    }
+}
+
 impl<T> floem_reactive::SignalWith<T> for Resource<T> {
     fn id(&self) -> floem_reactive::ReactiveId {
-        self.data.id()
+        floem_reactive::SignalWith::id(&self.data)
+    }
+
+    fn with<O>(&self, f: impl FnOnce(&T) -> O) -> O
+    where
+        T: 'static,
+    {
+        self.register_read();
+        floem_reactive::SignalWith::with(&self.data, f)
+    }
+
+    fn try_with<O>(&self, f: impl FnOnce(Option<&T>) -> O) -> O
+    where
+        T: 'static,
+    {
+        self.register_read();
+        floem_reactive::SignalWith::try_with(&self.data, f)
     }
 }
 
 impl<T> floem_reactive::SignalRead<T> for Resource<T> {
     fn id(&self) -> floem_reactive::ReactiveId {
-        self.data.id()
+        floem_reactive::SignalRead::id(&self.data)
+    }
+
+    fn try_read(&self) -> Option<floem_reactive::ReadSignalValue<T>>
+    where
+        T: 'static,
+    {
+        self.register_read();
+        floem_reactive::SignalRead::try_read(&self.data)
     }
 }
 
 impl<T: Clone> floem_reactive::SignalGet<T> for Resource<T> {
     fn id(&self) -> floem_reactive::ReactiveId {
-        self.data.id()
+        floem_reactive::SignalGet::id(&self.data)
+    }
+
+    fn try_get(&self) -> Option<T>
+    where
+        T: 'static,
+    {
+        self.register_read();
+        floem_reactive::SignalGet::try_get(&self.data)
     }
 }
 
 impl<T> floem_reactive::SignalTrack<T> for Resource<T> {
     fn id(&self) -> floem_reactive::ReactiveId {
-        self.data.id()
+        floem_reactive::SignalTrack::id(&self.data)
+    }
+
+    fn track(&self) {
+        self.register_read();
+        floem_reactive::SignalTrack::track(&self.data);
+    }
+
+    fn try_track(&self) {
+        self.register_read();
+        floem_reactive::SignalTrack::try_track(&self.data);
     }
 }
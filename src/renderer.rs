@@ -50,9 +50,10 @@
 use std::sync::Arc;
 
 use crate::kurbo::Point;
-use floem_renderer::Img;
 use floem_renderer::gpu_resources::GpuResources;
 use floem_renderer::text::LayoutRun;
+use floem_renderer::tiny_skia::Pixmap;
+use floem_renderer::{Filter, Img};
 use floem_tiny_skia_renderer::TinySkiaRenderer;
 #[cfg(feature = "vello")]
 use floem_vello_renderer::VelloRenderer;
@@ -148,6 +149,23 @@ impl Renderer {
         }
     }
 
+    /// A tiny-skia renderer painting into a pixmap, for windows without a surface.
+    pub(crate) fn new_offscreen(scale: f64, size: Size) -> Self {
+        let size = Size::new(size.width.max(1.0), size.height.max(1.0));
+        match TinySkiaRenderer::new_offscreen(size.width as u32, size.height as u32, scale, 0.) {
+            Ok(tiny_skia) => Self::TinySkia(tiny_skia),
+            Err(err) => panic!("Failed to create TinySkiaRenderer: {err}"),
+        }
+    }
+
+    /// The pixels painted by the last frame, if the renderer paints into a pixmap.
+    pub(crate) fn pixmap(&self) -> Option<&Pixmap> {
+        match self {
+            Renderer::TinySkia(r) => Some(r.pixmap()),
+            _ => None,
+        }
+    }
+
     pub fn resize(&mut self, scale: f64, size: Size) {
         let size = Size::new(size.width.max(1.0), size.height.max(1.0));
         match self {
@@ -328,6 +346,21 @@ impl floem_renderer::Renderer for Renderer {
         }
     }
 
+    fn push_filter_layer(&mut self, alpha: f32, filter: Filter, clip: &impl Shape) {
+        match self {
+            #[cfg(feature = "vello")]
+            Renderer::Vello(v) => {
+                v.push_filter_layer(alpha, filter, clip);
+            }
+            #[cfg(not(feature = "vello"))]
+            Renderer::Vger(v) => {
+                v.push_filter_layer(alpha, filter, clip);
+            }
+            Renderer::TinySkia(v) => v.push_filter_layer(alpha, filter, clip),
+            Renderer::Uninitialized { .. } => {}
+        }
+    }
+
     fn pop_layer(&mut self) {
         match self {
             #[cfg(feature = "vello")]
@@ -343,6 +376,21 @@ impl floem_renderer::Renderer for Renderer {
         }
     }
 
+    fn blur_backdrop(&mut self, shape: &impl Shape, radius: f64) {
+        match self {
+            #[cfg(feature = "vello")]
+            Renderer::Vello(v) => {
+                v.blur_backdrop(shape, radius);
+            }
+            #[cfg(not(feature = "vello"))]
+            Renderer::Vger(v) => {
+                v.blur_backdrop(shape, radius);
+            }
+            Renderer::TinySkia(v) => v.blur_backdrop(shape, radius),
+            Renderer::Uninitialized { .. } => {}
+        }
+    }
+
     fn draw_text_with_layout<'b>(
         &mut self,
         layout: impl Iterator<Item = LayoutRun<'b>>,
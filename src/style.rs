@@ -129,8 +129,11 @@
 //! You can create custom extractors and embed them in your custom views so that you can get out any built in prop, or any of your custom props from the final combined style that is applied to your `View`.
 
 use floem_reactive::{RwSignal, SignalGet, SignalUpdate as _, create_updater};
+pub use floem_renderer::Filter;
 use floem_renderer::Renderer;
-use floem_renderer::text::{LineHeightValue, Weight};
+use floem_renderer::text::{
+    LineHeightValue, TextDecoration, TextDecorationLine, TextDecorationStyle, TextShadow, Weight,
+};
 use imbl::hashmap::Entry;
 use imbl::shared_ptr::DefaultSharedPtr;
 use peniko::color::{HueDirection, palette};
@@ -407,6 +410,15 @@ where
         )
     }
 }
+impl StylePropValue for Filter {
+    fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
+        Some(Filter {
+            blur: self.blur.interpolate(&other.blur, value)?,
+            brightness: self.brightness.interpolate(&other.brightness, value)?,
+            grayscale: self.grayscale.interpolate(&other.grayscale, value)?,
+        })
+    }
+}
 impl StylePropValue for String {}
 impl StylePropValue for Weight {
     fn debug_view(&self) -> Option<Box<dyn View>> {
@@ -432,6 +444,40 @@ impl StylePropValue for crate::text::Style {
     }
 }
 impl StylePropValue for crate::text::Align {}
+impl StylePropValue for TextDecorationLine {}
+impl StylePropValue for TextDecorationStyle {}
+impl StylePropValue for TextDecoration {
+    fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
+        if self.line != other.line || self.style != other.style {
+            return None;
+        }
+        let color = match (self.color, other.color) {
+            (Some(c1), Some(c2)) => Some(c1.interpolate(&c2, value)?),
+            (None, None) => None,
+            _ => return None,
+        };
+        let thickness = match (self.thickness, other.thickness) {
+            (Some(t1), Some(t2)) => Some(t1.interpolate(&t2, value)?),
+            (None, None) => None,
+            _ => return None,
+        };
+        Some(TextDecoration {
+            color,
+            thickness,
+            ..*self
+        })
+    }
+}
+impl StylePropValue for TextShadow {
+    fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
+        Some(TextShadow {
+            color: self.color.interpolate(&other.color, value)?,
+            offset_x: self.offset_x.interpolate(&other.offset_x, value)?,
+            offset_y: self.offset_y.interpolate(&other.offset_y, value)?,
+            blur: self.blur.interpolate(&other.blur, value)?,
+        })
+    }
+}
 impl StylePropValue for TextOverflow {}
 impl StylePropValue for PointerEvents {}
 impl StylePropValue for LineHeightValue {
@@ -1528,7 +1574,7 @@ impl<T: StylePropValue> TransitionState<T> {
         }
         if self.transition.is_some() {
             self.active = Some(ActiveTransition {
-                start: Instant::now(),
+                start: crate::animate::now(),
                 before: before.clone(),
                 current: before.clone(),
                 after: after.clone(),
@@ -2123,6 +2169,16 @@ fn resolve_nested_maps_internal(
             }
         }
 
+        // Drag Over
+        if interact_state.is_drag_over {
+            if let Some(map) = style.get_nested_map(StyleSelector::DragOver.to_key()) {
+                classes_applied |= map.any_inherited();
+                style.apply_mut(map);
+                style.remove_nested_map(StyleSelector::DragOver.to_key());
+                changed = true;
+            }
+        }
+
         // Focus states
         if interact_state.is_focused {
             if let Some(map) = style.get_nested_map(StyleSelector::Focus.to_key()) {
@@ -2160,6 +2216,16 @@ fn resolve_nested_maps_internal(
                 changed = true;
             }
         }
+
+        // Invalid (applied last so it stays visible while hovered or focused)
+        if style.get(Invalid) {
+            if let Some(map) = style.get_nested_map(StyleSelector::Invalid.to_key()) {
+                classes_applied |= map.any_inherited();
+                style.apply_mut(map);
+                style.remove_nested_map(StyleSelector::Invalid.to_key());
+                changed = true;
+            }
+        }
     }
 
     // Recurse once at the end if anything changed
@@ -2594,6 +2660,8 @@ pub enum StyleSelector {
     Dragging,
     Selected,
     FileHover,
+    DragOver,
+    Invalid,
 }
 impl StyleSelector {
     pub const fn all() -> &'static [StyleSelector] {
@@ -2607,6 +2675,8 @@ impl StyleSelector {
             StyleSelector::Selected,
             StyleSelector::DarkMode,
             StyleSelector::FileHover,
+            StyleSelector::DragOver,
+            StyleSelector::Invalid,
         ]
     }
 
@@ -2621,6 +2691,8 @@ impl StyleSelector {
             StyleSelector::Selected => "Selected",
             StyleSelector::DarkMode => "DarkMode",
             StyleSelector::FileHover => "FileHover",
+            StyleSelector::DragOver => "DragOver",
+            StyleSelector::Invalid => "Invalid",
         }
     }
 }
@@ -2630,6 +2702,14 @@ style_key_selector!(
     file_hover,
     StyleSelectors::new().set(StyleSelector::FileHover, true)
 );
+style_key_selector!(
+    drag_over,
+    StyleSelectors::new().set(StyleSelector::DragOver, true)
+);
+style_key_selector!(
+    invalid,
+    StyleSelectors::new().set(StyleSelector::Invalid, true)
+);
 style_key_selector!(focus, StyleSelectors::new().set(StyleSelector::Focus, true));
 style_key_selector!(
     focus_visible,
@@ -2668,6 +2748,8 @@ impl StyleSelector {
             StyleSelector::Selected => selected(),
             StyleSelector::DarkMode => darkmode(),
             StyleSelector::FileHover => file_hover(),
+            StyleSelector::DragOver => drag_over(),
+            StyleSelector::Invalid => invalid(),
         }
     }
 }
@@ -2779,6 +2861,7 @@ pub enum CursorStyle {
     Move,
     Grab,
     Grabbing,
+    NotAllowed,
     ColResize,
     RowResize,
     WResize,
@@ -3910,6 +3993,28 @@ define_builtin_props!(
     /// This property is inherited by child views.
     LineHeight line_height { nocb, tr }: Option<LineHeightValue> { inherited } = None,
 
+    /// Draws lines under, over or through text, like the CSS `text-decoration` property.
+    ///
+    /// See [`Style::underline`], [`Style::overline`] and [`Style::line_through`].
+    /// This property is inherited by child views.
+    TextDecorationProp text_decoration {tr}: Option<TextDecoration> { inherited } = None,
+
+    /// Sets the extra space between letters in pixels.
+    ///
+    /// This property is inherited by child views.
+    LetterSpacing letter_spacing {tr}: f32 { inherited } = 0.0,
+
+    /// Sets the extra space between words in pixels.
+    ///
+    /// This property is inherited by child views.
+    WordSpacing word_spacing {tr}: f32 { inherited } = 0.0,
+
+    /// Draws a shadow behind text, like the CSS `text-shadow` property.
+    ///
+    /// Only the tiny-skia renderer blurs text shadows.
+    /// This property is inherited by child views.
+    TextShadowProp text_shadow {tr}: Option<TextShadow> { inherited } = None,
+
     /// Sets the preferred aspect ratio for the view.
     ///
     /// Maintains width-to-height proportions during layout.
@@ -3950,6 +4055,26 @@ define_builtin_props!(
     /// Positive values rotate clockwise, negative values rotate counter-clockwise.
     Rotation rotate {tr}: Px {} = Px(0.),
 
+    /// Sets the opacity of the view and its children.
+    ///
+    /// `0.0` is fully transparent and `1.0` is fully opaque.
+    Opacity opacity {tr}: f32 {} = 1.0,
+
+    /// Applies filters to the view and its children, like the CSS `filter` property.
+    ///
+    /// The blur spreads up to three times its radius past the view, even when the view clips
+    /// its children, but it is still cut by the clips of the ancestors of the view.
+    /// Only the tiny-skia renderer blurs: vello applies the other filters without the blur,
+    /// and vger ignores the filter.
+    ///
+    /// See [`Style::blur`], [`Style::brightness`] and [`Style::grayscale`].
+    FilterProp filter {tr}: Filter {} = Filter::default(),
+
+    /// Blurs what is behind the view, like the CSS `backdrop-filter: blur()` property.
+    ///
+    /// Only the tiny-skia renderer can blur what is behind views.
+    BackdropBlur backdrop_blur {tr}: f64 {} = 0.0,
+
     /// Controls the selected state of the view.
     ///
     /// This property is inherited by child views.
@@ -3960,6 +4085,9 @@ define_builtin_props!(
     /// This property is inherited by child views.
     Disabled set_disabled {}: bool { inherited } = false,
 
+    /// Controls the invalid state of the view, such as a text input failing validation.
+    Invalid set_invalid {}: bool {} = false,
+
     /// Controls the visibility of the view.
     ///
     /// This property is inherited by child views.
@@ -4188,6 +4316,17 @@ impl Style {
         self.selector(StyleSelector::FileHover, style)
     }
 
+    /// The visual style to apply when a dragged payload the view accepts in one of its
+    /// [`on_drop`](crate::views::Decorators::on_drop) handlers is over it.
+    pub fn drag_over(self, style: impl FnOnce(Style) -> Style) -> Self {
+        self.selector(StyleSelector::DragOver, style)
+    }
+
+    /// The visual style to apply when the view is in an invalid state, see [`Invalid`].
+    pub fn invalid(self, style: impl FnOnce(Style) -> Style) -> Self {
+        self.selector(StyleSelector::Invalid, style)
+    }
+
     /// The visual style to apply when the view is being actively pressed.
     pub fn active(self, style: impl FnOnce(Style) -> Style) -> Self {
         self.selector(StyleSelector::Active, style)
@@ -4894,6 +5033,37 @@ impl Style {
         self.set(BoxShadowProp, value)
     }
 
+    /// Blurs the view and its children, with a standard deviation of `radius` pixels.
+    ///
+    /// Only the tiny-skia renderer can blur views, the others paint them without the blur.
+    pub fn blur(self, radius: f64) -> Self {
+        let filter = self.get(FilterProp);
+        self.filter(Filter {
+            blur: radius,
+            ..filter
+        })
+    }
+
+    /// Multiplies the colors of the view and its children.
+    /// `0.0` makes them black and `1.0` leaves them unchanged.
+    pub fn brightness(self, brightness: f64) -> Self {
+        let filter = self.get(FilterProp);
+        self.filter(Filter {
+            brightness,
+            ..filter
+        })
+    }
+
+    /// Converts the colors of the view and its children to grayscale,
+    /// from `0.0` (unchanged) to `1.0` (completely gray).
+    pub fn grayscale(self, grayscale: f64) -> Self {
+        let filter = self.get(FilterProp);
+        self.filter(Filter {
+            grayscale,
+            ..filter
+        })
+    }
+
     /// Sets the font size for text content.
     pub fn font_size(self, size: impl Into<Px>) -> Self {
         let px = size.into();
@@ -4931,6 +5101,48 @@ impl Style {
         self.set(LineHeight, Some(LineHeightValue::Normal(normal)))
     }
 
+    fn text_decoration_line(self, line: TextDecorationLine) -> Self {
+        let decoration = self.get(TextDecorationProp).unwrap_or_default();
+        self.text_decoration(TextDecoration {
+            line: decoration.line.union(line),
+            ..decoration
+        })
+    }
+
+    /// Draws a line under text, in addition to the other text decoration lines.
+    pub fn underline(self) -> Self {
+        self.text_decoration_line(TextDecorationLine::UNDERLINE)
+    }
+
+    /// Draws a line over text, in addition to the other text decoration lines.
+    pub fn overline(self) -> Self {
+        self.text_decoration_line(TextDecorationLine::OVERLINE)
+    }
+
+    /// Draws a line through text, in addition to the other text decoration lines.
+    pub fn line_through(self) -> Self {
+        self.text_decoration_line(TextDecorationLine::LINE_THROUGH)
+    }
+
+    /// Sets the color of the text decoration lines, which is the text color by default.
+    pub fn text_decoration_color(self, color: impl Into<Color>) -> Self {
+        let decoration = self.get(TextDecorationProp).unwrap_or_default();
+        self.text_decoration(decoration.color(color.into()))
+    }
+
+    /// Sets how the text decoration lines are drawn, e.g. [`TextDecorationStyle::Wavy`].
+    pub fn text_decoration_style(self, style: TextDecorationStyle) -> Self {
+        let decoration = self.get(TextDecorationProp).unwrap_or_default();
+        self.text_decoration(decoration.style(style))
+    }
+
+    /// Sets the thickness of the text decoration lines in pixels, which is derived from the
+    /// font size by default.
+    pub fn text_decoration_thickness(self, thickness: f32) -> Self {
+        let decoration = self.get(TextDecorationProp).unwrap_or_default();
+        self.text_decoration(decoration.thickness(thickness))
+    }
+
     /// Enables pointer events for the view (allows mouse interaction).
     pub fn pointer_events_auto(self) -> Self {
         self.pointer_events(PointerEvents::Auto)
@@ -0,0 +1,520 @@
+//! Headless windows to test views without a display or a GPU.
+//!
+//! Enabled with the `testing` feature, which also enables
+//! [`floem_reactive::testing`], re-exported here.
+//!
+//! # Example
+//! ```rust,ignore
+//! use floem::{
+//!     prelude::*,
+//!     testing::{SnapshotTolerance, TestWindow},
+//! };
+//!
+//! let count = RwSignal::new(0);
+//! let mut window = TestWindow::new((200.0, 100.0), move || {
+//!     button("Increment")
+//!         .debug_name("increment")
+//!         .action(move || count.update(|c| *c += 1))
+//! });
+//!
+//! let increment = window.find("increment").unwrap();
+//! window.click_view(increment);
+//! assert_eq!(count.get(), 1);
+//!
+//! // Compares the pixels painted by tiny-skia with `tests/snapshots/counter.png`
+//! window.assert_snapshot("tests/snapshots/counter.png", SnapshotTolerance::default());
+//! ```
+
+use std::{path::Path, time::Duration};
+
+use dpi::PhysicalPosition;
+use floem_renderer::tiny_skia::Pixmap;
+use peniko::kurbo::{Point, Rect, Size, Vec2};
+use ui_events::{
+    ScrollDelta,
+    keyboard::{Code, Key, KeyState, KeyboardEvent, Location, Modifiers},
+    pointer::{
+        PointerButton, PointerButtonEvent, PointerButtons, PointerEvent, PointerId, PointerInfo,
+        PointerScrollEvent, PointerState, PointerType, PointerUpdate,
+    },
+};
+
+pub use floem_reactive::testing::*;
+
+mod snapshot;
+pub use snapshot::*;
+
+use crate::{
+    IntoView, ViewId,
+    accessibility::{ActionRequest, TreeUpdate},
+    event::Event,
+    style::{CursorStyle, StyleClass},
+    window_handle::WindowHandle,
+};
+
+const MOUSE: PointerInfo = PointerInfo {
+    pointer_id: Some(PointerId::PRIMARY),
+    persistent_device_id: None,
+    pointer_type: PointerType::Mouse,
+};
+
+/// The event of `key` pressed or released with `modifiers`, as sent by [`TestWindow`], to test
+/// the key handling of a view without a window.
+pub fn key_event(key: Key, modifiers: Modifiers, state: KeyState) -> KeyboardEvent {
+    KeyboardEvent {
+        state,
+        key,
+        code: Code::Unidentified,
+        location: Location::Standard,
+        modifiers,
+        repeat: false,
+        is_composing: false,
+    }
+}
+
+/// A window without an OS window or a GPU surface, driven by the test.
+///
+/// Events go through the same dispatch as a real window, and style, layout and paint run
+/// after each of them, painting into a pixmap with the tiny-skia renderer. The window
+/// installs a [`VirtualClock`], so timers and animations only move forward with
+/// [`TestWindow::advance`].
+pub struct TestWindow {
+    handle: WindowHandle,
+    runtime: TestRuntime,
+    pointer: Point,
+    buttons: PointerButtons,
+    modifiers: Modifiers,
+}
+
+impl TestWindow {
+    /// Mounts the view returned by `view_fn` in a window of the given logical size, with
+    /// the default theme.
+    pub fn new<V: IntoView + 'static>(
+        size: impl Into<Size>,
+        view_fn: impl FnOnce() -> V + 'static,
+    ) -> Self {
+        Self::with_scale(size, 1.0, view_fn)
+    }
+
+    /// Mounts the view returned by `view_fn` in a window of the given logical size and
+    /// scale factor.
+    pub fn with_scale<V: IntoView + 'static>(
+        size: impl Into<Size>,
+        scale: f64,
+        view_fn: impl FnOnce() -> V + 'static,
+    ) -> Self {
+        // The clock has to be installed before the view is built, so the timers it
+        // schedules are virtual too
+        let runtime = TestRuntime::new();
+        let handle =
+            WindowHandle::new_headless(move || view_fn().into_any(), size.into(), scale, true);
+        let mut window = Self {
+            handle,
+            runtime,
+            pointer: Point::ZERO,
+            buttons: PointerButtons::default(),
+            modifiers: Modifiers::default(),
+        };
+        window.frame();
+        window
+    }
+
+    /// The id of the window's root view, which contains the mounted view.
+    pub fn root(&self) -> ViewId {
+        self.handle.window_state.root_view_id
+    }
+
+    /// The clock driving the timers and animations of this window.
+    pub fn clock(&self) -> &VirtualClock {
+        self.runtime.clock()
+    }
+
+    /// Resizes the window, and lays it out again.
+    pub fn resize(&mut self, size: impl Into<Size>) {
+        self.handle.size(size.into());
+        self.frame();
+    }
+
+    /// Processes pending updates and paints a frame, like the event loop does when the
+    /// window is redrawn.
+    pub fn frame(&mut self) {
+        self.runtime.run_pending();
+        self.handle.render_frame(None);
+    }
+
+    /// Moves the virtual clock forward, firing due timers, then renders a frame so running
+    /// animations and transitions are updated.
+    pub fn advance(&mut self, by: Duration) {
+        self.runtime.advance(by);
+        self.frame();
+    }
+
+    /// Renders a frame and returns its pixels, painted by the tiny-skia renderer at the
+    /// window's scale factor.
+    pub fn render(&mut self) -> Pixmap {
+        self.frame();
+        self.handle
+            .paint_state
+            .renderer()
+            .pixmap()
+            .expect("headless windows paint into a pixmap")
+            .clone()
+    }
+
+    /// Renders a frame and panics if it doesn't match the golden PNG image at `golden`.
+    /// See [`compare_snapshot`].
+    #[track_caller]
+    pub fn assert_snapshot(&mut self, golden: impl AsRef<Path>, tolerance: SnapshotTolerance) {
+        assert_snapshot(&self.render(), golden, tolerance);
+    }
+
+    /// Dispatches a raw event to the window, then renders a frame.
+    pub fn event(&mut self, event: Event) {
+        self.handle.event(event);
+        self.frame();
+    }
+
+    fn pointer_state(&self, count: u8) -> PointerState {
+        let scale = self.handle.scale;
+        PointerState {
+            position: PhysicalPosition::new(self.pointer.x * scale, self.pointer.y * scale),
+            buttons: self.buttons,
+            modifiers: self.modifiers,
+            count,
+            scale_factor: scale,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the modifiers held while the next pointer events are sent.
+    pub fn set_modifiers(&mut self, modifiers: Modifiers) {
+        self.modifiers = modifiers;
+        self.handle.modifiers_changed(modifiers);
+    }
+
+    /// Moves the pointer to a position in logical window coordinates.
+    pub fn pointer_move(&mut self, pos: impl Into<Point>) {
+        self.pointer = pos.into();
+        let current = self.pointer_state(0);
+        self.handle.pointer_event(PointerEvent::Move(PointerUpdate {
+            pointer: MOUSE,
+            current,
+            coalesced: Vec::new(),
+            predicted: Vec::new(),
+        }));
+        self.frame();
+    }
+
+    /// Moves the pointer out of the window.
+    pub fn pointer_leave(&mut self) {
+        self.handle.pointer_event(PointerEvent::Leave(MOUSE));
+        self.frame();
+    }
+
+    /// Moves the pointer to `pos` and presses `button`, as the `count`th click in a row.
+    pub fn pointer_down(&mut self, pos: impl Into<Point>, button: PointerButton, count: u8) {
+        self.pointer_move(pos);
+        self.buttons.insert(button);
+        let state = self.pointer_state(count);
+        self.handle
+            .pointer_event(PointerEvent::Down(PointerButtonEvent {
+                button: Some(button),
+                pointer: MOUSE,
+                state,
+            }));
+        self.frame();
+    }
+
+    /// Moves the pointer to `pos` and releases `button`.
+    pub fn pointer_up(&mut self, pos: impl Into<Point>, button: PointerButton, count: u8) {
+        self.pointer_move(pos);
+        self.buttons.remove(button);
+        let state = self.pointer_state(count);
+        self.handle
+            .pointer_event(PointerEvent::Up(PointerButtonEvent {
+                button: Some(button),
+                pointer: MOUSE,
+                state,
+            }));
+        self.frame();
+    }
+
+    /// Presses and releases the primary button at `pos`.
+    pub fn click(&mut self, pos: impl Into<Point>) {
+        let pos = pos.into();
+        self.pointer_down(pos, PointerButton::Primary, 1);
+        self.pointer_up(pos, PointerButton::Primary, 1);
+    }
+
+    /// Clicks twice at `pos`, the second click being a double click.
+    pub fn double_click(&mut self, pos: impl Into<Point>) {
+        let pos = pos.into();
+        self.click(pos);
+        self.pointer_down(pos, PointerButton::Primary, 2);
+        self.pointer_up(pos, PointerButton::Primary, 2);
+    }
+
+    /// Clicks at the center of a view.
+    pub fn click_view(&mut self, id: ViewId) {
+        self.click(self.rect(id).center());
+    }
+
+    /// Scrolls by `delta` logical pixels with the pointer at `pos`.
+    pub fn scroll(&mut self, pos: impl Into<Point>, delta: impl Into<Vec2>) {
+        self.pointer_move(pos);
+        let delta = delta.into() * self.handle.scale;
+        let state = self.pointer_state(0);
+        self.handle
+            .pointer_event(PointerEvent::Scroll(PointerScrollEvent {
+                pointer: MOUSE,
+                delta: ScrollDelta::PixelDelta(PhysicalPosition::new(delta.x, delta.y)),
+                state,
+            }));
+        self.frame();
+    }
+
+    fn key(&mut self, key: Key, modifiers: Modifiers, state: KeyState) {
+        self.handle.key_event(key_event(key, modifiers, state));
+        self.frame();
+    }
+
+    /// Sends a key down event to the focused view.
+    pub fn key_down(&mut self, key: Key, modifiers: Modifiers) {
+        self.key(key, modifiers, KeyState::Down);
+    }
+
+    /// Sends a key up event to the focused view.
+    pub fn key_up(&mut self, key: Key, modifiers: Modifiers) {
+        self.key(key, modifiers, KeyState::Up);
+    }
+
+    /// Presses and releases a key.
+    pub fn key_press(&mut self, key: Key, modifiers: Modifiers) {
+        self.key_down(key.clone(), modifiers);
+        self.key_up(key, modifiers);
+    }
+
+    /// Types `text` one character at a time, without modifiers.
+    pub fn type_text(&mut self, text: &str) {
+        for c in text.chars() {
+            self.key_press(Key::Character(c.to_string()), Modifiers::empty());
+        }
+    }
+
+    /// Enables or disables the input method.
+    pub fn ime_enabled(&mut self, enabled: bool) {
+        self.event(if enabled {
+            Event::ImeEnabled
+        } else {
+            Event::ImeDisabled
+        });
+    }
+
+    /// Sends the text being composed by the input method, with the selected range.
+    pub fn ime_preedit(&mut self, text: &str, cursor: Option<(usize, usize)>) {
+        self.event(Event::ImePreedit {
+            text: text.to_string(),
+            cursor,
+        });
+    }
+
+    /// Commits text from the input method.
+    pub fn ime_commit(&mut self, text: &str) {
+        self.event(Event::ImeCommit(text.to_string()));
+    }
+
+    /// The changes to the window's accessibility tree since the last call, or the whole
+    /// tree on the first call.
+    pub fn accessibility_update(&mut self) -> TreeUpdate {
+        self.frame();
+        self.handle.accessibility_update()
+    }
+
+    /// Sends an action as an assistive technology would, to the view with the given node
+    /// id, such as `accessibility::node_id(view)`.
+    pub fn accessibility_action(&mut self, request: ActionRequest) {
+        self.handle.accessibility_action(request);
+        self.frame();
+    }
+
+    /// The view that has the keyboard focus.
+    pub fn focused(&self) -> Option<ViewId> {
+        self.handle.window_state.focus
+    }
+
+    /// The view that receives all pointer events, such as a slider being dragged.
+    pub fn active(&self) -> Option<ViewId> {
+        self.handle.window_state.active
+    }
+
+    /// The cursor set by the views under the pointer, or `None` for the default cursor.
+    pub fn cursor(&self) -> Option<CursorStyle> {
+        self.handle.window_state.cursor
+    }
+
+    /// Returns `true` if the pointer is over the view.
+    pub fn is_hovered(&self, id: ViewId) -> bool {
+        self.handle.window_state.is_hovered(&id)
+    }
+
+    /// The rectangle of a view in logical window coordinates.
+    pub fn rect(&self, id: ViewId) -> Rect {
+        let origin = id.state().borrow().window_origin;
+        Rect::from_origin_size(origin, id.get_size().unwrap_or_default())
+    }
+
+    /// Every view, depth first, starting from the root.
+    pub fn views(&self) -> Vec<ViewId> {
+        fn collect(id: ViewId, out: &mut Vec<ViewId>) {
+            out.push(id);
+            for child in id.children() {
+                collect(child, out);
+            }
+        }
+        let mut views = Vec::new();
+        collect(self.root(), &mut views);
+        views
+    }
+
+    /// The first view, depth first, with the given debug name.
+    ///
+    /// Names set with [`Decorators::debug_name`](crate::views::Decorators::debug_name)
+    /// match, as well as the name of the view type, such as `"Label"` or `"Button"`.
+    pub fn find(&self, name: &str) -> Option<ViewId> {
+        self.views()
+            .into_iter()
+            .find(|id| has_debug_name(*id, name))
+    }
+
+    /// Every view with the given debug name. See [`TestWindow::find`].
+    pub fn find_all(&self, name: &str) -> Vec<ViewId> {
+        self.views()
+            .into_iter()
+            .filter(|id| has_debug_name(*id, name))
+            .collect()
+    }
+
+    /// Every view with the style class `C`, added with
+    /// [`Decorators::class`](crate::views::Decorators::class) or as its view class.
+    pub fn find_by_class<C: StyleClass>(&self) -> Vec<ViewId> {
+        let class = C::class_ref();
+        self.views()
+            .into_iter()
+            .filter(|id| {
+                id.state().borrow().classes.contains(&class)
+                    || id.view().borrow().view_class() == Some(class)
+            })
+            .collect()
+    }
+}
+
+fn has_debug_name(id: ViewId, name: &str) -> bool {
+    id.state().borrow().debug_name.iter().any(|n| n == name)
+        || id.view().borrow().debug_name() == name
+}
+
+impl Drop for TestWindow {
+    fn drop(&mut self) {
+        self.handle.destroy();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::TestWindow;
+    use crate::{action::exec_after, prelude::*};
+
+    #[test]
+    fn click_runs_the_action_of_the_view() {
+        let count = RwSignal::new(0);
+        let mut window = TestWindow::new((200.0, 100.0), move || {
+            button("Increment")
+                .debug_name("increment")
+                .action(move || count.update(|count| *count += 1))
+        });
+
+        let increment = window.find("increment").unwrap();
+        window.click_view(increment);
+        window.click_view(increment);
+        assert_eq!(count.get_untracked(), 2);
+
+        // clicking outside of the button doesn't reach it
+        window.click((190.0, 90.0));
+        assert_eq!(count.get_untracked(), 2);
+    }
+
+    #[test]
+    fn typed_text_goes_to_the_focused_input() {
+        let text = RwSignal::new(String::new());
+        let mut window = TestWindow::new((200.0, 100.0), move || {
+            text_input(text)
+                .debug_name("input")
+                .style(|s| s.width(150.0))
+        });
+
+        let input = window.find("input").unwrap();
+        assert_eq!(window.focused(), None);
+        window.click_view(input);
+        assert_eq!(window.focused(), Some(input));
+
+        window.type_text("abc");
+        window.key_press(Key::Named(NamedKey::Backspace), Modifiers::empty());
+        assert_eq!(text.get_untracked(), "ab");
+    }
+
+    #[test]
+    fn views_are_found_by_name_and_class() {
+        let mut window = TestWindow::new((200.0, 100.0), || {
+            v_stack((
+                button("A").debug_name("a").debug_name("button"),
+                button("B").debug_name("button"),
+                empty().debug_name("square").style(|s| s.size(20.0, 30.0)),
+            ))
+        });
+
+        let square = window.find("square").unwrap();
+        assert_eq!(window.rect(square).size(), (20.0, 30.0).into());
+        assert_eq!(window.find_by_class::<ButtonClass>().len(), 2);
+        assert_eq!(window.find_all("button").len(), 2);
+        assert!(
+            window
+                .find("a")
+                .is_some_and(|a| window.views().contains(&a))
+        );
+        assert!(window.find("missing").is_none());
+
+        window.resize((300.0, 50.0));
+        assert_eq!(window.rect(window.root()).size(), (300.0, 50.0).into());
+    }
+
+    #[test]
+    fn timers_fire_when_the_clock_advances() {
+        let fired = RwSignal::new(false);
+        let mut window = TestWindow::new((200.0, 100.0), move || {
+            button("Later").debug_name("later").action(move || {
+                exec_after(Duration::from_millis(100), move |_| fired.set(true));
+            })
+        });
+
+        let later = window.find("later").unwrap();
+        window.click_view(later);
+        window.advance(Duration::from_millis(50));
+        assert!(!fired.get_untracked());
+        window.advance(Duration::from_millis(50));
+        assert!(fired.get_untracked());
+    }
+
+    #[test]
+    fn update_messages_reach_the_window() {
+        let mut window = TestWindow::new((200.0, 100.0), || {
+            text_input(RwSignal::new(String::new())).debug_name("input")
+        });
+
+        let input = window.find("input").unwrap();
+        input.request_focus();
+        window.frame();
+        assert_eq!(window.focused(), Some(input));
+    }
+}
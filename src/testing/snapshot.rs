@@ -0,0 +1,279 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use floem_renderer::tiny_skia::{Pixmap, PremultipliedColorU8};
+
+/// The environment variable that makes snapshot assertions overwrite their golden images.
+pub const UPDATE_SNAPSHOTS_VAR: &str = "FLOEM_UPDATE_SNAPSHOTS";
+
+/// The largest YIQ distance between two colors, between black and white.
+const MAX_YIQ_DELTA: f32 = 35215.0;
+
+/// How different a rendered image may be from its golden image.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SnapshotTolerance {
+    /// The perceived color difference, from `0.0` to `1.0`, above which two pixels differ.
+    ///
+    /// The default of `0.1` ignores the small color shifts anti-aliasing produces across
+    /// platforms.
+    pub threshold: f32,
+    /// The number of differing pixels that still lets the comparison pass.
+    pub max_diff_pixels: usize,
+}
+
+impl Default for SnapshotTolerance {
+    fn default() -> Self {
+        Self {
+            threshold: 0.1,
+            max_diff_pixels: 0,
+        }
+    }
+}
+
+impl SnapshotTolerance {
+    /// Only identical images match.
+    pub const EXACT: Self = Self {
+        threshold: 0.0,
+        max_diff_pixels: 0,
+    };
+}
+
+/// Why a rendered image doesn't match its golden image.
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// The golden image couldn't be read, or an image couldn't be written.
+    Io {
+        /// The path of the image.
+        path: PathBuf,
+        /// The underlying error.
+        message: String,
+    },
+    /// The images have different sizes.
+    SizeMismatch {
+        /// The width and height of the golden image.
+        expected: (u32, u32),
+        /// The width and height of the rendered image.
+        actual: (u32, u32),
+        /// Where the rendered image was written.
+        actual_path: PathBuf,
+    },
+    /// More pixels differ than the tolerance allows.
+    Mismatch {
+        /// The number of pixels that differ.
+        diff_pixels: usize,
+        /// Where the rendered image was written.
+        actual_path: PathBuf,
+        /// Where the image highlighting the differing pixels was written.
+        diff_path: PathBuf,
+    },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Io { path, message } => {
+                write!(f, "snapshot image {}: {message}", path.display())
+            }
+            SnapshotError::SizeMismatch {
+                expected,
+                actual,
+                actual_path,
+            } => write!(
+                f,
+                "snapshot is {}x{} but the golden image is {}x{}, rendered image written to {}",
+                actual.0,
+                actual.1,
+                expected.0,
+                expected.1,
+                actual_path.display()
+            ),
+            SnapshotError::Mismatch {
+                diff_pixels,
+                actual_path,
+                diff_path,
+            } => write!(
+                f,
+                "{diff_pixels} pixels differ from the golden image, rendered image written to {} and differences to {}",
+                actual_path.display(),
+                diff_path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+/// The pixels that differ between two images of the same size.
+pub struct ImageDiff {
+    /// The number of pixels whose perceived difference is above the threshold.
+    pub diff_pixels: usize,
+    /// The expected image, faded to gray, with the differing pixels in red.
+    pub image: Pixmap,
+}
+
+/// Compares two images of the same size pixel by pixel.
+///
+/// Colors are blended over white and compared by their perceived difference in the YIQ
+/// color space, which weighs brightness above hue. `threshold` goes from `0.0`, where
+/// any difference counts, to `1.0`, where none does.
+///
+/// Returns `None` if the images have different sizes.
+pub fn diff_images(expected: &Pixmap, actual: &Pixmap, threshold: f32) -> Option<ImageDiff> {
+    if expected.width() != actual.width() || expected.height() != actual.height() {
+        return None;
+    }
+    let mut image = Pixmap::new(expected.width(), expected.height())?;
+    let max_delta = MAX_YIQ_DELTA * threshold * threshold;
+    let mut diff_pixels = 0;
+    for ((expected, actual), out) in expected
+        .pixels()
+        .iter()
+        .zip(actual.pixels())
+        .zip(image.pixels_mut())
+    {
+        let expected = blend_over_white(*expected);
+        let actual = blend_over_white(*actual);
+        let differs = expected != actual && yiq_delta(expected, actual) > max_delta;
+        *out = if differs {
+            diff_pixels += 1;
+            opaque(255, 0, 0)
+        } else {
+            // Fade matching pixels so the differences stand out
+            let y = 255.0 - (255.0 - luma(expected)) * 0.1;
+            let y = y as u8;
+            opaque(y, y, y)
+        };
+    }
+    Some(ImageDiff { diff_pixels, image })
+}
+
+/// Compares `actual` with the golden PNG image at `golden`.
+///
+/// The golden image is written instead when it doesn't exist yet, or when the
+/// [`UPDATE_SNAPSHOTS_VAR`] environment variable is set to `1`. When the images don't
+/// match, the rendered image is written next to the golden one with the `.actual.png`
+/// extension, and the differences with the `.diff.png` extension.
+pub fn compare_snapshot(
+    actual: &Pixmap,
+    golden: impl AsRef<Path>,
+    tolerance: SnapshotTolerance,
+) -> Result<(), SnapshotError> {
+    let golden = golden.as_ref();
+    let update = std::env::var(UPDATE_SNAPSHOTS_VAR).is_ok_and(|v| v == "1");
+    if update || !golden.exists() {
+        if let Some(dir) = golden.parent() {
+            std::fs::create_dir_all(dir).map_err(|err| io_error(dir, err))?;
+        }
+        return save_png(actual, golden);
+    }
+
+    let expected = Pixmap::load_png(golden).map_err(|err| io_error(golden, err))?;
+    let actual_path = golden.with_extension("actual.png");
+    let diff_path = golden.with_extension("diff.png");
+    let Some(diff) = diff_images(&expected, actual, tolerance.threshold) else {
+        save_png(actual, &actual_path)?;
+        return Err(SnapshotError::SizeMismatch {
+            expected: (expected.width(), expected.height()),
+            actual: (actual.width(), actual.height()),
+            actual_path,
+        });
+    };
+    if diff.diff_pixels > tolerance.max_diff_pixels {
+        save_png(actual, &actual_path)?;
+        save_png(&diff.image, &diff_path)?;
+        return Err(SnapshotError::Mismatch {
+            diff_pixels: diff.diff_pixels,
+            actual_path,
+            diff_path,
+        });
+    }
+    // Leftovers from a previous failure would be confusing
+    let _ = std::fs::remove_file(actual_path);
+    let _ = std::fs::remove_file(diff_path);
+    Ok(())
+}
+
+/// Panics with a description of the differences if `actual` doesn't match the golden
+/// image at `golden`. See [`compare_snapshot`].
+#[track_caller]
+pub fn assert_snapshot(actual: &Pixmap, golden: impl AsRef<Path>, tolerance: SnapshotTolerance) {
+    if let Err(err) = compare_snapshot(actual, golden, tolerance) {
+        panic!("{err}");
+    }
+}
+
+fn save_png(pixmap: &Pixmap, path: &Path) -> Result<(), SnapshotError> {
+    pixmap.save_png(path).map_err(|err| io_error(path, err))
+}
+
+fn io_error(path: &Path, err: impl fmt::Display) -> SnapshotError {
+    SnapshotError::Io {
+        path: path.to_path_buf(),
+        message: err.to_string(),
+    }
+}
+
+fn opaque(r: u8, g: u8, b: u8) -> PremultipliedColorU8 {
+    PremultipliedColorU8::from_rgba(r, g, b, 255).unwrap()
+}
+
+/// Composites a premultiplied pixel over a white background.
+fn blend_over_white(pixel: PremultipliedColorU8) -> [f32; 3] {
+    let white = 255.0 - pixel.alpha() as f32;
+    [
+        pixel.red() as f32 + white,
+        pixel.green() as f32 + white,
+        pixel.blue() as f32 + white,
+    ]
+}
+
+fn luma([r, g, b]: [f32; 3]) -> f32 {
+    r * 0.29889531 + g * 0.58662247 + b * 0.11448223
+}
+
+fn yiq_delta(a: [f32; 3], b: [f32; 3]) -> f32 {
+    let [dr, dg, db] = [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+    let y = luma([dr, dg, db]);
+    let i = dr * 0.59597799 - dg * 0.27417610 - db * 0.32180189;
+    let q = dr * 0.21147017 - dg * 0.52261711 + db * 0.31114694;
+    0.5053 * y * y + 0.299 * i * i + 0.1957 * q * q
+}
+
+#[cfg(test)]
+mod tests {
+    use floem_renderer::tiny_skia::Color;
+
+    use super::*;
+
+    fn filled(color: Color) -> Pixmap {
+        let mut pixmap = Pixmap::new(4, 4).unwrap();
+        pixmap.fill(color);
+        pixmap
+    }
+
+    #[test]
+    fn small_differences_are_tolerated() {
+        let expected = filled(Color::from_rgba8(100, 100, 100, 255));
+        let actual = filled(Color::from_rgba8(102, 100, 100, 255));
+
+        let diff = diff_images(&expected, &actual, 0.1).unwrap();
+        assert_eq!(diff.diff_pixels, 0);
+        let diff = diff_images(&expected, &actual, 0.0).unwrap();
+        assert_eq!(diff.diff_pixels, 16);
+    }
+
+    #[test]
+    fn differing_pixels_are_marked() {
+        let expected = filled(Color::WHITE);
+        let mut actual = filled(Color::WHITE);
+        actual.pixels_mut()[5] = opaque(0, 0, 0);
+
+        let diff = diff_images(&expected, &actual, 0.1).unwrap();
+        assert_eq!(diff.diff_pixels, 1);
+        assert_eq!(diff.image.pixels()[5], opaque(255, 0, 0));
+        assert_eq!(diff.image.pixels()[0], opaque(255, 255, 255));
+        assert!(diff_images(&expected, &Pixmap::new(2, 2).unwrap(), 0.1).is_none());
+    }
+}
@@ -9,10 +9,22 @@ use crate::{
     style_class,
     unit::{DurationUnitExt, UnitExt},
     views::{
-        ButtonClass, CheckboxClass, LabelClass, LabelCustomStyle, LabeledCheckboxClass,
-        LabeledRadioButtonClass, ListClass, ListItemClass, PlaceholderTextClass, RadioButtonClass,
-        RadioButtonDotClass, SvgClass, TabSelectorClass, TextInputClass, ToggleButtonCircleRad,
-        ToggleButtonClass, ToggleButtonInset, TooltipClass, dropdown,
+        ButtonClass, CalendarClass, CalendarDayClass, CalendarFocusedDayClass, CalendarHeaderClass,
+        CalendarInRangeClass, CalendarNavClass, CalendarOutsideDayClass, CalendarTodayClass,
+        CalendarWeekdayClass, CheckboxClass, ColorPickerAreaClass, ColorPickerClass,
+        ColorPickerEyedropperClass, ColorPickerFormatClass, ColorPickerPreviewClass,
+        ColorPickerSliderClass, ColorPickerSwatchClass, DatePickerButtonClass, DatePickerClass,
+        DatePickerPopupClass, DialogActionsClass, DialogBackdropClass, DialogClass,
+        DialogMessageClass, DialogSheetClass, DialogTitleClass, LabelClass, LabelCustomStyle,
+        LabeledCheckboxClass, LabeledRadioButtonClass, ListClass, ListItemClass, MarkupTextClass,
+        MarkupTextCustomStyle, NumberInputButtonClass, NumberInputClass, NumberInputLabelClass,
+        PlaceholderTextClass, RadioButtonClass, RadioButtonDotClass, SvgClass, TabSelectorClass,
+        TableCellClass, TableClass, TableCustomStyle, TableHeaderClass, TextInputClass,
+        TimePickerClass, TimePickerPeriodClass, TimePickerSeparatorClass, ToastClass,
+        ToastCloseClass, ToastCountClass, ToastDangerClass, ToastInfoClass, ToastSuccessClass,
+        ToastTitleClass, ToastWarningClass, ToggleButtonCircleRad, ToggleButtonClass,
+        ToggleButtonInset, TooltipClass, TreeArrowClass, TreeGuideClass, TreeItemClass,
+        TreeItemFocusedClass, TreeViewClass, dropdown,
         resizable::{ResizableClass, ResizableCustomStyle},
         scroll,
         slider::{SliderClass, SliderCustomStyle},
@@ -378,6 +390,113 @@ pub fn overlay_style() -> Style {
         .dark_mode(|s| s.border(1).border_top(2.))
 }
 
+#[cfg(feature = "markdown")]
+fn markdown_style() -> Style {
+    use crate::views::{
+        MarkdownBlockClass, MarkdownBlockQuoteClass, MarkdownCodeBlockClass, MarkdownH1Class,
+        MarkdownH2Class, MarkdownH3Class, MarkdownH4Class, MarkdownH5Class, MarkdownH6Class,
+        MarkdownHeadingClass, MarkdownImageClass, MarkdownListClass, MarkdownListItemClass,
+        MarkdownListMarkerClass, MarkdownRuleClass, MarkdownTableCellClass, MarkdownTableClass,
+        MarkdownTableHeaderClass,
+    };
+
+    Style::new()
+        .class(MarkdownBlockClass, |s| {
+            s.with_theme(|s, t| s.padding_bottom(t.padding() * 2.))
+        })
+        .class(MarkdownHeadingClass, |s| s.font_weight(Weight::BOLD))
+        .class(MarkdownH1Class, |s| {
+            s.with_theme(|s, t| s.font_size(t.font_size() * 2.))
+        })
+        .class(MarkdownH2Class, |s| {
+            s.with_theme(|s, t| s.font_size(t.font_size() * 1.5))
+        })
+        .class(MarkdownH3Class, |s| {
+            s.with_theme(|s, t| s.font_size(t.font_size() * 1.25))
+        })
+        .class(MarkdownH4Class, |s| {
+            s.with_theme(|s, t| s.font_size(t.font_size()))
+        })
+        .class(MarkdownH5Class, |s| {
+            s.with_theme(|s, t| s.font_size(t.font_size() * 0.875))
+        })
+        .class(MarkdownH6Class, |s| {
+            s.with_theme(|s, t| s.font_size(t.font_size() * 0.85).color(t.text_muted()))
+        })
+        .class(MarkdownBlockQuoteClass, |s| {
+            s.with_theme(|s, t| {
+                s.border_left(3.)
+                    .border_color(t.border())
+                    .padding_left(t.padding() * 2.)
+                    .row_gap(t.padding())
+                    .color(t.text_muted())
+            })
+        })
+        .class(MarkdownListClass, |s| {
+            s.with_theme(|s, t| s.row_gap(t.padding()))
+        })
+        .class(MarkdownListItemClass, |s| {
+            s.with_theme(|s, t| s.col_gap(t.padding()))
+        })
+        .class(MarkdownListMarkerClass, |s| s.min_width(16.).justify_end())
+        .class(MarkdownCodeBlockClass, |s| {
+            s.with_theme(|s, t| {
+                s.background(t.bg_elevated())
+                    .border(1.)
+                    .border_color(t.border())
+                    .border_radius(t.border_radius())
+                    .padding(t.padding() * 2.)
+            })
+            .class(MarkupTextClass, |s| {
+                s.custom(|s: MarkupTextCustomStyle| s.code_background(css::TRANSPARENT))
+            })
+        })
+        .class(MarkdownTableClass, |s| {
+            s.with_theme(|s, t| {
+                s.border(1.)
+                    .border_color(t.border())
+                    .border_radius(t.border_radius())
+            })
+        })
+        .class(MarkdownTableHeaderClass, |s| {
+            s.with_theme(|s, t| {
+                s.font_weight(Weight::BOLD)
+                    .background(t.bg_elevated())
+                    .padding(t.padding())
+                    .border_bottom(1.)
+                    .border_color(t.border())
+            })
+        })
+        .class(MarkdownTableCellClass, |s| {
+            s.with_theme(|s, t| {
+                s.padding(t.padding())
+                    .border_bottom(1.)
+                    .border_color(t.border())
+            })
+        })
+        .class(MarkdownImageClass, |s| s.max_width_full())
+        .class(MarkdownRuleClass, |s| {
+            s.with_theme(|s, t| s.height(1.).width_full().background(t.border()))
+        })
+}
+
+#[cfg(not(feature = "markdown"))]
+fn markdown_style() -> Style {
+    Style::new()
+}
+
+#[cfg(feature = "editor")]
+fn text_area_style(input_style: Style) -> Style {
+    use crate::views::TextAreaClass;
+
+    Style::new().class(TextAreaClass, |_| input_style)
+}
+
+#[cfg(not(feature = "editor"))]
+fn text_area_style(_input_style: Style) -> Style {
+    Style::new()
+}
+
 pub(crate) fn default_theme(os_theme: winit::window::Theme) -> Style {
     let button_style = Style::new()
         .custom_style_class(|s: LabelCustomStyle| s.selectable(false))
@@ -519,6 +638,7 @@ pub(crate) fn default_theme(os_theme: winit::window::Theme) -> Style {
         .focus(|s| s.with_theme(|s, t| s.hover(|s| s.background(t.bg_overlay()))))
         .apply(border_style(true))
         .apply(focus_style())
+        .with_theme(|s, t| s.invalid(|s| s.border_color(t.danger())))
         .cursor(CursorStyle::Text);
 
     let tab_selector_style = Style::new()
@@ -573,6 +693,16 @@ pub(crate) fn default_theme(os_theme: winit::window::Theme) -> Style {
                 s.custom(|s: LabelCustomStyle| s.selection_color(t.primary_muted().with_alpha(0.5)))
             })
         })
+        .class(MarkupTextClass, |s| {
+            s.with_theme(|s, t| {
+                s.custom(|s: MarkupTextCustomStyle| {
+                    s.link_color(t.primary())
+                        .code_color(t.text())
+                        .code_background(t.border().with_alpha(0.4))
+                        .emphasis_color(t.text())
+                })
+            })
+        })
         .class(ListClass, |s| {
             s.apply(focus_style()).class(ListItemClass, |s| {
                 s.with_theme(|s, t| {
@@ -587,11 +717,76 @@ pub(crate) fn default_theme(os_theme: winit::window::Theme) -> Style {
                 .items_center()
             })
         })
+        .class(TableClass, |s| {
+            s.with_theme(|s, t| {
+                s.custom(|cs: TableCustomStyle| {
+                    cs.focus_color(t.primary())
+                        .handle_color(t.primary())
+                        .handle_thickness(3.)
+                })
+            })
+            .class(TableHeaderClass, |s| {
+                s.with_theme(|s, t| {
+                    s.background(t.bg_elevated())
+                        .border_color(t.border())
+                        .padding_horiz(t.padding())
+                })
+                .border_bottom(1.)
+                .border_right(1.)
+                .font_bold()
+                .items_center()
+            })
+            .class(TableCellClass, |s| {
+                s.with_theme(|s, t| {
+                    s.background(t.bg_base())
+                        .border_color(t.border().with_alpha(0.5))
+                        .padding_horiz(t.padding())
+                        .selected(|s| s.background(t.primary_muted()))
+                })
+                .border_bottom(1.)
+                .items_center()
+            })
+        })
+        .class(TreeViewClass, |s| {
+            s.apply(focus_style())
+                .class(TreeItemClass, |s| {
+                    s.with_theme(|s, t| {
+                        s.hover(|s| s.background(t.bg_elevated()))
+                            .selected(|s| {
+                                s.background(t.primary())
+                                    .color(t.bg_base)
+                                    .hover(|s| s.background(t.primary_muted()))
+                            })
+                            .border_radius(t.border_radius())
+                            .padding_right(t.padding())
+                    })
+                    .width_full()
+                    .items_center()
+                    .gap(4.)
+                })
+                .class(TreeItemFocusedClass, |s| {
+                    s.with_theme(|s, t| s.outline_color(t.primary_muted()))
+                        .outline(1.)
+                })
+                .class(TreeGuideClass, |s| {
+                    s.with_theme(|s, t| s.border_color(t.border()))
+                        .margin_left(8.)
+                        .width(8.)
+                        .height_full()
+                        .border_left(1.)
+                })
+                .class(TreeArrowClass, |s| {
+                    s.with_theme(|s, t| s.color(t.text_muted()))
+                        .width(16.)
+                        .font_size(10.)
+                })
+        })
         .class(CheckboxClass, |_| checkbox_style)
         .class(LabeledCheckboxClass, |_| labeled_checkbox_style)
         .class(RadioButtonClass, |_| radio_button_style)
         .class(RadioButtonDotClass, |_| radio_button_dot_style)
         .class(LabeledRadioButtonClass, |_| labeled_radio_button_style)
+        .apply(text_area_style(input_style.clone()))
         .class(TextInputClass, |_| input_style)
         .class(ButtonClass, |_| button_style)
         .class(TabSelectorClass, |_| tab_selector_style)
@@ -627,6 +822,212 @@ pub(crate) fn default_theme(os_theme: winit::window::Theme) -> Style {
                 })
             })
         })
+        .class(NumberInputClass, |s| {
+            s.gap(4)
+                .class(NumberInputLabelClass, |s| {
+                    s.cursor(CursorStyle::ColResize)
+                        .with_theme(|s, t| s.color(t.text_muted()))
+                })
+                .class(NumberInputButtonClass, |s| {
+                    s.size(16, 12)
+                        .cursor(CursorStyle::Pointer)
+                        .with_theme(|s, t| {
+                            s.color(t.text_muted())
+                                .border_radius(t.border_radius())
+                                .hover(|s| s.color(t.text()).background(t.bg_elevated()))
+                                .disabled(|s| s.color(t.text_muted().with_alpha(0.5)))
+                        })
+                })
+        })
+        .class(CalendarClass, |s| {
+            s.flex_col()
+                .gap(4)
+                .class(CalendarHeaderClass, |s| s.font_weight(Weight::SEMIBOLD))
+                .class(CalendarNavClass, |s| {
+                    s.size(24, 24)
+                        .items_center()
+                        .justify_center()
+                        .cursor(CursorStyle::Pointer)
+                        .with_theme(|s, t| {
+                            s.border_radius(t.border_radius())
+                                .hover(|s| s.background(t.bg_elevated()))
+                        })
+                })
+                .class(CalendarWeekdayClass, |s| {
+                    s.width(32)
+                        .justify_center()
+                        .with_theme(|s, t| s.color(t.text_muted()))
+                })
+                .class(CalendarDayClass, |s| {
+                    s.size(32, 32)
+                        .items_center()
+                        .justify_center()
+                        .cursor(CursorStyle::Pointer)
+                        .with_theme(|s, t| {
+                            s.border_radius(t.border_radius())
+                                .hover(|s| s.background(t.bg_elevated()))
+                                .selected(|s| {
+                                    s.background(t.primary())
+                                        .color(t.bg_base)
+                                        .hover(|s| s.background(t.primary_muted()))
+                                })
+                                .disabled(|s| {
+                                    s.color(t.text_muted().with_alpha(0.5))
+                                        .cursor(CursorStyle::Default)
+                                })
+                        })
+                })
+                .class(CalendarOutsideDayClass, |s| {
+                    s.with_theme(|s, t| s.color(t.text_muted()))
+                })
+                .class(CalendarInRangeClass, |s| {
+                    s.with_theme(|s, t| s.background(t.primary_muted()))
+                })
+                .class(CalendarTodayClass, |s| {
+                    s.with_theme(|s, t| s.outline_color(t.border())).outline(1.)
+                })
+                .class(CalendarFocusedDayClass, |s| {
+                    s.with_theme(|s, t| s.outline_color(t.primary_muted()))
+                        .outline(2.)
+                })
+        })
+        .class(DatePickerClass, |s| {
+            s.gap(4).class(DatePickerButtonClass, |s| {
+                s.size(16, 16)
+                    .cursor(CursorStyle::Pointer)
+                    .with_theme(|s, t| {
+                        s.color(t.text_muted())
+                            .hover(|s| s.color(t.text()))
+                            .disabled(|s| s.color(t.text_muted().with_alpha(0.5)))
+                    })
+            })
+        })
+        .class(DatePickerPopupClass, |s| s.apply(overlay_style()))
+        .class(TimePickerClass, |s| {
+            s.gap(2)
+                .class(TimePickerSeparatorClass, |s| {
+                    s.with_theme(|s, t| s.color(t.text_muted()))
+                })
+                .class(TimePickerPeriodClass, |s| {
+                    s.cursor(CursorStyle::Pointer)
+                        .margin_left(4)
+                        .with_theme(|s, t| {
+                            s.padding_horiz(t.padding())
+                                .border_radius(t.border_radius())
+                                .hover(|s| s.background(t.bg_elevated()))
+                        })
+                })
+        })
+        .class(ColorPickerClass, |s| {
+            s.gap(8)
+                .width(240)
+                .class(ColorPickerAreaClass, |s| {
+                    s.width_full()
+                        .height(150)
+                        .cursor(CursorStyle::Pointer)
+                        .with_theme(|s, t| {
+                            s.border_radius(t.border_radius())
+                                .focus_visible(|s| s.outline(2).outline_color(t.primary_muted()))
+                        })
+                })
+                .class(ColorPickerSliderClass, |s| s.width_full().height(12))
+                .class(ColorPickerPreviewClass, |s| {
+                    s.size(28, 28).border(1).with_theme(|s, t| {
+                        s.border_color(t.border()).border_radius(t.border_radius())
+                    })
+                })
+                .class(ColorPickerEyedropperClass, |s| {
+                    s.size(16, 16)
+                        .cursor(CursorStyle::Pointer)
+                        .with_theme(|s, t| s.color(t.text_muted()).hover(|s| s.color(t.text())))
+                })
+                .class(ColorPickerFormatClass, |s| {
+                    s.min_width(48)
+                        .justify_center()
+                        .cursor(CursorStyle::Pointer)
+                        .with_theme(|s, t| {
+                            s.color(t.text_muted())
+                                .padding_horiz(t.padding())
+                                .border_radius(t.border_radius())
+                                .hover(|s| s.color(t.text()).background(t.bg_elevated()))
+                        })
+                })
+                .class(ColorPickerSwatchClass, |s| {
+                    s.size(18, 18)
+                        .margin(2)
+                        .border(1)
+                        .cursor(CursorStyle::Pointer)
+                        .with_theme(|s, t| {
+                            s.border_color(t.border())
+                                .border_radius(t.border_radius())
+                                .hover(|s| s.border_color(t.text_muted()))
+                        })
+                })
+        })
+        .class(DialogBackdropClass, |s| {
+            s.background(css::BLACK.with_alpha(0.4))
+        })
+        .class(DialogClass, |s| {
+            s.apply(overlay_style())
+                .min_width(300)
+                .max_width(560)
+                .with_theme(|s, t| s.padding(t.padding() * 2.))
+        })
+        .class(DialogSheetClass, |s| {
+            s.width_full()
+                .max_width_full()
+                .border_bottom(0)
+                .with_theme(|s, t| {
+                    s.border_radius(0.)
+                        .border_top_left_radius(t.border_radius())
+                        .border_top_right_radius(t.border_radius())
+                })
+        })
+        .class(DialogTitleClass, |s| {
+            s.font_size(16.).font_weight(Weight::SEMIBOLD)
+        })
+        .class(DialogMessageClass, |s| {
+            s.with_theme(|s, t| s.color(t.text_muted()))
+        })
+        .class(DialogActionsClass, |s| s.justify_end().gap(8).margin_top(4))
+        .class(ToastClass, |s| {
+            s.apply(overlay_style())
+                .items_center()
+                .gap(8)
+                .min_width(260)
+                .max_width(380)
+                .border_left(4)
+                .class(ToastTitleClass, |s| s.font_weight(Weight::SEMIBOLD))
+                .class(ToastCloseClass, |s| {
+                    s.padding(4)
+                        .cursor(CursorStyle::Pointer)
+                        .with_theme(|s, t| {
+                            s.color(t.text_muted())
+                                .border_radius(t.border_radius())
+                                .hover(|s| s.color(t.text()).background(t.bg_elevated()))
+                        })
+                })
+        })
+        .class(ToastInfoClass, |s| {
+            s.with_theme(|s, t| s.border_left_color(t.info()))
+        })
+        .class(ToastSuccessClass, |s| {
+            s.with_theme(|s, t| s.border_left_color(t.success()))
+        })
+        .class(ToastWarningClass, |s| {
+            s.with_theme(|s, t| s.border_left_color(t.warning()))
+        })
+        .class(ToastDangerClass, |s| {
+            s.with_theme(|s, t| s.border_left_color(t.danger()))
+        })
+        .class(ToastCountClass, |s| {
+            s.font_size(12.).with_theme(|s, t| {
+                s.color(t.text_muted())
+                    .padding_horiz(t.padding())
+                    .border_radius(t.border_radius())
+                    .background(t.bg_elevated())
+            })
+        })
         .class(PlaceholderTextClass, |s| {
             s.with_theme(|s, t| {
                 s.color(t.text_muted()).disabled(|s| {
@@ -687,4 +1088,5 @@ pub(crate) fn default_theme(os_theme: winit::window::Theme) -> Style {
             })
             .transition(Background, Transition::linear(100.millis()))
         })
+        .apply(markdown_style())
 }
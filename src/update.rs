@@ -26,9 +26,14 @@ thread_local! {
 
 type DeferredUpdateMessages = HashMap<ViewId, Vec<(ViewId, Box<dyn Any>)>>;
 
+/// Receives the image rendered for [`UpdateMessage::RenderToImage`].
+pub(crate) type ImageCallback = Box<dyn FnOnce(peniko::ImageBrush)>;
+
 pub(crate) enum UpdateMessage {
     Focus(ViewId),
     ClearFocus(ViewId),
+    TrapFocus(ViewId),
+    ReleaseFocus(ViewId),
     ClearAppFocus,
     Active(ViewId),
     ClearActive(ViewId),
@@ -54,4 +59,5 @@ pub(crate) enum UpdateMessage {
     WindowVisible(bool),
     ViewTransitionAnimComplete(ViewId),
     SetTheme(Option<Theme>),
+    RenderToImage(ViewId, f64, ImageCallback),
 }
@@ -50,13 +50,14 @@ use taffy::tree::NodeId;
 
 use crate::{
     Renderer,
+    accessibility::Accessibility,
     context::{ComputeLayoutCx, EventCx, LayoutCx, PaintCx, StyleCx, UpdateCx},
     event::{Event, EventPropagation},
     id::ViewId,
     style::{LayoutProps, Style, StyleClassRef},
     unit::PxPct,
     view_state::ViewStyleProps,
-    views::{DynamicView, dyn_view},
+    views::{DynamicView, SelectableText, dyn_view},
     window_state::WindowState,
 };
 
@@ -195,6 +196,25 @@ impl<IV: IntoView + 'static> IntoView for Vec<IV> {
     }
 }
 
+/// An `Err` is reported to the closest [`error_boundary`](crate::views::error_boundary) and
+/// renders nothing. Without a boundary, the error message is shown as text.
+impl<IV: IntoView + 'static, E: std::fmt::Display> IntoView for Result<IV, E> {
+    type V = AnyView;
+
+    fn into_view(self) -> Self::V {
+        match self {
+            Ok(view) => view.into_any(),
+            Err(error) => {
+                if crate::views::report_error(&error) {
+                    crate::views::empty().into_any()
+                } else {
+                    crate::views::text(error).into_any()
+                }
+            }
+        }
+    }
+}
+
 /// Default implementation of `View::layout()` which can be used by
 /// view implementations that need the default behavior and also need
 /// to implement that method to do additional work.
@@ -269,6 +289,21 @@ pub trait View {
         core::any::type_name::<Self>().into()
     }
 
+    /// Describes the view to assistive technologies such as screen readers.
+    ///
+    /// Views returning `None` are exposed as generic containers. When the state described
+    /// here changes, call [`ViewId::request_accessibility`] so the description is built
+    /// again. See the [`accessibility`](crate::accessibility) module.
+    fn accessibility(&self) -> Option<Accessibility> {
+        None
+    }
+
+    /// The text of the view that a [`selectable_area`](crate::views::selectable_area) can
+    /// select, if any.
+    fn selectable_text(&mut self) -> Option<&mut dyn SelectableText> {
+        None
+    }
+
     /// Use this method to react to changes in view-related state.
     /// You will usually send state to this hook manually using the `View`'s `Id` handle
     ///
@@ -399,6 +434,14 @@ impl View for Box<dyn View> {
     fn scroll_to(&mut self, cx: &mut WindowState, target: ViewId, rect: Option<Rect>) -> bool {
         (**self).scroll_to(cx, target, rect)
     }
+
+    fn accessibility(&self) -> Option<Accessibility> {
+        (**self).accessibility()
+    }
+
+    fn selectable_text(&mut self) -> Option<&mut dyn SelectableText> {
+        (**self).selectable_text()
+    }
 }
 
 /// Computes the layout of the view's children, if any.
@@ -503,6 +546,16 @@ pub(crate) fn paint_bg(cx: &mut PaintCx, style: &ViewStyleProps, size: Size) {
     }
 }
 
+/// Blurs what was painted behind the view, within its border radius.
+pub(crate) fn paint_backdrop_blur(cx: &mut PaintCx, style: &ViewStyleProps, size: Size) {
+    let radius = style.backdrop_blur();
+    if radius <= 0.0 {
+        return;
+    }
+    let radii = border_to_radii_view(style, size);
+    cx.blur_backdrop(&size.to_rect().to_rounded_rect(radii), radius);
+}
+
 fn paint_box_shadow(
     cx: &mut PaintCx,
     style: &ViewStyleProps,
@@ -795,12 +848,19 @@ pub(crate) fn view_tab_navigation(
     let start = window_state
         .focus
         .unwrap_or(window_state.prev_focus.unwrap_or(root_view));
+    // navigation starts over from the root when the focus is outside of it, such as when a
+    // focus trap was just set
+    let start = if start.is_descendant_of(root_view) {
+        start
+    } else {
+        root_view
+    };
 
     let tree_iter = |id: ViewId| {
         if backwards {
             view_tree_previous(root_view, id).unwrap_or_else(|| view_nested_last_child(root_view))
         } else {
-            view_tree_next(id).unwrap_or(root_view)
+            view_tree_next(root_view, id).unwrap_or(root_view)
         }
     };
 
@@ -813,14 +873,18 @@ pub(crate) fn view_tab_navigation(
     window_state.update_focus(new_focus, true);
 }
 
-/// Get the next item in the tree, either the first child or the next sibling of this view or of the first parent view
-fn view_tree_next(id: ViewId) -> Option<ViewId> {
+/// Get the next item in the tree, either the first child or the next sibling of this view or of the first parent view,
+/// without leaving the tree of `root_view`
+fn view_tree_next(root_view: ViewId, id: ViewId) -> Option<ViewId> {
     if let Some(child) = id.children().into_iter().next() {
         return Some(child);
     }
 
     let mut ancestor = id;
     loop {
+        if ancestor == root_view {
+            return None;
+        }
         if let Some(next_sibling) = view_next_sibling(ancestor) {
             return Some(next_sibling);
         }
@@ -848,12 +912,16 @@ fn view_next_sibling(id: ViewId) -> Option<ViewId> {
     }
 }
 
-/// Get the next item in the tree, the deepest last child of the previous sibling of this view or the parent
+/// Get the previous item in the tree, the deepest last child of the previous sibling of this view or the parent,
+/// without leaving the tree of `root_view`
 fn view_tree_previous(root_view: ViewId, id: ViewId) -> Option<ViewId> {
+    if id == root_view {
+        return None;
+    }
     view_previous_sibling(id)
         .map(view_nested_last_child)
         .or_else(|| {
-            (root_view != id).then_some(
+            Some(
                 id.parent()
                     .unwrap_or_else(|| view_nested_last_child(root_view)),
             )
@@ -1,8 +1,9 @@
 use crate::{
+    accessibility::Accessibility,
     animate::Animation,
     context::{
-        CleanupListeners, EventCallback, InteractionState, MenuCallback, MoveListeners,
-        ResizeCallback, ResizeListeners,
+        CleanupListeners, DragPayloadFn, DragPreviewFn, DropHandler, EventCallback,
+        InteractionState, MenuCallback, MoveListeners, ResizeCallback, ResizeListeners,
     },
     event::EventListener,
     prop_extractor,
@@ -82,6 +83,9 @@ prop_extractor! {
         pub border_color: BorderColorProp,
         pub background: Background,
         pub shadow: BoxShadowProp,
+        pub opacity: crate::style::Opacity,
+        pub filter: crate::style::FilterProp,
+        pub backdrop_blur: crate::style::BackdropBlur,
     }
 }
 // removing outlines to make clippy happy about progress fields not being read
@@ -95,6 +99,9 @@ prop_extractor! {
         pub border_color: BorderColorProp,
         pub background: Background,
         pub shadow: BoxShadowProp,
+        pub opacity: crate::style::Opacity,
+        pub filter: crate::style::FilterProp,
+        pub backdrop_blur: crate::style::BackdropBlur,
     }
 }
 
@@ -186,6 +193,9 @@ pub struct ViewState {
     pub(crate) animations: Stack<Animation>,
     pub(crate) classes: Vec<StyleClassRef>,
     pub(crate) dragging_style: Option<Style>,
+    pub(crate) drag_payload: Option<Rc<DragPayloadFn>>,
+    pub(crate) drag_preview: Option<Rc<DragPreviewFn>>,
+    pub(crate) drop_handlers: Vec<Rc<DropHandler>>,
     /// Combine the stacked style into one style, and apply the interact state.
     pub(crate) combined_style: Style,
     /// The final style including inherited style from parent.
@@ -204,6 +214,8 @@ pub struct ViewState {
     pub(crate) disable_default_events: HashSet<EventListener>,
     pub(crate) transform: Affine,
     pub(crate) debug_name: SmallVec<[String; 1]>,
+    pub(crate) accessibility: Vec<Accessibility>,
+    pub(crate) accessibility_dirty: bool,
 }
 
 impl ViewState {
@@ -229,6 +241,9 @@ impl ViewState {
             computed_style: Style::new(),
             taffy_style: taffy::style::Style::DEFAULT,
             dragging_style: None,
+            drag_payload: None,
+            drag_preview: None,
+            drop_handlers: Vec::new(),
             event_listeners: HashMap::new(),
             context_menu: None,
             popout_menu: None,
@@ -242,6 +257,8 @@ impl ViewState {
             disable_default_events: HashSet::new(),
             transform: Affine::IDENTITY,
             debug_name: Default::default(),
+            accessibility: Vec::new(),
+            accessibility_dirty: true,
         }
     }
 
@@ -1,5 +1,10 @@
 #![deny(missing_docs)]
-use crate::{IntoView, View, ViewId, style_class, views::Decorators};
+use crate::{
+    IntoView, View, ViewId,
+    accessibility::{Accessibility, Action, Role},
+    style_class,
+    views::Decorators,
+};
 
 style_class!(
     /// The style class that is applied to buttons.
@@ -45,6 +50,10 @@ impl View for Button {
     fn id(&self) -> ViewId {
         self.id
     }
+
+    fn accessibility(&self) -> Option<Accessibility> {
+        Some(Accessibility::new(Role::Button).action(Action::Click))
+    }
 }
 
 impl Button {
@@ -0,0 +1,856 @@
+#![deny(missing_docs)]
+
+//! A month grid for picking dates or ranges of dates. See [`calendar`] and [`range_calendar`].
+
+use std::{fmt, rc::Rc, str::FromStr};
+
+use floem_reactive::{RwSignal, SignalGet, SignalUpdate, SignalWith};
+use ui_events::keyboard::{Key, KeyState, KeyboardEvent, Modifiers, NamedKey};
+
+use super::{Decorators, h_stack, h_stack_from_iter, label, v_stack_from_iter};
+use crate::{
+    accessibility::{Accessibility, Role},
+    event::{Event, EventListener, EventPropagation},
+    id::ViewId,
+    style_class,
+    view::{IntoView, View},
+};
+#[cfg(feature = "localization")]
+use crate::{
+    context::StyleCx,
+    prop_extractor,
+    views::localization::{L10nBundle, L10nLocale, LanguageIdentifier, LocaleMap},
+};
+
+style_class!(
+    /// The style class that is applied to all [`Calendar`] views.
+    pub CalendarClass
+);
+style_class!(
+    /// The style class that is applied to the month and year shown at the top of a [`Calendar`].
+    pub CalendarHeaderClass
+);
+style_class!(
+    /// The style class that is applied to the previous and next month buttons of a [`Calendar`].
+    pub CalendarNavClass
+);
+style_class!(
+    /// The style class that is applied to the names of the days of the week of a [`Calendar`].
+    pub CalendarWeekdayClass
+);
+style_class!(
+    /// The style class that is applied to every day of a [`Calendar`].
+    pub CalendarDayClass
+);
+style_class!(
+    /// The style class that is applied to the days of a [`Calendar`] from the previous and next
+    /// months.
+    pub CalendarOutsideDayClass
+);
+style_class!(
+    /// The style class that is applied to the current day in a [`Calendar`].
+    pub CalendarTodayClass
+);
+style_class!(
+    /// The style class that is applied to the days between the start and end of the range
+    /// selected in a [`range_calendar`].
+    pub CalendarInRangeClass
+);
+style_class!(
+    /// The style class that is applied to the day focused with the keyboard while the
+    /// [`Calendar`] has focus.
+    pub CalendarFocusedDayClass
+);
+
+/// A day of the week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Weekday {
+    /// Monday.
+    Monday,
+    /// Tuesday.
+    Tuesday,
+    /// Wednesday.
+    Wednesday,
+    /// Thursday.
+    Thursday,
+    /// Friday.
+    Friday,
+    /// Saturday.
+    Saturday,
+    /// Sunday.
+    Sunday,
+}
+
+impl Weekday {
+    const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+
+    /// The ISO 8601 number of the day, from `1` for Monday to `7` for Sunday.
+    pub fn number(self) -> u8 {
+        self as u8 + 1
+    }
+
+    /// The day with the ISO 8601 `number`, from `1` for Monday to `7` for Sunday.
+    pub fn from_number(number: u8) -> Option<Weekday> {
+        Self::ALL.get(usize::from(number).checked_sub(1)?).copied()
+    }
+
+    /// The day `days` days after this one.
+    pub fn add_days(self, days: i64) -> Weekday {
+        Self::ALL[(self as i64 + days).rem_euclid(7) as usize]
+    }
+
+    /// The number of days from `other` to the next or same day that is this one.
+    pub fn days_since(self, other: Weekday) -> u8 {
+        (self as u8 + 7 - other as u8) % 7
+    }
+}
+
+/// A date in the proleptic Gregorian calendar, without a time zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+/// The error returned when parsing a [`Date`] that isn't a valid `YYYY-MM-DD` date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDateError;
+
+impl fmt::Display for ParseDateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid date, expected YYYY-MM-DD")
+    }
+}
+
+impl std::error::Error for ParseDateError {}
+
+impl Date {
+    /// The date of the `day` of the `month` of the `year`, if it exists. Months and days
+    /// start at `1`.
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Date> {
+        let valid =
+            (1..=12).contains(&month) && (1..=Self::days_in_month(year, month)).contains(&day);
+        valid.then_some(Date { year, month, day })
+    }
+
+    /// The current date in the local time zone with the `chrono` feature, and in UTC
+    /// without it.
+    pub fn today() -> Date {
+        #[cfg(feature = "chrono")]
+        {
+            Self::from_chrono(chrono::Local::now())
+        }
+        #[cfg(not(feature = "chrono"))]
+        {
+            #[cfg(not(target_arch = "wasm32"))]
+            use std::time::{SystemTime, UNIX_EPOCH};
+            #[cfg(target_arch = "wasm32")]
+            use web_time::{SystemTime, UNIX_EPOCH};
+
+            let secs = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs());
+            Self::from_days((secs / 86_400) as i64)
+        }
+    }
+
+    /// The date of `time` in its time zone.
+    #[cfg(feature = "chrono")]
+    fn from_chrono<Tz: chrono::TimeZone>(time: chrono::DateTime<Tz>) -> Date {
+        use chrono::Datelike;
+
+        let date = time.date_naive();
+        Date {
+            year: date.year(),
+            month: date.month() as u8,
+            day: date.day() as u8,
+        }
+    }
+
+    /// The year.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// The month, from `1` to `12`.
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// The day of the month, from `1` to `31`.
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// The day of the week.
+    pub fn weekday(&self) -> Weekday {
+        // 1970-01-01 was a Thursday
+        Weekday::Thursday.add_days(self.to_days())
+    }
+
+    /// Whether `year` has a 29th of February.
+    pub fn is_leap_year(year: i32) -> bool {
+        year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+    }
+
+    /// The number of days of the `month` of the `year`.
+    pub fn days_in_month(year: i32, month: u8) -> u8 {
+        match month {
+            2 if Self::is_leap_year(year) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    /// The first day of the month of this date.
+    pub fn first_of_month(&self) -> Date {
+        Date { day: 1, ..*self }
+    }
+
+    /// The date `days` days after this one, or before it if `days` is negative.
+    pub fn add_days(&self, days: i64) -> Date {
+        Self::from_days(self.to_days() + days)
+    }
+
+    /// The date `months` months after this one, or before it if `months` is negative.
+    ///
+    /// The day is clamped to the length of the month, so one month after the 31st of January
+    /// is the last day of February.
+    pub fn add_months(&self, months: i32) -> Date {
+        let month = self.year * 12 + i32::from(self.month) - 1 + months;
+        let year = month.div_euclid(12);
+        let month = month.rem_euclid(12) as u8 + 1;
+        Date {
+            year,
+            month,
+            day: self.day.min(Self::days_in_month(year, month)),
+        }
+    }
+
+    /// The number of days since 1970-01-01.
+    fn to_days(self) -> i64 {
+        // http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month = i64::from(self.month);
+        let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5
+            + i64::from(self.day)
+            - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// The date `days` days after 1970-01-01.
+    fn from_days(days: i64) -> Date {
+        // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Date {
+            year: year as i32,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    /// Formats the date as `YYYY-MM-DD`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = ParseDateError;
+
+    /// Parses a `YYYY-MM-DD` date.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().splitn(3, '-');
+        let mut next = || parts.next().ok_or(ParseDateError);
+        let (year, month, day) = (next()?, next()?, next()?);
+        let parse = |part: &str, len: usize| {
+            if part.len() == len && part.bytes().all(|b| b.is_ascii_digit()) {
+                part.parse().map_err(|_| ParseDateError)
+            } else {
+                Err(ParseDateError)
+            }
+        };
+        Date::new(
+            parse(year, 4)?,
+            parse(month, 2)? as u8,
+            parse(day, 2)? as u8,
+        )
+        .ok_or(ParseDateError)
+    }
+}
+
+/// A range of dates, including both its start and end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateRange {
+    /// The first date of the range.
+    pub start: Date,
+    /// The last date of the range.
+    pub end: Date,
+}
+
+impl DateRange {
+    /// The range between two dates, in any order.
+    pub fn new(a: Date, b: Date) -> Self {
+        Self {
+            start: a.min(b),
+            end: a.max(b),
+        }
+    }
+
+    /// Whether `date` is in the range.
+    pub fn contains(&self, date: Date) -> bool {
+        (self.start..=self.end).contains(&date)
+    }
+}
+
+/// The names and first day of the week shown by a [`Calendar`].
+#[derive(Debug, Clone, PartialEq)]
+struct CalendarLocale {
+    months: [String; 12],
+    weekdays: [String; 7],
+    first_day: Weekday,
+}
+
+impl Default for CalendarLocale {
+    fn default() -> Self {
+        Self {
+            months: [
+                "January",
+                "February",
+                "March",
+                "April",
+                "May",
+                "June",
+                "July",
+                "August",
+                "September",
+                "October",
+                "November",
+                "December",
+            ]
+            .map(String::from),
+            weekdays: ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"].map(String::from),
+            first_day: Weekday::Monday,
+        }
+    }
+}
+
+#[cfg(feature = "localization")]
+impl CalendarLocale {
+    /// Reads the names from the messages of `bundle` for `locale`, keeping the English names
+    /// of the missing messages.
+    fn from_bundle(bundle: &LocaleMap, locale: &LanguageIdentifier) -> Self {
+        let mut names = Self::default();
+        for (idx, month) in names.months.iter_mut().enumerate() {
+            if let Some(name) = bundle.message(locale, &format!("calendar-month-{}", idx + 1), None)
+            {
+                *month = name;
+            }
+        }
+        for (idx, weekday) in names.weekdays.iter_mut().enumerate() {
+            if let Some(name) =
+                bundle.message(locale, &format!("calendar-weekday-{}", idx + 1), None)
+            {
+                *weekday = name;
+            }
+        }
+        if let Some(first_day) = bundle
+            .message(locale, "calendar-first-day-of-week", None)
+            .and_then(|number| number.trim().parse().ok())
+            .and_then(Weekday::from_number)
+        {
+            names.first_day = first_day;
+        }
+        names
+    }
+}
+
+#[cfg(feature = "localization")]
+prop_extractor! {
+    LocaleExtractor {
+        locale: L10nLocale,
+        bundle: L10nBundle,
+    }
+}
+
+/// Where the dates picked in a [`Calendar`] are stored.
+#[derive(Clone, Copy)]
+enum CalendarSelection {
+    Single(RwSignal<Option<Date>>),
+    Range(RwSignal<Option<DateRange>>),
+}
+
+pub(crate) type DateFilter = Rc<dyn Fn(Date) -> bool>;
+
+#[derive(Clone, Copy)]
+struct CalendarState {
+    calendar: ViewId,
+    selection: CalendarSelection,
+    /// The first date picked in a range calendar, until the second one is picked.
+    anchor: RwSignal<Option<Date>>,
+    /// The first day of the month shown.
+    month: RwSignal<Date>,
+    focused: RwSignal<Date>,
+    has_focus: RwSignal<bool>,
+    first_day: RwSignal<Option<Weekday>>,
+    locale: RwSignal<CalendarLocale>,
+    disabled: RwSignal<Option<DateFilter>>,
+    on_select: RwSignal<Option<Rc<dyn Fn(Date)>>>,
+}
+
+impl CalendarState {
+    fn first_day(&self) -> Weekday {
+        self.first_day
+            .get()
+            .unwrap_or_else(|| self.locale.with(|locale| locale.first_day))
+    }
+
+    /// The first date of the grid, which starts on the first day of the week on or before the
+    /// first day of the month.
+    fn grid_start(&self) -> Date {
+        let month = self.month.get();
+        month.add_days(-i64::from(month.weekday().days_since(self.first_day())))
+    }
+
+    fn is_disabled(&self, date: Date) -> bool {
+        self.disabled
+            .with(|disabled| disabled.as_ref().is_some_and(|disabled| disabled(date)))
+    }
+
+    fn is_selected(&self, date: Date) -> bool {
+        match self.selection {
+            CalendarSelection::Single(selected) => selected.get() == Some(date),
+            CalendarSelection::Range(range) => {
+                self.anchor.get() == Some(date)
+                    || range.with(|range| {
+                        range.is_some_and(|range| range.start == date || range.end == date)
+                    })
+            }
+        }
+    }
+
+    fn is_in_range(&self, date: Date) -> bool {
+        match self.selection {
+            CalendarSelection::Single(_) => false,
+            CalendarSelection::Range(range) => {
+                self.anchor.get().is_none()
+                    && range.with(|range| {
+                        range.is_some_and(|range| range.start < date && date < range.end)
+                    })
+            }
+        }
+    }
+
+    /// Moves the keyboard focus to `date`, showing its month.
+    fn focus_date(&self, date: Date) {
+        self.focused.set(date);
+        if date.first_of_month() != self.month.get_untracked() {
+            self.month.set(date.first_of_month());
+        }
+    }
+
+    fn shift_month(&self, months: i32) {
+        self.month.update(|month| *month = month.add_months(months));
+        self.focused
+            .update(|focused| *focused = focused.add_months(months));
+    }
+
+    fn select(&self, date: Date) {
+        if self
+            .disabled
+            .with_untracked(|disabled| disabled.as_ref().is_some_and(|disabled| disabled(date)))
+        {
+            return;
+        }
+        match self.selection {
+            CalendarSelection::Single(selected) => selected.set(Some(date)),
+            CalendarSelection::Range(range) => match self.anchor.get_untracked() {
+                None => {
+                    self.anchor.set(Some(date));
+                    return;
+                }
+                Some(anchor) => {
+                    self.anchor.set(None);
+                    range.set(Some(DateRange::new(anchor, date)));
+                }
+            },
+        }
+        if let Some(on_select) = self.on_select.get_untracked() {
+            on_select(date);
+        }
+    }
+
+    fn handle_key(&self, event: &KeyboardEvent) -> bool {
+        let focused = self.focused.get_untracked();
+        let shift = event.modifiers.contains(Modifiers::SHIFT);
+        let into_week = i64::from(focused.weekday().days_since(self.first_day()));
+        let target = match &event.key {
+            Key::Named(NamedKey::ArrowLeft) => focused.add_days(-1),
+            Key::Named(NamedKey::ArrowRight) => focused.add_days(1),
+            Key::Named(NamedKey::ArrowUp) => focused.add_days(-7),
+            Key::Named(NamedKey::ArrowDown) => focused.add_days(7),
+            Key::Named(NamedKey::Home) => focused.add_days(-into_week),
+            Key::Named(NamedKey::End) => focused.add_days(6 - into_week),
+            Key::Named(NamedKey::PageUp) => focused.add_months(if shift { -12 } else { -1 }),
+            Key::Named(NamedKey::PageDown) => focused.add_months(if shift { 12 } else { 1 }),
+            Key::Named(NamedKey::Enter) => {
+                self.select(focused);
+                return true;
+            }
+            Key::Character(c) if c == " " => {
+                self.select(focused);
+                return true;
+            }
+            _ => return false,
+        };
+        self.focus_date(target);
+        true
+    }
+}
+
+/// A month grid for picking a date, or a range of dates. See [`calendar`] and
+/// [`range_calendar`].
+///
+/// The day focused with the keyboard moves with the arrow keys, to the start and end of the
+/// week with `Home` and `End`, and by a month with `PageUp` and `PageDown`, or by a year
+/// while holding `Shift`. `Enter` and `Space` pick the focused day.
+///
+/// ## Localization
+/// With the `localization` feature, the names of the months and days of the week and the
+/// first day of the week are read from these messages of the [`L10nBundle`](crate::views::localization::L10nBundle)
+/// for the [`L10nLocale`](crate::views::localization::L10nLocale) of the calendar, falling back
+/// to English names and Monday:
+/// ```ftl
+/// calendar-month-1 = janvier
+/// # ... up to calendar-month-12
+/// calendar-weekday-1 = lu
+/// # ... up to calendar-weekday-7, from Monday to Sunday
+/// # from 1 for Monday to 7 for Sunday
+/// calendar-first-day-of-week = 1
+/// ```
+pub struct Calendar {
+    id: ViewId,
+    state: CalendarState,
+    #[cfg(feature = "localization")]
+    locale_style: LocaleExtractor,
+}
+
+/// Creates a [`Calendar`] picking a single date into `selected`.
+///
+/// The calendar shows the month of the selected date, or of the current date if there is none.
+/// ```
+/// # use floem::prelude::*;
+/// let day = RwSignal::new(None);
+/// calendar(day).disabled_dates(|date| date.weekday() == Weekday::Sunday);
+/// ```
+pub fn calendar(selected: RwSignal<Option<Date>>) -> Calendar {
+    let initial = selected.get_untracked();
+    Calendar::new(CalendarSelection::Single(selected), initial)
+}
+
+/// Creates a [`Calendar`] picking a range of dates into `range`.
+///
+/// The first date clicked starts the range and the second one ends it, in any order.
+/// The dates in between aren't checked against the [disabled dates](Calendar::disabled_dates).
+/// ```
+/// # use floem::prelude::*;
+/// let stay = RwSignal::new(None);
+/// range_calendar(stay).on_select(move |_| {
+///     if let Some(DateRange { start, end }) = stay.get_untracked() {
+///         println!("staying from {start} to {end}");
+///     }
+/// });
+/// ```
+pub fn range_calendar(range: RwSignal<Option<DateRange>>) -> Calendar {
+    let initial = range.get_untracked().map(|range| range.start);
+    Calendar::new(CalendarSelection::Range(range), initial)
+}
+
+impl Calendar {
+    fn new(selection: CalendarSelection, initial: Option<Date>) -> Self {
+        let id = ViewId::new();
+        let today = Date::today();
+        let focused = initial.unwrap_or(today);
+        let state = CalendarState {
+            calendar: id,
+            selection,
+            anchor: RwSignal::new(None),
+            month: RwSignal::new(focused.first_of_month()),
+            focused: RwSignal::new(focused),
+            has_focus: RwSignal::new(false),
+            first_day: RwSignal::new(None),
+            locale: RwSignal::new(CalendarLocale::default()),
+            disabled: RwSignal::new(None),
+            on_select: RwSignal::new(None),
+        };
+
+        let header = h_stack((
+            label(|| "‹")
+                .class(CalendarNavClass)
+                .on_click_stop(move |_| state.shift_month(-1)),
+            label(move || {
+                let month = state.month.get();
+                state.locale.with(|locale| {
+                    format!(
+                        "{} {}",
+                        locale.months[month.month() as usize - 1],
+                        month.year()
+                    )
+                })
+            })
+            .class(CalendarHeaderClass),
+            label(|| "›")
+                .class(CalendarNavClass)
+                .on_click_stop(move |_| state.shift_month(1)),
+        ))
+        .style(|s| s.items_center().justify_between());
+
+        let weekdays = h_stack_from_iter((0..7).map(move |idx| {
+            label(move || {
+                let weekday = state.first_day().add_days(idx);
+                state
+                    .locale
+                    .with(|locale| locale.weekdays[weekday as usize].clone())
+            })
+            .class(CalendarWeekdayClass)
+        }));
+
+        let weeks = v_stack_from_iter((0..6).map(move |week| {
+            h_stack_from_iter((0..7).map(move |day| day_cell(state, week * 7 + day, today)))
+        }));
+
+        id.set_children([header.into_any(), weekdays.into_any(), weeks.into_any()]);
+
+        Calendar {
+            id,
+            state,
+            #[cfg(feature = "localization")]
+            locale_style: Default::default(),
+        }
+        .class(CalendarClass)
+        .keyboard_navigable()
+        .on_event_cont(EventListener::FocusGained, move |_| {
+            state.has_focus.set(true)
+        })
+        .on_event_cont(EventListener::FocusLost, move |_| {
+            state.has_focus.set(false)
+        })
+        .on_event(EventListener::KeyDown, move |e| {
+            if let Event::Key(
+                key_event @ KeyboardEvent {
+                    state: KeyState::Down,
+                    ..
+                },
+            ) = e
+            {
+                if state.handle_key(key_event) {
+                    return EventPropagation::Stop;
+                }
+            }
+            EventPropagation::Continue
+        })
+    }
+
+    /// Sets the first day of the week, overriding the one of the locale.
+    pub fn first_day_of_week(self, weekday: Weekday) -> Self {
+        self.state.first_day.set(Some(weekday));
+        self
+    }
+
+    /// Prevents the dates for which `disabled` returns `true` from being picked.
+    pub fn disabled_dates(self, disabled: impl Fn(Date) -> bool + 'static) -> Self {
+        self.state.disabled.set(Some(Rc::new(disabled)));
+        self
+    }
+
+    /// Calls `on_select` with the date picked with the pointer or keyboard, once the
+    /// selection is updated. In a [`range_calendar`], it is only called when the range is complete.
+    pub fn on_select(self, on_select: impl Fn(Date) + 'static) -> Self {
+        self.state.on_select.set(Some(Rc::new(on_select)));
+        self
+    }
+
+    /// Returns the first day of the month shown.
+    pub fn month(&self) -> RwSignal<Date> {
+        self.state.month
+    }
+
+    /// Returns the day focused with the keyboard.
+    pub fn focused(&self) -> RwSignal<Date> {
+        self.state.focused
+    }
+}
+
+fn day_cell(state: CalendarState, idx: usize, today: Date) -> impl IntoView {
+    let date = move || state.grid_start().add_days(idx as i64);
+    label(move || date().day())
+        .class(CalendarDayClass)
+        .class_if(
+            move || date().month() != state.month.get().month(),
+            CalendarOutsideDayClass,
+        )
+        .class_if(move || date() == today, CalendarTodayClass)
+        .class_if(move || state.is_in_range(date()), CalendarInRangeClass)
+        .class_if(
+            move || state.has_focus.get() && state.focused.get() == date(),
+            CalendarFocusedDayClass,
+        )
+        .style(move |s| {
+            let date = date();
+            s.set_selected(state.is_selected(date))
+                .set_disabled(state.is_disabled(date))
+        })
+        .on_click_stop(move |_| {
+            let date = date();
+            state.calendar.request_focus();
+            state.focus_date(date);
+            state.select(date);
+        })
+}
+
+impl View for Calendar {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Calendar".into()
+    }
+
+    fn accessibility(&self) -> Option<Accessibility> {
+        Some(Accessibility::new(Role::Grid))
+    }
+
+    #[cfg(feature = "localization")]
+    fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
+        if self.locale_style.read(cx) {
+            let locale = self
+                .locale_style
+                .locale()
+                .map(|locale| CalendarLocale::from_bundle(&self.locale_style.bundle(), &locale))
+                .unwrap_or_default();
+            if self
+                .state
+                .locale
+                .with_untracked(|current| *current != locale)
+            {
+                self.state.locale.set(locale);
+            }
+        }
+        for child in self.id.children() {
+            cx.style_view(child);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Date, DateRange, Weekday};
+
+    #[test]
+    fn days_round_trip() {
+        for days in [-719_468, -1, 0, 59, 11_016, 19_782, 2_932_896] {
+            assert_eq!(Date::from_days(days).to_days(), days);
+        }
+        assert_eq!(Date::from_days(0), Date::new(1970, 1, 1).unwrap());
+        assert_eq!(
+            Date::new(2024, 2, 29).unwrap().add_days(1),
+            Date::new(2024, 3, 1).unwrap()
+        );
+        assert_eq!(Date::new(2024, 7, 4).unwrap().weekday(), Weekday::Thursday);
+        assert_eq!(Date::new(2000, 1, 1).unwrap().weekday(), Weekday::Saturday);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn today_is_the_local_date() {
+        use chrono::{FixedOffset, Local, NaiveDate};
+
+        // the day can change between the reads
+        let before = Date::from_chrono(Local::now());
+        let today = Date::today();
+        let after = Date::from_chrono(Local::now());
+        assert!(today == before || today == after);
+
+        // near midnight, the local day differs from the UTC one
+        let utc = NaiveDate::from_ymd_opt(2024, 3, 9)
+            .unwrap()
+            .and_hms_opt(23, 30, 0)
+            .unwrap()
+            .and_utc();
+        assert_eq!(Date::from_chrono(utc), Date::new(2024, 3, 9).unwrap());
+        let east = FixedOffset::east_opt(3600).unwrap();
+        assert_eq!(
+            Date::from_chrono(utc.with_timezone(&east)),
+            Date::new(2024, 3, 10).unwrap()
+        );
+        let west = FixedOffset::west_opt(3600).unwrap();
+        let utc = utc + chrono::Duration::hours(1);
+        assert_eq!(Date::from_chrono(utc), Date::new(2024, 3, 10).unwrap());
+        assert_eq!(
+            Date::from_chrono(utc.with_timezone(&west)),
+            Date::new(2024, 3, 9).unwrap()
+        );
+    }
+
+    #[test]
+    fn add_months_clamps_day() {
+        let date = Date::new(2024, 1, 31).unwrap();
+        assert_eq!(date.add_months(1), Date::new(2024, 2, 29).unwrap());
+        assert_eq!(date.add_months(-1), Date::new(2023, 12, 31).unwrap());
+        assert_eq!(date.add_months(13), Date::new(2025, 2, 28).unwrap());
+    }
+
+    #[test]
+    fn parse_and_format() {
+        let date: Date = "2024-03-09".parse().unwrap();
+        assert_eq!(date, Date::new(2024, 3, 9).unwrap());
+        assert_eq!(date.to_string(), "2024-03-09");
+        assert!("2023-02-29".parse::<Date>().is_err());
+        assert!("2024-3-9".parse::<Date>().is_err());
+        assert!("2024-03-09-1".parse::<Date>().is_err());
+    }
+
+    #[test]
+    fn weekday_arithmetic() {
+        assert_eq!(Weekday::Sunday.days_since(Weekday::Monday), 6);
+        assert_eq!(Weekday::Monday.days_since(Weekday::Sunday), 1);
+        assert_eq!(Weekday::Monday.add_days(-1), Weekday::Sunday);
+        assert_eq!(Weekday::from_number(7), Some(Weekday::Sunday));
+        assert_eq!(Weekday::from_number(0), None);
+
+        let a = Date::new(2024, 5, 10).unwrap();
+        let b = Date::new(2024, 5, 1).unwrap();
+        let range = DateRange::new(a, b);
+        assert_eq!(range.start, b);
+        assert!(range.contains(Date::new(2024, 5, 5).unwrap()));
+    }
+}
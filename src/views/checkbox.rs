@@ -2,6 +2,8 @@
 //! A checkbox view for boolean selection.
 
 use crate::{
+    View,
+    accessibility::{Accessibility, Action, Role},
     style_class,
     view::IntoView,
     views::{
@@ -44,6 +46,11 @@ fn checkbox_svg(
         .update_value(update_svg)
         .class(CheckboxClass)
         .style(|s| s.focusable(true))
+        .accessibility(move || {
+            Accessibility::new(Role::CheckBox)
+                .toggled(checked.get())
+                .action(Action::Click)
+        })
 }
 
 /// # A customizable checkbox view for boolean selection.
@@ -132,14 +139,18 @@ impl Checkbox {
         custom_check: impl Into<String> + Clone + 'static,
     ) -> ValueContainer<bool> {
         let (inbound_signal, outbound_signal) = create_value_container_signals(checked);
+        let label = views::label(label);
+        let label_id = label.id();
 
         value_container(
             h_stack((
-                checkbox_svg(inbound_signal.read_only(), custom_check).on_click_stop(move |_| {
-                    let checked = inbound_signal.get_untracked();
-                    outbound_signal.set(!checked);
-                }),
-                views::label(label),
+                checkbox_svg(inbound_signal.read_only(), custom_check)
+                    .on_click_stop(move |_| {
+                        let checked = inbound_signal.get_untracked();
+                        outbound_signal.set(!checked);
+                    })
+                    .accessibility(move || Accessibility::default().labelled_by(label_id)),
+                label,
             ))
             .class(LabeledCheckboxClass)
             .on_click_stop(move |_| {
@@ -171,11 +182,15 @@ impl Checkbox {
         label: impl Fn() -> S + 'static,
         custom_check: impl Into<String> + Clone + 'static,
     ) -> impl IntoView {
+        let label = views::label(label);
+        let label_id = label.id();
         h_stack((
-            checkbox_svg(checked, custom_check).on_click_stop(move |_| {
-                checked.update(|val| *val = !*val);
-            }),
-            views::label(label),
+            checkbox_svg(checked, custom_check)
+                .on_click_stop(move |_| {
+                    checked.update(|val| *val = !*val);
+                })
+                .accessibility(move || Accessibility::default().labelled_by(label_id)),
+            label,
         ))
         .on_click_stop(move |_| {
             checked.update(|val| *val = !*val);
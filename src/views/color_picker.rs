@@ -0,0 +1,698 @@
+#![deny(missing_docs)]
+
+//! A color picker with a saturation/value area, hue and alpha sliders, text entry and
+//! palettes. See [`color_picker`].
+
+use std::rc::Rc;
+
+use floem_reactive::{RwSignal, SignalGet, SignalUpdate, SignalWith, create_effect};
+use peniko::{
+    Color, Gradient,
+    color::{AlphaColor, Hsl, Hwb, Oklch, Rgba8, Srgb, palette::css},
+    kurbo::{Circle, Point, Rect, Size, Stroke},
+};
+use ui_events::{
+    keyboard::{Key, KeyState, KeyboardEvent, Modifiers, NamedKey},
+    pointer::{PointerButton, PointerButtonEvent, PointerEvent},
+};
+
+use super::{
+    Decorators, dyn_stack, empty, h_stack, label, slider::Slider, svg, text_input, v_stack,
+};
+use crate::{
+    accessibility::{Accessibility, Role},
+    context::{ComputeLayoutCx, EventCx, PaintCx},
+    event::{Event, EventListener, EventPropagation},
+    id::ViewId,
+    style::{FlexWrap, Style},
+    style_class,
+    view::{IntoView, View},
+};
+
+style_class!(
+    /// The style class that is applied to all [`ColorPicker`] views.
+    pub ColorPickerClass
+);
+style_class!(
+    /// The style class that is applied to the saturation/value area of a [`ColorPicker`].
+    pub ColorPickerAreaClass
+);
+style_class!(
+    /// The style class that is applied to the hue and alpha sliders of a [`ColorPicker`].
+    pub ColorPickerSliderClass
+);
+style_class!(
+    /// The style class that is applied to the preview of the color of a [`ColorPicker`].
+    pub ColorPickerPreviewClass
+);
+style_class!(
+    /// The style class that is applied to the button switching the [`ColorFormat`] of the text
+    /// of a [`ColorPicker`].
+    pub ColorPickerFormatClass
+);
+style_class!(
+    /// The style class that is applied to the eyedropper button of a [`ColorPicker`].
+    pub ColorPickerEyedropperClass
+);
+style_class!(
+    /// The style class that is applied to the swatches and recent colors of a [`ColorPicker`].
+    pub ColorPickerSwatchClass
+);
+
+const EYEDROPPER_ICON: &str = r#"<svg width="16" height="16" viewBox="0 0 16 16" fill="currentColor"><path d="M13.354.646a1.207 1.207 0 00-1.708 0L8.5 3.793l-.646-.647a.5.5 0 10-.708.708L8.293 5l-7.147 7.146A.5.5 0 001 12.5v1.793l-.854.853a.5.5 0 10.708.707L1.707 15H3.5a.5.5 0 00.354-.146L11 7.707l1.146 1.147a.5.5 0 00.708-.708l-.647-.646 3.147-3.146a1.207 1.207 0 000-1.708l-2-2zM2 12.707l7-7L10.293 7l-7 7H2v-1.293z"/></svg>"#;
+
+/// The number of colors remembered as recent colors.
+const MAX_RECENT_COLORS: usize = 8;
+/// How much the arrow keys move the saturation or value, and how much more with Shift.
+const AREA_KEY_STEP: f32 = 0.01;
+const AREA_LARGE_KEY_STEP: f32 = 0.1;
+
+/// A color in the HSV color space, which the saturation/value area and the hue slider edit.
+///
+/// Grays have no hue, so the hue they were picked with is kept to let the area keep its hue
+/// while the saturation or value goes through zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Hsv {
+    /// The hue in degrees.
+    hue: f32,
+    /// The saturation from `0` to `1`.
+    saturation: f32,
+    /// The value from `0` to `1`.
+    value: f32,
+    alpha: f32,
+}
+
+impl Hsv {
+    /// Converts `color`, keeping `hue` if the color is a gray.
+    fn from_color(color: Color, hue: f32) -> Hsv {
+        // HWB is HSV with the saturation and value expressed as whiteness and blackness
+        let [color_hue, whiteness, blackness, alpha] = color.convert::<Hwb>().components;
+        let value = (1.0 - blackness / 100.0).clamp(0.0, 1.0);
+        let saturation = if value > 0.0 {
+            (1.0 - whiteness / 100.0 / value).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let is_gray = saturation == 0.0 || value == 0.0 || !color_hue.is_finite();
+        Hsv {
+            hue: if is_gray { hue } else { color_hue },
+            saturation,
+            value,
+            alpha,
+        }
+    }
+
+    fn to_color(self) -> Color {
+        let whiteness = (1.0 - self.saturation) * self.value;
+        let blackness = 1.0 - self.value;
+        AlphaColor::<Hwb>::new([self.hue, whiteness * 100.0, blackness * 100.0, self.alpha])
+            .convert::<Srgb>()
+    }
+
+    /// The fully saturated and bright color of the hue.
+    fn hue_color(hue: f32) -> Color {
+        Hsv {
+            hue,
+            saturation: 1.0,
+            value: 1.0,
+            alpha: 1.0,
+        }
+        .to_color()
+    }
+}
+
+/// How the text of a [`ColorPicker`] shows its color.
+///
+/// Text in any of the formats, or any other CSS color, can be typed whatever the format.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorFormat {
+    /// `#rrggbb`, or `#rrggbbaa` if the color is translucent.
+    #[default]
+    Hex,
+    /// `rgb(r g b)`, with components from `0` to `255`.
+    Rgb,
+    /// `hsl(h s% l%)`.
+    Hsl,
+    /// `oklch(l% c h)`.
+    Oklch,
+}
+
+impl ColorFormat {
+    /// All the formats, in the order the format button of a [`ColorPicker`] cycles through them.
+    pub const ALL: [ColorFormat; 4] = [
+        ColorFormat::Hex,
+        ColorFormat::Rgb,
+        ColorFormat::Hsl,
+        ColorFormat::Oklch,
+    ];
+
+    /// The short name of the format, such as `"HEX"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorFormat::Hex => "HEX",
+            ColorFormat::Rgb => "RGB",
+            ColorFormat::Hsl => "HSL",
+            ColorFormat::Oklch => "OKLCH",
+        }
+    }
+
+    /// Formats `color` as CSS text in this format.
+    pub fn format(self, color: Color) -> String {
+        let alpha = color.components[3];
+        let alpha = if alpha < 1.0 {
+            format!(" / {}", decimal(alpha, 2))
+        } else {
+            String::new()
+        };
+        match self {
+            ColorFormat::Hex => {
+                let Rgba8 { r, g, b, a } = color.to_rgba8();
+                if a == u8::MAX {
+                    format!("#{r:02x}{g:02x}{b:02x}")
+                } else {
+                    format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+                }
+            }
+            ColorFormat::Rgb => {
+                let Rgba8 { r, g, b, .. } = color.to_rgba8();
+                format!("rgb({r} {g} {b}{alpha})")
+            }
+            ColorFormat::Hsl => {
+                let [h, s, l, _] = color.convert::<Hsl>().components;
+                format!(
+                    "hsl({} {}% {}%{alpha})",
+                    decimal(h, 1),
+                    decimal(s, 1),
+                    decimal(l, 1)
+                )
+            }
+            ColorFormat::Oklch => {
+                let [l, c, h, _] = color.convert::<Oklch>().components;
+                format!(
+                    "oklch({}% {} {}{alpha})",
+                    decimal(l * 100.0, 1),
+                    decimal(c, 3),
+                    decimal(h, 1)
+                )
+            }
+        }
+    }
+
+    fn next(self) -> ColorFormat {
+        let idx = Self::ALL
+            .iter()
+            .position(|format| *format == self)
+            .unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+}
+
+/// Formats `value` with at most `decimals` decimals, without trailing zeros.
+fn decimal(value: f32, decimals: usize) -> String {
+    let value = if value.is_finite() { value } else { 0.0 };
+    let text = format!("{value:.decimals$}");
+    let text = if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &text
+    };
+    // rounding small negative numbers gives "-0"
+    match text {
+        "-0" => "0".to_string(),
+        text => text.to_string(),
+    }
+}
+
+/// Parses CSS color text, also accepting hex colors without the `#`.
+fn parse_color(text: &str) -> Option<Color> {
+    let text = text.trim();
+    let is_bare_hex =
+        matches!(text.len(), 3 | 4 | 6 | 8) && text.bytes().all(|b| b.is_ascii_hexdigit());
+    let color = if is_bare_hex {
+        peniko::color::parse_color(&format!("#{text}"))
+    } else {
+        peniko::color::parse_color(text)
+    };
+    color.ok().map(|color| color.to_alpha_color::<Srgb>())
+}
+
+/// Identifies colors that look the same, as colors can't be compared exactly.
+fn color_key(color: &Color) -> [u8; 4] {
+    let Rgba8 { r, g, b, a } = color.to_rgba8();
+    [r, g, b, a]
+}
+
+type Eyedropper = Rc<dyn Fn(Box<dyn FnOnce(Color)>)>;
+
+/// The state of a [`ColorPicker`], shared by its parts.
+#[derive(Clone, Copy)]
+struct ColorPickerState {
+    color: RwSignal<Color>,
+    hsv: RwSignal<Hsv>,
+    recent: RwSignal<RwSignal<Vec<Color>>>,
+}
+
+impl ColorPickerState {
+    fn set_hsv(&self, hsv: Hsv) {
+        self.hsv.set(hsv);
+        let color = hsv.to_color();
+        if color != self.color.get_untracked() {
+            self.color.set(color);
+        }
+    }
+
+    fn set_color(&self, color: Color) {
+        if color != self.color.get_untracked() {
+            self.color.set(color);
+        }
+    }
+
+    /// Adds the current color to the front of the recent colors.
+    fn remember(&self) {
+        let color = self.color.get_untracked();
+        self.recent.get_untracked().update(|recent| {
+            recent.retain(|recent| color_key(recent) != color_key(&color));
+            recent.insert(0, color);
+            recent.truncate(MAX_RECENT_COLORS);
+        });
+    }
+}
+
+/// A color picker, with an area picking the saturation and value, sliders for the hue and the
+/// alpha, and text entry accepting CSS colors.
+///
+/// Swatches set with [`swatches`](Self::swatches) and the recent colors are shown below.
+/// A color becomes a recent color when it is typed, picked with the eyedropper, or when
+/// dragging the area or a slider ends.
+///
+/// See [`color_picker`].
+pub struct ColorPicker {
+    id: ViewId,
+    state: ColorPickerState,
+    format: RwSignal<ColorFormat>,
+    swatches: RwSignal<Vec<Color>>,
+    show_alpha: RwSignal<bool>,
+    eyedropper: RwSignal<Option<Eyedropper>>,
+}
+
+/// Creates a [`ColorPicker`] editing `color`.
+/// ```
+/// # use floem::prelude::*;
+/// # use floem::peniko::color::palette::css;
+/// let accent = RwSignal::new(css::ROYAL_BLUE);
+/// color_picker(accent)
+///     .format(ColorFormat::Oklch)
+///     .swatches([css::TOMATO, css::GOLD, css::SEA_GREEN]);
+/// ```
+/// ### Reactivity
+/// The picker is updated whenever `color` changes.
+pub fn color_picker(color: RwSignal<Color>) -> ColorPicker {
+    let id = ViewId::new();
+    let hsv = RwSignal::new(Hsv::from_color(color.get_untracked(), 0.0));
+    let state = ColorPickerState {
+        color,
+        hsv,
+        recent: RwSignal::new(RwSignal::new(Vec::new())),
+    };
+    let format = RwSignal::new(ColorFormat::default());
+    let swatches = RwSignal::new(Vec::new());
+    let show_alpha = RwSignal::new(true);
+    let eyedropper: RwSignal<Option<Eyedropper>> = RwSignal::new(None);
+
+    create_effect(move |_| {
+        let color = color.get();
+        let current = hsv.get_untracked();
+        if current.to_color() != color {
+            hsv.set(Hsv::from_color(color, current.hue));
+        }
+        id.request_accessibility();
+    });
+
+    let area = SaturationValueArea {
+        id: ViewId::new(),
+        state,
+        size: Size::ZERO,
+        dragging: false,
+    }
+    .class(ColorPickerAreaClass)
+    .keyboard_navigable();
+
+    let hue_width = RwSignal::new(0.0);
+    let hue_slider = Slider::new_ranged(move || f64::from(hsv.get().hue), 0.0..=360.0)
+        .on_change_value(move |hue| {
+            state.set_hsv(Hsv {
+                hue: hue as f32,
+                ..hsv.get_untracked()
+            })
+        })
+        .on_event_cont(EventListener::PointerUp, move |_| state.remember())
+        .on_resize(move |rect| hue_width.set(rect.width()))
+        .class(ColorPickerSliderClass)
+        .slider_style(move |s| {
+            let width = hue_width.get();
+            let stops = [0, 1, 2, 3, 4, 5, 6].map(|idx| {
+                let hue = idx as f32 * 60.0;
+                (hue / 360.0, Hsv::hue_color(hue))
+            });
+            let gradient =
+                Gradient::new_linear(Point::ZERO, Point::new(width, 0.0)).with_stops(stops);
+            s.edge_align(true)
+                .bar_color(gradient)
+                .accent_bar_color(css::TRANSPARENT)
+        });
+
+    let alpha_width = RwSignal::new(0.0);
+    let alpha_slider = Slider::new_ranged(move || f64::from(hsv.get().alpha), 0.0..=1.0)
+        .on_change_value(move |alpha| {
+            state.set_hsv(Hsv {
+                alpha: alpha as f32,
+                ..hsv.get_untracked()
+            })
+        })
+        .on_event_cont(EventListener::PointerUp, move |_| state.remember())
+        .on_resize(move |rect| alpha_width.set(rect.width()))
+        .class(ColorPickerSliderClass)
+        .style(move |s| s.apply_if(!show_alpha.get(), |s| s.hide()))
+        .slider_style(move |s| {
+            let width = alpha_width.get();
+            let color = Hsv {
+                alpha: 1.0,
+                ..hsv.get()
+            }
+            .to_color();
+            let gradient = Gradient::new_linear(Point::ZERO, Point::new(width, 0.0))
+                .with_stops([(0.0, color.with_alpha(0.0)), (1.0, color)]);
+            s.edge_align(true)
+                .bar_color(gradient)
+                .accent_bar_color(css::TRANSPARENT)
+        });
+
+    let preview = empty()
+        .class(ColorPickerPreviewClass)
+        .style(move |s| s.background(color.get()));
+
+    let eyedropper_button = svg(EYEDROPPER_ICON)
+        .class(ColorPickerEyedropperClass)
+        .on_click_stop(move |_| {
+            if let Some(eyedropper) = eyedropper.get_untracked() {
+                eyedropper(Box::new(move |picked| {
+                    state.set_color(picked);
+                    state.remember();
+                }));
+            }
+        })
+        .style(move |s| s.apply_if(eyedropper.with(Option::is_none), |s| s.hide()));
+
+    let text = RwSignal::new(String::new());
+    create_effect(move |_| {
+        let formatted = format.get().format(color.get());
+        text.set(formatted);
+    });
+    let commit = move || {
+        let formatted = format.get_untracked().format(color.get_untracked());
+        // the text is only parsed when it was edited, so that committing it doesn't round
+        // the color to the precision of the format
+        let edited = text.with_untracked(|text| *text != formatted);
+        if let Some(parsed) = text
+            .with_untracked(|text| parse_color(text))
+            .filter(|_| edited)
+        {
+            state.set_color(parsed);
+            state.remember();
+        }
+        text.set(format.get_untracked().format(color.get_untracked()));
+    };
+    let input = text_input(text)
+        .on_enter(commit)
+        .on_event_cont(EventListener::FocusLost, move |_| commit())
+        .style(|s| s.flex_grow(1.0).flex_basis(0.0).min_width(0.0));
+    let format_button = label(move || format.get().name())
+        .class(ColorPickerFormatClass)
+        .on_click_stop(move |_| format.update(|format| *format = format.next()));
+
+    let swatch = move |swatch_color: Color| {
+        empty()
+            .class(ColorPickerSwatchClass)
+            .style(move |s| s.background(swatch_color))
+            .on_click_stop(move |_| state.set_color(swatch_color))
+    };
+    let swatch_row = move |colors: RwSignal<Vec<Color>>| {
+        dyn_stack(move || colors.get(), color_key, swatch).style(move |s| {
+            s.flex_wrap(FlexWrap::Wrap)
+                .apply_if(colors.with(Vec::is_empty), |s| s.hide())
+        })
+    };
+    let recent_row =
+        dyn_stack(move || state.recent.get().get(), color_key, swatch).style(move |s| {
+            s.flex_wrap(FlexWrap::Wrap)
+                .apply_if(state.recent.get().with(Vec::is_empty), |s| s.hide())
+        });
+
+    id.set_children([
+        area.into_any(),
+        h_stack((
+            preview,
+            v_stack((hue_slider, alpha_slider)).style(|s| s.flex_grow(1.0).gap(4)),
+            eyedropper_button,
+        ))
+        .style(|s| s.items_center().gap(8))
+        .into_any(),
+        h_stack((format_button, input))
+            .style(|s| s.items_center().gap(4))
+            .into_any(),
+        swatch_row(swatches).into_any(),
+        recent_row.into_any(),
+    ]);
+
+    ColorPicker {
+        id,
+        state,
+        format,
+        swatches,
+        show_alpha,
+        eyedropper,
+    }
+    .class(ColorPickerClass)
+}
+
+impl ColorPicker {
+    /// Sets the format the text shows the color in. The format can be changed by clicking it.
+    pub fn format(self, format: ColorFormat) -> Self {
+        self.format.set(format);
+        self
+    }
+
+    /// Sets the colors shown as swatches, which pick their color when clicked.
+    pub fn swatches(self, swatches: impl IntoIterator<Item = Color>) -> Self {
+        self.swatches.set(swatches.into_iter().collect());
+        self
+    }
+
+    /// Keeps the recent colors in `recent`, so that they can be shared between pickers or
+    /// saved. The most recent color is first.
+    pub fn recent_colors(self, recent: RwSignal<Vec<Color>>) -> Self {
+        self.state.recent.set(recent);
+        self
+    }
+
+    /// Sets whether the alpha slider is shown.
+    pub fn show_alpha(self, show_alpha: bool) -> Self {
+        self.show_alpha.set(show_alpha);
+        self
+    }
+
+    /// Shows an eyedropper button calling `eyedropper` when clicked, which should call the
+    /// callback it is given with the color picked, such as from a screen capture.
+    ///
+    /// Floem can't pick colors outside of its windows by itself, so the button is only shown
+    /// once this is set.
+    pub fn eyedropper(self, eyedropper: impl Fn(Box<dyn FnOnce(Color)>) + 'static) -> Self {
+        self.eyedropper.set(Some(Rc::new(eyedropper)));
+        self
+    }
+}
+
+impl View for ColorPicker {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Color Picker".into()
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::new().flex_col())
+    }
+
+    fn accessibility(&self) -> Option<Accessibility> {
+        let color = self.state.color.get_untracked();
+        Some(Accessibility::new(Role::ColorWell).value(ColorFormat::Hex.format(color)))
+    }
+}
+
+/// The area of a [`ColorPicker`] picking the saturation horizontally and the value vertically.
+struct SaturationValueArea {
+    id: ViewId,
+    state: ColorPickerState,
+    size: Size,
+    dragging: bool,
+}
+
+impl SaturationValueArea {
+    fn pick(&self, point: Point) {
+        let hsv = self.state.hsv.get_untracked();
+        let saturation = (point.x / self.size.width).clamp(0.0, 1.0) as f32;
+        let value = (1.0 - point.y / self.size.height).clamp(0.0, 1.0) as f32;
+        self.state.set_hsv(Hsv {
+            saturation,
+            value,
+            ..hsv
+        });
+    }
+}
+
+impl View for SaturationValueArea {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Color Picker Area".into()
+    }
+
+    fn compute_layout(&mut self, _cx: &mut ComputeLayoutCx) -> Option<Rect> {
+        self.size = self.id.get_size().unwrap_or_default();
+        None
+    }
+
+    fn event_before_children(&mut self, _cx: &mut EventCx, event: &Event) -> EventPropagation {
+        match event {
+            Event::Pointer(PointerEvent::Down(PointerButtonEvent {
+                button: Some(PointerButton::Primary),
+                state,
+                ..
+            })) => {
+                self.dragging = true;
+                self.id.request_active();
+                self.id.request_focus();
+                self.pick(state.logical_point());
+                EventPropagation::Stop
+            }
+            Event::Pointer(PointerEvent::Move(update)) if self.dragging => {
+                self.pick(update.current.logical_point());
+                EventPropagation::Stop
+            }
+            Event::Pointer(PointerEvent::Up(PointerButtonEvent { state, .. })) if self.dragging => {
+                self.dragging = false;
+                self.id.clear_active();
+                self.pick(state.logical_point());
+                self.state.remember();
+                EventPropagation::Stop
+            }
+            Event::Key(KeyboardEvent {
+                state: KeyState::Down,
+                key: Key::Named(key),
+                modifiers,
+                ..
+            }) => {
+                let step = if modifiers.contains(Modifiers::SHIFT) {
+                    AREA_LARGE_KEY_STEP
+                } else {
+                    AREA_KEY_STEP
+                };
+                let (saturation, value) = match key {
+                    NamedKey::ArrowLeft => (-step, 0.0),
+                    NamedKey::ArrowRight => (step, 0.0),
+                    NamedKey::ArrowUp => (0.0, step),
+                    NamedKey::ArrowDown => (0.0, -step),
+                    _ => return EventPropagation::Continue,
+                };
+                let hsv = self.state.hsv.get_untracked();
+                self.state.set_hsv(Hsv {
+                    saturation: (hsv.saturation + saturation).clamp(0.0, 1.0),
+                    value: (hsv.value + value).clamp(0.0, 1.0),
+                    ..hsv
+                });
+                EventPropagation::Stop
+            }
+            _ => EventPropagation::Continue,
+        }
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let size = self.size;
+        if size.is_zero_area() {
+            return;
+        }
+        let rect = size.to_rect();
+        let hsv = self.state.hsv.get_untracked();
+
+        cx.fill(&rect, Hsv::hue_color(hsv.hue), 0.);
+        let saturation = Gradient::new_linear(Point::ZERO, Point::new(size.width, 0.0))
+            .with_stops([(0.0, css::WHITE), (1.0, css::WHITE.with_alpha(0.0))]);
+        cx.fill(&rect, &saturation, 0.);
+        let value = Gradient::new_linear(Point::ZERO, Point::new(0.0, size.height))
+            .with_stops([(0.0, css::BLACK.with_alpha(0.0)), (1.0, css::BLACK)]);
+        cx.fill(&rect, &value, 0.);
+
+        let center = Point::new(
+            f64::from(hsv.saturation) * size.width,
+            f64::from(1.0 - hsv.value) * size.height,
+        );
+        cx.save();
+        cx.clip(&rect);
+        cx.stroke(&Circle::new(center, 6.0), css::WHITE, &Stroke::new(2.0));
+        cx.stroke(&Circle::new(center, 4.0), css::BLACK, &Stroke::new(1.0));
+        cx.restore();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use peniko::color::palette::css;
+
+    use super::{ColorFormat, Hsv, color_key, decimal, parse_color};
+
+    #[test]
+    fn hsv_round_trip() {
+        for color in [css::RED, css::ROYAL_BLUE, css::GOLD, css::WHITE, css::BLACK] {
+            let hsv = Hsv::from_color(color, 0.0);
+            assert_eq!(color_key(&hsv.to_color()), color_key(&color));
+        }
+        let red = Hsv::from_color(css::RED, 0.0);
+        assert_eq!((red.saturation, red.value), (1.0, 1.0));
+        // grays keep the hue they had
+        assert_eq!(Hsv::from_color(css::GRAY, 120.0).hue, 120.0);
+    }
+
+    #[test]
+    fn format_and_parse() {
+        let color = css::ROYAL_BLUE;
+        assert_eq!(ColorFormat::Hex.format(color), "#4169e1");
+        assert_eq!(ColorFormat::Rgb.format(color), "rgb(65 105 225)");
+        assert_eq!(
+            ColorFormat::Rgb.format(color.with_alpha(0.5)),
+            "rgb(65 105 225 / 0.5)"
+        );
+        // the other formats are rounded, so they only round trip closely
+        for format in ColorFormat::ALL {
+            let parsed = parse_color(&format.format(color)).unwrap();
+            let channels = color_key(&parsed).into_iter().zip(color_key(&color));
+            assert!(channels.all(|(a, b)| a.abs_diff(b) <= 1), "{format:?}");
+        }
+        assert_eq!(
+            color_key(&parse_color("4169E1").unwrap()),
+            color_key(&color)
+        );
+        assert!(parse_color("not a color").is_none());
+    }
+
+    #[test]
+    fn decimals_are_trimmed() {
+        assert_eq!(decimal(0.5, 2), "0.5");
+        assert_eq!(decimal(1.0, 3), "1");
+        assert_eq!(decimal(-0.0001, 2), "0");
+        assert_eq!(decimal(f32::NAN, 1), "0");
+    }
+}
@@ -0,0 +1,280 @@
+#![deny(missing_docs)]
+
+//! A text input for dates with a [`Calendar`] dropdown. See [`date_picker`].
+
+use std::rc::Rc;
+
+use floem_reactive::{RwSignal, SignalGet, SignalUpdate, SignalWith, create_effect};
+use peniko::kurbo::{Point, Rect, Size};
+use ui_events::keyboard::{Key, Modifiers, NamedKey};
+
+use super::{
+    Calendar, Date, DateFilter, Decorators, Weekday, calendar, container, svg, text_input,
+};
+use crate::{
+    action::{add_overlay, remove_overlay},
+    context::{ComputeLayoutCx, UpdateCx},
+    event::EventListener,
+    id::ViewId,
+    style::Style,
+    style_class,
+    view::{IntoView, View, default_compute_layout},
+};
+
+style_class!(
+    /// The style class that is applied to all [`DatePicker`] views.
+    pub DatePickerClass
+);
+style_class!(
+    /// The style class that is applied to the button opening the calendar of a [`DatePicker`].
+    pub DatePickerButtonClass
+);
+style_class!(
+    /// The style class that is applied to the popup holding the calendar of a [`DatePicker`].
+    pub DatePickerPopupClass
+);
+
+const CALENDAR_ICON: &str = r#"<svg width="16" height="16" viewBox="0 0 16 16" fill="currentColor"><path d="M4.75 0a.75.75 0 01.75.75V2h5V.75a.75.75 0 011.5 0V2h1.25c.966 0 1.75.784 1.75 1.75v10.5A1.75 1.75 0 0113.25 16H2.75A1.75 1.75 0 011 14.25V3.75C1 2.784 1.784 2 2.75 2H4V.75A.75.75 0 014.75 0zm0 3.5h8.5a.25.25 0 01.25.25V6h-11V3.75a.25.25 0 01.25-.25h2zm-2.25 4v6.75c0 .138.112.25.25.25h10.5a.25.25 0 00.25-.25V7.5h-11z"/></svg>"#;
+
+/// The distance kept between the calendar popup and the edges of the window.
+const POPUP_MARGIN: f64 = 5.0;
+
+enum DatePickerMessage {
+    Toggle,
+    Close,
+}
+
+/// A text input for a date, with a button opening a [`Calendar`] to pick it.
+///
+/// Dates are typed as `YYYY-MM-DD`. The text is parsed when it is committed with Enter or when
+/// the input loses focus: an empty text clears the date, and text that isn't an enabled date
+/// reverts to the current date. `Alt+ArrowDown` in the input opens the calendar, and `Escape`
+/// or clicking outside of it closes it.
+///
+/// See [`date_picker`].
+pub struct DatePicker {
+    id: ViewId,
+    date: RwSignal<Option<Date>>,
+    input_id: ViewId,
+    first_day: RwSignal<Option<Weekday>>,
+    disabled: RwSignal<Option<DateFilter>>,
+    overlay_id: Option<ViewId>,
+    window_origin: Option<Point>,
+}
+
+/// Creates a [`DatePicker`] editing `date`.
+/// ```
+/// # use floem::prelude::*;
+/// let due = RwSignal::new(None);
+/// date_picker(due)
+///     .first_day_of_week(Weekday::Sunday)
+///     .disabled_dates(|date| date < Date::today());
+/// ```
+/// ### Reactivity
+/// The text is updated whenever `date` changes.
+pub fn date_picker(date: RwSignal<Option<Date>>) -> DatePicker {
+    let id = ViewId::new();
+    let text = RwSignal::new(String::new());
+    let disabled: RwSignal<Option<DateFilter>> = RwSignal::new(None);
+
+    create_effect(move |_| {
+        let formatted = date.get().map(|date| date.to_string()).unwrap_or_default();
+        text.set(formatted);
+    });
+
+    let commit = move || {
+        let typed = text.with_untracked(|text| {
+            if text.trim().is_empty() {
+                Some(None)
+            } else {
+                text.parse::<Date>().ok().map(Some)
+            }
+        });
+        let is_disabled = |new_date: &Option<Date>| {
+            disabled.with_untracked(|disabled| {
+                disabled
+                    .as_ref()
+                    .zip(*new_date)
+                    .is_some_and(|(disabled, new_date)| disabled(new_date))
+            })
+        };
+        match typed {
+            Some(new_date) if !is_disabled(&new_date) => {
+                if new_date != date.get_untracked() {
+                    date.set(new_date);
+                }
+            }
+            _ => {}
+        }
+        let formatted = date
+            .get_untracked()
+            .map(|date| date.to_string())
+            .unwrap_or_default();
+        text.set(formatted);
+    };
+
+    let input = text_input(text)
+        .input_mask("####-##-##")
+        .placeholder("YYYY-MM-DD")
+        .on_enter(commit)
+        .on_event_cont(EventListener::FocusLost, move |_| commit())
+        .on_key_down(
+            Key::Named(NamedKey::ArrowDown),
+            |modifiers| modifiers.contains(Modifiers::ALT),
+            move |_| id.update_state(DatePickerMessage::Toggle),
+        )
+        .style(|s| s.flex_grow(1.0).flex_basis(0.0).min_width(0.0));
+    let input_id = input.id();
+
+    let button = svg(CALENDAR_ICON)
+        .class(DatePickerButtonClass)
+        .on_click_stop(move |_| id.update_state(DatePickerMessage::Toggle));
+
+    id.set_children([input.into_any(), button.into_any()]);
+
+    DatePicker {
+        id,
+        date,
+        input_id,
+        first_day: RwSignal::new(None),
+        disabled,
+        overlay_id: None,
+        window_origin: None,
+    }
+    .class(DatePickerClass)
+}
+
+impl DatePicker {
+    /// Sets the first day of the week of the calendar, overriding the one of the locale.
+    pub fn first_day_of_week(self, weekday: Weekday) -> Self {
+        self.first_day.set(Some(weekday));
+        self
+    }
+
+    /// Prevents the dates for which `disabled` returns `true` from being picked or typed.
+    pub fn disabled_dates(self, disabled: impl Fn(Date) -> bool + 'static) -> Self {
+        self.disabled.set(Some(Rc::new(disabled)));
+        self
+    }
+
+    fn calendar(&self) -> Calendar {
+        let id = self.id;
+        let input_id = self.input_id;
+        let mut popup = calendar(self.date).on_select(move |_| {
+            id.update_state(DatePickerMessage::Close);
+            input_id.request_focus();
+        });
+        if let Some(first_day) = self.first_day.get_untracked() {
+            popup = popup.first_day_of_week(first_day);
+        }
+        if let Some(disabled) = self.disabled.get_untracked() {
+            popup = popup.disabled_dates(move |date| disabled(date));
+        }
+        popup
+    }
+
+    fn open(&mut self, cx: &mut UpdateCx) {
+        if self.overlay_id.is_some() {
+            return;
+        }
+        self.id.request_layout();
+        cx.window_state.compute_layout();
+        let Some(layout) = self.id.get_layout() else {
+            return;
+        };
+        let origin = self.window_origin.unwrap_or_default() + (0., layout.size.height as f64);
+
+        let id = self.id;
+        let popup = self.calendar();
+        popup.id().request_focus();
+
+        let popup_size = RwSignal::new(None);
+        let window_size = RwSignal::new(None);
+        let initial_inset = Size::new(origin.x, origin.y);
+        let inset = RwSignal::new(initial_inset);
+
+        create_effect(move |_| {
+            let (Some(popup_size), Some(window_size)) = (popup_size.get(), window_size.get())
+            else {
+                return;
+            };
+
+            // keeps the popup inside of the window
+            let margin = Size::new(POPUP_MARGIN, POPUP_MARGIN);
+            let new_inset = initial_inset
+                .min(window_size - popup_size - margin)
+                .max(margin);
+
+            if new_inset != inset.get_untracked() {
+                inset.set(new_inset);
+            }
+        });
+
+        let popup = container(popup.on_key_down(
+            Key::Named(NamedKey::Escape),
+            |_| true,
+            move |_| id.update_state(DatePickerMessage::Close),
+        ))
+        .class(DatePickerPopupClass)
+        // keeps clicks in the popup from reaching the backdrop
+        .on_event_stop(EventListener::PointerDown, |_| {})
+        .on_resize(move |rect| popup_size.set(Some(rect.size())))
+        .style(move |s| {
+            let inset = inset.get();
+            s.absolute().inset_left(inset.width).inset_top(inset.height)
+        });
+
+        // a backdrop closing the popup when clicking outside of it
+        self.overlay_id = Some(add_overlay(
+            container(popup)
+                .on_resize(move |rect| window_size.set(Some(rect.size())))
+                .on_event_stop(EventListener::PointerDown, move |_| {
+                    id.update_state(DatePickerMessage::Close)
+                })
+                .style(|s| s.size_full()),
+        ));
+    }
+
+    fn close(&mut self) {
+        if let Some(overlay_id) = self.overlay_id.take() {
+            remove_overlay(overlay_id);
+        }
+    }
+}
+
+impl View for DatePicker {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Date Picker".into()
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::new().flex_row().items_center())
+    }
+
+    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
+        self.window_origin = Some(cx.window_origin);
+        default_compute_layout(self.id, cx)
+    }
+
+    fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn std::any::Any>) {
+        if let Ok(message) = state.downcast::<DatePickerMessage>() {
+            match *message {
+                DatePickerMessage::Toggle if self.overlay_id.is_some() => self.close(),
+                DatePickerMessage::Toggle => self.open(cx),
+                DatePickerMessage::Close => self.close(),
+            }
+        }
+    }
+}
+
+impl Drop for DatePicker {
+    fn drop(&mut self) {
+        if let Some(overlay_id) = self.overlay_id {
+            remove_overlay(overlay_id)
+        }
+    }
+}
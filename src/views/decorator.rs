@@ -6,12 +6,14 @@
 
 use floem_reactive::{SignalUpdate, create_effect, create_updater};
 use peniko::kurbo::{Point, Rect};
-use std::rc::Rc;
+use std::{any::Any, rc::Rc};
 use ui_events::keyboard::{Key, KeyState, KeyboardEvent, Modifiers};
 
 use crate::{
+    accessibility::Accessibility,
     action::{set_window_menu, set_window_scale, set_window_title},
     animate::Animation,
+    context::DropHandler,
     event::{Event, EventListener, EventPropagation},
     menu::Menu,
     style::{Style, StyleClass},
@@ -99,6 +101,34 @@ pub trait Decorators: IntoView<V = Self::DV> + Sized {
         view
     }
 
+    /// Describe the view to assistive technologies such as screen readers.
+    ///
+    /// The properties set in the returned [`Accessibility`] replace the ones reported by
+    /// the view itself, like a role or a label for a view built from other views. When
+    /// called several times, the later calls take precedence.
+    ///
+    /// # Reactivity
+    /// The description function is reactive.
+    ///
+    /// ## Example
+    /// ```rust,ignore
+    /// svg(ICON).on_click_stop(move |_| open()).accessibility(|| {
+    ///     Accessibility::new(Role::Button).label("Open")
+    /// })
+    /// ```
+    fn accessibility(self, accessibility: impl Fn() -> Accessibility + 'static) -> Self::DV {
+        let view = self.into_view();
+        let view_id = view.id();
+        let state = view_id.state();
+        let offset = state.borrow().accessibility.len();
+        let accessibility = create_updater(accessibility, move |accessibility| {
+            view_id.state().borrow_mut().accessibility[offset] = accessibility;
+            view_id.request_accessibility();
+        });
+        state.borrow_mut().accessibility.push(accessibility);
+        view
+    }
+
     /// Conditionally add a debug name to the view that will be shown in the inspector.
     ///
     /// # Reactivity
@@ -141,6 +171,76 @@ pub trait Decorators: IntoView<V = Self::DV> + Sized {
         view
     }
 
+    /// Makes the view draggable, carrying the value returned by `payload` to the view it is
+    /// dropped on.
+    ///
+    /// `payload` is called when a drag starts. Views accept the value with
+    /// [`Decorators::on_drop`] for its type.
+    ///
+    /// ```rust
+    /// # use floem::views::{Decorators, text};
+    /// #[derive(Clone, Copy)]
+    /// struct Card(usize);
+    ///
+    /// let card = text("Card").draggable_with(|| Card(3));
+    /// let column = text("Done").on_drop(|card: &Card, _| println!("moved card {}", card.0));
+    /// ```
+    fn draggable_with<T: 'static>(self, payload: impl Fn() -> T + 'static) -> Self::DV {
+        let view = self.style(|s| s.draggable(true));
+        view.id().state().borrow_mut().drag_payload =
+            Some(Rc::new(move || Rc::new(payload()) as Rc<dyn Any>));
+        view
+    }
+
+    /// Shows the view returned by `preview` under the pointer while the view is dragged,
+    /// instead of the view itself.
+    ///
+    /// The preview is built when a drag starts and removed when it ends.
+    fn drag_preview<V: IntoView + 'static>(self, preview: impl Fn() -> V + 'static) -> Self::DV {
+        let view = self.into_view();
+        view.id().state().borrow_mut().drag_preview = Some(Rc::new(move || preview().into_any()));
+        view
+    }
+
+    /// Handles payloads of type `T` from [`Decorators::draggable_with`] dropped on the view,
+    /// with the position of the drop in the view.
+    ///
+    /// While such a payload is dragged over the view, the [`Style::drag_over`] style applies.
+    /// The innermost view accepting a payload gets it.
+    fn on_drop<T: 'static>(self, on_drop: impl Fn(&T, Point) + 'static) -> Self::DV {
+        self.on_drop_if(|_: &T, _| true, on_drop)
+    }
+
+    /// Like [`Decorators::on_drop`], but only accepts the payloads for which `accepts`
+    /// returns `true` at a position in the view.
+    ///
+    /// Dragging a payload the view rejects over it shows a not-allowed cursor.
+    fn on_drop_if<T: 'static>(
+        self,
+        accepts: impl Fn(&T, Point) -> bool + 'static,
+        on_drop: impl Fn(&T, Point) + 'static,
+    ) -> Self::DV {
+        let view = self.into_view();
+        let handler = DropHandler {
+            accepts: Box::new(move |payload, point| {
+                payload
+                    .downcast_ref::<T>()
+                    .is_some_and(|payload| accepts(payload, point))
+            }),
+            on_drop: Box::new(move |payload, point| {
+                if let Some(payload) = payload.downcast_ref::<T>() {
+                    on_drop(payload, point);
+                }
+            }),
+        };
+        view.id()
+            .state()
+            .borrow_mut()
+            .drop_handlers
+            .push(Rc::new(handler));
+        view
+    }
+
     /// Add a style class to the view
     fn class<C: StyleClass>(self, _class: C) -> Self::DV {
         let view = self.into_view();
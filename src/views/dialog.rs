@@ -0,0 +1,419 @@
+#![deny(missing_docs)]
+
+//! Modal dialogs and sheets shown over the window. See [`show_dialog`].
+
+use std::{cell::Cell, future::Future, rc::Rc};
+
+use floem_reactive::{RwSignal, SignalGet, SignalUpdate};
+use futures::channel::oneshot;
+use ui_events::keyboard::{Key, NamedKey};
+
+use super::{Decorators, button, container, h_stack, text, v_stack};
+use crate::{
+    accessibility::{Accessibility, Role},
+    action::{add_overlay, remove_overlay},
+    event::EventListener,
+    id::ViewId,
+    style::Style,
+    style_class,
+    view::{AnyView, IntoView, View},
+};
+
+style_class!(
+    /// The style class that is applied to the backdrop dimming the window behind a dialog.
+    pub DialogBackdropClass
+);
+style_class!(
+    /// The style class that is applied to all dialogs shown with [`show_dialog`] or [`Dialog`].
+    pub DialogClass
+);
+style_class!(
+    /// The style class that is applied to dialogs shown as a sheet.
+    /// See [`Dialog::sheet`].
+    pub DialogSheetClass
+);
+style_class!(
+    /// The style class that is applied to the title of [`alert`] and [`confirm`] dialogs.
+    pub DialogTitleClass
+);
+style_class!(
+    /// The style class that is applied to the message of [`alert`] and [`confirm`] dialogs.
+    pub DialogMessageClass
+);
+style_class!(
+    /// The style class that is applied to the row of buttons of [`alert`] and [`confirm`]
+    /// dialogs.
+    pub DialogActionsClass
+);
+
+/// A modal dialog, shown over the window by [`show`](Self::show).
+///
+/// While it is shown, the window behind it is dimmed and doesn't get pointer events, Tab
+/// navigation only cycles through the views of the dialog, and key events that no view of the
+/// dialog handles aren't sent to the window. When it closes, the focus goes back to the view
+/// focused before it was shown.
+///
+/// By default, the dialog is centered and closes on Escape or when clicking the backdrop.
+pub struct Dialog {
+    content: AnyView,
+    close_on_escape: bool,
+    close_on_backdrop_click: bool,
+    sheet: bool,
+    on_close: Option<Box<dyn FnOnce()>>,
+}
+
+/// Shows `content` in a modal [`Dialog`] with the default options.
+/// ```no_run
+/// # use floem::prelude::*;
+/// let dialog: RwSignal<Option<DialogHandle>> = RwSignal::new(None);
+/// button("Settings").action(move || {
+///     let handle = show_dialog(
+///         v_stack((
+///             text("Settings"),
+///             button("Done").action(move || {
+///                 if let Some(handle) = dialog.get_untracked() {
+///                     handle.close();
+///                 }
+///             }),
+///         )),
+///     );
+///     dialog.set(Some(handle));
+/// });
+/// ```
+pub fn show_dialog(content: impl IntoView) -> DialogHandle {
+    Dialog::new(content).show()
+}
+
+/// A handle to a dialog that is shown, to close it.
+#[derive(Clone, Copy)]
+pub struct DialogHandle {
+    backdrop_id: ViewId,
+    open: RwSignal<bool>,
+    on_close: RwSignal<Option<Box<dyn FnOnce()>>>,
+}
+
+impl DialogHandle {
+    /// Closes the dialog, if it is still open.
+    pub fn close(&self) {
+        if !self.open.get_untracked() {
+            return;
+        }
+        self.open.set(false);
+        remove_overlay(self.backdrop_id);
+        if let Some(on_close) = self.on_close.try_update(Option::take).flatten() {
+            on_close();
+        }
+    }
+
+    /// Returns whether the dialog is still open.
+    pub fn is_open(&self) -> bool {
+        self.open.get()
+    }
+}
+
+impl Dialog {
+    /// Creates a dialog showing `content`.
+    pub fn new(content: impl IntoView) -> Self {
+        Self {
+            content: content.into_any(),
+            close_on_escape: true,
+            close_on_backdrop_click: true,
+            sheet: false,
+            on_close: None,
+        }
+    }
+
+    /// Sets whether pressing Escape closes the dialog.
+    pub fn close_on_escape(mut self, close: bool) -> Self {
+        self.close_on_escape = close;
+        self
+    }
+
+    /// Sets whether clicking the backdrop around the dialog closes it.
+    pub fn close_on_backdrop_click(mut self, close: bool) -> Self {
+        self.close_on_backdrop_click = close;
+        self
+    }
+
+    /// Shows the dialog as a sheet spanning the bottom of the window, instead of centered.
+    pub fn sheet(mut self, sheet: bool) -> Self {
+        self.sheet = sheet;
+        self
+    }
+
+    /// Calls `on_close` once the dialog is closed, however it was closed.
+    pub fn on_close(mut self, on_close: impl FnOnce() + 'static) -> Self {
+        self.on_close = Some(Box::new(on_close));
+        self
+    }
+
+    /// Shows the dialog over the current window.
+    pub fn show(self) -> DialogHandle {
+        let Dialog {
+            content,
+            close_on_escape,
+            close_on_backdrop_click,
+            sheet,
+            on_close,
+        } = self;
+
+        let backdrop_id = ViewId::new();
+        let handle = DialogHandle {
+            backdrop_id,
+            open: RwSignal::new(true),
+            on_close: RwSignal::new(on_close),
+        };
+
+        let mut dialog = container(content)
+            .class(DialogClass)
+            .keyboard_navigable()
+            .accessibility(|| Accessibility::new(Role::Dialog).modal(true))
+            // keeps clicks in the dialog from reaching the backdrop
+            .on_event_stop(EventListener::PointerDown, |_| {});
+        if sheet {
+            dialog = dialog.class(DialogSheetClass);
+        }
+        if close_on_escape {
+            dialog = dialog.on_key_down(
+                Key::Named(NamedKey::Escape),
+                |modifiers| modifiers.is_empty(),
+                move |_| handle.close(),
+            );
+        }
+        let dialog_id = dialog.id();
+
+        let mut backdrop = DialogBackdrop { id: backdrop_id }
+            .class(DialogBackdropClass)
+            .style(move |s| {
+                s.size_full()
+                    .flex_col()
+                    .items_center()
+                    .justify_center()
+                    .apply_if(sheet, |s| s.justify_end().items_stretch())
+            });
+        if close_on_backdrop_click {
+            backdrop = backdrop.on_click_stop(move |_| handle.close());
+        }
+        backdrop_id.set_children([dialog]);
+
+        add_overlay(backdrop);
+        dialog_id.trap_focus();
+        dialog_id.request_focus();
+
+        handle
+    }
+}
+
+/// The overlay holding a dialog, covering the window so that the views behind don't get
+/// pointer events.
+struct DialogBackdrop {
+    id: ViewId,
+}
+
+impl View for DialogBackdrop {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Dialog Backdrop".into()
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::new().flex_col())
+    }
+}
+
+fn message_dialog(
+    title: String,
+    message: String,
+    actions: impl IntoView + 'static,
+    on_close: impl FnOnce() + 'static,
+) -> DialogHandle {
+    Dialog::new(
+        v_stack((
+            text(title).class(DialogTitleClass),
+            text(message).class(DialogMessageClass),
+            actions.class(DialogActionsClass),
+        ))
+        .style(|s| s.gap(12)),
+    )
+    .on_close(on_close)
+    .show()
+}
+
+/// Shows a dialog with a `title`, a `message` and an OK button, and calls `on_close` once it
+/// is closed.
+pub fn alert(
+    title: impl Into<String>,
+    message: impl Into<String>,
+    on_close: impl FnOnce() + 'static,
+) -> DialogHandle {
+    let handle = Rc::new(Cell::new(None::<DialogHandle>));
+    let ok = button("OK").action({
+        let handle = handle.clone();
+        move || {
+            if let Some(handle) = handle.get() {
+                handle.close();
+            }
+        }
+    });
+    let ok_id = ok.id();
+    let dialog = message_dialog(title.into(), message.into(), h_stack((ok,)), on_close);
+    handle.set(Some(dialog));
+    ok_id.request_focus();
+    dialog
+}
+
+/// Shows a dialog with a `title`, a `message`, an OK button and a Cancel button, and calls
+/// `on_result` once it is closed with whether OK was pressed.
+///
+/// Closing the dialog in any other way, such as with Escape, is the same as pressing Cancel.
+pub fn confirm(
+    title: impl Into<String>,
+    message: impl Into<String>,
+    on_result: impl FnOnce(bool) + 'static,
+) -> DialogHandle {
+    let handle = Rc::new(Cell::new(None::<DialogHandle>));
+    let confirmed = Rc::new(Cell::new(false));
+    let close = {
+        let handle = handle.clone();
+        let confirmed = confirmed.clone();
+        move |result: bool| {
+            confirmed.set(result);
+            if let Some(handle) = handle.get() {
+                handle.close();
+            }
+        }
+    };
+    let cancel = button("Cancel").action({
+        let close = close.clone();
+        move || close(false)
+    });
+    let ok = button("OK").action(move || close(true));
+    let ok_id = ok.id();
+    let dialog = message_dialog(
+        title.into(),
+        message.into(),
+        h_stack((cancel, ok)),
+        move || on_result(confirmed.get()),
+    );
+    handle.set(Some(dialog));
+    ok_id.request_focus();
+    dialog
+}
+
+/// Shows an [`alert`] dialog, returning a future that completes once it is closed.
+pub fn alert_async(
+    title: impl Into<String>,
+    message: impl Into<String>,
+) -> impl Future<Output = ()> {
+    let (sender, receiver) = oneshot::channel();
+    alert(title, message, move || {
+        let _ = sender.send(());
+    });
+    async move {
+        let _ = receiver.await;
+    }
+}
+
+/// Shows a [`confirm`] dialog, returning a future that completes with whether OK was pressed
+/// once it is closed.
+pub fn confirm_async(
+    title: impl Into<String>,
+    message: impl Into<String>,
+) -> impl Future<Output = bool> {
+    let (sender, receiver) = oneshot::channel();
+    confirm(title, message, move |confirmed| {
+        let _ = sender.send(confirmed);
+    });
+    async move { receiver.await.unwrap_or(false) }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use ui_events::keyboard::Modifiers;
+
+    use super::*;
+    use crate::{accessibility, testing::TestWindow};
+
+    /// A window with a button opening a dialog with the buttons `a` and `b`, and the handle
+    /// of that dialog once it is open.
+    fn window() -> (TestWindow, RwSignal<Option<DialogHandle>>) {
+        let handle = RwSignal::new(None);
+        let mut window = TestWindow::new((400.0, 300.0), move || {
+            button("Open").debug_name("open").action(move || {
+                handle.set(Some(show_dialog(v_stack((
+                    button("A").debug_name("a"),
+                    button("B").debug_name("b"),
+                )))));
+            })
+        });
+        let open = window.find("open").unwrap();
+        window.click_view(open);
+        window.frame();
+        (window, handle)
+    }
+
+    fn dialog(window: &TestWindow) -> ViewId {
+        window.find_by_class::<DialogClass>()[0]
+    }
+
+    #[test]
+    fn tab_wraps_inside_the_dialog() {
+        let (mut window, _) = window();
+        let (dialog, a, b) = (
+            dialog(&window),
+            window.find("a").unwrap(),
+            window.find("b").unwrap(),
+        );
+        assert_eq!(window.focused(), Some(dialog));
+
+        let mut order = Vec::new();
+        for _ in 0..4 {
+            window.key_press(Key::Named(NamedKey::Tab), Modifiers::empty());
+            order.push(window.focused().unwrap());
+        }
+        assert_eq!(order, [a, b, dialog, a]);
+    }
+
+    #[test]
+    fn shift_tab_wraps_backwards_inside_the_dialog() {
+        let (mut window, _) = window();
+        let (dialog, a, b) = (
+            dialog(&window),
+            window.find("a").unwrap(),
+            window.find("b").unwrap(),
+        );
+
+        let mut order = Vec::new();
+        for _ in 0..4 {
+            window.key_press(Key::Named(NamedKey::Tab), Modifiers::SHIFT);
+            order.push(window.focused().unwrap());
+        }
+        assert_eq!(order, [b, a, dialog, b]);
+    }
+
+    #[test]
+    fn closing_restores_the_focus() {
+        let (mut window, handle) = window();
+        let open = window.find("open").unwrap();
+        window.key_press(Key::Named(NamedKey::Tab), Modifiers::empty());
+        assert_eq!(window.focused(), window.find("a"));
+
+        handle.get_untracked().unwrap().close();
+        window.frame();
+        assert_eq!(window.find("a"), None);
+        assert_eq!(window.focused(), Some(open));
+    }
+
+    #[test]
+    fn the_dialog_is_a_modal_dialog_for_assistive_technologies() {
+        let (mut window, _) = window();
+        let dialog = accessibility::node_id(dialog(&window));
+        let update = window.accessibility_update();
+        let (_, node) = update.nodes.iter().find(|(id, _)| *id == dialog).unwrap();
+        assert_eq!(node.role(), Role::Dialog);
+        assert!(node.is_modal());
+    }
+}
@@ -0,0 +1,206 @@
+#![deny(missing_docs)]
+
+use std::{
+    fmt,
+    panic::{AssertUnwindSafe, catch_unwind},
+    rc::Rc,
+};
+
+use floem_reactive::{
+    RwSignal, Scope, SignalGet, SignalUpdate, SignalWith, batch, untrack, use_context, with_scope,
+};
+
+use crate::{
+    id::ViewId,
+    view::{AnyView, IntoView, View},
+    views::{dyn_container, empty},
+};
+
+/// An error caught by an [`error_boundary`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoundaryError {
+    message: Rc<str>,
+    panic: bool,
+}
+
+impl BoundaryError {
+    /// The message of the error, or of the panic payload.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns `true` if the error was a panic raised while building the child.
+    pub fn is_panic(&self) -> bool {
+        self.panic
+    }
+
+    fn from_panic(payload: Box<dyn std::any::Any + Send>) -> Self {
+        let message = if let Some(s) = payload.downcast_ref::<&str>() {
+            (*s).into()
+        } else if let Some(s) = payload.downcast_ref::<String>() {
+            s.as_str().into()
+        } else {
+            "panic while building the view".into()
+        };
+        Self {
+            message,
+            panic: true,
+        }
+    }
+}
+
+impl fmt::Display for BoundaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// The errors and refetch triggers collected by the closest [`error_boundary`].
+#[derive(Clone, Copy)]
+struct ErrorBoundaryContext {
+    errors: RwSignal<Vec<BoundaryError>>,
+    sources: RwSignal<Vec<RwSignal<u64>>>,
+}
+
+/// Reports an error to the closest [`error_boundary`], returning `false` if there is none.
+pub(crate) fn report_error(error: &dyn fmt::Display) -> bool {
+    let Some(cx) = use_context::<ErrorBoundaryContext>() else {
+        return false;
+    };
+    let error = BoundaryError {
+        message: error.to_string().into(),
+        panic: false,
+    };
+    cx.errors.update(|errors| errors.push(error));
+    true
+}
+
+/// Lets the closest [`error_boundary`] rerun a resource when the user retries.
+pub(crate) fn register_error_source(refetch: RwSignal<u64>) {
+    if let Some(cx) = use_context::<ErrorBoundaryContext>() {
+        if !cx.sources.with_untracked(|s| s.contains(&refetch)) {
+            cx.sources.update(|s| s.push(refetch));
+        }
+    }
+}
+
+/// The retry action handed to the recovery view of an [`error_boundary`].
+#[derive(Clone, Copy)]
+pub struct ErrorRetry {
+    cx: ErrorBoundaryContext,
+    generation: RwSignal<u64>,
+}
+
+impl ErrorRetry {
+    /// Clears the errors, refetches the resources read inside the boundary and builds the
+    /// child again.
+    pub fn retry(&self) {
+        batch(|| {
+            let sources = self
+                .cx
+                .sources
+                .try_update(std::mem::take)
+                .unwrap_or_default();
+            for refetch in sources {
+                // Resources created inside the child were disposed with it, and are simply
+                // created again
+                refetch.try_update(|count| *count += 1);
+            }
+            self.cx.errors.update(Vec::clear);
+            self.generation.update(|generation| *generation += 1);
+        });
+    }
+}
+
+/// A view that catches errors in its child. See [`error_boundary`].
+pub struct ErrorBoundary {
+    id: ViewId,
+}
+
+/// A view that renders `on_error` instead of `child` once an error is caught inside it.
+///
+/// Errors are caught when:
+/// - a `Result::Err` is converted into a view inside `child`, including in dynamic views
+///   rebuilt later,
+/// - building `child` panics.
+///
+/// `on_error` receives the caught errors and an [`ErrorRetry`], which clears them, refetches
+/// the [`Resource`](crate::receiver_signal::Resource)s read inside the boundary and builds
+/// `child` again.
+///
+/// ## Example
+/// ```rust,ignore
+/// let user = Resource::new(move || user_id.get(), fetch_user);
+///
+/// error_boundary(
+///     |errors, retry| {
+///         v_stack((
+///             text(errors[0].message()),
+///             button("Retry").action(move || retry.retry()),
+///         ))
+///     },
+///     move || dyn_container(move || user.get(), |user| user.map(|u| text(u.name))),
+/// )
+/// ```
+pub fn error_boundary<EV, V>(
+    on_error: impl Fn(&[BoundaryError], ErrorRetry) -> EV + 'static,
+    child: impl Fn() -> V + 'static,
+) -> ErrorBoundary
+where
+    EV: IntoView + 'static,
+    V: IntoView + 'static,
+{
+    let id = ViewId::new();
+
+    let scope = Scope::current().create_child();
+    let cx = ErrorBoundaryContext {
+        errors: scope.create_rw_signal(Vec::new()),
+        sources: scope.create_rw_signal(Vec::new()),
+    };
+    scope.provide_context(cx);
+    let retry = ErrorRetry {
+        cx,
+        generation: scope.create_rw_signal(0),
+    };
+
+    let content = with_scope(scope, || {
+        dyn_container(
+            move || (retry.generation.get(), cx.errors.with(|e| !e.is_empty())),
+            move |(_, failed)| -> AnyView {
+                if failed {
+                    return cx
+                        .errors
+                        .with_untracked(|errors| on_error(errors, retry).into_any());
+                }
+                // The runtime's current scope and effect are restored even if `child` panics
+                let current = Scope::current();
+                let result = untrack(|| {
+                    with_scope(current, || {
+                        catch_unwind(AssertUnwindSafe(|| Ok(child().into_any())))
+                            .unwrap_or_else(|payload| Err(BoundaryError::from_panic(payload)))
+                    })
+                });
+                match result {
+                    Ok(view) => view,
+                    Err(error) => {
+                        cx.errors.update(|errors| errors.push(error));
+                        empty().into_any()
+                    }
+                }
+            },
+        )
+    });
+    id.set_children([content]);
+
+    ErrorBoundary { id }
+}
+
+impl View for ErrorBoundary {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Error Boundary".into()
+    }
+}
@@ -1,13 +1,18 @@
 //! Module defining image view and its properties: style, position and fit.
 #![deny(missing_docs)]
-use std::{path::PathBuf, sync::Arc};
+use std::{path::PathBuf, sync::Arc, time::Duration};
 
-use floem_reactive::create_effect;
+use floem_reactive::{RwSignal, SignalGet, SignalTrack, SignalUpdate, create_effect};
+use peniko::kurbo::{Rect, Size};
 use peniko::{Blob, ImageAlphaType, ImageData};
 use sha2::{Digest, Sha256};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
 use taffy::NodeId;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
 
-use crate::{Renderer, id::ViewId, style::Style, unit::UnitExt, view::View};
+use crate::{Renderer, action::exec_after, id::ViewId, style::Style, unit::UnitExt, view::View};
 
 /// Holds information about image position and size inside container.
 pub struct ImageStyle {
@@ -40,12 +45,17 @@ pub enum ObjectFit {
 /// Areas of the box which aren't covered by the replaced element's object will show the element's background.
 /// See <https://developer.mozilla.org/en-US/docs/Web/CSS/object-position>.
 pub struct ObjectPosition {
-    #[allow(unused)]
     horiz: HorizPosition,
-    #[allow(unused)]
     vert: VertPosition,
 }
 
+impl ObjectPosition {
+    /// Creates a position from its horizontal and vertical parts.
+    pub fn new(horiz: HorizPosition, vert: VertPosition) -> Self {
+        Self { horiz, vert }
+    }
+}
+
 /// Specifies object position on horizontal axis inside the element's box.
 pub enum HorizPosition {
     /// Top position inside the element's box on the horizontal axis.
@@ -99,11 +109,133 @@ impl ImageStyle {
     }
 }
 
+impl ObjectFit {
+    /// The size of an object of `size` fitted in a box of `box_size`.
+    fn fit(&self, size: Size, box_size: Size) -> Size {
+        if size.is_zero_area() {
+            return box_size;
+        }
+        let contain = (box_size.width / size.width).min(box_size.height / size.height);
+        let scale = match self {
+            ObjectFit::Fill => return box_size,
+            ObjectFit::Contain => contain,
+            ObjectFit::Cover => (box_size.width / size.width).max(box_size.height / size.height),
+            ObjectFit::ScaleDown => contain.min(1.0),
+            ObjectFit::None => 1.0,
+        };
+        size * scale
+    }
+}
+
+impl ObjectPosition {
+    /// The rect of an object of `size` positioned in `rect`.
+    fn position(&self, size: Size, rect: Rect) -> Rect {
+        let free = rect.size() - size;
+        let x = match self.horiz {
+            HorizPosition::Top => 0.0,
+            HorizPosition::Center => free.width / 2.0,
+            HorizPosition::Bot => free.width,
+            HorizPosition::Px(px) => px,
+            HorizPosition::Pct(pct) => free.width * pct / 100.0,
+        };
+        let y = match self.vert {
+            VertPosition::Left => 0.0,
+            VertPosition::Center => free.height / 2.0,
+            VertPosition::Right => free.height,
+            VertPosition::Px(px) => px,
+            VertPosition::Pct(pct) => free.height * pct / 100.0,
+        };
+        Rect::from_origin_size((rect.x0 + x, rect.y0 + y), size)
+    }
+}
+
+/// Controls the playback of an animated image, see [`Img::playback`].
+///
+/// Images with a single frame ignore it.
+#[derive(Clone, Copy)]
+pub struct ImgPlayback {
+    /// Whether the animation is playing. It is set to `false` when an animation that doesn't
+    /// loop reaches its last frame, and playing it again starts over.
+    pub playing: RwSignal<bool>,
+    /// Whether the animation starts over after its last frame.
+    pub looping: RwSignal<bool>,
+}
+
+impl ImgPlayback {
+    /// Creates playback controls that play the animation in a loop.
+    pub fn new() -> Self {
+        Self {
+            playing: RwSignal::new(true),
+            looping: RwSignal::new(true),
+        }
+    }
+
+    /// Plays the animation from its current frame.
+    pub fn play(&self) {
+        self.playing.set(true);
+    }
+
+    /// Pauses the animation on its current frame.
+    pub fn pause(&self) {
+        self.playing.set(false);
+    }
+
+    /// Sets whether the animation starts over after its last frame.
+    pub fn set_looping(&self, looping: bool) {
+        self.looping.set(looping);
+    }
+}
+
+impl Default for ImgPlayback {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A frame of an image and how long it is shown.
+#[derive(Clone)]
+pub(crate) struct ImgFrame {
+    image: peniko::ImageBrush,
+    hash: Vec<u8>,
+    delay: Duration,
+}
+
+impl ImgFrame {
+    fn new(image: peniko::ImageBrush, delay: Duration) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(image.image.data.data());
+        Self {
+            hash: hasher.finalize().to_vec(),
+            image,
+            delay,
+        }
+    }
+
+    /// How long the frame is shown. Like browsers, very short delays are shown for 100ms.
+    fn delay(&self) -> Duration {
+        if self.delay < Duration::from_millis(10) {
+            Duration::from_millis(100)
+        } else {
+            self.delay
+        }
+    }
+}
+
 /// Holds the data needed for [img] view fn to display images.
 pub struct Img {
     id: ViewId,
-    img: Option<peniko::ImageBrush>,
-    img_hash: Option<Vec<u8>>,
+    frames: Vec<ImgFrame>,
+    /// The index of the frame that is shown
+    frame: usize,
+    /// When the shown frame started to be shown while playing
+    frame_start: Option<Instant>,
+    /// When the next frame is due, if a paint is scheduled for it
+    next_frame: Option<Instant>,
+    /// Whether an animation that doesn't loop reached its last frame, until `playing` is set
+    /// to `false` outside of the paint
+    ended: bool,
+    playback: ImgPlayback,
+    style: ImageStyle,
     content_node: Option<NodeId>,
 }
 
@@ -153,20 +285,32 @@ pub struct Img {
 /// });
 /// ```
 pub fn img(image: impl Fn() -> Vec<u8> + 'static) -> Img {
-    let image = image::load_from_memory(&image()).ok();
-    let width = image.as_ref().map_or(0, |img| img.width());
-    let height = image.as_ref().map_or(0, |img| img.height());
-    let data = Arc::new(image.map_or(Default::default(), |img| img.into_rgba8().into_vec()));
-    let blob = Blob::new(data);
-    let image = peniko::ImageBrush::new(ImageData {
-        data: blob,
-        format: peniko::ImageFormat::Rgba8,
-        alpha_type: ImageAlphaType::AlphaPremultiplied,
-        width,
-        height,
-    })
-    .with_quality(peniko::ImageQuality::High);
-    img_dynamic(move || image.clone())
+    let frames = decode_frames(&image()).unwrap_or_else(|| {
+        let image = image_brush(0, 0, Vec::new()).with_quality(peniko::ImageQuality::High);
+        vec![ImgFrame::new(image, Duration::ZERO)]
+    });
+    img_frames(frames)
+}
+
+/// Decodes the frames of an image, which has a single frame unless it is animated.
+///
+/// Returns `None` if the image can't be decoded.
+pub(crate) fn decode_frames(data: &[u8]) -> Option<Vec<ImgFrame>> {
+    if let Some(frames) = decode_animation(data) {
+        let frames = frames
+            .into_iter()
+            .map(|(image, delay)| {
+                let image = image.with_quality(peniko::ImageQuality::High);
+                ImgFrame::new(image, delay)
+            })
+            .collect();
+        return Some(frames);
+    }
+    let image = image::load_from_memory(data).ok()?;
+    let (width, height) = (image.width(), image.height());
+    let image = image_brush(width, height, image.into_rgba8().into_vec())
+        .with_quality(peniko::ImageQuality::High);
+    Some(vec![ImgFrame::new(image, Duration::ZERO)])
 }
 
 /// A view that can display an image and controls its position.
@@ -188,34 +332,195 @@ pub fn img(image: impl Fn() -> Vec<u8> + 'static) -> Img {
 /// The `img` function is not reactive, so to make it change on event, wrap it
 /// with [`dyn_view`](crate::views::dyn_view::dyn_view).
 pub fn img_from_path(image: impl Fn() -> PathBuf + 'static) -> Img {
-    let image = image::open(image()).ok();
+    let path = image();
+    if let Some(frames) = std::fs::read(&path)
+        .ok()
+        .and_then(|data| decode_animation(&data))
+    {
+        let frames = frames
+            .into_iter()
+            .map(|(image, delay)| ImgFrame::new(image, delay))
+            .collect();
+        return img_frames(frames);
+    }
+    let image = image::open(path).ok();
     let width = image.as_ref().map_or(0, |img| img.width());
     let height = image.as_ref().map_or(0, |img| img.height());
-    let data = Arc::new(image.map_or(Default::default(), |img| img.into_rgba8().into_vec()));
-    let blob = Blob::new(data);
-    let image = peniko::ImageBrush::new(ImageData {
-        data: blob,
+    let data = image.map_or(Default::default(), |img| img.into_rgba8().into_vec());
+    let image = image_brush(width, height, data);
+    img_dynamic(move || image.clone())
+}
+
+fn image_brush(width: u32, height: u32, data: Vec<u8>) -> peniko::ImageBrush {
+    peniko::ImageBrush::new(ImageData {
+        data: Blob::new(Arc::new(data)),
         format: peniko::ImageFormat::Rgba8,
         alpha_type: ImageAlphaType::AlphaPremultiplied,
         width,
         height,
-    });
-    img_dynamic(move || image.clone())
+    })
+}
+
+/// Decodes the frames of an animated GIF, APNG or WebP and their delays.
+///
+/// Returns `None` if the image has a single frame, or if the cargo feature of its format isn't
+/// enabled.
+fn decode_animation(data: &[u8]) -> Option<Vec<(peniko::ImageBrush, Duration)>> {
+    let frames = match image::guess_format(data).ok()? {
+        #[cfg(feature = "image-gif")]
+        image::ImageFormat::Gif => {
+            let decoder = image::codecs::gif::GifDecoder::new(std::io::Cursor::new(data)).ok()?;
+            image::AnimationDecoder::into_frames(decoder)
+        }
+        #[cfg(feature = "image-png")]
+        image::ImageFormat::Png => {
+            let decoder = image::codecs::png::PngDecoder::new(std::io::Cursor::new(data)).ok()?;
+            if !decoder.is_apng().ok()? {
+                return None;
+            }
+            image::AnimationDecoder::into_frames(decoder.apng().ok()?)
+        }
+        #[cfg(feature = "image-webp")]
+        image::ImageFormat::WebP => {
+            let decoder = image::codecs::webp::WebPDecoder::new(std::io::Cursor::new(data)).ok()?;
+            if !decoder.has_animation() {
+                return None;
+            }
+            image::AnimationDecoder::into_frames(decoder)
+        }
+        _ => return None,
+    };
+    let frames = frames.collect_frames().ok()?;
+    if frames.len() < 2 {
+        return None;
+    }
+    let frames = frames
+        .into_iter()
+        .map(|frame| {
+            let delay = Duration::from(frame.delay());
+            let buffer = frame.into_buffer();
+            let (width, height) = buffer.dimensions();
+            (image_brush(width, height, buffer.into_vec()), delay)
+        })
+        .collect();
+    Some(frames)
+}
+
+pub(crate) fn img_frames(frames: Vec<ImgFrame>) -> Img {
+    let img = Img::new();
+    img.id.update_state(frames);
+    img
 }
 
 pub(crate) fn img_dynamic(image: impl Fn() -> peniko::ImageBrush + 'static) -> Img {
-    let id = ViewId::new();
+    let img = Img::new();
+    let id = img.id;
     create_effect(move |_| {
         id.update_state(image());
     });
-    Img {
-        id,
-        img: None,
-        img_hash: None,
-        content_node: None,
+    img
+}
+
+impl Img {
+    fn new() -> Self {
+        let id = ViewId::new();
+        let playback = ImgPlayback::new();
+        Self::track_playback(id, playback);
+        Img {
+            id,
+            frames: Vec::new(),
+            frame: 0,
+            frame_start: None,
+            next_frame: None,
+            ended: false,
+            playback,
+            style: ImageStyle::BASE,
+            content_node: None,
+        }
+    }
+
+    fn track_playback(id: ViewId, playback: ImgPlayback) {
+        create_effect(move |_| {
+            playback.playing.track();
+            playback.looping.track();
+            id.request_paint();
+        });
+    }
+
+    /// Sets the signals that control the playback of an animated image.
+    ///
+    /// ### Example:
+    /// ```rust
+    /// # use floem::views::{img, ImgPlayback};
+    /// let spinner = include_bytes!("../../examples/widget-gallery/assets/ferris.png");
+    /// let playback = ImgPlayback::new();
+    /// img(move || spinner.to_vec()).playback(playback);
+    /// // Later, e.g. when loading is done:
+    /// playback.pause();
+    /// ```
+    pub fn playback(mut self, playback: ImgPlayback) -> Self {
+        Self::track_playback(self.id, playback);
+        self.playback = playback;
+        self
+    }
+
+    /// Sets how the image is resized and positioned in the content box of the view.
+    pub fn image_style(mut self, style: ImageStyle) -> Self {
+        self.style = style;
+        self.id.request_paint();
+        self
+    }
+
+    /// Advances the shown frame on the timeline of the animation.
+    fn advance(&mut self, now: Instant) -> Advance {
+        if self.frames.len() < 2 || !self.playback.playing.get_untracked() {
+            self.frame_start = None;
+            self.ended = false;
+            return Advance::Stopped;
+        }
+        if self.ended {
+            return Advance::Stopped;
+        }
+        let looping = self.playback.looping.get_untracked();
+        let last = self.frames.len() - 1;
+        let mut start = match self.frame_start {
+            Some(start) => start,
+            None => {
+                // Playing an animation that ended starts it over
+                if self.frame >= last && !looping {
+                    self.frame = 0;
+                }
+                now
+            }
+        };
+        loop {
+            let delay = self.frames[self.frame].delay();
+            if now.saturating_duration_since(start) < delay {
+                self.frame_start = Some(start);
+                return Advance::NextFrameAt(start + delay);
+            }
+            if self.frame == last && !looping {
+                self.frame_start = None;
+                self.ended = true;
+                return Advance::Ended;
+            }
+            start += delay;
+            self.frame = (self.frame + 1) % self.frames.len();
+        }
     }
 }
 
+/// What an animated image does after [`Img::advance`].
+#[derive(Debug, PartialEq)]
+enum Advance {
+    /// The animation isn't playing.
+    Stopped,
+    /// The shown frame is replaced by the next one at the given time.
+    NextFrameAt(Instant),
+    /// The animation doesn't loop and reached its last frame.
+    Ended,
+}
+
 impl View for Img {
     fn id(&self) -> ViewId {
         self.id
@@ -226,14 +531,18 @@ impl View for Img {
     }
 
     fn update(&mut self, _cx: &mut crate::context::UpdateCx, state: Box<dyn std::any::Any>) {
-        if let Ok(img) = state.downcast::<peniko::ImageBrush>() {
-            let mut hasher = Sha256::new();
-            hasher.update(img.image.data.data());
-            self.img_hash = Some(hasher.finalize().to_vec());
-
-            self.img = Some(*img);
-            self.id.request_layout();
-        }
+        let frames = match state.downcast::<peniko::ImageBrush>() {
+            Ok(img) => vec![ImgFrame::new(*img, Duration::ZERO)],
+            Err(state) => match state.downcast::<Vec<ImgFrame>>() {
+                Ok(frames) => *frames,
+                Err(_) => return,
+            },
+        };
+        self.frames = frames;
+        self.frame = 0;
+        self.frame_start = None;
+        self.ended = false;
+        self.id.request_layout();
     }
 
     fn layout(&mut self, cx: &mut crate::context::LayoutCx) -> taffy::tree::NodeId {
@@ -250,9 +559,9 @@ impl View for Img {
             let content_node = self.content_node.unwrap();
 
             let (width, height) = self
-                .img
-                .as_ref()
-                .map(|img| (img.image.width, img.image.height))
+                .frames
+                .first()
+                .map(|frame| (frame.image.image.width, frame.image.image.height))
                 .unwrap_or((0, 0));
 
             let style = Style::new()
@@ -266,15 +575,136 @@ impl View for Img {
     }
 
     fn paint(&mut self, cx: &mut crate::context::PaintCx) {
-        if let Some(ref img) = self.img {
+        let now = crate::animate::now();
+        match self.advance(now) {
+            Advance::NextFrameAt(deadline) => {
+                if self.next_frame != Some(deadline) {
+                    self.next_frame = Some(deadline);
+                    let id = self.id;
+                    exec_after(deadline.saturating_duration_since(now), move |_| {
+                        id.request_paint();
+                    });
+                }
+            }
+            Advance::Ended => {
+                // Signals aren't set while painting
+                let playing = self.playback.playing;
+                exec_after(Duration::ZERO, move |_| playing.set(false));
+            }
+            Advance::Stopped => {}
+        }
+        if let Some(frame) = self.frames.get(self.frame) {
             let rect = self.id.get_content_rect();
+            let image = &frame.image.image;
+            let size = Size::new(image.width as f64, image.height as f64);
+            let size = self.style.fit.fit(size, rect.size());
+            let img_rect = self.style.position.position(size, rect);
+            let overflows = rect.intersect(img_rect) != img_rect;
+            if overflows {
+                cx.save();
+                cx.clip(&rect);
+            }
             cx.draw_img(
                 floem_renderer::Img {
-                    img: img.clone(),
-                    hash: self.img_hash.as_ref().unwrap(),
+                    img: frame.image.clone(),
+                    hash: &frame.hash,
                 },
-                rect,
+                img_rect,
             );
+            if overflows {
+                cx.restore();
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DELAY: Duration = Duration::from_millis(100);
+
+    fn animated(frames: usize, looping: bool) -> Img {
+        let mut img = Img::new();
+        img.frames = (0..frames)
+            .map(|_| ImgFrame::new(image_brush(1, 1, vec![0; 4]), DELAY))
+            .collect();
+        img.playback.set_looping(looping);
+        img
+    }
+
+    fn ms(start: Instant, ms: u64) -> Instant {
+        start + Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn frames_advance_after_their_delay() {
+        let mut img = animated(3, true);
+        let start = Instant::now();
+
+        assert_eq!(img.advance(start), Advance::NextFrameAt(ms(start, 100)));
+        assert_eq!(img.frame, 0);
+        assert_eq!(
+            img.advance(ms(start, 150)),
+            Advance::NextFrameAt(ms(start, 200))
+        );
+        assert_eq!(img.frame, 1);
+        // Frames that were missed are skipped, and the animation loops
+        assert_eq!(
+            img.advance(ms(start, 450)),
+            Advance::NextFrameAt(ms(start, 500))
+        );
+        assert_eq!(img.frame, 1);
+    }
+
+    #[test]
+    fn single_frames_are_not_animated() {
+        let mut img = animated(1, true);
+        assert_eq!(img.advance(Instant::now()), Advance::Stopped);
+    }
+
+    #[test]
+    fn animations_that_do_not_loop_end_on_their_last_frame() {
+        let mut img = animated(3, false);
+        let start = Instant::now();
+
+        img.advance(start);
+        assert_eq!(img.advance(ms(start, 350)), Advance::Ended);
+        assert_eq!(img.frame, 2);
+        // Stays ended until `playing` is set to `false` after the paint
+        assert_eq!(img.advance(ms(start, 400)), Advance::Stopped);
+        assert_eq!(img.frame, 2);
+        img.playback.pause();
+        assert_eq!(img.advance(ms(start, 450)), Advance::Stopped);
+
+        // Playing it again starts over
+        img.playback.play();
+        assert_eq!(
+            img.advance(ms(start, 500)),
+            Advance::NextFrameAt(ms(start, 600))
+        );
+        assert_eq!(img.frame, 0);
+    }
+
+    #[test]
+    fn paused_animations_resume_on_their_frame() {
+        let mut img = animated(3, true);
+        let start = Instant::now();
+
+        img.advance(start);
+        img.advance(ms(start, 150));
+        assert_eq!(img.frame, 1);
+
+        img.playback.pause();
+        assert_eq!(img.advance(ms(start, 1000)), Advance::Stopped);
+        assert_eq!(img.frame, 1);
+
+        // The resumed frame is shown for its whole delay
+        img.playback.play();
+        assert_eq!(
+            img.advance(ms(start, 1020)),
+            Advance::NextFrameAt(ms(start, 1120))
+        );
+        assert_eq!(img.frame, 1);
+    }
+}
@@ -2,14 +2,15 @@ use std::{any::Any, fmt::Display, mem::swap};
 
 use crate::{
     Clipboard,
+    accessibility::{Accessibility, Role},
     context::{PaintCx, UpdateCx},
     event::{Event, EventListener, EventPropagation},
     id::ViewId,
     prop_extractor,
     style::{
-        CursorColor, CustomStylable, CustomStyle, FontProps, LineHeight, Selectable,
-        SelectionCornerRadius, SelectionStyle, Style, TextAlignProp, TextColor, TextOverflow,
-        TextOverflowProp,
+        CursorColor, CustomStylable, CustomStyle, FontProps, LetterSpacing, LineHeight, Selectable,
+        SelectionCornerRadius, SelectionStyle, Style, TextAlignProp, TextColor, TextDecorationProp,
+        TextOverflow, TextOverflowProp, TextShadowProp, WordSpacing,
     },
     style_class,
     text::{Attrs, AttrsList, FamilyOwned, TextLayout},
@@ -29,7 +30,7 @@ use ui_events::{
     pointer::{PointerButtonEvent, PointerEvent},
 };
 
-use super::{Decorators, TextCommand};
+use super::{Decorators, SelectableText, TextCommand, TextSelection, selectable_area::index_at};
 
 prop_extractor! {
     Extractor {
@@ -38,6 +39,10 @@ prop_extractor! {
         line_height: LineHeight,
         text_selectable: Selectable,
         text_align: TextAlignProp,
+        text_decoration: TextDecorationProp,
+        letter_spacing: LetterSpacing,
+        word_spacing: WordSpacing,
+        text_shadow: TextShadowProp,
     }
 }
 
@@ -72,6 +77,8 @@ pub struct Label {
     selection_state: SelectionState,
     selection_range: Option<(Cursor, Cursor)>,
     selection_style: SelectionStyle,
+    /// The selection of a [`selectable_area`](super::selectable_area) around the label
+    area_selection: Option<TextSelection>,
     font: FontProps,
     style: Extractor,
 }
@@ -90,6 +97,7 @@ impl Label {
             selection_state: SelectionState::None,
             selection_range: None,
             selection_style: Default::default(),
+            area_selection: None,
             font: FontProps::default(),
             style: Default::default(),
         }
@@ -174,6 +182,15 @@ impl Label {
         if let Some(line_height) = self.style.line_height() {
             attrs = attrs.line_height(line_height);
         }
+        if let Some(decoration) = self.style.text_decoration() {
+            attrs = attrs.decoration(decoration);
+        }
+        if let Some(shadow) = self.style.text_shadow() {
+            attrs = attrs.shadow(shadow);
+        }
+        attrs = attrs
+            .letter_spacing(self.style.letter_spacing())
+            .word_spacing(self.style.word_spacing());
         AttrsList::new(attrs)
     }
 
@@ -302,6 +319,10 @@ impl View for Label {
         format!("Label: {:?}", self.label).into()
     }
 
+    fn accessibility(&self) -> Option<Accessibility> {
+        Some(Accessibility::new(Role::Label).value(&self.label))
+    }
+
     fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn Any>) {
         if let Ok(state) = state.downcast() {
             self.label = *state;
@@ -524,11 +545,43 @@ impl View for Label {
         let point = Point::new(location.x as f64, location.y as f64);
 
         let text_layout = self.effective_text_layout();
+        if let Some(selection) = &self.area_selection {
+            selection.paint(cx, text_layout, point);
+        }
         cx.draw_text(text_layout, point);
         if cx.window_state.is_focused(&self.id()) {
             self.paint_selection(text_layout, cx);
         }
     }
+
+    fn selectable_text(&mut self) -> Option<&mut dyn SelectableText> {
+        Some(self)
+    }
+}
+
+impl SelectableText for Label {
+    fn text(&self) -> &str {
+        &self.label
+    }
+
+    fn hit_index(&self, point: Point) -> usize {
+        if self.text_layout.is_none() {
+            return 0;
+        }
+        let index = self
+            .get_hit_point(point)
+            .map_or(0, |cursor| index_at(self.effective_text_layout(), cursor));
+        // The ellipsis is not part of the label
+        let mut index = index.min(self.label.len());
+        while !self.label.is_char_boundary(index) {
+            index -= 1;
+        }
+        index
+    }
+
+    fn set_selection(&mut self, selection: Option<TextSelection>) {
+        self.area_selection = selection;
+    }
 }
 
 /// Represents a custom style for a `Label`.
@@ -7,6 +7,7 @@ use crate::style::Style;
 use crate::style_class;
 use crate::view::IntoView;
 use crate::{
+    accessibility::{Accessibility, Action, Role},
     event::{Event, EventListener},
     view::View,
 };
@@ -186,6 +187,10 @@ impl View for List {
         self.id
     }
 
+    fn accessibility(&self) -> Option<Accessibility> {
+        Some(Accessibility::new(Role::List))
+    }
+
     fn update(&mut self, _cx: &mut crate::context::UpdateCx, state: Box<dyn std::any::Any>) {
         if let Ok(change) = state.downcast::<ListUpdate>() {
             match *change {
@@ -193,10 +198,12 @@ impl View for List {
                     if let Some(old_idx) = old_idx {
                         let child = self.child.children()[old_idx];
                         child.request_style_recursive();
+                        child.request_accessibility();
                     }
                     if let Some(index) = self.selection.get_untracked() {
                         let child = self.child.children()[index];
                         child.request_style_recursive();
+                        child.request_accessibility();
                         child.scroll_to(None);
                     }
                 }
@@ -223,6 +230,15 @@ impl View for Item {
         "List Item".into()
     }
 
+    fn accessibility(&self) -> Option<Accessibility> {
+        let selected = self.selection.get_untracked() == Some(self.index);
+        Some(
+            Accessibility::new(Role::ListItem)
+                .selected(selected)
+                .action(Action::Click),
+        )
+    }
+
     fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
         let selected = self.selection.get_untracked();
         if Some(self.index) == selected {
@@ -142,6 +142,28 @@ impl LocaleMap {
         Ok(Self(map))
     }
 
+    /// Formats the message `key` for `locale`, returning `None` if the message is missing or
+    /// fails to format.
+    ///
+    /// The bundle of `locale` is used if there is one, otherwise a bundle of the same language.
+    pub fn message(
+        &self,
+        locale: &LanguageIdentifier,
+        key: &str,
+        args: Option<&FluentArgs>,
+    ) -> Option<String> {
+        let resource = self.find_bundle(locale)?;
+        let message = resource.get_message(key)?;
+        let pattern = message.value()?;
+        let errors = &mut vec![];
+        let value = resource.format_pattern(pattern, args, errors);
+        if errors.is_empty() {
+            Some(value.to_string())
+        } else {
+            None
+        }
+    }
+
     /// Find a language bundle that matches the given locale.
     /// First, it tries exact match, then falls back to matching only the language component.
     fn find_bundle(
@@ -299,18 +321,10 @@ impl L10n {
     }
 
     fn try_format_message(&self) -> Option<String> {
-        let bundle = self.locale.bundle();
         let locale = self.locale.locale()?;
-        let resource = bundle.find_bundle(&locale)?;
-        let message = resource.get_message(&self.key)?;
-        let pattern = message.value()?;
-        let errors = &mut vec![];
-        let value = resource.format_pattern(pattern, Some(&self.args), errors);
-        if errors.is_empty() {
-            Some(value.to_string())
-        } else {
-            None
-        }
+        self.locale
+            .bundle()
+            .message(&locale, &self.key, Some(&self.args))
     }
 
     fn apply_fallback(&self) {
@@ -0,0 +1,1087 @@
+//! A view that displays a Markdown document.
+#![deny(missing_docs)]
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    hash::{DefaultHasher, Hash, Hasher},
+    iter::Peekable,
+    ops::Range,
+    path::PathBuf,
+    rc::Rc,
+};
+
+use floem_reactive::{RwSignal, Scope, SignalGet, SignalUpdate, SignalWith, create_effect};
+use imbl::Vector;
+use peniko::Color;
+use pulldown_cmark::{Alignment, CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+use taffy::{GridTemplateComponent, prelude::fr};
+
+use crate::{
+    AnyView, IntoView,
+    ext_event::create_ext_action,
+    id::ViewId,
+    style::{Style, TextOverflow},
+    style_class,
+    view::View,
+};
+
+use super::{
+    Decorators, Markup, MarkupSpan, MarkupText, ScrollExt, Stack, checkbox, container,
+    dyn_container, empty, h_stack,
+    img::{ImgFrame, decode_frames, img_frames},
+    text, v_stack_from_iter, virtual_stack,
+};
+
+style_class!(
+    /// The style class that is applied to a [`markdown`] document.
+    pub MarkdownClass
+);
+style_class!(
+    /// The style class that is applied to each top level block of a [`markdown`] document.
+    pub MarkdownBlockClass
+);
+style_class!(
+    /// The style class that is applied to paragraphs.
+    pub MarkdownParagraphClass
+);
+style_class!(
+    /// The style class that is applied to headings, along with the class of their level.
+    pub MarkdownHeadingClass
+);
+style_class!(
+    /// The style class that is applied to `#` headings.
+    pub MarkdownH1Class
+);
+style_class!(
+    /// The style class that is applied to `##` headings.
+    pub MarkdownH2Class
+);
+style_class!(
+    /// The style class that is applied to `###` headings.
+    pub MarkdownH3Class
+);
+style_class!(
+    /// The style class that is applied to `####` headings.
+    pub MarkdownH4Class
+);
+style_class!(
+    /// The style class that is applied to `#####` headings.
+    pub MarkdownH5Class
+);
+style_class!(
+    /// The style class that is applied to `######` headings.
+    pub MarkdownH6Class
+);
+style_class!(
+    /// The style class that is applied to block quotes.
+    pub MarkdownBlockQuoteClass
+);
+style_class!(
+    /// The style class that is applied to ordered and unordered lists.
+    pub MarkdownListClass
+);
+style_class!(
+    /// The style class that is applied to the items of lists.
+    pub MarkdownListItemClass
+);
+style_class!(
+    /// The style class that is applied to the bullets, numbers and checkboxes of list items.
+    pub MarkdownListMarkerClass
+);
+style_class!(
+    /// The style class that is applied to fenced and indented code blocks.
+    pub MarkdownCodeBlockClass
+);
+style_class!(
+    /// The style class that is applied to tables.
+    pub MarkdownTableClass
+);
+style_class!(
+    /// The style class that is applied to the cells of the header row of tables.
+    pub MarkdownTableHeaderClass
+);
+style_class!(
+    /// The style class that is applied to the cells of tables below the header row.
+    pub MarkdownTableCellClass
+);
+style_class!(
+    /// The style class that is applied to images.
+    pub MarkdownImageClass
+);
+style_class!(
+    /// The style class that is applied to horizontal rules.
+    pub MarkdownRuleClass
+);
+
+/// Loads the bytes of the image at a URL of a [`markdown`] document, see
+/// [`Markdown::image_loader`].
+pub type MarkdownImageLoader = dyn Fn(&str) -> Option<Vec<u8>>;
+
+/// Colors ranges of the code of a code block given its language, see [`Markdown::highlighter`].
+pub type MarkdownHighlighter = dyn Fn(&str, &str) -> Vec<(Range<usize>, Color)>;
+
+struct MarkdownOptions {
+    /// The scope of the document, which owns the signals of its images
+    scope: Scope,
+    on_link: RefCell<Option<Rc<dyn Fn(&str)>>>,
+    image_loader: RefCell<Option<Box<MarkdownImageLoader>>>,
+    highlighter: RefCell<Option<Box<MarkdownHighlighter>>>,
+    /// The images of the document by URL, which are loaded once for all the views that show them
+    images: RefCell<HashMap<String, RwSignal<Image>>>,
+}
+
+/// An image of a document.
+#[derive(Clone)]
+enum Image {
+    Loading,
+    Loaded(Rc<[ImgFrame]>),
+    Failed,
+}
+
+/// A view that displays a Markdown document. See [`markdown`].
+pub struct Markdown {
+    id: ViewId,
+    options: Rc<MarkdownOptions>,
+}
+
+/// A view that displays a CommonMark document with the table, strikethrough and task list
+/// extensions.
+///
+/// Each top level block of the document is a view in a [`virtual_stack`], so only the blocks
+/// that are visible are built when the view is in a [`scroll`](super::scroll()). The heights
+/// of the blocks are measured once they are laid out, and the heights of the blocks that have
+/// not been built are estimated from their text and the width of the document.
+///
+/// Every kind of block has a style class to theme it with, such as [`MarkdownHeadingClass`],
+/// [`MarkdownCodeBlockClass`] and [`MarkdownTableClass`]. The inline text is displayed with
+/// [`MarkupText`](super::MarkupText) views.
+///
+/// ### Example
+/// ```rust
+/// # use floem::views::*;
+/// markdown(|| "# Release notes\n\n- Added **markdown**\n- [x] Tested".to_string())
+///     .on_link(|url| println!("open {url}"))
+///     .scroll();
+/// ```
+pub fn markdown(source: impl Fn() -> String + 'static) -> Markdown {
+    let id = ViewId::new();
+    let options = Rc::new(MarkdownOptions {
+        scope: Scope::current(),
+        on_link: RefCell::new(None),
+        image_loader: RefCell::new(None),
+        highlighter: RefCell::new(None),
+        images: RefCell::new(HashMap::new()),
+    });
+    let heights = BlockHeights::new();
+
+    let blocks = RwSignal::new(Vector::new());
+    {
+        let heights = heights.clone();
+        create_effect(move |_| {
+            let document = parse_document(&source());
+            heights.retain(&document);
+            blocks.set(document);
+        });
+    }
+
+    let view_options = options.clone();
+    let measured = heights.clone();
+    let stack = virtual_stack(
+        move || blocks.get(),
+        |block: &DocumentBlock| block.key,
+        move |block| {
+            let heights = measured.clone();
+            block_view(&block.block, &view_options)
+                .class(MarkdownBlockClass)
+                .on_resize(move |rect| heights.measure(block.key, rect.width(), rect.height()))
+        },
+    )
+    .item_size_fn(move |block: &DocumentBlock| heights.height(block))
+    .style(|s| s.flex_col().width_full());
+    id.set_children([stack.into_any()]);
+
+    Markdown { id, options }.class(MarkdownClass)
+}
+
+impl Markdown {
+    /// Sets the callback that is called with the destination of a link when it is clicked.
+    pub fn on_link(self, on_link: impl Fn(&str) + 'static) -> Self {
+        *self.options.on_link.borrow_mut() = Some(Rc::new(on_link));
+        self
+    }
+
+    /// Sets the function that loads the bytes of the image at a URL. It is called on the UI
+    /// thread once per URL, and the images are decoded on another thread.
+    ///
+    /// By default images are read from the file system on another thread, with the URL as the
+    /// path, and images at URLs with a scheme other than `file://`, such as `https://`, are not
+    /// loaded. The alt text of an image is displayed until it is loaded, or if it can't be.
+    pub fn image_loader(self, loader: impl Fn(&str) -> Option<Vec<u8>> + 'static) -> Self {
+        *self.options.image_loader.borrow_mut() = Some(Box::new(loader));
+        self
+    }
+
+    /// Sets the function that highlights code blocks. It is called with the language of a block,
+    /// which may be empty, and its code, and returns the colors of byte ranges of the code.
+    pub fn highlighter(
+        self,
+        highlighter: impl Fn(&str, &str) -> Vec<(Range<usize>, Color)> + 'static,
+    ) -> Self {
+        *self.options.highlighter.borrow_mut() = Some(Box::new(highlighter));
+        self
+    }
+}
+
+impl View for Markdown {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Markdown".into()
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::new().flex_col())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Inline {
+    Text(Markup),
+    Image { url: String, alt: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Block {
+    Paragraph(Vec<Inline>),
+    Heading(usize, Markup),
+    BlockQuote(Vec<Block>),
+    List {
+        start: Option<u64>,
+        items: Vec<ListItem>,
+    },
+    CodeBlock {
+        language: String,
+        code: String,
+    },
+    Table {
+        alignments: Vec<Alignment>,
+        head: Vec<Markup>,
+        rows: Vec<Vec<Markup>>,
+    },
+    Rule,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ListItem {
+    /// Whether the task of a task list item is done
+    task: Option<bool>,
+    blocks: Vec<Block>,
+}
+
+/// A top level block of a document
+#[derive(Debug, Clone)]
+struct DocumentBlock {
+    /// The hash of the source of the block and the number of blocks before it with the same
+    /// source, which keeps the views of unchanged blocks when the document is edited
+    key: BlockKey,
+    block: Rc<Block>,
+}
+
+fn parse_document(source: &str) -> Vector<DocumentBlock> {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut builder = Builder {
+        events: Parser::new_ext(source, options)
+            .into_offset_iter()
+            .peekable(),
+        task: None,
+    };
+    let mut document = Vector::new();
+    let mut occurrences = HashMap::new();
+    let mut blocks = Vec::new();
+    while let Some((_, range)) = builder.events.peek() {
+        let range = range.clone();
+        if !builder.next_block(&mut blocks) {
+            break;
+        }
+        let mut hasher = DefaultHasher::new();
+        source[range].hash(&mut hasher);
+        let hash = hasher.finish();
+        for block in blocks.drain(..) {
+            let occurrence = occurrences.entry(hash).or_insert(0);
+            *occurrence += 1;
+            document.push_back(DocumentBlock {
+                key: (hash, *occurrence),
+                block: Rc::new(block),
+            });
+        }
+    }
+    document
+}
+
+/// The key of a [`DocumentBlock`]
+type BlockKey = (u64, usize);
+
+/// The heights of the top level blocks of a document in its [`virtual_stack`].
+///
+/// The views of the blocks report their size once they are laid out. The heights of the blocks
+/// that have not been laid out at the current width are estimated from their text.
+#[derive(Clone)]
+struct BlockHeights {
+    /// The width of the blocks when they were last laid out
+    width: RwSignal<f64>,
+    /// The width and height of the blocks that were laid out
+    measured: RwSignal<HashMap<BlockKey, (f64, f64)>>,
+    /// The width the estimates are for and the estimated heights of blocks
+    estimated: Rc<RefCell<(f64, HashMap<BlockKey, f64>)>>,
+}
+
+impl BlockHeights {
+    fn new() -> Self {
+        Self {
+            width: RwSignal::new(0.0),
+            measured: RwSignal::new(HashMap::new()),
+            estimated: Rc::new(RefCell::new((0.0, HashMap::new()))),
+        }
+    }
+
+    fn height(&self, block: &DocumentBlock) -> f64 {
+        let width = self.width.get();
+        match self
+            .measured
+            .with(|measured| measured.get(&block.key).copied())
+        {
+            Some((measured_width, height)) if measured_width == width => return height,
+            _ => {}
+        }
+        let mut estimated = self.estimated.borrow_mut();
+        if estimated.0 != width {
+            *estimated = (width, HashMap::new());
+        }
+        *estimated.1.entry(block.key).or_insert_with(|| {
+            estimated_lines(&block.block, chars_per_line(width)) * ESTIMATED_LINE_HEIGHT
+                + ESTIMATED_BLOCK_GAP
+        })
+    }
+
+    fn measure(&self, key: BlockKey, width: f64, height: f64) {
+        if self.width.get_untracked() != width {
+            self.width.set(width);
+        }
+        if self
+            .measured
+            .with_untracked(|measured| measured.get(&key) != Some(&(width, height)))
+        {
+            self.measured.update(|measured| {
+                measured.insert(key, (width, height));
+            });
+        }
+    }
+
+    /// Forgets the blocks that are not in `document` anymore.
+    fn retain(&self, document: &Vector<DocumentBlock>) {
+        let keys: HashSet<_> = document.iter().map(|block| block.key).collect();
+        if self
+            .measured
+            .with_untracked(|measured| measured.keys().any(|key| !keys.contains(key)))
+        {
+            self.measured
+                .update(|measured| measured.retain(|key, _| keys.contains(key)));
+        }
+        self.estimated
+            .borrow_mut()
+            .1
+            .retain(|key, _| keys.contains(key));
+    }
+}
+
+const ESTIMATED_LINE_HEIGHT: f64 = 20.0;
+const ESTIMATED_CHAR_WIDTH: f64 = 8.0;
+/// The number of characters of a line before any block is laid out
+const ESTIMATED_LINE_LENGTH: usize = 80;
+const ESTIMATED_BLOCK_GAP: f64 = 12.0;
+/// The number of lines of an image that has not been laid out
+const ESTIMATED_IMAGE_LINES: f64 = 10.0;
+
+fn chars_per_line(width: f64) -> usize {
+    if width > 0.0 {
+        ((width / ESTIMATED_CHAR_WIDTH) as usize).max(1)
+    } else {
+        ESTIMATED_LINE_LENGTH
+    }
+}
+
+fn estimated_text_lines(text: &str, chars_per_line: usize) -> f64 {
+    text.lines()
+        .map(|line| line.len().div_ceil(chars_per_line).max(1))
+        .sum::<usize>()
+        .max(1) as f64
+}
+
+fn estimated_lines(block: &Block, chars_per_line: usize) -> f64 {
+    match block {
+        Block::Paragraph(inlines) => inlines
+            .iter()
+            .map(|inline| match inline {
+                Inline::Text(markup) => estimated_text_lines(markup.text(), chars_per_line),
+                Inline::Image { .. } => ESTIMATED_IMAGE_LINES,
+            })
+            .sum(),
+        Block::Heading(level, markup) => {
+            let scale = if *level <= 2 { 2.0 } else { 1.5 };
+            estimated_text_lines(markup.text(), (chars_per_line as f64 / scale) as usize + 1)
+                * scale
+        }
+        Block::BlockQuote(blocks) => blocks
+            .iter()
+            .map(|block| estimated_lines(block, chars_per_line))
+            .sum(),
+        Block::List { items, .. } => items
+            .iter()
+            .flat_map(|item| &item.blocks)
+            .map(|block| estimated_lines(block, chars_per_line))
+            .sum(),
+        Block::CodeBlock { code, .. } => code.lines().count() as f64 + 1.0,
+        Block::Table { rows, .. } => rows.len() as f64 + 1.5,
+        Block::Rule => 1.0,
+    }
+}
+
+/// Builds [`Block`]s from the events of a [`Parser`].
+struct Builder<'a, I: Iterator<Item = (Event<'a>, Range<usize>)>> {
+    events: Peekable<I>,
+    /// The task list marker of the list item that is being built
+    task: Option<bool>,
+}
+
+fn is_inline(event: &Event) -> bool {
+    match event {
+        Event::Start(tag) => matches!(
+            tag,
+            Tag::Emphasis
+                | Tag::Strong
+                | Tag::Strikethrough
+                | Tag::Superscript
+                | Tag::Subscript
+                | Tag::Link { .. }
+                | Tag::Image { .. }
+        ),
+        Event::End(tag) => matches!(
+            tag,
+            TagEnd::Emphasis
+                | TagEnd::Strong
+                | TagEnd::Strikethrough
+                | TagEnd::Superscript
+                | TagEnd::Subscript
+                | TagEnd::Link
+                | TagEnd::Image
+        ),
+        Event::Text(_)
+        | Event::Code(_)
+        | Event::InlineMath(_)
+        | Event::InlineHtml(_)
+        | Event::FootnoteReference(_)
+        | Event::SoftBreak
+        | Event::HardBreak => true,
+        _ => false,
+    }
+}
+
+impl<'a, I: Iterator<Item = (Event<'a>, Range<usize>)>> Builder<'a, I> {
+    /// Builds the blocks up to the end of the current container.
+    fn blocks(&mut self) -> Vec<Block> {
+        let mut blocks = Vec::new();
+        while self.next_block(&mut blocks) {}
+        blocks
+    }
+
+    /// Builds the next block into `blocks`, returning `false` at the end of the current
+    /// container.
+    fn next_block(&mut self, blocks: &mut Vec<Block>) -> bool {
+        let Some((event, _)) = self.events.peek() else {
+            return false;
+        };
+        if is_inline(event) {
+            // The text of tight list items isn't in a paragraph
+            blocks.push(Block::Paragraph(self.inlines()));
+            return true;
+        }
+        let Some((event, _)) = self.events.next() else {
+            return false;
+        };
+        match event {
+            Event::End(_) => return false,
+            Event::Start(tag) => self.block(tag, blocks),
+            Event::Rule => blocks.push(Block::Rule),
+            Event::TaskListMarker(checked) => self.task = Some(checked),
+            _ => {}
+        }
+        true
+    }
+
+    /// Builds the block that starts with `tag`, up to and including its end.
+    fn block(&mut self, tag: Tag<'a>, blocks: &mut Vec<Block>) {
+        match tag {
+            Tag::Paragraph => {
+                if let Some((Event::TaskListMarker(checked), _)) = self.events.peek() {
+                    self.task = Some(*checked);
+                    self.events.next();
+                }
+                let inlines = self.inlines();
+                self.finish();
+                if !inlines.is_empty() {
+                    blocks.push(Block::Paragraph(inlines));
+                }
+            }
+            Tag::Heading { level, .. } => {
+                let markup = inline_markup(self.inlines());
+                self.finish();
+                blocks.push(Block::Heading(level as usize, markup));
+            }
+            Tag::BlockQuote(_) => blocks.push(Block::BlockQuote(self.blocks())),
+            Tag::CodeBlock(kind) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => {
+                        info.split_whitespace().next().unwrap_or("").to_string()
+                    }
+                    CodeBlockKind::Indented => String::new(),
+                };
+                let mut code = String::new();
+                while let Some((Event::Text(text), _)) = self.events.peek() {
+                    code.push_str(text);
+                    self.events.next();
+                }
+                self.finish();
+                if code.ends_with('\n') {
+                    code.pop();
+                }
+                blocks.push(Block::CodeBlock { language, code });
+            }
+            Tag::List(start) => {
+                let mut items = Vec::new();
+                while let Some((Event::Start(Tag::Item), _)) = self.events.peek() {
+                    self.events.next();
+                    // Keep the marker of the item that contains this list
+                    let outer_task = self.task.take();
+                    let blocks = self.blocks();
+                    let task = std::mem::replace(&mut self.task, outer_task);
+                    items.push(ListItem { task, blocks });
+                }
+                self.finish();
+                blocks.push(Block::List { start, items });
+            }
+            Tag::Table(alignments) => {
+                let mut head = Vec::new();
+                let mut rows = Vec::new();
+                while let Some((Event::Start(tag @ (Tag::TableHead | Tag::TableRow)), _)) =
+                    self.events.next()
+                {
+                    let mut cells = Vec::new();
+                    while let Some((Event::Start(Tag::TableCell), _)) = self.events.next() {
+                        cells.push(inline_markup(self.inlines()));
+                        self.finish();
+                    }
+                    if matches!(tag, Tag::TableHead) {
+                        head = cells;
+                    } else {
+                        rows.push(cells);
+                    }
+                }
+                blocks.push(Block::Table {
+                    alignments,
+                    head,
+                    rows,
+                });
+            }
+            Tag::HtmlBlock | Tag::MetadataBlock(_) => self.finish(),
+            // Other containers are shown as their content
+            _ => blocks.extend(self.blocks()),
+        }
+    }
+
+    /// Skips to the end of the current block.
+    fn finish(&mut self) {
+        let mut depth = 0;
+        for (event, _) in self.events.by_ref() {
+            match event {
+                Event::Start(_) => depth += 1,
+                Event::End(_) if depth == 0 => return,
+                Event::End(_) => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+
+    /// The text of the description of the image that just started, up to and including its end.
+    fn image_alt(&mut self) -> String {
+        let mut alt = String::new();
+        let mut depth = 0;
+        for (event, _) in self.events.by_ref() {
+            match event {
+                Event::Start(_) => depth += 1,
+                Event::End(_) if depth == 0 => break,
+                Event::End(_) => depth -= 1,
+                Event::Text(text) | Event::Code(text) => alt.push_str(&text),
+                _ => {}
+            }
+        }
+        alt
+    }
+
+    /// Builds the inline content at the current position.
+    fn inlines(&mut self) -> Vec<Inline> {
+        let mut inlines = Vec::new();
+        let mut markup = Markup::default();
+        let mut formats = vec![MarkupSpan::default()];
+        while let Some((event, _)) = self.events.next_if(|(event, _)| is_inline(event)) {
+            let format = formats.last().cloned().unwrap_or_default();
+            match event {
+                Event::Start(Tag::Image { dest_url, .. }) => {
+                    if !markup.text().is_empty() {
+                        inlines.push(Inline::Text(std::mem::take(&mut markup)));
+                    }
+                    let alt = self.image_alt();
+                    inlines.push(Inline::Image {
+                        url: dest_url.to_string(),
+                        alt,
+                    });
+                }
+                Event::Start(tag) => formats.push(match tag {
+                    Tag::Emphasis => MarkupSpan {
+                        emphasis: true,
+                        ..format
+                    },
+                    Tag::Strong => MarkupSpan {
+                        strong: true,
+                        ..format
+                    },
+                    Tag::Strikethrough => MarkupSpan {
+                        strikethrough: true,
+                        ..format
+                    },
+                    Tag::Link { dest_url, .. } => MarkupSpan {
+                        link: Some(dest_url.to_string()),
+                        ..format
+                    },
+                    _ => format,
+                }),
+                Event::End(_) => {
+                    if formats.len() > 1 {
+                        formats.pop();
+                    }
+                }
+                Event::Code(code) => markup.push_text(
+                    &code,
+                    &MarkupSpan {
+                        code: true,
+                        ..format
+                    },
+                ),
+                Event::SoftBreak => markup.push_text(" ", &format),
+                Event::HardBreak => markup.push_text("\n", &format),
+                Event::Text(text)
+                | Event::InlineMath(text)
+                | Event::InlineHtml(text)
+                | Event::FootnoteReference(text) => markup.push_text(&text, &format),
+                _ => {}
+            }
+        }
+        if !markup.text().is_empty() {
+            inlines.push(Inline::Text(markup));
+        }
+        inlines
+    }
+}
+
+/// Joins inline content into a single [`Markup`], with images replaced by their alt text.
+fn inline_markup(inlines: Vec<Inline>) -> Markup {
+    let mut joined = Markup::default();
+    for inline in inlines {
+        match inline {
+            Inline::Text(markup) => {
+                for span in markup.spans() {
+                    joined.push_text(&markup.text()[span.range.clone()], span);
+                }
+            }
+            Inline::Image { alt, .. } => joined.push_text(&alt, &MarkupSpan::default()),
+        }
+    }
+    joined
+}
+
+fn markup_view(markup: Markup, options: &MarkdownOptions) -> MarkupText {
+    let on_link = options.on_link.borrow().clone();
+    MarkupText::new(markup)
+        .on_link(move |url| {
+            if let Some(on_link) = &on_link {
+                on_link(url);
+            }
+        })
+        .style(|s| s.min_width(0.))
+}
+
+fn block_view(block: &Block, options: &MarkdownOptions) -> AnyView {
+    match block {
+        Block::Paragraph(inlines) => v_stack_from_iter(inlines.iter().map(|inline| match inline {
+            Inline::Text(markup) => markup_view(markup.clone(), options).into_any(),
+            Inline::Image { url, alt } => image_view(url, alt, options),
+        }))
+        .class(MarkdownParagraphClass)
+        .into_any(),
+        Block::Heading(level, markup) => {
+            let heading = markup_view(markup.clone(), options).class(MarkdownHeadingClass);
+            match level {
+                1 => heading.class(MarkdownH1Class),
+                2 => heading.class(MarkdownH2Class),
+                3 => heading.class(MarkdownH3Class),
+                4 => heading.class(MarkdownH4Class),
+                5 => heading.class(MarkdownH5Class),
+                _ => heading.class(MarkdownH6Class),
+            }
+            .into_any()
+        }
+        Block::BlockQuote(blocks) => blocks_view(blocks, options)
+            .class(MarkdownBlockQuoteClass)
+            .into_any(),
+        Block::List { start, items } => {
+            v_stack_from_iter(items.iter().enumerate().map(|(i, item)| {
+                let marker = match (item.task, start) {
+                    (Some(done), _) => checkbox(move || done).disabled(|| true).into_any(),
+                    (None, Some(start)) => text(format!("{}.", start + i as u64)).into_any(),
+                    (None, None) => text("•").into_any(),
+                };
+                h_stack((
+                    marker.class(MarkdownListMarkerClass),
+                    blocks_view(&item.blocks, options).style(|s| s.flex_grow(1.).min_width(0.)),
+                ))
+                .class(MarkdownListItemClass)
+            }))
+            .class(MarkdownListClass)
+            .into_any()
+        }
+        Block::CodeBlock { language, code } => {
+            let colors = options
+                .highlighter
+                .borrow()
+                .as_ref()
+                .map(|highlighter| highlighter(language, code))
+                .unwrap_or_default();
+            markup_view(code_markup(code, colors), options)
+                .style(|s| s.text_overflow(TextOverflow::Clip))
+                .scroll()
+                .class(MarkdownCodeBlockClass)
+                .into_any()
+        }
+        Block::Table {
+            alignments,
+            head,
+            rows,
+        } => {
+            let columns = head.len().max(alignments.len());
+            let cell = |markup: &Markup, column: usize| {
+                let alignment = alignments.get(column).copied().unwrap_or(Alignment::None);
+                container(markup_view(markup.clone(), options)).style(move |s| match alignment {
+                    Alignment::Center => s.justify_center(),
+                    Alignment::Right => s.justify_end(),
+                    Alignment::Left | Alignment::None => s.justify_start(),
+                })
+            };
+            let head = head
+                .iter()
+                .enumerate()
+                .map(|(column, markup)| cell(markup, column).class(MarkdownTableHeaderClass));
+            let rows = rows.iter().flat_map(|row| {
+                row.iter()
+                    .enumerate()
+                    .map(|(column, markup)| cell(markup, column).class(MarkdownTableCellClass))
+            });
+            let template: Vec<GridTemplateComponent<String>> = vec![fr(1.); columns];
+            v_stack_from_iter(head.chain(rows).collect::<Vec<_>>())
+                .style(move |s| s.grid().grid_template_columns(template.clone()))
+                .class(MarkdownTableClass)
+                .into_any()
+        }
+        Block::Rule => empty().class(MarkdownRuleClass).into_any(),
+    }
+}
+
+fn blocks_view(blocks: &[Block], options: &MarkdownOptions) -> Stack {
+    v_stack_from_iter(
+        blocks
+            .iter()
+            .map(|block| block_view(block, options))
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn image_view(url: &str, alt: &str, options: &MarkdownOptions) -> AnyView {
+    let image = load_image(url, options);
+    let alt = alt.to_string();
+    dyn_container(
+        move || image.get(),
+        move |image| match image {
+            Image::Loaded(frames) => img_frames(frames.to_vec())
+                .class(MarkdownImageClass)
+                .into_any(),
+            Image::Loading | Image::Failed => text(&alt).class(MarkdownImageClass).into_any(),
+        },
+    )
+    .into_any()
+}
+
+/// Returns the image at `url`, which starts to load the first time.
+fn load_image(url: &str, options: &MarkdownOptions) -> RwSignal<Image> {
+    if let Some(image) = options.images.borrow().get(url) {
+        return *image;
+    }
+    let image = options.scope.create_rw_signal(Image::Loading);
+    options.images.borrow_mut().insert(url.to_string(), image);
+
+    let bytes = options
+        .image_loader
+        .borrow()
+        .as_ref()
+        .map(|loader| loader(url));
+    let path = file_path(url).map(PathBuf::from);
+    if bytes.is_none() && path.is_none() {
+        image.set(Image::Failed);
+        return image;
+    }
+    let loaded = create_ext_action(options.scope, move |frames: Option<Vec<ImgFrame>>| {
+        image.set(match frames {
+            Some(frames) => Image::Loaded(frames.into()),
+            None => Image::Failed,
+        });
+    });
+    std::thread::spawn(move || {
+        let bytes = match bytes {
+            Some(bytes) => bytes,
+            None => path.and_then(|path| std::fs::read(path).ok()),
+        };
+        loaded(bytes.and_then(|bytes| decode_frames(&bytes)));
+    });
+    image
+}
+
+/// The path of the file at `url`, which is either a `file://` URL or a path, or `None` if it
+/// has another scheme.
+fn file_path(url: &str) -> Option<&str> {
+    if let Some(path) = url.strip_prefix("file://") {
+        return Some(path);
+    }
+    let has_scheme = url.split_once(':').is_some_and(|(scheme, _)| {
+        // A single letter is the drive of a Windows path
+        scheme.len() > 1
+            && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    });
+    (!has_scheme).then_some(url)
+}
+
+/// The markup of a code block, with the colors of its highlighting.
+fn code_markup(code: &str, mut colors: Vec<(Range<usize>, Color)>) -> Markup {
+    colors.sort_by_key(|(range, _)| range.start);
+    let mut markup = Markup::default();
+    let code_format = MarkupSpan {
+        code: true,
+        ..Default::default()
+    };
+    let mut end = 0;
+    for (range, color) in colors {
+        let range = range.start.max(end)..range.end.min(code.len());
+        let (Some(before), Some(colored)) = (code.get(end..range.start), code.get(range.clone()))
+        else {
+            continue;
+        };
+        markup.push_text(before, &code_format);
+        markup.push_text(
+            colored,
+            &MarkupSpan {
+                color: Some(color),
+                ..code_format.clone()
+            },
+        );
+        end = range.end;
+    }
+    markup.push_text(&code[end..], &code_format);
+    markup
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocks(source: &str) -> Vec<Block> {
+        parse_document(source)
+            .into_iter()
+            .map(|block| Block::clone(&block.block))
+            .collect()
+    }
+
+    fn plain(text: &str) -> Markup {
+        Markup::parse(text)
+    }
+
+    #[test]
+    fn blocks_of_a_document() {
+        assert_eq!(
+            blocks("# Title\n\nSome *text*.\n\n---\n\n```rust\nfn main() {}\n```"),
+            vec![
+                Block::Heading(1, plain("Title")),
+                Block::Paragraph(vec![Inline::Text(Markup::parse("Some *text*."))]),
+                Block::Rule,
+                Block::CodeBlock {
+                    language: "rust".to_string(),
+                    code: "fn main() {}".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn lists() {
+        assert_eq!(
+            blocks("3. three\n4. four\n\n- [x] done\n- [ ] todo\n  - nested"),
+            vec![
+                Block::List {
+                    start: Some(3),
+                    items: vec![
+                        ListItem {
+                            task: None,
+                            blocks: vec![Block::Paragraph(vec![Inline::Text(plain("three"))])],
+                        },
+                        ListItem {
+                            task: None,
+                            blocks: vec![Block::Paragraph(vec![Inline::Text(plain("four"))])],
+                        },
+                    ],
+                },
+                Block::List {
+                    start: None,
+                    items: vec![
+                        ListItem {
+                            task: Some(true),
+                            blocks: vec![Block::Paragraph(vec![Inline::Text(plain("done"))])],
+                        },
+                        ListItem {
+                            task: Some(false),
+                            blocks: vec![
+                                Block::Paragraph(vec![Inline::Text(plain("todo"))]),
+                                Block::List {
+                                    start: None,
+                                    items: vec![ListItem {
+                                        task: None,
+                                        blocks: vec![Block::Paragraph(vec![Inline::Text(plain(
+                                            "nested"
+                                        ))])],
+                                    }],
+                                },
+                            ],
+                        },
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn table_and_image() {
+        assert_eq!(
+            blocks("| a | **b** |\n|:--|--:|\n| 1 | 2 |\n\n![alt text](logo.png)"),
+            vec![
+                Block::Table {
+                    alignments: vec![Alignment::Left, Alignment::Right],
+                    head: vec![plain("a"), plain("**b**")],
+                    rows: vec![vec![plain("1"), plain("2")]],
+                },
+                Block::Paragraph(vec![Inline::Image {
+                    url: "logo.png".to_string(),
+                    alt: "alt text".to_string(),
+                }]),
+            ]
+        );
+    }
+
+    #[test]
+    fn code_highlighting() {
+        let markup = code_markup(
+            "let x = 1;",
+            vec![(8..9, Color::WHITE), (0..3, Color::BLACK)],
+        );
+        let colors: Vec<_> = markup
+            .spans()
+            .iter()
+            .map(|span| (&markup.text()[span.range.clone()], span.color))
+            .collect();
+        assert_eq!(
+            colors,
+            vec![
+                ("let", Some(Color::BLACK)),
+                (" x = ", None),
+                ("1", Some(Color::WHITE)),
+                (";", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn file_paths_of_urls() {
+        assert_eq!(file_path("logo.png"), Some("logo.png"));
+        assert_eq!(file_path("../assets/logo.png"), Some("../assets/logo.png"));
+        assert_eq!(file_path("/tmp/logo.png"), Some("/tmp/logo.png"));
+        assert_eq!(file_path("file:///tmp/logo.png"), Some("/tmp/logo.png"));
+        assert_eq!(file_path(r"C:\logo.png"), Some(r"C:\logo.png"));
+        assert_eq!(file_path("https://example.com/logo.png"), None);
+        assert_eq!(file_path("http://example.com/logo.png"), None);
+        assert_eq!(file_path("data:image/png;base64,AAAA"), None);
+    }
+
+    #[test]
+    fn estimates_follow_the_width() {
+        let paragraph = Block::Paragraph(vec![Inline::Text(plain(&"word ".repeat(40)))]);
+        assert_eq!(estimated_lines(&paragraph, chars_per_line(0.0)), 3.0);
+        assert_eq!(estimated_lines(&paragraph, chars_per_line(800.0)), 2.0);
+        assert_eq!(estimated_lines(&paragraph, chars_per_line(1600.0)), 1.0);
+    }
+
+    #[test]
+    fn measured_heights_replace_estimates() {
+        let document = parse_document("one\n\ntwo");
+        let heights = BlockHeights::new();
+        let estimate = heights.height(&document[0]);
+        assert_eq!(estimate, ESTIMATED_LINE_HEIGHT + ESTIMATED_BLOCK_GAP);
+
+        heights.measure(document[0].key, 400.0, 31.0);
+        assert_eq!(heights.height(&document[0]), 31.0);
+        assert_eq!(heights.height(&document[1]), estimate);
+
+        // Measurements at another width are estimated again
+        heights.measure(document[1].key, 300.0, 52.0);
+        assert_eq!(heights.height(&document[0]), estimate);
+        assert_eq!(heights.height(&document[1]), 52.0);
+
+        heights.retain(&document.skip(1));
+        let keys = heights
+            .measured
+            .with_untracked(|measured| measured.keys().copied().collect::<Vec<_>>());
+        assert_eq!(keys, vec![document[1].key]);
+    }
+
+    #[test]
+    fn unchanged_blocks_keep_their_keys() {
+        let keys = |source: &str| -> Vec<_> {
+            parse_document(source)
+                .iter()
+                .map(|block| block.key)
+                .collect()
+        };
+        let before = keys("one\n\ntwo\n\ntwo");
+        let after = keys("zero\n\none\n\ntwo\n\ntwo");
+        assert_eq!(&after[1..], &before[..]);
+        assert_ne!(before[1], before[2]);
+    }
+}
@@ -0,0 +1,895 @@
+//! A view that displays text with inline Markdown formatting.
+#![deny(missing_docs)]
+use std::ops::Range;
+
+use peniko::{
+    Brush, Color,
+    color::palette,
+    kurbo::{Point, Rect},
+};
+use ui_events::pointer::{PointerButton, PointerButtonEvent, PointerEvent};
+
+use crate::{
+    Renderer,
+    accessibility::{Accessibility, Role},
+    context::{ComputeLayoutCx, EventCx, LayoutCx, PaintCx, StyleCx},
+    event::{Event, EventPropagation},
+    id::ViewId,
+    prop, prop_extractor,
+    style::{
+        CursorStyle, CustomStylable, CustomStyle, FontProps, LetterSpacing, LineHeight, Style,
+        TextAlignProp, TextColor, WordSpacing,
+    },
+    style_class,
+    text::{Attrs, AttrsList, FamilyOwned, TextDecoration, TextDecorationLine, TextLayout, Weight},
+    view::View,
+};
+
+use super::{Decorators, RichText, SelectableText, selectable_area::range_rects};
+
+/// A run of the text of a [`Markup`] with the same inline formatting.
+///
+/// Unlike a [`RichSpan`](super::RichSpan), which holds resolved text attributes, it records
+/// which formatting the markup asks for. The attributes are resolved from the style of the
+/// [`MarkupText`] each time it changes, and links keep their destination for
+/// [`MarkupText::on_link`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MarkupSpan {
+    /// The byte range of the run in [`Markup::text`]
+    pub range: Range<usize>,
+    /// Whether the run is strongly emphasized, `**like this**`
+    pub strong: bool,
+    /// Whether the run is emphasized, `*like this*`
+    pub emphasis: bool,
+    /// Whether the run is struck through, `~~like this~~`
+    pub strikethrough: bool,
+    /// Whether the run is inline code, `` `like this` ``
+    pub code: bool,
+    /// The destination of the link around the run, `[like this](destination)`
+    pub link: Option<String>,
+    /// The color of the run, which overrides the color of its formatting
+    pub color: Option<Color>,
+}
+
+impl MarkupSpan {
+    fn same_format(&self, other: &MarkupSpan) -> bool {
+        self.strong == other.strong
+            && self.emphasis == other.emphasis
+            && self.strikethrough == other.strikethrough
+            && self.code == other.code
+            && self.link == other.link
+            && self.color == other.color
+    }
+}
+
+/// Text with inline formatting parsed from the inline subset of CommonMark.
+///
+/// The supported markup is emphasis (`*em*`, `_em_`), strong emphasis (`**strong**`,
+/// `__strong__`), strikethrough (`~~struck~~`), inline code (`` `code` ``), links
+/// (`[text](destination)`), autolinks (`<https://example.com>`) and backslash escapes. Any other
+/// text, including line breaks, is kept as it is.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Markup {
+    text: String,
+    spans: Vec<MarkupSpan>,
+}
+
+impl Markup {
+    /// Parses the inline formatting of `markup`.
+    pub fn parse(markup: &str) -> Self {
+        let mut parsed = Markup::default();
+        parsed.push_inlines(parse_inlines(markup, true), &MarkupSpan::default());
+        parsed
+    }
+
+    /// The text without its markup.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The runs of the text with the same formatting, in order and covering all of the text.
+    pub fn spans(&self) -> &[MarkupSpan] {
+        &self.spans
+    }
+
+    fn push_inlines(&mut self, inlines: Vec<Inline>, format: &MarkupSpan) {
+        for inline in inlines {
+            match inline {
+                Inline::Text(text) => self.push_text(&text, format),
+                Inline::Delim { ch, count, .. } => {
+                    self.push_text(&ch.to_string().repeat(count), format)
+                }
+                Inline::Code(code) => self.push_text(
+                    &code,
+                    &MarkupSpan {
+                        code: true,
+                        ..format.clone()
+                    },
+                ),
+                Inline::Formatted(kind, inlines) => {
+                    let mut format = format.clone();
+                    match kind {
+                        Format::Strong => format.strong = true,
+                        Format::Emphasis => format.emphasis = true,
+                        Format::Strikethrough => format.strikethrough = true,
+                    }
+                    self.push_inlines(inlines, &format);
+                }
+                Inline::Link(destination, inlines) => self.push_inlines(
+                    inlines,
+                    &MarkupSpan {
+                        link: Some(destination),
+                        ..format.clone()
+                    },
+                ),
+            }
+        }
+    }
+
+    /// Appends `text` with the formatting of `format`, ignoring its range.
+    pub(crate) fn push_text(&mut self, text: &str, format: &MarkupSpan) {
+        if text.is_empty() {
+            return;
+        }
+        let start = self.text.len();
+        self.text.push_str(text);
+        let end = self.text.len();
+        match self.spans.last_mut() {
+            Some(last) if last.range.end == start && last.same_format(format) => {
+                last.range.end = end;
+            }
+            _ => self.spans.push(MarkupSpan {
+                range: start..end,
+                ..format.clone()
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    Strong,
+    Emphasis,
+    Strikethrough,
+}
+
+#[derive(Debug)]
+enum Inline {
+    Text(String),
+    Code(String),
+    /// A run of `*`, `_` or `~` that may open or close a [`Format`]
+    Delim {
+        ch: char,
+        count: usize,
+        can_open: bool,
+        can_close: bool,
+    },
+    Formatted(Format, Vec<Inline>),
+    Link(String, Vec<Inline>),
+}
+
+fn parse_inlines(src: &str, links: bool) -> Vec<Inline> {
+    let mut inlines = Vec::new();
+    let mut text = String::new();
+    let flush = |text: &mut String, inlines: &mut Vec<Inline>| {
+        if !text.is_empty() {
+            inlines.push(Inline::Text(std::mem::take(text)));
+        }
+    };
+    let mut i = 0;
+    while let Some(ch) = src[i..].chars().next() {
+        match ch {
+            '\\' => match src[i + 1..].chars().next() {
+                Some(next) if next.is_ascii_punctuation() || next == '\n' => {
+                    text.push(next);
+                    i += 2;
+                }
+                _ => {
+                    text.push('\\');
+                    i += 1;
+                }
+            },
+            '`' => {
+                let run = run_len(src, i, '`');
+                if let Some((code, end)) = code_span(src, i, run) {
+                    flush(&mut text, &mut inlines);
+                    inlines.push(Inline::Code(code));
+                    i = end;
+                } else {
+                    text.push_str(&src[i..i + run]);
+                    i += run;
+                }
+            }
+            '*' | '_' | '~' => {
+                let run = run_len(src, i, ch);
+                if ch == '~' && run > 2 {
+                    text.push_str(&src[i..i + run]);
+                } else {
+                    let before = src[..i].chars().next_back();
+                    let after = src[i + run..].chars().next();
+                    let (can_open, can_close) = flanking(ch, before, after);
+                    flush(&mut text, &mut inlines);
+                    inlines.push(Inline::Delim {
+                        ch,
+                        count: run,
+                        can_open,
+                        can_close,
+                    });
+                }
+                i += run;
+            }
+            '[' if links => {
+                if let Some((label, destination, end)) = link(src, i) {
+                    flush(&mut text, &mut inlines);
+                    inlines.push(Inline::Link(destination, parse_inlines(label, false)));
+                    i = end;
+                } else {
+                    text.push('[');
+                    i += 1;
+                }
+            }
+            '<' if links => {
+                if let Some((destination, end)) = autolink(src, i) {
+                    flush(&mut text, &mut inlines);
+                    inlines.push(Inline::Link(
+                        destination.clone(),
+                        vec![Inline::Text(destination)],
+                    ));
+                    i = end;
+                } else {
+                    text.push('<');
+                    i += 1;
+                }
+            }
+            _ => {
+                text.push(ch);
+                i += ch.len_utf8();
+            }
+        }
+    }
+    flush(&mut text, &mut inlines);
+    process_emphasis(&mut inlines);
+    inlines
+}
+
+fn run_len(src: &str, start: usize, ch: char) -> usize {
+    src[start..].chars().take_while(|c| *c == ch).count()
+}
+
+fn is_punctuation(ch: char) -> bool {
+    ch.is_ascii_punctuation() || !(ch.is_alphanumeric() || ch.is_whitespace())
+}
+
+/// Whether a delimiter run of `ch` between `before` and `after` can open and close emphasis,
+/// following the left and right flanking rules of CommonMark.
+fn flanking(ch: char, before: Option<char>, after: Option<char>) -> (bool, bool) {
+    let whitespace = |c: Option<char>| c.is_none_or(char::is_whitespace);
+    let punctuation = |c: Option<char>| c.is_some_and(is_punctuation);
+    let left =
+        !whitespace(after) && (!punctuation(after) || whitespace(before) || punctuation(before));
+    let right =
+        !whitespace(before) && (!punctuation(before) || whitespace(after) || punctuation(after));
+    if ch == '_' {
+        // Underscores don't emphasize inside words
+        (
+            left && (!right || punctuation(before)),
+            right && (!left || punctuation(after)),
+        )
+    } else {
+        (left, right)
+    }
+}
+
+/// Parses the code span that starts with `run` backticks at `start`, returning the code and the
+/// end of the span.
+fn code_span(src: &str, start: usize, run: usize) -> Option<(String, usize)> {
+    let content_start = start + run;
+    let mut i = content_start;
+    while let Some(offset) = src[i..].find('`') {
+        let close = i + offset;
+        let len = run_len(src, close, '`');
+        if len == run {
+            let code = src[content_start..close].replace('\n', " ");
+            let code = if code.len() > 2
+                && code.starts_with(' ')
+                && code.ends_with(' ')
+                && !code.trim().is_empty()
+            {
+                code[1..code.len() - 1].to_string()
+            } else {
+                code
+            };
+            return Some((code, close + run));
+        }
+        i = close + len;
+    }
+    None
+}
+
+/// Parses the `[label](destination "title")` link that starts at `start`, returning the label,
+/// the destination and the end of the link.
+fn link(src: &str, start: usize) -> Option<(&str, String, usize)> {
+    let bytes = src.as_bytes();
+    let mut depth = 0;
+    let mut i = start;
+    let label_end = loop {
+        match bytes.get(i)? {
+            b'\\' => i += 1,
+            b'[' => depth += 1,
+            b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    break i;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    };
+    if bytes.get(label_end + 1) != Some(&b'(') {
+        return None;
+    }
+
+    let rest = &src[label_end + 2..];
+    let bytes = rest.as_bytes();
+    let skip_whitespace = |mut i: usize| {
+        while bytes.get(i).is_some_and(u8::is_ascii_whitespace) {
+            i += 1;
+        }
+        i
+    };
+    let mut i = skip_whitespace(0);
+    let destination = if bytes.get(i) == Some(&b'<') {
+        let end = i + rest[i..].find('>')?;
+        let destination = &rest[i + 1..end];
+        i = end + 1;
+        destination
+    } else {
+        let destination_start = i;
+        let mut depth = 0;
+        while let Some(&b) = bytes.get(i) {
+            match b {
+                b'\\' => i += 1,
+                b'(' => depth += 1,
+                b')' if depth == 0 => break,
+                b')' => depth -= 1,
+                b if b.is_ascii_whitespace() => break,
+                _ => {}
+            }
+            i += 1;
+        }
+        rest.get(destination_start..i)?
+    };
+    i = skip_whitespace(i);
+    if let Some(&quote @ (b'"' | b'\'')) = bytes.get(i) {
+        i += 1 + rest[i + 1..].find(quote as char)? + 1;
+        i = skip_whitespace(i);
+    }
+    if bytes.get(i) != Some(&b')') {
+        return None;
+    }
+    Some((
+        &src[start + 1..label_end],
+        unescape(destination),
+        label_end + 2 + i + 1,
+    ))
+}
+
+/// Parses the `<scheme:destination>` autolink that starts at `start`, returning the destination
+/// and the end of the link.
+fn autolink(src: &str, start: usize) -> Option<(String, usize)> {
+    let rest = &src[start + 1..];
+    let end = rest.find(|c: char| c == '>' || c == '<' || c.is_whitespace() || c.is_control())?;
+    if !rest[end..].starts_with('>') {
+        return None;
+    }
+    let destination = &rest[..end];
+    let (scheme, _) = destination.split_once(':')?;
+    let valid = (2..=32).contains(&scheme.len())
+        && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '.' | '-'));
+    valid.then(|| (destination.to_string(), start + 1 + end + 1))
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match chars.peek() {
+            Some(next) if ch == '\\' && next.is_ascii_punctuation() => {
+                unescaped.push(*next);
+                chars.next();
+            }
+            _ => unescaped.push(ch),
+        }
+    }
+    unescaped
+}
+
+/// Matches the delimiter runs of `inlines` into [`Inline::Formatted`], following the emphasis
+/// rules of CommonMark. Unmatched delimiters are left as they are.
+fn process_emphasis(inlines: &mut Vec<Inline>) {
+    let mut closer = 0;
+    while closer < inlines.len() {
+        let Inline::Delim {
+            ch,
+            count: closer_count,
+            can_open: closer_can_open,
+            can_close: true,
+        } = inlines[closer]
+        else {
+            closer += 1;
+            continue;
+        };
+        let opener = (0..closer).rev().find(|&i| match inlines[i] {
+            Inline::Delim {
+                ch: opener_ch,
+                count,
+                can_open: true,
+                can_close: opener_can_close,
+            } => {
+                opener_ch == ch
+                    && (ch != '~' || count == closer_count)
+                    // The "multiple of 3" rule
+                    && (!(opener_can_close || closer_can_open)
+                        || (count + closer_count) % 3 != 0
+                        || (count % 3 == 0 && closer_count % 3 == 0))
+            }
+            _ => false,
+        });
+        let Some(opener) = opener else {
+            closer += 1;
+            continue;
+        };
+        let Inline::Delim {
+            count: opener_count,
+            ..
+        } = inlines[opener]
+        else {
+            unreachable!()
+        };
+        let (format, used) = if ch == '~' {
+            (Format::Strikethrough, closer_count)
+        } else if opener_count >= 2 && closer_count >= 2 {
+            (Format::Strong, 2)
+        } else {
+            (Format::Emphasis, 1)
+        };
+
+        let formatted: Vec<Inline> = inlines.drain(opener + 1..closer).collect();
+        inlines.insert(opener + 1, Inline::Formatted(format, formatted));
+        closer = opener + 2;
+        for i in [opener, closer] {
+            if let Inline::Delim { count, .. } = &mut inlines[i] {
+                *count -= used;
+            }
+        }
+        if matches!(inlines[closer], Inline::Delim { count: 0, .. }) {
+            inlines.remove(closer);
+        }
+        if matches!(inlines[opener], Inline::Delim { count: 0, .. }) {
+            inlines.remove(opener);
+            closer -= 1;
+        }
+    }
+}
+
+prop!(pub LinkColor: Option<Color> {} = None);
+prop!(pub CodeColor: Option<Color> {} = None);
+prop!(pub CodeBackground: Option<Brush> {} = None);
+prop!(pub CodeFontFamily: String {} = "monospace".to_string());
+prop!(pub EmphasisColor: Option<Color> {} = None);
+
+prop_extractor! {
+    Extractor {
+        color: TextColor,
+        line_height: LineHeight,
+        text_align: TextAlignProp,
+        letter_spacing: LetterSpacing,
+        word_spacing: WordSpacing,
+    }
+}
+
+prop_extractor! {
+    MarkupStyle {
+        link_color: LinkColor,
+        code_color: CodeColor,
+        code_background: CodeBackground,
+        code_font_family: CodeFontFamily,
+        emphasis_color: EmphasisColor,
+    }
+}
+
+style_class!(
+    /// The style class that is applied to markup text.
+    pub MarkupTextClass
+);
+
+/// A view that displays text with inline Markdown formatting. See [`markup_text`].
+pub struct MarkupText {
+    id: ViewId,
+    rich_text: RichText,
+    markup: Markup,
+    on_link: Option<Box<dyn Fn(&str)>>,
+    /// The index of the span of the link the pointer was pressed on
+    pressed_link: Option<usize>,
+    font: FontProps,
+    style: Extractor,
+    markup_style: MarkupStyle,
+}
+
+/// A view that displays text with inline Markdown formatting, parsed with [`Markup::parse`].
+///
+/// Links are underlined and call the [`MarkupText::on_link`] callback with their destination
+/// when clicked. The colors of links, inline code and emphasis can be set with
+/// [`MarkupText::markup_style`] and default to the colors of the theme.
+///
+/// ### Example
+/// ```rust
+/// # use floem::views::*;
+/// markup_text("Hello **world**, see the [docs](https://docs.rs/floem) or run `cargo doc`")
+///     .on_link(|url| println!("open {url}"));
+/// ```
+pub fn markup_text(markup: impl AsRef<str>) -> MarkupText {
+    MarkupText::new(Markup::parse(markup.as_ref()))
+}
+
+impl MarkupText {
+    pub(crate) fn new(markup: Markup) -> Self {
+        let id = ViewId::new();
+        let mut markup_text = MarkupText {
+            id,
+            rich_text: RichText::new(id, TextLayout::new()),
+            markup,
+            on_link: None,
+            pressed_link: None,
+            font: FontProps::default(),
+            style: Default::default(),
+            markup_style: Default::default(),
+        };
+        markup_text
+            .rich_text
+            .set_text_layout(markup_text.text_layout());
+        markup_text.class(MarkupTextClass)
+    }
+
+    /// Sets the callback that is called with the destination of a link when it is clicked.
+    pub fn on_link(mut self, on_link: impl Fn(&str) + 'static) -> Self {
+        self.on_link = Some(Box::new(on_link));
+        self
+    }
+
+    /// Sets the custom style properties of the `MarkupText`.
+    pub fn markup_style(
+        self,
+        style: impl Fn(MarkupTextCustomStyle) -> MarkupTextCustomStyle + 'static,
+    ) -> Self {
+        self.custom_style(style)
+    }
+
+    fn text_layout(&self) -> TextLayout {
+        let mut attrs = Attrs::new().color(self.style.color().unwrap_or(palette::css::BLACK));
+        if let Some(font_size) = self.font.size() {
+            attrs = attrs.font_size(font_size);
+        }
+        if let Some(font_style) = self.font.style() {
+            attrs = attrs.style(font_style);
+        }
+        let font_family = self
+            .font
+            .family()
+            .map(|family| FamilyOwned::parse_list(&family).collect::<Vec<_>>());
+        if let Some(font_family) = font_family.as_ref() {
+            attrs = attrs.family(font_family);
+        }
+        if let Some(font_weight) = self.font.weight() {
+            attrs = attrs.weight(font_weight);
+        }
+        if let Some(line_height) = self.style.line_height() {
+            attrs = attrs.line_height(line_height);
+        }
+        attrs = attrs
+            .letter_spacing(self.style.letter_spacing())
+            .word_spacing(self.style.word_spacing());
+
+        let code_family: Vec<FamilyOwned> =
+            FamilyOwned::parse_list(&self.markup_style.code_font_family()).collect();
+        let mut attrs_list = AttrsList::new(attrs.clone());
+        for span in self.markup.spans() {
+            let mut attrs = attrs.clone();
+            let mut decoration = TextDecorationLine::NONE;
+            if span.strong || span.emphasis {
+                if let Some(color) = self.markup_style.emphasis_color() {
+                    attrs = attrs.color(color);
+                }
+            }
+            if span.strong {
+                attrs = attrs.weight(Weight::BOLD);
+            }
+            if span.emphasis {
+                attrs = attrs.style(crate::text::Style::Italic);
+            }
+            if span.strikethrough {
+                decoration = decoration.union(TextDecorationLine::LINE_THROUGH);
+            }
+            if span.code {
+                attrs = attrs.family(&code_family);
+                if let Some(color) = self.markup_style.code_color() {
+                    attrs = attrs.color(color);
+                }
+            }
+            if span.link.is_some() {
+                decoration = decoration.union(TextDecorationLine::UNDERLINE);
+                if let Some(color) = self.markup_style.link_color() {
+                    attrs = attrs.color(color);
+                }
+            }
+            if let Some(color) = span.color {
+                attrs = attrs.color(color);
+            }
+            if !decoration.is_none() {
+                attrs = attrs.decoration(TextDecoration::new(decoration));
+            }
+            attrs_list.add_span(span.range.clone(), attrs);
+        }
+
+        let mut text_layout = TextLayout::new();
+        text_layout.set_text(self.markup.text(), attrs_list, self.style.text_align());
+        text_layout
+    }
+
+    /// The index of the span of the link at `point`, which is relative to the view.
+    fn link_at(&self, point: Point) -> Option<usize> {
+        let origin = self.rich_text.text_location();
+        let text_layout = self.rich_text.effective_text_layout();
+        self.markup.spans().iter().position(|span| {
+            span.link.is_some()
+                && range_rects(text_layout, span.range.clone(), origin)
+                    .iter()
+                    .any(|rect| rect.contains(point))
+        })
+    }
+}
+
+impl View for MarkupText {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        format!("MarkupText: {:?}", self.markup.text()).into()
+    }
+
+    fn accessibility(&self) -> Option<Accessibility> {
+        Some(Accessibility::new(Role::Label).value(self.markup.text()))
+    }
+
+    fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
+        if self.font.read(cx) | self.style.read(cx) | self.markup_style.read(cx) {
+            let text_layout = self.text_layout();
+            self.rich_text.set_text_layout(text_layout);
+        }
+    }
+
+    fn event_before_children(&mut self, cx: &mut EventCx, event: &Event) -> EventPropagation {
+        match event {
+            Event::Pointer(PointerEvent::Down(PointerButtonEvent {
+                button: Some(PointerButton::Primary),
+                state,
+                ..
+            })) => {
+                self.pressed_link = self.link_at(state.logical_point());
+            }
+            Event::Pointer(PointerEvent::Move(pu)) => {
+                if self.link_at(pu.current.logical_point()).is_some()
+                    && cx.window_state.cursor.is_none()
+                {
+                    cx.window_state.cursor = Some(CursorStyle::Pointer);
+                }
+            }
+            Event::Pointer(PointerEvent::Up(PointerButtonEvent { state, .. })) => {
+                if let Some(index) = self.pressed_link.take() {
+                    let span = &self.markup.spans()[index];
+                    if let (Some(on_link), Some(destination), true) = (
+                        &self.on_link,
+                        &span.link,
+                        self.link_at(state.logical_point()) == Some(index),
+                    ) {
+                        on_link(destination);
+                        return EventPropagation::Stop;
+                    }
+                }
+            }
+            _ => {}
+        }
+        EventPropagation::Continue
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> taffy::tree::NodeId {
+        self.rich_text.layout(cx)
+    }
+
+    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
+        self.rich_text.compute_layout(cx)
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        if let Some(background) = self.markup_style.code_background() {
+            let origin = self.rich_text.text_location();
+            let text_layout = self.rich_text.effective_text_layout();
+            for span in self.markup.spans().iter().filter(|span| span.code) {
+                for rect in range_rects(text_layout, span.range.clone(), origin) {
+                    cx.fill(
+                        &rect.inflate(2.0, 0.0).to_rounded_rect(3.0),
+                        &background,
+                        0.0,
+                    );
+                }
+            }
+        }
+        self.rich_text.paint(cx);
+    }
+
+    fn selectable_text(&mut self) -> Option<&mut dyn SelectableText> {
+        self.rich_text.selectable_text()
+    }
+}
+
+/// Represents a custom style for a [`MarkupText`].
+#[derive(Debug, Default, Clone)]
+pub struct MarkupTextCustomStyle(Style);
+impl From<MarkupTextCustomStyle> for Style {
+    fn from(val: MarkupTextCustomStyle) -> Self {
+        val.0
+    }
+}
+impl From<Style> for MarkupTextCustomStyle {
+    fn from(val: Style) -> Self {
+        Self(val)
+    }
+}
+impl CustomStyle for MarkupTextCustomStyle {
+    type StyleClass = MarkupTextClass;
+}
+
+impl CustomStylable<MarkupTextCustomStyle> for MarkupText {
+    type DV = Self;
+}
+
+impl MarkupTextCustomStyle {
+    /// Creates a new `MarkupTextCustomStyle`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the color of links.
+    pub fn link_color(mut self, color: impl Into<Color>) -> Self {
+        self = Self(self.0.set(LinkColor, Some(color.into())));
+        self
+    }
+
+    /// Sets the color of inline code.
+    pub fn code_color(mut self, color: impl Into<Color>) -> Self {
+        self = Self(self.0.set(CodeColor, Some(color.into())));
+        self
+    }
+
+    /// Sets the background behind inline code.
+    pub fn code_background(mut self, background: impl Into<Brush>) -> Self {
+        self = Self(self.0.set(CodeBackground, Some(background.into())));
+        self
+    }
+
+    /// Sets the font family of inline code, `monospace` by default.
+    pub fn code_font_family(mut self, family: impl Into<String>) -> Self {
+        self = Self(self.0.set(CodeFontFamily, family.into()));
+        self
+    }
+
+    /// Sets the color of emphasized and strongly emphasized text.
+    pub fn emphasis_color(mut self, color: impl Into<Color>) -> Self {
+        self = Self(self.0.set(EmphasisColor, Some(color.into())));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(markup: &str) -> Vec<(String, &'static str, Option<String>)> {
+        let markup = Markup::parse(markup);
+        markup
+            .spans()
+            .iter()
+            .map(|span| {
+                let format = match (span.strong, span.emphasis, span.strikethrough, span.code) {
+                    (true, true, _, _) => "strong emphasis",
+                    (true, _, _, _) => "strong",
+                    (_, true, _, _) => "emphasis",
+                    (_, _, true, _) => "strikethrough",
+                    (_, _, _, true) => "code",
+                    _ => "",
+                };
+                (
+                    markup.text()[span.range.clone()].to_string(),
+                    format,
+                    span.link.clone(),
+                )
+            })
+            .collect()
+    }
+
+    fn span(text: &str, format: &'static str) -> (String, &'static str, Option<String>) {
+        (text.to_string(), format, None)
+    }
+
+    #[test]
+    fn emphasis() {
+        assert_eq!(
+            spans("Hello **world** and *you*"),
+            vec![
+                span("Hello ", ""),
+                span("world", "strong"),
+                span(" and ", ""),
+                span("you", "emphasis"),
+            ]
+        );
+        assert_eq!(
+            spans("***both*** ~~gone~~"),
+            vec![
+                span("both", "strong emphasis"),
+                span(" ", ""),
+                span("gone", "strikethrough"),
+            ]
+        );
+    }
+
+    #[test]
+    fn unmatched_delimiters_are_text() {
+        assert_eq!(spans("2 * 3 * 4"), vec![span("2 * 3 * 4", "")]);
+        assert_eq!(spans("snake_case_name"), vec![span("snake_case_name", "")]);
+        assert_eq!(spans("**open"), vec![span("**open", "")]);
+        assert_eq!(
+            spans(r"\*escaped\* [not a link]"),
+            vec![span("*escaped* [not a link]", "")]
+        );
+    }
+
+    #[test]
+    fn code() {
+        assert_eq!(
+            spans("run `cargo **doc**` now"),
+            vec![
+                span("run ", ""),
+                span("cargo **doc**", "code"),
+                span(" now", ""),
+            ]
+        );
+        assert_eq!(spans("`` a`b ``"), vec![span("a`b", "code")]);
+    }
+
+    #[test]
+    fn links() {
+        assert_eq!(
+            spans("see [the *docs*](https://docs.rs \"Docs\") or <https://floem.dev>"),
+            vec![
+                span("see ", ""),
+                ("the ".to_string(), "", Some("https://docs.rs".to_string())),
+                (
+                    "docs".to_string(),
+                    "emphasis",
+                    Some("https://docs.rs".to_string())
+                ),
+                span(" or ", ""),
+                (
+                    "https://floem.dev".to_string(),
+                    "",
+                    Some("https://floem.dev".to_string())
+                ),
+            ]
+        );
+    }
+}
@@ -73,6 +73,17 @@ pub use label::*;
 mod rich_text;
 pub use rich_text::*;
 
+mod selectable_area;
+pub use selectable_area::*;
+
+mod markup_text;
+pub use markup_text::*;
+
+#[cfg(feature = "markdown")]
+mod markdown;
+#[cfg(feature = "markdown")]
+pub use markdown::*;
+
 mod dyn_stack;
 pub use dyn_stack::*;
 
@@ -91,6 +102,12 @@ pub use dyn_container::*;
 mod dyn_view;
 pub use dyn_view::*;
 
+mod suspense;
+pub use suspense::*;
+
+mod error_boundary;
+pub use error_boundary::*;
+
 mod value_container;
 pub use value_container::*;
 
@@ -106,6 +123,12 @@ pub use virtual_list::*;
 mod virtual_stack;
 pub use virtual_stack::*;
 
+mod table;
+pub use table::*;
+
+mod tree_view;
+pub use tree_view::*;
+
 pub mod scroll;
 pub use scroll::{Scroll, ScrollExt, scroll};
 
@@ -121,6 +144,27 @@ pub use stack::*;
 mod text_input;
 pub use text_input::*;
 
+mod number_input;
+pub use number_input::*;
+
+mod calendar;
+pub use calendar::*;
+
+mod date_picker;
+pub use date_picker::*;
+
+mod time_picker;
+pub use time_picker::*;
+
+mod color_picker;
+pub use color_picker::*;
+
+mod dialog;
+pub use dialog::*;
+
+mod toast;
+pub use toast::*;
+
 mod empty;
 pub use empty::*;
 
@@ -148,6 +192,11 @@ pub mod text_editor;
 #[cfg(feature = "editor")]
 pub use text_editor::*;
 
+#[cfg(feature = "editor")]
+mod text_area;
+#[cfg(feature = "editor")]
+pub use text_area::*;
+
 pub mod dropdown;
 
 pub mod slider;
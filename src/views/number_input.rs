@@ -0,0 +1,574 @@
+#![deny(missing_docs)]
+
+//! A numeric input with stepper buttons and drag-to-scrub. See [`number_input`].
+
+use std::ops::RangeInclusive;
+
+use floem_reactive::{RwSignal, SignalGet, SignalUpdate, SignalWith, create_effect};
+use peniko::Brush;
+use ui_events::{
+    ScrollDelta,
+    keyboard::{Key, KeyState, KeyboardEvent, Modifiers, NamedKey},
+    pointer::{PointerButton, PointerButtonEvent, PointerEvent, PointerScrollEvent, PointerUpdate},
+};
+
+use super::{Decorators, label, svg, text_input, v_stack};
+use crate::{
+    accessibility::{Accessibility, Action, ActionData, NumericValue, Role},
+    context::{EventCx, StyleCx},
+    event::{Event, EventListener, EventPropagation},
+    id::ViewId,
+    prop, prop_extractor,
+    style::{CustomStylable, CustomStyle, Style},
+    style_class,
+    view::{IntoView, View},
+};
+
+style_class!(
+    /// The style class that is applied to all [`NumberInput`] views.
+    pub NumberInputClass
+);
+style_class!(
+    /// The style class that is applied to the scrub label of a [`NumberInput`].
+    pub NumberInputLabelClass
+);
+style_class!(
+    /// The style class that is applied to the increment and decrement buttons of a
+    /// [`NumberInput`].
+    pub NumberInputButtonClass
+);
+
+prop!(
+    /// The distance in pixels the pointer has to be dragged over the label of a [`NumberInput`]
+    /// to change its value by one step.
+    pub ScrubPixelsPerStep: f64 {} = 4.0
+);
+
+prop_extractor! {
+    ScrubStyle {
+        pixels_per_step: ScrubPixelsPerStep,
+    }
+}
+
+const CHEVRON_UP: &str = r#"<svg width="16" height="16" viewBox="0 0 16 16" fill="currentColor"><path d="M4.427 9.573l3.396-3.396a.25.25 0 01.354 0l3.396 3.396a.25.25 0 01-.177.427H4.604a.25.25 0 01-.177-.427z"/></svg>"#;
+const CHEVRON_DOWN: &str = r#"<svg width="16" height="16" viewBox="0 0 16 16" fill="currentColor"><path d="M4.427 6.427l3.396 3.396a.25.25 0 00.354 0l3.396-3.396A.25.25 0 0011.396 6H4.604a.25.25 0 00-.177.427z"/></svg>"#;
+
+/// How many steps a single step is multiplied by while Shift is held.
+const LARGE_STEP: f64 = 10.0;
+/// The scroll distance in pixels that changes the value by one step.
+const WHEEL_PIXELS_PER_STEP: f64 = 40.0;
+/// Values can't be shown with more decimals than an `f64` holds.
+const MAX_PRECISION: usize = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct NumberConfig {
+    min: f64,
+    max: f64,
+    step: f64,
+    precision: Option<usize>,
+    integer_digits: usize,
+}
+
+impl Default for NumberConfig {
+    fn default() -> Self {
+        Self {
+            min: f64::NEG_INFINITY,
+            max: f64::INFINITY,
+            step: 1.0,
+            precision: None,
+            integer_digits: 1,
+        }
+    }
+}
+
+impl NumberConfig {
+    /// The number of decimals of the value, which defaults to the number of decimals of the step.
+    fn precision(&self) -> usize {
+        self.precision
+            .unwrap_or_else(|| {
+                let step = self.step.abs().to_string();
+                step.split_once('.')
+                    .map_or(0, |(_, decimals)| decimals.len())
+            })
+            .min(MAX_PRECISION)
+    }
+
+    /// Rounds `value` to the precision and clamps it to the range.
+    fn normalize(&self, value: f64) -> f64 {
+        let factor = 10f64.powi(self.precision() as i32);
+        let rounded = (value * factor).round() / factor;
+        let value = if rounded.is_finite() { rounded } else { value };
+        // adding zero turns a negative zero into a positive one, which isn't shown as "-0"
+        value.max(self.min).min(self.max) + 0.0
+    }
+
+    fn stepped(&self, value: f64, steps: f64) -> f64 {
+        self.normalize(value + steps * self.step)
+    }
+
+    fn format(&self, value: f64) -> String {
+        let precision = self.precision();
+        let width = self.integer_digits + if precision > 0 { precision + 1 } else { 0 };
+        let sign = if value < 0.0 { "-" } else { "" };
+        format!("{sign}{:0width$.precision$}", value.abs())
+    }
+
+    fn parse(&self, text: &str) -> Option<f64> {
+        text.trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .map(|value| self.normalize(value))
+    }
+
+    fn bound(value: f64) -> Option<f64> {
+        value.is_finite().then_some(value)
+    }
+}
+
+fn step_multiplier(modifiers: Modifiers) -> f64 {
+    if modifiers.contains(Modifiers::SHIFT) {
+        LARGE_STEP
+    } else {
+        1.0
+    }
+}
+
+/// A numeric input, with buttons, the arrow keys and the mouse wheel stepping its value and
+/// an optional label that changes the value when dragged horizontally.
+///
+/// The text is parsed, rounded to the precision and clamped to the range when it is committed
+/// with Enter or when the input loses focus. Text that isn't a number reverts to the current value.
+///
+/// See [`number_input`].
+pub struct NumberInput {
+    id: ViewId,
+    value: RwSignal<f64>,
+    config: RwSignal<NumberConfig>,
+    label: RwSignal<String>,
+    input_id: ViewId,
+    wheel_delta: f64,
+}
+
+/// Creates a [`NumberInput`] editing `value`.
+///
+/// The value is stepped by `1` by default, and has no bounds.
+/// ```
+/// # use floem::prelude::*;
+/// let opacity = RwSignal::new(100.0);
+/// number_input(opacity)
+///     .range(0.0..=100.0)
+///     .step(5.0)
+///     .label("Opacity");
+/// ```
+/// ### Reactivity
+/// The text is updated whenever `value` changes.
+pub fn number_input(value: RwSignal<f64>) -> NumberInput {
+    let id = ViewId::new();
+    let config = RwSignal::new(NumberConfig::default());
+    let label_text = RwSignal::new(String::new());
+    let text = RwSignal::new(String::new());
+
+    create_effect(move |_| {
+        let formatted = config.with(|config| config.format(value.get()));
+        text.set(formatted);
+        id.request_accessibility();
+    });
+
+    let commit = move || {
+        let config = config.get_untracked();
+        if let Some(parsed) = text.with_untracked(|text| config.parse(text)) {
+            if parsed != value.get_untracked() {
+                value.set(parsed);
+            }
+        }
+        text.set(config.format(value.get_untracked()));
+    };
+
+    // steps from the text being typed, if it is a number
+    let step = move |steps: f64| {
+        let config = config.get_untracked();
+        let current = text
+            .with_untracked(|text| config.parse(text))
+            .unwrap_or_else(|| value.get_untracked());
+        let stepped = config.stepped(current, steps);
+        if stepped != value.get_untracked() {
+            value.set(stepped);
+        }
+        text.set(config.format(stepped));
+    };
+
+    let input = text_input(text)
+        .char_filter(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E'))
+        .on_enter(commit)
+        .on_event_cont(EventListener::FocusLost, move |_| commit())
+        .on_event(EventListener::KeyDown, move |event| {
+            let Event::Key(KeyboardEvent {
+                state: KeyState::Down,
+                key: Key::Named(key),
+                modifiers,
+                ..
+            }) = event
+            else {
+                return EventPropagation::Continue;
+            };
+            let steps = match key {
+                NamedKey::ArrowUp => step_multiplier(*modifiers),
+                NamedKey::ArrowDown => -step_multiplier(*modifiers),
+                NamedKey::PageUp => LARGE_STEP,
+                NamedKey::PageDown => -LARGE_STEP,
+                _ => return EventPropagation::Continue,
+            };
+            step(steps);
+            EventPropagation::Stop
+        })
+        .style(|s| s.flex_grow(1.0).flex_basis(0.0).min_width(0.0));
+    let input_id = input.id();
+
+    let scrub = ScrubLabel {
+        id: ViewId::new(),
+        value,
+        config,
+        text,
+        input_id,
+        drag: None,
+        style: Default::default(),
+    };
+    scrub.id.set_children([label(move || label_text.get())]);
+    let scrub = scrub
+        .class(NumberInputLabelClass)
+        .style(move |s| s.apply_if(label_text.with(String::is_empty), |s| s.hide()));
+
+    let buttons = v_stack((
+        svg(CHEVRON_UP)
+            .class(NumberInputButtonClass)
+            .on_click_stop(move |_| step(1.0)),
+        svg(CHEVRON_DOWN)
+            .class(NumberInputButtonClass)
+            .on_click_stop(move |_| step(-1.0)),
+    ))
+    .style(|s| s.justify_center());
+
+    id.set_children([scrub.into_any(), input.into_any(), buttons.into_any()]);
+
+    NumberInput {
+        id,
+        value,
+        config,
+        label: label_text,
+        input_id,
+        wheel_delta: 0.0,
+    }
+    .class(NumberInputClass)
+}
+
+impl NumberInput {
+    /// Sets the smallest value.
+    pub fn min(self, min: f64) -> Self {
+        self.config.update(|config| config.min = min);
+        self
+    }
+
+    /// Sets the largest value.
+    pub fn max(self, max: f64) -> Self {
+        self.config.update(|config| config.max = max);
+        self
+    }
+
+    /// Sets the smallest and largest values.
+    pub fn range(self, range: RangeInclusive<f64>) -> Self {
+        self.min(*range.start()).max(*range.end())
+    }
+
+    /// Sets the amount the value changes by for each step of the buttons, the arrow keys, the
+    /// mouse wheel and scrubbing. Holding Shift makes steps ten times larger.
+    pub fn step(self, step: f64) -> Self {
+        self.config.update(|config| config.step = step);
+        self
+    }
+
+    /// Sets the number of decimals of the value.
+    ///
+    /// By default, the value has as many decimals as the [`step`](Self::step).
+    pub fn precision(self, decimals: usize) -> Self {
+        self.config
+            .update(|config| config.precision = Some(decimals));
+        self
+    }
+
+    /// Pads the integer part of the value with leading zeros to at least `digits` digits,
+    /// such as `2` to show `5` as `05`.
+    pub fn integer_digits(self, digits: usize) -> Self {
+        self.config.update(|config| config.integer_digits = digits);
+        self
+    }
+
+    /// Shows a label before the input which changes the value when dragged horizontally, like
+    /// in design tools. Clicking the label without dragging focuses the input.
+    pub fn label(self, label: impl Into<String>) -> Self {
+        self.label.set(label.into());
+        self
+    }
+
+    /// Sets the custom style properties of the `NumberInput`.
+    pub fn number_input_style(
+        self,
+        style: impl Fn(NumberInputCustomStyle) -> NumberInputCustomStyle + 'static,
+    ) -> Self {
+        self.custom_style(style)
+    }
+}
+
+impl View for NumberInput {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Number Input".into()
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::new().flex_row().items_center())
+    }
+
+    fn accessibility(&self) -> Option<Accessibility> {
+        let config = self.config.get_untracked();
+        Some(
+            Accessibility::new(Role::SpinButton)
+                .numeric_value(NumericValue {
+                    value: self.value.get_untracked(),
+                    min: NumberConfig::bound(config.min),
+                    max: NumberConfig::bound(config.max),
+                    step: Some(config.step),
+                })
+                .action(Action::Increment)
+                .action(Action::Decrement)
+                .action(Action::SetValue),
+        )
+    }
+
+    fn event_before_children(&mut self, cx: &mut EventCx, event: &Event) -> EventPropagation {
+        let config = self.config.get_untracked();
+        let value = self.value.get_untracked();
+        let new_value = match event {
+            // the wheel only steps the value while the input is focused, so that it doesn't
+            // get in the way of scrolling past it
+            Event::Pointer(PointerEvent::Scroll(PointerScrollEvent { delta, state, .. }))
+                if cx.window_state.is_focused(&self.input_id) =>
+            {
+                let steps = match delta {
+                    ScrollDelta::LineDelta(_, y) => *y as f64,
+                    ScrollDelta::PixelDelta(_) => {
+                        let delta = event.pixel_scroll_delta_vec2().unwrap_or_default();
+                        delta.y / WHEEL_PIXELS_PER_STEP
+                    }
+                    _ => 0.0,
+                };
+                self.wheel_delta += steps;
+                let steps = self.wheel_delta.trunc();
+                self.wheel_delta -= steps;
+                if steps != 0.0 {
+                    self.value
+                        .set(config.stepped(value, steps * step_multiplier(state.modifiers)));
+                }
+                return EventPropagation::Stop;
+            }
+            Event::Accessibility(request) => match (request.action, &request.data) {
+                (Action::Increment, _) => config.stepped(value, 1.0),
+                (Action::Decrement, _) => config.stepped(value, -1.0),
+                (Action::SetValue, Some(ActionData::NumericValue(new_value))) => {
+                    config.normalize(*new_value)
+                }
+                _ => return EventPropagation::Continue,
+            },
+            _ => return EventPropagation::Continue,
+        };
+        if new_value != value {
+            self.value.set(new_value);
+        }
+        EventPropagation::Stop
+    }
+}
+
+/// The label of a [`NumberInput`], changing its value when dragged.
+struct ScrubLabel {
+    id: ViewId,
+    value: RwSignal<f64>,
+    config: RwSignal<NumberConfig>,
+    text: RwSignal<String>,
+    input_id: ViewId,
+    /// The pointer position and value when the drag started, and whether it moved a step.
+    drag: Option<(f64, f64, bool)>,
+    style: ScrubStyle,
+}
+
+impl View for ScrubLabel {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Number Input Label".into()
+    }
+
+    fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
+        self.style.read(cx);
+        for child in self.id.children() {
+            cx.style_view(child);
+        }
+    }
+
+    fn event_before_children(&mut self, cx: &mut EventCx, event: &Event) -> EventPropagation {
+        match event {
+            Event::Pointer(PointerEvent::Down(PointerButtonEvent {
+                button: Some(PointerButton::Primary),
+                state,
+                ..
+            })) => {
+                cx.update_active(self.id);
+                // start from the text being typed, if it is a number
+                let config = self.config.get_untracked();
+                let start = self
+                    .text
+                    .with_untracked(|text| config.parse(text))
+                    .unwrap_or_else(|| self.value.get_untracked());
+                self.drag = Some((state.logical_point().x, start, false));
+                EventPropagation::Stop
+            }
+            Event::Pointer(PointerEvent::Move(PointerUpdate { current, .. })) => {
+                let Some((start_x, start_value, moved)) = &mut self.drag else {
+                    return EventPropagation::Continue;
+                };
+                let distance = current.logical_point().x - *start_x;
+                let steps = (distance / self.style.pixels_per_step().max(1.0)).trunc();
+                if steps != 0.0 {
+                    *moved = true;
+                }
+                if *moved {
+                    let config = self.config.get_untracked();
+                    let value =
+                        config.stepped(*start_value, steps * step_multiplier(current.modifiers));
+                    if value != self.value.get_untracked() {
+                        self.value.set(value);
+                    }
+                    self.text.set(config.format(value));
+                }
+                EventPropagation::Stop
+            }
+            Event::Pointer(PointerEvent::Up(_)) => match self.drag.take() {
+                Some((_, _, moved)) => {
+                    if !moved {
+                        self.input_id.request_focus();
+                    }
+                    EventPropagation::Stop
+                }
+                None => EventPropagation::Continue,
+            },
+            _ => EventPropagation::Continue,
+        }
+    }
+}
+
+/// Represents a custom style for a [`NumberInput`].
+#[derive(Debug, Default, Clone)]
+pub struct NumberInputCustomStyle(Style);
+impl From<NumberInputCustomStyle> for Style {
+    fn from(val: NumberInputCustomStyle) -> Self {
+        val.0
+    }
+}
+impl From<Style> for NumberInputCustomStyle {
+    fn from(val: Style) -> Self {
+        Self(val)
+    }
+}
+impl CustomStyle for NumberInputCustomStyle {
+    type StyleClass = NumberInputClass;
+}
+
+impl CustomStylable<NumberInputCustomStyle> for NumberInput {
+    type DV = Self;
+}
+
+impl NumberInputCustomStyle {
+    /// Creates a new, empty custom style for a [`NumberInput`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the distance in pixels the label has to be dragged to change the value by one step.
+    pub fn scrub_pixels_per_step(mut self, pixels: f64) -> Self {
+        self = Self(
+            self.0
+                .class(NumberInputLabelClass, |s| s.set(ScrubPixelsPerStep, pixels)),
+        );
+        self
+    }
+
+    /// Sets the text color of the label.
+    pub fn label_color(mut self, color: impl Into<Brush>) -> Self {
+        self = Self(self.0.class(NumberInputLabelClass, |s| s.color(color)));
+        self
+    }
+
+    /// Sets whether the increment and decrement buttons are shown.
+    pub fn show_buttons(mut self, show: bool) -> Self {
+        self = Self(
+            self.0
+                .class(NumberInputButtonClass, |s| s.apply_if(!show, |s| s.hide())),
+        );
+        self
+    }
+
+    /// Sets the color of the increment and decrement buttons.
+    pub fn button_color(mut self, color: impl Into<Brush>) -> Self {
+        self = Self(self.0.class(NumberInputButtonClass, |s| s.color(color)));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NumberConfig;
+
+    #[test]
+    fn precision_follows_step() {
+        let config = NumberConfig {
+            step: 0.25,
+            ..Default::default()
+        };
+        assert_eq!(config.precision(), 2);
+        assert_eq!(config.format(1.0), "1.00");
+        assert_eq!(config.stepped(0.1, 1.0), 0.35);
+
+        let config = NumberConfig {
+            precision: Some(1),
+            ..config
+        };
+        assert_eq!(config.format(1.0), "1.0");
+
+        let config = NumberConfig {
+            integer_digits: 2,
+            ..config
+        };
+        assert_eq!(config.format(5.0), "05.0");
+        assert_eq!(config.format(-5.0), "-05.0");
+        assert_eq!(config.format(123.0), "123.0");
+    }
+
+    #[test]
+    fn parse_rounds_and_clamps() {
+        let config = NumberConfig {
+            min: -10.0,
+            max: 10.0,
+            ..Default::default()
+        };
+        assert_eq!(config.parse(" 4.6 "), Some(5.0));
+        assert_eq!(config.parse("42"), Some(10.0));
+        assert_eq!(config.parse("-1e3"), Some(-10.0));
+        assert_eq!(config.parse("abc"), None);
+        assert_eq!(config.parse("inf"), None);
+        assert_eq!(config.format(config.normalize(-0.2)), "0");
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     IntoView,
+    accessibility::{Accessibility, Action, Role},
     style::StyleSelector,
     style_class,
     view::View,
@@ -29,6 +30,20 @@ where
         .class(RadioButtonClass)
 }
 
+fn radio_button_accessibility<T>(
+    represented_value: T,
+    actual_value: impl SignalGet<T> + 'static,
+) -> impl Fn() -> Accessibility + 'static
+where
+    T: Eq + PartialEq + Clone + 'static,
+{
+    move || {
+        Accessibility::new(Role::RadioButton)
+            .toggled(actual_value.get() == represented_value)
+            .action(Action::Click)
+    }
+}
+
 /// The `RadioButton` struct provides various methods to create and manage radio buttons.
 ///
 /// # Related Functions
@@ -51,6 +66,10 @@ impl RadioButton {
         value_container(
             radio_button_svg(represented_value.clone(), inbound_signal.read_only())
                 .style(|s| s.focusable(true))
+                .accessibility(radio_button_accessibility(
+                    represented_value.clone(),
+                    inbound_signal.read_only(),
+                ))
                 .on_click_stop(move |_| {
                     outbound_signal.set(represented_value.clone());
                 }),
@@ -70,10 +89,13 @@ impl RadioButton {
         T: Eq + PartialEq + Clone + 'static,
     {
         let clone = represented_value.clone();
-        radio_button_svg(represented_value, actual_value).style(move |s| {
-            s.focusable(true)
-                .apply_if(clone == actual_value.get(), |s| s.set_selected(true))
-        })
+        let accessibility = radio_button_accessibility(represented_value.clone(), actual_value);
+        radio_button_svg(represented_value, actual_value)
+            .style(move |s| {
+                s.focusable(true)
+                    .apply_if(clone == actual_value.get(), |s| s.set_selected(true))
+            })
+            .accessibility(accessibility)
     }
 
     /// Creates a new radio button with a signal that provides and updates its selected state.
@@ -97,6 +119,7 @@ impl RadioButton {
                         s.set_selected(true)
                     })
             })
+            .accessibility(radio_button_accessibility(represented_value, actual_value))
             .on_click_stop(move |_| {
                 actual_value.set(cloneable_represented_value.clone());
             })
@@ -116,6 +139,8 @@ impl RadioButton {
     {
         let (inbound_signal, outbound_signal) = create_value_container_signals(actual_value);
         let clone = represented_value.clone();
+        let accessibility =
+            radio_button_accessibility(represented_value.clone(), inbound_signal.read_only());
 
         value_container(
             h_stack((
@@ -130,6 +155,7 @@ impl RadioButton {
                         s.apply_selectors(&[StyleSelector::Selected])
                     })
             })
+            .accessibility(accessibility)
             .on_click_stop(move |_| {
                 outbound_signal.set(represented_value.clone());
             }),
@@ -150,6 +176,7 @@ impl RadioButton {
         T: Eq + PartialEq + Clone + 'static,
     {
         let clone = represented_value.clone();
+        let accessibility = radio_button_accessibility(represented_value.clone(), actual_value);
         h_stack((
             radio_button_svg(represented_value, actual_value),
             views::label(label),
@@ -160,6 +187,7 @@ impl RadioButton {
                 .focusable(true)
                 .apply_if(clone == actual_value.get(), |s| s.set_selected(true))
         })
+        .accessibility(accessibility)
     }
 
     /// Creates a new labeled radio button with a signal that provides and updates its selected state.
@@ -188,6 +216,7 @@ impl RadioButton {
                 |s| s.set_selected(true),
             )
         })
+        .accessibility(radio_button_accessibility(represented_value, actual_value))
         .on_click_stop(move |_| {
             actual_value.set(cloneable_represented_value.clone());
         })
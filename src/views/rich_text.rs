@@ -22,29 +22,77 @@ use crate::{
     view::View,
 };
 
+use super::{SelectableText, TextSelection, selectable_area::index_at};
+
 pub struct RichText {
     id: ViewId,
     text_layout: TextLayout,
+    /// The text of the layout, with its line endings
+    text: String,
     text_node: Option<NodeId>,
     text_overflow: TextOverflow,
     available_width: Option<f32>,
     available_text_layout: Option<TextLayout>,
+    /// The selection of a [`selectable_area`](super::selectable_area) around the text
+    area_selection: Option<TextSelection>,
 }
 
 pub fn rich_text(text_layout: impl Fn() -> TextLayout + 'static) -> RichText {
     let id = ViewId::new();
-    let text = text_layout();
+    let text_layout_value = text_layout();
     create_effect(move |_| {
         let new_text_layout = text_layout();
         id.update_state(new_text_layout);
     });
-    RichText {
-        id,
-        text_layout: text,
-        text_node: None,
-        text_overflow: TextOverflow::Wrap,
-        available_width: None,
-        available_text_layout: None,
+    RichText::new(id, text_layout_value)
+}
+
+fn layout_text(text_layout: &TextLayout) -> String {
+    text_layout
+        .lines()
+        .iter()
+        .map(|line| format!("{}{}", line.text(), line.ending().as_str()))
+        .collect()
+}
+
+impl RichText {
+    pub(crate) fn new(id: ViewId, text_layout: TextLayout) -> Self {
+        RichText {
+            id,
+            text: layout_text(&text_layout),
+            text_layout,
+            text_node: None,
+            text_overflow: TextOverflow::Wrap,
+            available_width: None,
+            available_text_layout: None,
+            area_selection: None,
+        }
+    }
+
+    pub(crate) fn set_text_layout(&mut self, text_layout: TextLayout) {
+        self.text_layout = text_layout;
+        self.text = layout_text(&self.text_layout);
+        self.area_selection = None;
+        self.available_width = None;
+        self.available_text_layout = None;
+        self.id.request_layout();
+    }
+
+    /// The layout of the text, wrapped to the available width.
+    pub(crate) fn effective_text_layout(&self) -> &TextLayout {
+        self.available_text_layout
+            .as_ref()
+            .unwrap_or(&self.text_layout)
+    }
+
+    /// The location of the text in the view.
+    pub(crate) fn text_location(&self) -> Point {
+        let location = self
+            .text_node
+            .and_then(|node| self.id.taffy().borrow().layout(node).ok().copied())
+            .unwrap_or_default()
+            .location;
+        Point::new(location.x as f64, location.y as f64)
     }
 }
 
@@ -67,10 +115,7 @@ impl View for RichText {
 
     fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn Any>) {
         if let Ok(state) = state.downcast() {
-            self.text_layout = *state;
-            self.available_width = None;
-            self.available_text_layout = None;
-            self.id.request_layout();
+            self.set_text_layout(*state);
         }
     }
 
@@ -144,21 +189,34 @@ impl View for RichText {
     }
 
     fn paint(&mut self, cx: &mut crate::context::PaintCx) {
-        let text_node = self.text_node.unwrap();
-        let location = self
-            .id
-            .taffy()
-            .borrow_mut()
-            .layout(text_node)
-            .cloned()
-            .unwrap_or_default()
-            .location;
-        let point = Point::new(location.x as f64, location.y as f64);
-        if let Some(text_layout) = self.available_text_layout.as_ref() {
-            cx.draw_text(text_layout, point);
-        } else {
-            cx.draw_text(&self.text_layout, point);
+        let point = self.text_location();
+        let text_layout = self.effective_text_layout();
+        if let Some(selection) = &self.area_selection {
+            selection.paint(cx, text_layout, point);
         }
+        cx.draw_text(text_layout, point);
+    }
+
+    fn selectable_text(&mut self) -> Option<&mut dyn SelectableText> {
+        Some(self)
+    }
+}
+
+impl SelectableText for RichText {
+    fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn hit_index(&self, point: Point) -> usize {
+        let location = self.text_location();
+        let text_layout = self.effective_text_layout();
+        text_layout
+            .hit((point.x - location.x) as f32, (point.y - location.y) as f32)
+            .map_or(0, |cursor| index_at(text_layout, cursor))
+    }
+
+    fn set_selection(&mut self, selection: Option<TextSelection>) {
+        self.area_selection = selection;
     }
 }
 
@@ -12,6 +12,7 @@ use crate::style::{
 use crate::unit::PxPct;
 use crate::{
     Renderer,
+    accessibility::{Accessibility, Action, Role},
     context::{ComputeLayoutCx, PaintCx},
     event::{Event, EventPropagation},
     id::ViewId,
@@ -477,6 +478,7 @@ impl Scroll {
             self.child.set_viewport(child_viewport);
             window_state.request_compute_layout_recursive(self.id());
             window_state.request_paint(self.id());
+            self.id.request_accessibility();
             self.child_viewport = child_viewport;
             // Mark as scrolling when viewport changes
             self.is_scrolling_or_interacting = true;
@@ -831,6 +833,16 @@ impl View for Scroll {
         "Scroll".into()
     }
 
+    fn accessibility(&self) -> Option<Accessibility> {
+        let origin = self.child_viewport.origin();
+        Some(
+            Accessibility::new(Role::ScrollView)
+                .scroll_offset(origin.x, origin.y)
+                .action(Action::ScrollUp)
+                .action(Action::ScrollDown),
+        )
+    }
+
     fn view_style(&self) -> Option<Style> {
         Some(
             Style::new()
@@ -1063,6 +1075,20 @@ impl View for Scroll {
             }
         }
 
+        if let Event::Accessibility(request) = &event {
+            // Scrolls by a page
+            let page = self.child_viewport.height();
+            let delta = match request.action {
+                Action::ScrollUp => Some(Vec2::new(0.0, -page)),
+                Action::ScrollDown => Some(Vec2::new(0.0, page)),
+                _ => None,
+            };
+            if let Some(delta) = delta {
+                self.do_scroll_delta(cx.window_state, delta);
+                return EventPropagation::Stop;
+            }
+        }
+
         EventPropagation::Continue
     }
 
@@ -0,0 +1,555 @@
+//! A container that selects text across the labels and rich text inside it.
+#![deny(missing_docs)]
+use std::ops::Range;
+
+use floem_renderer::text::{Cursor, TextLayout};
+use peniko::{
+    Brush,
+    kurbo::{Point, Rect},
+};
+use ui_events::{
+    keyboard::{KeyState, KeyboardEvent},
+    pointer::{PointerButton, PointerButtonEvent, PointerEvent},
+};
+
+use crate::{
+    Clipboard, Renderer,
+    context::{EventCx, PaintCx, StyleCx},
+    event::{Event, EventPropagation},
+    id::ViewId,
+    style::{SelectionStyle, Style},
+    view::{IntoView, View},
+};
+
+use super::{TextCommand, get_dbl_click_selection};
+
+/// A view with text that a [`selectable_area`] can select, see [`View::selectable_text`].
+pub trait SelectableText {
+    /// The text of the view.
+    fn text(&self) -> &str;
+
+    /// The byte index in the text closest to `point`, which is relative to the view.
+    fn hit_index(&self, point: Point) -> usize;
+
+    /// Highlights a range of the text, or nothing if `selection` is `None`.
+    fn set_selection(&mut self, selection: Option<TextSelection>);
+}
+
+/// A highlighted range of the text of a view, see [`SelectableText::set_selection`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextSelection {
+    /// The byte range of the selected text
+    pub range: Range<usize>,
+    /// The color of the highlight
+    pub color: Brush,
+    /// The corner radius of the highlight
+    pub corner_radius: f64,
+}
+
+impl TextSelection {
+    /// Fills the highlight of the selection, with `text_layout` drawn at `origin`.
+    pub(crate) fn paint(&self, cx: &mut PaintCx, text_layout: &TextLayout, origin: Point) {
+        for rect in range_rects(text_layout, self.range.clone(), origin) {
+            cx.fill(&rect.to_rounded_rect(self.corner_radius), &self.color, 0.0);
+        }
+    }
+}
+
+/// The rects of the lines of a byte range of the text of a layout drawn at `origin`.
+pub(crate) fn range_rects(
+    text_layout: &TextLayout,
+    range: Range<usize>,
+    origin: Point,
+) -> Vec<Rect> {
+    let start = cursor_at(text_layout, range.start);
+    let end = cursor_at(text_layout, range.end);
+    text_layout
+        .layout_runs()
+        .filter_map(|run| {
+            let (start_x, width) = run.highlight(start, end)?;
+            let x0 = origin.x + start_x as f64;
+            let y0 = origin.y + run.line_top as f64;
+            Some(Rect::new(
+                x0,
+                y0,
+                x0 + width as f64,
+                y0 + run.line_height as f64,
+            ))
+        })
+        .collect()
+}
+
+/// The cursor at a byte index of the text of a layout.
+pub(crate) fn cursor_at(text_layout: &TextLayout, index: usize) -> Cursor {
+    let lines = text_layout.lines_range();
+    let line = lines
+        .iter()
+        .rposition(|range| range.start <= index)
+        .unwrap_or(0);
+    let start = lines.get(line).map_or(0, |range| range.start);
+    Cursor::new(line, index - start)
+}
+
+/// The byte index of a cursor in the text of a layout.
+pub(crate) fn index_at(text_layout: &TextLayout, cursor: Cursor) -> usize {
+    text_layout
+        .lines_range()
+        .get(cursor.line)
+        .map_or(0, |range| (range.start + cursor.index).min(range.end))
+}
+
+/// A position in the text of the views of a [`SelectableArea`]: the index of the view in
+/// document order and a byte index in its text.
+type TextPosition = (usize, usize);
+
+#[derive(Debug, Clone, Copy)]
+enum DragState {
+    None,
+    Ready(Point),
+    Selecting,
+}
+
+/// A container that selects text across its descendants. See [`selectable_area`].
+pub struct SelectableArea {
+    id: ViewId,
+    /// The descendants with selectable text in document order, collected when a selection starts
+    texts: Vec<ViewId>,
+    anchor: Option<TextPosition>,
+    focus: Option<TextPosition>,
+    drag: DragState,
+    selection_style: SelectionStyle,
+}
+
+/// A container that lets the user select the text of every [`Label`](super::Label) and
+/// [`RichText`](super::RichText) inside it, like the text of a web page.
+///
+/// Dragging selects the text in document order across views, double-clicking selects a word
+/// and triple-clicking a paragraph. `Ctrl+A` (`Cmd+A` on macOS) selects all of the text and
+/// `Ctrl+C` (`Cmd+C`) copies the selected text, with a line break between views.
+///
+/// The selection is highlighted with the [`SelectionStyle`] of the area. The text views inside
+/// it are not selectable on their own.
+///
+/// ### Example
+/// ```rust
+/// # use floem::views::*;
+/// selectable_area(v_stack((
+///     text("Select me"),
+///     text("and me too"),
+/// )));
+/// ```
+pub fn selectable_area<V: IntoView + 'static>(child: V) -> SelectableArea {
+    let id = ViewId::new();
+    id.set_children([child.into_view()]);
+    SelectableArea {
+        id,
+        texts: Vec::new(),
+        anchor: None,
+        focus: None,
+        drag: DragState::None,
+        selection_style: Default::default(),
+    }
+}
+
+fn collect_texts(id: ViewId, texts: &mut Vec<ViewId>) {
+    for child in id.children() {
+        if child.is_hidden() {
+            continue;
+        }
+        if child.view().borrow_mut().selectable_text().is_some() {
+            texts.push(child);
+        }
+        collect_texts(child, texts);
+    }
+}
+
+fn with_text<R>(id: ViewId, f: impl FnOnce(&mut dyn SelectableText) -> R) -> Option<R> {
+    let view = id.view();
+    let mut view = view.borrow_mut();
+    view.selectable_text().map(f)
+}
+
+fn text_len(id: ViewId) -> usize {
+    with_text(id, |text| text.text().len()).unwrap_or(0)
+}
+
+/// The rect of a view in window coordinates.
+fn window_rect(id: ViewId) -> Rect {
+    let origin = id.state().borrow().window_origin;
+    id.get_size()
+        .unwrap_or_default()
+        .to_rect()
+        .with_origin(origin)
+}
+
+impl SelectableArea {
+    /// The text position closest to `point`, in window coordinates.
+    fn hit(&self, point: Point) -> Option<TextPosition> {
+        // The last text that starts before the point
+        let mut before = None;
+        for (i, id) in self.texts.iter().enumerate() {
+            let rect = window_rect(*id);
+            if rect.contains(point) {
+                let local = point - rect.origin().to_vec2();
+                return Some((i, with_text(*id, |text| text.hit_index(local))?));
+            }
+            if rect.y0 <= point.y && (rect.y1 <= point.y || rect.x0 <= point.x) {
+                before = Some((i, rect));
+            }
+        }
+        match before {
+            Some((i, rect)) if point.y < rect.y1 => {
+                let local = point - rect.origin().to_vec2();
+                Some((i, with_text(self.texts[i], |text| text.hit_index(local))?))
+            }
+            Some((i, _)) => Some((i, text_len(self.texts[i]))),
+            None => (!self.texts.is_empty()).then_some((0, 0)),
+        }
+    }
+
+    fn ordered(&self) -> Option<(TextPosition, TextPosition)> {
+        let (anchor, focus) = (self.anchor?, self.focus?);
+        Some(if anchor <= focus {
+            (anchor, focus)
+        } else {
+            (focus, anchor)
+        })
+    }
+
+    /// The selected range of the text of the view at `index`.
+    fn range_of(&self, index: usize) -> Option<Range<usize>> {
+        let (start, end) = self.ordered()?;
+        if index < start.0 || index > end.0 {
+            return None;
+        }
+        let from = if index == start.0 { start.1 } else { 0 };
+        let to = if index == end.0 {
+            end.1
+        } else {
+            text_len(self.texts[index])
+        };
+        (from < to).then_some(from..to)
+    }
+
+    /// Sends the selected ranges to the text views.
+    fn update_selection(&self) {
+        let color = self.selection_style.selection_color();
+        let corner_radius = self.selection_style.corner_radius();
+        for (i, id) in self.texts.iter().enumerate() {
+            let selection = self.range_of(i).map(|range| TextSelection {
+                range,
+                color: color.clone(),
+                corner_radius,
+            });
+            with_text(*id, |text| text.set_selection(selection));
+            id.request_paint();
+        }
+    }
+
+    fn clear_selection(&mut self) {
+        self.anchor = None;
+        self.focus = None;
+        self.update_selection();
+    }
+
+    fn select_all(&mut self) {
+        self.texts.clear();
+        collect_texts(self.id, &mut self.texts);
+        if let Some(last) = self.texts.len().checked_sub(1) {
+            self.anchor = Some((0, 0));
+            self.focus = Some((last, text_len(self.texts[last])));
+        }
+        self.update_selection();
+    }
+
+    /// The selected text, with a line break between views.
+    fn selected_text(&self) -> String {
+        let mut selected = Vec::new();
+        for (i, id) in self.texts.iter().enumerate() {
+            if let Some(range) = self.range_of(i) {
+                if let Some(text) =
+                    with_text(*id, |text| text.text().get(range).map(String::from)).flatten()
+                {
+                    selected.push(text);
+                }
+            }
+        }
+        selected.join("\n")
+    }
+
+    fn handle_pointer_down(&mut self, point: Point, count: u8) {
+        self.clear_selection();
+        self.texts.clear();
+        collect_texts(self.id, &mut self.texts);
+        self.drag = DragState::Ready(point);
+        if count < 2 {
+            return;
+        }
+        let window_point = point + self.id.state().borrow().window_origin.to_vec2();
+        let Some((i, index)) = self.hit(window_point) else {
+            return;
+        };
+        let range = with_text(self.texts[i], |text| {
+            let text = text.text();
+            if count == 2 {
+                get_dbl_click_selection(index, text)
+            } else {
+                // The paragraph is the line of the text around the index
+                let start = text[..index].rfind('\n').map_or(0, |i| i + 1);
+                let end = text[index..].find('\n').map_or(text.len(), |i| index + i);
+                start..end
+            }
+        });
+        if let Some(range) = range {
+            self.anchor = Some((i, range.start));
+            self.focus = Some((i, range.end));
+            self.update_selection();
+            self.id.request_focus();
+        }
+    }
+}
+
+impl View for SelectableArea {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "SelectableArea".into()
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        // The area selects the text instead of the views inside it
+        Some(Style::new().selectable(false))
+    }
+
+    fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
+        if self.selection_style.read(cx) && self.anchor.is_some() {
+            self.update_selection();
+        }
+        for child in self.id.children() {
+            cx.style_view(child);
+        }
+    }
+
+    fn event_before_children(&mut self, cx: &mut EventCx, event: &Event) -> EventPropagation {
+        match event {
+            Event::Pointer(PointerEvent::Down(PointerButtonEvent {
+                button: Some(PointerButton::Primary),
+                state,
+                ..
+            })) => {
+                self.handle_pointer_down(state.logical_point(), state.count);
+            }
+            Event::Pointer(PointerEvent::Move(pu)) => {
+                let point = pu.current.logical_point();
+                match self.drag {
+                    // this check is here to make it so that text selection doesn't eat pointer
+                    // events on very small move events
+                    DragState::Ready(start) if start.distance(point) > 2. => {
+                        let origin = self.id.state().borrow().window_origin.to_vec2();
+                        self.anchor = self.hit(start + origin);
+                        self.focus = self.hit(point + origin);
+                        self.drag = DragState::Selecting;
+                        self.update_selection();
+                        self.id.request_active();
+                        self.id.request_focus();
+                    }
+                    DragState::Selecting => {
+                        let origin = self.id.state().borrow().window_origin.to_vec2();
+                        self.focus = self.hit(point + origin);
+                        self.update_selection();
+                        return EventPropagation::Stop;
+                    }
+                    _ => {}
+                }
+            }
+            Event::Pointer(PointerEvent::Up { .. }) => {
+                if matches!(self.drag, DragState::Selecting) && cx.is_active(self.id) {
+                    self.id.clear_active();
+                }
+                self.drag = DragState::None;
+            }
+            Event::Key(
+                ke @ KeyboardEvent {
+                    state: KeyState::Down,
+                    ..
+                },
+            ) => match TextCommand::from(ke) {
+                TextCommand::SelectAll => {
+                    self.select_all();
+                    return EventPropagation::Stop;
+                }
+                TextCommand::Copy if self.anchor.is_some() => {
+                    let _ = Clipboard::set_contents(self.selected_text());
+                    return EventPropagation::Stop;
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        EventPropagation::Continue
+    }
+
+    fn event_after_children(&mut self, cx: &mut EventCx, event: &Event) -> EventPropagation {
+        // Clicking text that no child handled focuses the area so that the keyboard shortcuts
+        // work
+        if let Event::Pointer(PointerEvent::Down(_)) = event {
+            if cx.window_state.focus.is_none() && !self.texts.is_empty() {
+                self.id.request_focus();
+            }
+        }
+        EventPropagation::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+    use crate::{
+        unit::UnitExt,
+        views::{h_stack, v_stack},
+    };
+
+    const CHAR_WIDTH: f64 = 10.0;
+    const LINE_HEIGHT: f64 = 20.0;
+
+    type Selection = Rc<RefCell<Option<Range<usize>>>>;
+
+    /// Text with a fixed width per byte, so that hit-testing doesn't depend on fonts.
+    struct FakeText {
+        id: ViewId,
+        text: String,
+        selection: Selection,
+    }
+
+    fn fake_text(text: &str) -> (FakeText, Selection) {
+        let selection = Selection::default();
+        let view = FakeText {
+            id: ViewId::new(),
+            text: text.to_string(),
+            selection: selection.clone(),
+        };
+        (view, selection)
+    }
+
+    impl View for FakeText {
+        fn id(&self) -> ViewId {
+            self.id
+        }
+
+        fn view_style(&self) -> Option<Style> {
+            let columns = self.text.lines().map(str::len).max().unwrap_or(0);
+            let lines = self.text.lines().count().max(1);
+            Some(
+                Style::new()
+                    .width((columns as f64 * CHAR_WIDTH).px())
+                    .height((lines as f64 * LINE_HEIGHT).px()),
+            )
+        }
+
+        fn selectable_text(&mut self) -> Option<&mut dyn SelectableText> {
+            Some(self)
+        }
+    }
+
+    impl SelectableText for FakeText {
+        fn text(&self) -> &str {
+            &self.text
+        }
+
+        fn hit_index(&self, point: Point) -> usize {
+            let line = (point.y / LINE_HEIGHT).floor().max(0.0) as usize;
+            let mut start = 0;
+            for (i, text) in self.text.split('\n').enumerate() {
+                if i == line {
+                    let column = (point.x / CHAR_WIDTH).round().max(0.0) as usize;
+                    return start + column.min(text.len());
+                }
+                start += text.len() + 1;
+            }
+            self.text.len()
+        }
+
+        fn set_selection(&mut self, selection: Option<TextSelection>) {
+            *self.selection.borrow_mut() = selection.map(|selection| selection.range);
+        }
+    }
+
+    #[test]
+    fn selected_text_joins_the_texts_in_document_order() {
+        let (a, _) = fake_text("hello");
+        let (b, _) = fake_text("big");
+        let (c, _) = fake_text("wide");
+        let (d, _) = fake_text("world");
+        let mut area = selectable_area(v_stack((a, h_stack((b, c)), d)));
+
+        area.select_all();
+        assert_eq!(area.selected_text(), "hello\nbig\nwide\nworld");
+
+        // The anchor can come after the focus
+        area.anchor = Some((1, 2));
+        area.focus = Some((0, 1));
+        assert_eq!(area.selected_text(), "ello\nbi");
+    }
+
+    #[cfg(feature = "testing")]
+    mod window {
+        use ui_events::pointer::PointerButton;
+
+        use super::*;
+        use crate::testing::TestWindow;
+
+        #[test]
+        fn dragging_selects_across_texts() {
+            let (a, a_selection) = fake_text("hello");
+            let (b, b_selection) = fake_text("world");
+            let (c, c_selection) = fake_text("again");
+            let (a_id, c_id) = (a.id, c.id);
+            let mut window =
+                TestWindow::new((300.0, 200.0), move || selectable_area(v_stack((a, b, c))));
+            let a_rect = window.rect(a_id);
+            let c_rect = window.rect(c_id);
+
+            window.pointer_down(a_rect.origin() + (20.0, 10.0), PointerButton::Primary, 1);
+            window.pointer_move(c_rect.origin() + (30.0, 10.0));
+            assert_eq!(*a_selection.borrow(), Some(2..5));
+            assert_eq!(*b_selection.borrow(), Some(0..5));
+            assert_eq!(*c_selection.borrow(), Some(0..3));
+
+            // Right of the last text on its row selects up to its end
+            window.pointer_move(c_rect.origin() + (250.0, 10.0));
+            assert_eq!(*c_selection.borrow(), Some(0..5));
+
+            // Above the first text, the selection goes backwards to its start
+            window.pointer_move(c_rect.origin() + (30.0, 10.0));
+            window.pointer_move(a_rect.origin() + (0.0, 5.0));
+            assert_eq!(*a_selection.borrow(), Some(0..2));
+            assert_eq!(*b_selection.borrow(), None);
+            assert_eq!(*c_selection.borrow(), None);
+            window.pointer_up(a_rect.origin(), PointerButton::Primary, 1);
+        }
+
+        #[test]
+        fn double_and_triple_clicks_select_words_and_paragraphs() {
+            let (text, selection) = fake_text("one two\nthree four");
+            let id = text.id;
+            let mut window = TestWindow::new((300.0, 200.0), move || selectable_area(text));
+            let origin = window.rect(id).origin();
+
+            window.double_click(origin + (50.0, 10.0));
+            assert_eq!(*selection.borrow(), Some(4..7));
+
+            let on_second_line = origin + (20.0, 30.0);
+            window.pointer_down(on_second_line, PointerButton::Primary, 3);
+            window.pointer_up(on_second_line, PointerButton::Primary, 3);
+            assert_eq!(*selection.borrow(), Some(8..18));
+
+            // A single click clears the selection
+            window.click(on_second_line);
+            assert_eq!(*selection.borrow(), None);
+        }
+    }
+}
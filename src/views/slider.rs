@@ -13,6 +13,7 @@ use crate::style::{BorderRadiusProp, CustomStyle};
 use crate::unit::Pct;
 use crate::{
     Renderer,
+    accessibility::{Accessibility, Action, ActionData, NumericValue, Role},
     event::EventPropagation,
     id::ViewId,
     prop, prop_extractor,
@@ -135,6 +136,22 @@ impl View for Slider {
         self.id
     }
 
+    fn accessibility(&self) -> Option<Accessibility> {
+        let value_range = self.range.end() - self.range.start();
+        Some(
+            Accessibility::new(Role::Slider)
+                .numeric_value(NumericValue {
+                    value: self.range.start() + value_range * (self.percent / 100.0),
+                    min: Some(*self.range.start()),
+                    max: Some(*self.range.end()),
+                    step: self.step,
+                })
+                .action(Action::Increment)
+                .action(Action::Decrement)
+                .action(Action::SetValue),
+        )
+    }
+
     fn update(&mut self, _cx: &mut crate::context::UpdateCx, state: Box<dyn std::any::Any>) {
         if let Ok(update) = state.downcast::<SliderUpdate>() {
             match *update {
@@ -208,6 +225,27 @@ impl View for Slider {
                     false
                 }
             }
+            crate::event::Event::Accessibility(request) => match (request.action, &request.data) {
+                (Action::Increment, _) => {
+                    self.id.request_layout();
+                    self.percent += 10.;
+                    true
+                }
+                (Action::Decrement, _) => {
+                    self.id.request_layout();
+                    self.percent -= 10.;
+                    true
+                }
+                (Action::SetValue, Some(ActionData::NumericValue(value))) => {
+                    self.id.request_layout();
+                    let value_range = self.range.end() - self.range.start();
+                    if value_range != 0.0 {
+                        self.percent = (value - self.range.start()) / value_range * 100.0;
+                    }
+                    true
+                }
+                _ => false,
+            },
             _ => false,
         };
 
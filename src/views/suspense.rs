@@ -0,0 +1,137 @@
+#![deny(missing_docs)]
+
+use floem_reactive::{
+    ReadSignal, RwSignal, Scope, SignalGet, SignalUpdate, SignalWith, use_context, with_scope,
+};
+
+use crate::{
+    id::ViewId,
+    view::{IntoView, View},
+    views::{Decorators, error_boundary::register_error_source},
+};
+
+/// The async sources read inside the closest [`suspense`].
+#[derive(Clone, Copy)]
+struct SuspenseContext {
+    sources: RwSignal<Vec<ReadSignal<bool>>>,
+}
+
+impl SuspenseContext {
+    fn register(&self, finished: ReadSignal<bool>) {
+        if !self.sources.with_untracked(|s| s.contains(&finished)) {
+            self.sources.update(|s| s.push(finished));
+        }
+    }
+
+    fn is_pending(&self) -> bool {
+        // Sources that were disposed with the view that read them are not pending anymore
+        self.sources
+            .with(|s| s.iter().any(|finished| finished.try_get() == Some(false)))
+    }
+}
+
+/// Lets the closest [`suspense`] and [`error_boundary`](super::error_boundary) know that an
+/// async source was read, with the signal tracking whether it finished and the trigger used
+/// to run it again.
+pub(crate) fn register_async_read(finished: ReadSignal<bool>, refetch: Option<RwSignal<u64>>) {
+    if let Some(cx) = use_context::<SuspenseContext>() {
+        cx.register(finished);
+    }
+    if let Some(refetch) = refetch {
+        register_error_source(refetch);
+    }
+}
+
+/// A view that shows a fallback while async data read by its child is loading. See [`suspense`].
+pub struct Suspense {
+    id: ViewId,
+}
+
+/// A view that shows `fallback` while any [`Resource`](crate::receiver_signal::Resource),
+/// [`FutureSignal`](crate::receiver_signal::FutureSignal) or
+/// [`AsyncMemo`](crate::receiver_signal::AsyncMemo) read inside `child` is pending.
+///
+/// Sources are picked up when they are read while building `child`, or later from effects
+/// and dynamic views created inside it. The child stays mounted while the fallback is shown,
+/// so the effects that read the pending sources keep running.
+///
+/// ## Example
+/// ```rust,ignore
+/// let user = Resource::new(move || user_id.get(), fetch_user);
+///
+/// suspense("Loading...", move || {
+///     label(move || user.get().map(|u| u.name).unwrap_or_default())
+/// })
+/// ```
+pub fn suspense<F: IntoView + 'static, V: IntoView + 'static>(
+    fallback: F,
+    child: impl FnOnce() -> V,
+) -> Suspense {
+    let id = ViewId::new();
+
+    let scope = Scope::current().create_child();
+    let cx = SuspenseContext {
+        sources: scope.create_rw_signal(Vec::new()),
+    };
+    scope.provide_context(cx);
+    let pending = scope.create_memo(move |_| cx.is_pending());
+
+    let child = with_scope(scope, || child().into_any())
+        .style(move |s| s.apply_if(pending.get(), |s| s.hide()));
+    let fallback = fallback
+        .into_any()
+        .style(move |s| s.apply_if(!pending.get(), |s| s.hide()));
+    id.set_children([child, fallback]);
+
+    Suspense { id }
+}
+
+impl View for Suspense {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Suspense".into()
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use floem_reactive::SignalWith;
+
+    use crate::{prelude::*, receiver_signal::AsyncMemo, testing::TestWindow};
+
+    /// A memo whose future never completes, as its executor drops it.
+    fn pending() -> AsyncMemo<i32, String> {
+        AsyncMemo::custom(|| async {
+            std::future::pending::<()>().await;
+            Ok(1)
+        })
+        .executor(|_, _, _, _| {})
+        .build()
+    }
+
+    fn window(read: impl Fn(AsyncMemo<i32, String>) -> bool + 'static) -> TestWindow {
+        TestWindow::new((100.0, 100.0), move || {
+            let memo = pending();
+            suspense(label(|| "Loading").debug_name("fallback"), move || {
+                label(move || read(memo).to_string()).debug_name("content")
+            })
+        })
+    }
+
+    #[test]
+    fn tracked_reads_show_the_fallback() {
+        let window = window(|memo| memo.with(|value| value.is_some()));
+        assert!(window.find("content").unwrap().is_hidden());
+        assert!(!window.find("fallback").unwrap().is_hidden());
+    }
+
+    #[test]
+    fn untracked_reads_do_not_show_the_fallback() {
+        let window = window(|memo| memo.with_untracked(|value| value.is_some()));
+        assert!(!window.find("content").unwrap().is_hidden());
+        assert!(window.find("fallback").unwrap().is_hidden());
+    }
+}
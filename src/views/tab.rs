@@ -5,6 +5,7 @@ use floem_reactive::{Scope, as_child_of_current_scope, create_effect};
 use smallvec::SmallVec;
 
 use crate::{
+    accessibility::{Accessibility, Role},
     context::{StyleCx, UpdateCx},
     id::ViewId,
     style_class,
@@ -250,6 +251,10 @@ impl<T> View for Tab<T> {
         format!("Tab: {:?}", self.active).into()
     }
 
+    fn accessibility(&self) -> Option<Accessibility> {
+        Some(Accessibility::new(Role::TabPanel))
+    }
+
     fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn std::any::Any>) {
         if let Ok(state) = state.downcast::<TabState<T>>() {
             match *state {
@@ -0,0 +1,1284 @@
+#![deny(missing_docs)]
+
+//! A virtualized data table. See [`table`].
+
+use std::{
+    any::Any,
+    cmp::Ordering,
+    collections::{BTreeSet, HashMap},
+    rc::Rc,
+};
+
+use floem_reactive::{
+    RwSignal, Scope, SignalGet, SignalTrack, SignalUpdate, SignalWith, as_child_of_current_scope,
+    create_effect,
+};
+use peniko::{
+    Brush,
+    kurbo::{Line, Point, Rect, Stroke},
+};
+use ui_events::{
+    keyboard::{Key, KeyState, KeyboardEvent, Modifiers, NamedKey},
+    pointer::{PointerButton, PointerButtonEvent, PointerEvent, PointerState, PointerUpdate},
+};
+
+use super::{
+    Decorators, clip, h_stack, label,
+    resizable::{HandleColor, HandleThickness, HitExt},
+};
+use crate::{
+    AnyView,
+    accessibility::{Accessibility, Role},
+    context::{ComputeLayoutCx, EventCx, PaintCx, StyleCx, UpdateCx},
+    event::{Event, EventPropagation},
+    id::ViewId,
+    prop, prop_extractor,
+    style::{CursorStyle, CustomStylable, CustomStyle, Style, StyleClass},
+    style_class,
+    unit::Px,
+    view::{self, IntoView, View},
+    view_state::StackOffset,
+    window_state::WindowState,
+};
+
+style_class!(
+    /// The style class that is applied to all [`Table`] views.
+    pub TableClass
+);
+style_class!(
+    /// The style class that is applied to every body cell of a [`Table`].
+    pub TableCellClass
+);
+style_class!(
+    /// The style class that is applied to every header cell of a [`Table`].
+    pub TableHeaderClass
+);
+
+prop!(
+    /// The height of each body row of a table.
+    pub TableRowHeight: Px {} = Px(28.)
+);
+prop!(
+    /// The height of the header row of a table.
+    pub TableHeaderHeight: Px {} = Px(32.)
+);
+prop!(
+    /// The color of the outline drawn around the focused cell of a table.
+    pub TableFocusColor: Option<Brush> {} = None
+);
+
+prop_extractor! {
+    TableStyle {
+        row_height: TableRowHeight,
+        header_height: TableHeaderHeight,
+        focus_color: TableFocusColor,
+        handle_color: HandleColor,
+        handle_thickness: HandleThickness,
+    }
+}
+
+/// How the width of a [`TableColumn`] is determined.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColumnWidth {
+    /// A fixed width in logical pixels.
+    Fixed(f64),
+    /// A share of the width that is left after all fixed columns are laid out,
+    /// proportional to the given weight.
+    Flex(f64),
+}
+
+/// The direction a [`Table`] is sorted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    /// Smallest values first.
+    Ascending,
+    /// Largest values first.
+    Descending,
+}
+
+/// Which rows of a [`Table`] can be selected by the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableSelectionMode {
+    /// Rows can't be selected.
+    None,
+    /// At most one row can be selected.
+    Single,
+    /// Any number of rows can be selected, using `Ctrl`/`Cmd` to toggle rows
+    /// and `Shift` to select ranges.
+    Multiple,
+}
+
+type CellFn<T> = Rc<dyn Fn(&T) -> AnyView>;
+/// Sorts the indices of rows by the rows they point to.
+type SortFn<T> = Rc<dyn Fn(&[&T], &mut [usize], SortDirection)>;
+
+/// The definition of a single column of a [`Table`].
+pub struct TableColumn<T> {
+    header: AnyView,
+    cell: CellFn<T>,
+    width: ColumnWidth,
+    min_width: f64,
+    resizable: bool,
+    sort: Option<SortFn<T>>,
+}
+
+impl<T: 'static> TableColumn<T> {
+    /// Creates a column with a `header` view and a function building the view of a cell from its row.
+    ///
+    /// Columns are flexible with a weight of `1.0` by default.
+    pub fn new<V: IntoView + 'static>(
+        header: impl IntoView + 'static,
+        cell: impl Fn(&T) -> V + 'static,
+    ) -> Self {
+        Self {
+            header: header.into_any(),
+            cell: Rc::new(move |row: &T| cell(row).into_any()),
+            width: ColumnWidth::Flex(1.),
+            min_width: 30.,
+            resizable: true,
+            sort: None,
+        }
+    }
+
+    /// Gives the column a fixed width.
+    pub fn width(mut self, width: f64) -> Self {
+        self.width = ColumnWidth::Fixed(width);
+        self
+    }
+
+    /// Makes the column take a share of the remaining width, proportional to `weight`.
+    pub fn flex(mut self, weight: f64) -> Self {
+        self.width = ColumnWidth::Flex(weight);
+        self
+    }
+
+    /// Sets the width the column can't be shrunk below, either by flex layout or by resizing.
+    pub fn min_width(mut self, min_width: f64) -> Self {
+        self.min_width = min_width;
+        self
+    }
+
+    /// Sets whether the column can be resized by dragging the right edge of its header.
+    ///
+    /// Columns are resizable by default.
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// Makes the column sortable by clicking its header, ordering rows with `compare`.
+    pub fn sort_by(mut self, compare: impl Fn(&T, &T) -> Ordering + 'static) -> Self {
+        self.sort = Some(Rc::new(move |rows, order, direction| {
+            order.sort_by(|a, b| {
+                let ordering = compare(rows[*a], rows[*b]);
+                match direction {
+                    SortDirection::Ascending => ordering,
+                    SortDirection::Descending => ordering.reverse(),
+                }
+            });
+        }));
+        self
+    }
+
+    /// Makes the column sortable by clicking its header, ordering rows by the key returned by `key`.
+    ///
+    /// The key of each row is computed once per sort.
+    pub fn sort_by_key<K: Ord>(mut self, key: impl Fn(&T) -> K + 'static) -> Self {
+        self.sort = Some(Rc::new(move |rows, order, direction| match direction {
+            SortDirection::Ascending => order.sort_by_cached_key(|row| key(rows[*row])),
+            SortDirection::Descending => {
+                order.sort_by_cached_key(|row| std::cmp::Reverse(key(rows[*row])))
+            }
+        }));
+        self
+    }
+}
+
+struct Column<T> {
+    cell: Box<dyn Fn(T) -> (AnyView, Scope)>,
+    width: ColumnWidth,
+    initial_width: ColumnWidth,
+    min_width: f64,
+    resizable: bool,
+    sort: Option<SortFn<T>>,
+}
+
+struct PlacedCell {
+    id: ViewId,
+    offset: StackOffset<Style>,
+    rect: Rect,
+}
+
+impl PlacedCell {
+    fn new(id: ViewId) -> Self {
+        let state = id.state();
+        let offset = state.borrow_mut().style.next_offset();
+        state.borrow_mut().style.push(Style::new());
+        Self {
+            id,
+            offset,
+            rect: Rect::ZERO,
+        }
+    }
+
+    fn place(&mut self, rect: Rect) {
+        if self.rect != rect {
+            self.rect = rect;
+            self.id.update_style(
+                self.offset,
+                Style::new()
+                    .absolute()
+                    .inset_left(rect.x0)
+                    .inset_top(rect.y0)
+                    .width(rect.width())
+                    .height(rect.height()),
+            );
+        }
+    }
+}
+
+enum TableUpdate<T> {
+    Rows(imbl::Vector<T>),
+    Sort,
+    Selection,
+    Refresh,
+}
+
+enum HandleState {
+    None,
+    Hovered(usize),
+    Active(usize),
+}
+
+#[derive(Debug, PartialEq)]
+enum Hit {
+    Header(usize),
+    Cell(usize, usize),
+}
+
+/// A virtualized data table. See [`table`].
+pub struct Table<T: 'static> {
+    id: ViewId,
+    rows: imbl::Vector<T>,
+    columns: Vec<Column<T>>,
+    /// Maps display rows to data rows while the table is sorted.
+    order: Option<Vec<usize>>,
+    sort: RwSignal<Option<(usize, SortDirection)>>,
+    selection: RwSignal<BTreeSet<usize>>,
+    selection_mode: TableSelectionMode,
+    frozen_columns: usize,
+    frozen_rows: usize,
+    headers: Vec<PlacedCell>,
+    /// The built body cells, keyed by data row and column.
+    cells: HashMap<(usize, usize), (PlacedCell, Scope)>,
+    viewport: Rect,
+    widths: Vec<f64>,
+    column_x: Vec<f64>,
+    /// The focused cell, as display row and column.
+    focused: Option<(usize, usize)>,
+    /// The display row range selections are extended from.
+    anchor: Option<usize>,
+    handle: HandleState,
+    cursor: Option<CursorStyle>,
+    style: TableStyle,
+}
+
+/// Creates a table showing `rows` with the given `columns`.
+///
+/// Only the cells that are visible in the enclosing [scroll view](super::scroll()) are built,
+/// in both directions, so the table stays fast with millions of rows. Every row has the same
+/// height, set with [`TableCustomStyle::row_height`].
+///
+/// The header row always stays at the top of the scroll view, and the first columns and rows
+/// can be kept in place with [`Table::frozen_columns`] and [`Table::frozen_rows`].
+/// Clicking the header of a column that has a comparator cycles between ascending, descending and
+/// unsorted order, and dragging the right edge of a header resizes its column.
+///
+/// Rows are selected with the pointer or the keyboard according to [`Table::selection_mode`],
+/// and the focused cell moves with the arrow, `Home`, `End`, `PageUp` and `PageDown` keys.
+///
+/// ## Example
+/// ```rust
+/// use floem::prelude::*;
+///
+/// #[derive(Clone)]
+/// struct Person {
+///     name: String,
+///     age: usize,
+/// }
+///
+/// let people: floem::imbl::Vector<Person> = (0..1_000_000)
+///     .map(|i| Person {
+///         name: format!("Person {i}"),
+///         age: i % 90,
+///     })
+///     .collect();
+///
+/// table(
+///     move || people.clone(),
+///     [
+///         TableColumn::new("Name", |p: &Person| p.name.clone())
+///             .sort_by_key(|p: &Person| p.name.clone()),
+///         TableColumn::new("Age", |p: &Person| p.age)
+///             .width(60.)
+///             .sort_by_key(|p: &Person| p.age),
+///     ],
+/// )
+/// .frozen_columns(1)
+/// .scroll()
+/// .style(|s| s.size(400., 300.));
+/// ```
+pub fn table<T: Clone + 'static>(
+    rows: impl Fn() -> imbl::Vector<T> + 'static,
+    columns: impl IntoIterator<Item = TableColumn<T>>,
+) -> Table<T> {
+    let id = ViewId::new();
+    let sort = RwSignal::new(None);
+    let selection = RwSignal::new(BTreeSet::new());
+
+    let mut header_views = Vec::new();
+    let columns = columns
+        .into_iter()
+        .enumerate()
+        .map(|(idx, column)| {
+            let indicator = label(move || match sort.get() {
+                Some((col, SortDirection::Ascending)) if col == idx => "▲",
+                Some((col, SortDirection::Descending)) if col == idx => "▼",
+                _ => "",
+            });
+            header_views.push(
+                clip(
+                    h_stack((column.header, indicator))
+                        .style(|s| s.size_full().items_center().gap(4.)),
+                )
+                .class(TableHeaderClass)
+                .into_any(),
+            );
+            let cell = column.cell;
+            Column {
+                cell: Box::new(as_child_of_current_scope(move |row: T| cell(&row))),
+                width: column.width,
+                initial_width: column.width,
+                min_width: column.min_width,
+                resizable: column.resizable,
+                sort: column.sort,
+            }
+        })
+        .collect::<Vec<_>>();
+    let headers = header_views
+        .iter()
+        .map(|header| PlacedCell::new(header.id()))
+        .collect();
+    id.set_children_vec(header_views);
+
+    create_effect(move |_| {
+        id.update_state(TableUpdate::Rows(rows()));
+    });
+    create_effect(move |_| {
+        sort.track();
+        id.update_state(TableUpdate::<T>::Sort);
+    });
+    create_effect(move |_| {
+        selection.track();
+        id.update_state(TableUpdate::<T>::Selection);
+    });
+
+    Table {
+        id,
+        rows: imbl::Vector::new(),
+        columns,
+        order: None,
+        sort,
+        selection,
+        selection_mode: TableSelectionMode::Single,
+        frozen_columns: 0,
+        frozen_rows: 0,
+        headers,
+        cells: HashMap::new(),
+        viewport: Rect::ZERO,
+        widths: Vec::new(),
+        column_x: vec![0.],
+        focused: None,
+        anchor: None,
+        handle: HandleState::None,
+        cursor: None,
+        style: Default::default(),
+    }
+    .keyboard_navigable()
+}
+
+impl<T: Clone + 'static> Table<T> {
+    /// Keeps the first `count` columns in place when scrolling horizontally.
+    pub fn frozen_columns(mut self, count: usize) -> Self {
+        self.frozen_columns = count;
+        self
+    }
+
+    /// Keeps the first `count` rows (in display order) below the header when scrolling vertically.
+    pub fn frozen_rows(mut self, count: usize) -> Self {
+        self.frozen_rows = count;
+        self
+    }
+
+    /// Sets which rows can be selected. Defaults to [`TableSelectionMode::Single`].
+    pub fn selection_mode(mut self, mode: TableSelectionMode) -> Self {
+        self.selection_mode = mode;
+        self
+    }
+
+    /// Returns the selected rows, as indices into the rows given to the table.
+    pub fn selection(&self) -> RwSignal<BTreeSet<usize>> {
+        self.selection
+    }
+
+    /// Returns the column the table is sorted by and the direction it is sorted in, if any.
+    pub fn sort(&self) -> RwSignal<Option<(usize, SortDirection)>> {
+        self.sort
+    }
+
+    /// Sets the custom style properties of the `Table`.
+    pub fn table_style(
+        self,
+        style: impl Fn(TableCustomStyle) -> TableCustomStyle + 'static,
+    ) -> Self {
+        self.custom_style(style)
+    }
+
+    fn row_height(&self) -> f64 {
+        self.style.row_height().0
+    }
+
+    fn header_height(&self) -> f64 {
+        self.style.header_height().0
+    }
+
+    fn frozen_width(&self) -> f64 {
+        self.column_x[self.frozen_columns.min(self.columns.len())]
+    }
+
+    fn frozen_height(&self) -> f64 {
+        self.frozen_rows.min(self.rows.len()) as f64 * self.row_height()
+    }
+
+    fn data_index(&self, display_row: usize) -> usize {
+        self.order
+            .as_ref()
+            .map_or(display_row, |order| order[display_row])
+    }
+
+    fn resolve_widths(&mut self) {
+        let (fixed, flex) =
+            self.columns
+                .iter()
+                .fold((0., 0.), |(fixed, flex), column| match column.width {
+                    ColumnWidth::Fixed(width) => (fixed + width.max(column.min_width), flex),
+                    ColumnWidth::Flex(weight) => (fixed, flex + weight),
+                });
+        let free = (self.viewport.width() - fixed).max(0.);
+        self.widths = self
+            .columns
+            .iter()
+            .map(|column| match column.width {
+                ColumnWidth::Fixed(width) => width.max(column.min_width),
+                ColumnWidth::Flex(weight) if flex > 0. => {
+                    (free * weight / flex).max(column.min_width)
+                }
+                ColumnWidth::Flex(_) => column.min_width,
+            })
+            .collect();
+        self.column_x = std::iter::once(0.)
+            .chain(self.widths.iter().scan(0., |x, width| {
+                *x += width;
+                Some(*x)
+            }))
+            .collect();
+    }
+
+    /// The left edge of a column, taking frozen columns into account.
+    fn column_left(&self, col: usize) -> f64 {
+        if col < self.frozen_columns {
+            self.column_x[col] + self.viewport.x0
+        } else {
+            self.column_x[col]
+        }
+    }
+
+    /// The top edge of a display row, taking frozen rows into account.
+    fn row_top(&self, display_row: usize) -> f64 {
+        let y = self.header_height() + display_row as f64 * self.row_height();
+        if display_row < self.frozen_rows {
+            y + self.viewport.y0
+        } else {
+            y
+        }
+    }
+
+    fn cell_rect(&self, display_row: usize, col: usize) -> Rect {
+        let x = self.column_left(col);
+        let y = self.row_top(display_row);
+        Rect::new(x, y, x + self.widths[col], y + self.row_height())
+    }
+
+    fn header_rect(&self, col: usize) -> Rect {
+        let x = self.column_left(col);
+        let y = self.viewport.y0;
+        Rect::new(x, y, x + self.widths[col], y + self.header_height())
+    }
+
+    /// The part of the viewport a cell can be seen in, which excludes the frozen regions
+    /// that are painted over it.
+    fn cell_region(&self, display_row: usize, col: usize) -> Rect {
+        let x0 = if col < self.frozen_columns {
+            self.viewport.x0
+        } else {
+            self.viewport.x0 + self.frozen_width()
+        };
+        let y0 = if display_row < self.frozen_rows {
+            self.viewport.y0 + self.header_height()
+        } else {
+            self.viewport.y0 + self.header_height() + self.frozen_height()
+        };
+        Rect::new(x0, y0, self.viewport.x1, self.viewport.y1)
+    }
+
+    fn visible_columns(&self) -> Vec<usize> {
+        let frozen = self.frozen_columns.min(self.columns.len());
+        let x0 = self.viewport.x0 + self.frozen_width();
+        (0..frozen)
+            .chain((frozen..self.columns.len()).filter(|col| {
+                self.column_x[col + 1] > x0 && self.column_x[*col] < self.viewport.x1
+            }))
+            .collect()
+    }
+
+    fn visible_rows(&self) -> Vec<usize> {
+        let len = self.rows.len();
+        let frozen = self.frozen_rows.min(len);
+        let row_height = self.row_height();
+        if row_height <= 0. {
+            return (0..frozen).collect();
+        }
+        let start = ((self.viewport.y0 + self.frozen_height()) / row_height).floor() as usize;
+        let end = ((self.viewport.y1 - self.header_height()) / row_height).ceil() as usize;
+        (0..frozen).chain(start.max(frozen)..end.min(len)).collect()
+    }
+
+    fn hit(&self, point: Point) -> Option<Hit> {
+        let frozen = self.frozen_columns.min(self.columns.len());
+        let col = (0..frozen)
+            .find(|col| {
+                let x = self.column_left(*col);
+                point.x >= x && point.x < x + self.widths[*col]
+            })
+            .or_else(|| {
+                if point.x < self.viewport.x0 + self.frozen_width() {
+                    return None;
+                }
+                (frozen..self.columns.len())
+                    .find(|col| point.x >= self.column_x[*col] && point.x < self.column_x[col + 1])
+            })?;
+
+        let header_bottom = self.viewport.y0 + self.header_height();
+        if point.y < header_bottom {
+            return Some(Hit::Header(col));
+        }
+        let row_height = self.row_height();
+        let display_row = if point.y < header_bottom + self.frozen_height() {
+            ((point.y - header_bottom) / row_height).floor() as usize
+        } else {
+            ((point.y - self.header_height()) / row_height).floor() as usize
+        };
+        (display_row < self.rows.len()).then_some(Hit::Cell(display_row, col))
+    }
+
+    fn handle_line(&self, col: usize) -> Line {
+        let x = self.column_left(col) + self.widths[col];
+        Line::new(
+            Point::new(x, self.viewport.y0),
+            Point::new(x, self.viewport.y0 + self.header_height()),
+        )
+    }
+
+    fn find_handle_at_position(&self, point: Point) -> Option<usize> {
+        let hidden_before = self.viewport.x0 + self.frozen_width();
+        (0..self.columns.len()).find(|col| {
+            let line = self.handle_line(*col);
+            self.columns[*col].resizable
+                && (*col < self.frozen_columns || line.p0.x > hidden_before)
+                && line.hit(point, 5.)
+        })
+    }
+
+    /// Builds the cells that became visible, drops the ones that are no longer visible and
+    /// positions all of them.
+    fn sync(&mut self, window_state: &mut WindowState) {
+        self.resolve_widths();
+
+        let columns = self.visible_columns();
+        let mut old_cells = std::mem::take(&mut self.cells);
+        // Scrolling cells, cells in frozen columns, cells in frozen rows and the frozen corner,
+        // in paint order.
+        let mut layers: [Vec<ViewId>; 4] = Default::default();
+        for display_row in self.visible_rows() {
+            let data_row = self.data_index(display_row);
+            for &col in &columns {
+                let (mut cell, scope) = old_cells.remove(&(data_row, col)).unwrap_or_else(|| {
+                    let (view, scope) = (self.columns[col].cell)(self.rows[data_row].clone());
+                    let view = clip(view).class(TableCellClass).into_any();
+                    let id = view.id();
+                    id.set_view(view);
+                    id.set_parent(self.id);
+                    (PlacedCell::new(id), scope)
+                });
+                cell.place(self.cell_rect(display_row, col));
+                let layer = usize::from(display_row < self.frozen_rows) * 2
+                    + usize::from(col < self.frozen_columns);
+                layers[layer].push(cell.id);
+                self.cells.insert((data_row, col), (cell, scope));
+            }
+        }
+        for (cell, scope) in old_cells.into_values() {
+            window_state.remove_view(cell.id);
+            scope.dispose();
+        }
+
+        let rects = (0..self.columns.len())
+            .map(|col| self.header_rect(col))
+            .collect::<Vec<_>>();
+        let mut frozen_headers = Vec::new();
+        let mut headers = Vec::new();
+        for (col, (header, rect)) in self.headers.iter_mut().zip(rects).enumerate() {
+            header.place(rect);
+            if col < self.frozen_columns {
+                frozen_headers.push(header.id);
+            } else {
+                headers.push(header.id);
+            }
+        }
+
+        let children = layers
+            .into_iter()
+            .flatten()
+            .chain(headers)
+            .chain(frozen_headers)
+            .collect();
+        self.id.set_children_ids(children);
+        self.id.request_all();
+    }
+
+    fn clear_cells(&mut self, window_state: &mut WindowState) {
+        for (cell, scope) in std::mem::take(&mut self.cells).into_values() {
+            window_state.remove_view(cell.id);
+            scope.dispose();
+        }
+    }
+
+    fn sort_rows(&mut self) {
+        self.order = self.sort.get_untracked().and_then(|(col, direction)| {
+            let sort = self.columns.get(col)?.sort.clone()?;
+            let rows = self.rows.iter().collect::<Vec<_>>();
+            let mut order = (0..rows.len()).collect::<Vec<_>>();
+            sort(&rows, &mut order, direction);
+            Some(order)
+        });
+    }
+
+    fn toggle_sort(&self, col: usize) {
+        if self.columns[col].sort.is_none() {
+            return;
+        }
+        self.sort.update(|sort| {
+            *sort = match *sort {
+                Some((current, SortDirection::Ascending)) if current == col => {
+                    Some((col, SortDirection::Descending))
+                }
+                Some((current, SortDirection::Descending)) if current == col => None,
+                _ => Some((col, SortDirection::Ascending)),
+            }
+        });
+    }
+
+    /// Selects a display row. `extend` selects the range from the anchor row and `toggle`
+    /// adds to or removes from the current selection instead of replacing it.
+    fn select(&mut self, display_row: usize, extend: bool, toggle: bool) {
+        let data_row = self.data_index(display_row);
+        match self.selection_mode {
+            TableSelectionMode::None => {}
+            TableSelectionMode::Single => {
+                self.anchor = Some(display_row);
+                self.selection.set(BTreeSet::from([data_row]));
+            }
+            TableSelectionMode::Multiple => {
+                if let (true, Some(anchor)) = (extend, self.anchor) {
+                    let range = (anchor.min(display_row)..=anchor.max(display_row))
+                        .map(|row| self.data_index(row))
+                        .collect::<Vec<_>>();
+                    self.selection.update(|selection| {
+                        if !toggle {
+                            selection.clear();
+                        }
+                        selection.extend(range);
+                    });
+                } else if toggle {
+                    self.anchor = Some(display_row);
+                    self.selection.update(|selection| {
+                        if !selection.remove(&data_row) {
+                            selection.insert(data_row);
+                        }
+                    });
+                } else {
+                    self.anchor = Some(display_row);
+                    self.selection.set(BTreeSet::from([data_row]));
+                }
+            }
+        }
+    }
+
+    fn scroll_to_focused(&self) {
+        let Some((display_row, col)) = self.focused else {
+            return;
+        };
+        let rect = self.cell_rect(display_row, col);
+        // Also reveal the area covered by the frozen regions so the cell isn't hidden behind them
+        let left = if col < self.frozen_columns {
+            0.
+        } else {
+            self.frozen_width()
+        };
+        let top = if display_row < self.frozen_rows {
+            self.header_height()
+        } else {
+            self.header_height() + self.frozen_height()
+        };
+        self.id.scroll_to(Some(Rect::new(
+            rect.x0 - left,
+            rect.y0 - top,
+            rect.x1,
+            rect.y1,
+        )));
+    }
+
+    fn handle_key(&mut self, event: &KeyboardEvent) -> bool {
+        let rows = self.rows.len();
+        let columns = self.columns.len();
+        if rows == 0 || columns == 0 {
+            return false;
+        }
+        let command = if cfg!(target_os = "macos") {
+            event.modifiers.contains(Modifiers::META)
+        } else {
+            event.modifiers.contains(Modifiers::CONTROL)
+        };
+        let shift = event.modifiers.contains(Modifiers::SHIFT);
+        let (row, col) = self.focused.unwrap_or((0, 0));
+        let row_height = self.row_height();
+        let page = if row_height > 0. {
+            ((self.viewport.height() - self.header_height() - self.frozen_height()) / row_height)
+                .floor()
+                .max(1.) as usize
+        } else {
+            1
+        };
+
+        let target = match &event.key {
+            Key::Named(NamedKey::ArrowUp) => (row.saturating_sub(1), col),
+            Key::Named(NamedKey::ArrowDown) => ((row + 1).min(rows - 1), col),
+            Key::Named(NamedKey::ArrowLeft) => (row, col.saturating_sub(1)),
+            Key::Named(NamedKey::ArrowRight) => (row, (col + 1).min(columns - 1)),
+            Key::Named(NamedKey::Home) if command => (0, col),
+            Key::Named(NamedKey::Home) => (row, 0),
+            Key::Named(NamedKey::End) if command => (rows - 1, col),
+            Key::Named(NamedKey::End) => (row, columns - 1),
+            Key::Named(NamedKey::PageUp) => (row.saturating_sub(page), col),
+            Key::Named(NamedKey::PageDown) => (row.saturating_add(page).min(rows - 1), col),
+            Key::Named(NamedKey::Enter) => {
+                self.select(row, false, false);
+                return true;
+            }
+            Key::Character(c) if c == " " => {
+                self.select(row, shift, command);
+                return true;
+            }
+            Key::Character(c)
+                if command
+                    && c.eq_ignore_ascii_case("a")
+                    && self.selection_mode == TableSelectionMode::Multiple =>
+            {
+                self.selection
+                    .set((0..rows).map(|row| self.data_index(row)).collect());
+                return true;
+            }
+            _ => return false,
+        };
+
+        if target.0 != row {
+            if shift {
+                if self.anchor.is_none() {
+                    self.anchor = Some(row);
+                }
+                self.select(target.0, true, false);
+            } else if !command {
+                self.select(target.0, false, false);
+            }
+        }
+        self.focused = Some(target);
+        self.scroll_to_focused();
+        self.id.request_paint();
+        true
+    }
+
+    fn set_cursor(&mut self, cursor: Option<CursorStyle>) {
+        if self.cursor != cursor {
+            self.cursor = cursor;
+            self.id.request_style();
+        }
+    }
+}
+
+impl<T: Clone + 'static> View for Table<T> {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Table".into()
+    }
+
+    fn view_class(&self) -> Option<crate::style::StyleClassRef> {
+        Some(TableClass::class_ref())
+    }
+
+    fn accessibility(&self) -> Option<Accessibility> {
+        Some(Accessibility::new(Role::Table))
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        let width = self.column_x.last().copied().unwrap_or_default();
+        let height = self.header_height() + self.rows.len() as f64 * self.row_height();
+        // using min width and height because the scroll view respects them
+        Some(
+            Style::new()
+                .min_width(width)
+                .min_height(height)
+                .apply_opt(self.cursor, |s, cursor| s.cursor(cursor)),
+        )
+    }
+
+    fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn Any>) {
+        if let Ok(update) = state.downcast::<TableUpdate<T>>() {
+            match *update {
+                TableUpdate::Rows(rows) => {
+                    self.rows = rows;
+                    self.clear_cells(cx.window_state);
+                    self.sort_rows();
+                    let len = self.rows.len();
+                    self.focused = self
+                        .focused
+                        .filter(|_| len > 0)
+                        .map(|(row, col)| (row.min(len - 1), col));
+                    self.anchor = self.anchor.filter(|row| *row < len);
+                }
+                TableUpdate::Sort => self.sort_rows(),
+                TableUpdate::Selection => self.id.request_style_recursive(),
+                TableUpdate::Refresh => {}
+            }
+            self.sync(cx.window_state);
+        }
+    }
+
+    fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
+        if self.style.read(cx) {
+            self.id.update_state(TableUpdate::<T>::Refresh);
+        }
+        for header in &self.headers {
+            cx.style_view(header.id);
+        }
+        self.selection.with_untracked(|selection| {
+            for ((data_row, _), (cell, _)) in &self.cells {
+                if selection.contains(data_row) {
+                    cx.save();
+                    cx.selected();
+                    cx.style_view(cell.id);
+                    cx.restore();
+                } else {
+                    cx.style_view(cell.id);
+                }
+            }
+        });
+    }
+
+    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
+        let viewport = cx.current_viewport();
+        if self.viewport != viewport {
+            self.viewport = viewport;
+            self.id.update_state(TableUpdate::<T>::Refresh);
+        }
+        view::default_compute_layout(self.id, cx)
+    }
+
+    fn event_before_children(&mut self, cx: &mut EventCx, event: &Event) -> EventPropagation {
+        match event {
+            Event::Pointer(PointerEvent::Down(PointerButtonEvent {
+                button: Some(PointerButton::Primary),
+                state: state @ PointerState { count, .. },
+                ..
+            })) => {
+                if let Some(col) = self.find_handle_at_position(state.logical_point()) {
+                    if *count == 2 {
+                        // Reset the column to its initial width on double-click
+                        self.columns[col].width = self.columns[col].initial_width;
+                        self.sync(cx.window_state);
+                    }
+                    self.id.request_active();
+                    self.handle = HandleState::Active(col);
+                    self.set_cursor(Some(CursorStyle::ColResize));
+                    return EventPropagation::Stop;
+                }
+            }
+            Event::Pointer(PointerEvent::Move(PointerUpdate { current, .. })) => {
+                let point = current.logical_point();
+                if let HandleState::Active(col) = self.handle {
+                    let width = (point.x - self.column_left(col)).max(self.columns[col].min_width);
+                    self.columns[col].width = ColumnWidth::Fixed(width);
+                    self.sync(cx.window_state);
+                    return EventPropagation::Stop;
+                } else if let Some(col) = self.find_handle_at_position(point) {
+                    self.handle = HandleState::Hovered(col);
+                    self.set_cursor(Some(CursorStyle::ColResize));
+                    self.id.request_paint();
+                    return EventPropagation::Stop;
+                } else if let HandleState::Hovered(_) = self.handle {
+                    self.handle = HandleState::None;
+                    self.set_cursor(None);
+                    self.id.request_paint();
+                }
+            }
+            Event::Pointer(PointerEvent::Up(PointerButtonEvent {
+                button: Some(PointerButton::Primary),
+                ..
+            })) => {
+                if let HandleState::Active(_) = self.handle {
+                    self.id.clear_active();
+                    self.handle = HandleState::None;
+                    self.set_cursor(None);
+                    self.id.request_paint();
+                    return EventPropagation::Stop;
+                }
+            }
+            _ => {}
+        }
+        EventPropagation::Continue
+    }
+
+    fn event_after_children(&mut self, cx: &mut EventCx, event: &Event) -> EventPropagation {
+        match event {
+            Event::Pointer(PointerEvent::Down(PointerButtonEvent {
+                button: Some(PointerButton::Primary),
+                state,
+                ..
+            })) => {
+                self.id.request_focus();
+                match self.hit(state.logical_point()) {
+                    Some(Hit::Header(col)) => self.toggle_sort(col),
+                    Some(Hit::Cell(display_row, col)) => {
+                        let command = if cfg!(target_os = "macos") {
+                            state.modifiers.contains(Modifiers::META)
+                        } else {
+                            state.modifiers.contains(Modifiers::CONTROL)
+                        };
+                        self.select(
+                            display_row,
+                            state.modifiers.contains(Modifiers::SHIFT),
+                            command,
+                        );
+                        self.focused = Some((display_row, col));
+                        self.id.request_paint();
+                    }
+                    None => return EventPropagation::Continue,
+                }
+                EventPropagation::Stop
+            }
+            Event::Key(
+                key_event @ KeyboardEvent {
+                    state: KeyState::Down,
+                    ..
+                },
+            ) if cx.window_state.is_focused(&self.id) => {
+                if self.handle_key(key_event) {
+                    EventPropagation::Stop
+                } else {
+                    EventPropagation::Continue
+                }
+            }
+            _ => EventPropagation::Continue,
+        }
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        cx.paint_children(self.id);
+
+        if let (Some((display_row, col)), Some(color)) = (self.focused, self.style.focus_color()) {
+            if cx.is_focused(self.id) && display_row < self.rows.len() && col < self.widths.len() {
+                cx.save();
+                cx.clip(&self.cell_region(display_row, col));
+                cx.stroke(
+                    &self.cell_rect(display_row, col).inset(-1.),
+                    &color,
+                    &Stroke::new(2.),
+                );
+                cx.restore();
+            }
+        }
+
+        if let (HandleState::Hovered(col) | HandleState::Active(col), Some(color)) =
+            (&self.handle, self.style.handle_color())
+        {
+            cx.stroke(
+                &self.handle_line(*col),
+                &color,
+                &Stroke::new(self.style.handle_thickness().0),
+            );
+        }
+    }
+}
+
+/// Represents a custom style for a [`Table`].
+#[derive(Debug, Default, Clone)]
+pub struct TableCustomStyle(Style);
+impl From<TableCustomStyle> for Style {
+    fn from(val: TableCustomStyle) -> Self {
+        val.0
+    }
+}
+impl From<Style> for TableCustomStyle {
+    fn from(val: Style) -> Self {
+        Self(val)
+    }
+}
+impl CustomStyle for TableCustomStyle {
+    type StyleClass = TableClass;
+}
+
+impl<T: Clone + 'static> CustomStylable<TableCustomStyle> for Table<T> {
+    type DV = Self;
+}
+
+impl TableCustomStyle {
+    /// Creates a new `TableCustomStyle`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the height of every body row.
+    pub fn row_height(mut self, height: impl Into<Px>) -> Self {
+        self = TableCustomStyle(self.0.set(TableRowHeight, height));
+        self
+    }
+
+    /// Sets the height of the header row.
+    pub fn header_height(mut self, height: impl Into<Px>) -> Self {
+        self = TableCustomStyle(self.0.set(TableHeaderHeight, height));
+        self
+    }
+
+    /// Sets the color of the outline around the focused cell.
+    pub fn focus_color(mut self, color: impl Into<Brush>) -> Self {
+        self = TableCustomStyle(self.0.set(TableFocusColor, Some(color.into())));
+        self
+    }
+
+    /// Sets the color of the column resize handle when it is hovered or dragged.
+    pub fn handle_color(mut self, color: impl Into<Brush>) -> Self {
+        self = TableCustomStyle(self.0.set(HandleColor, Some(color.into())));
+        self
+    }
+
+    /// Sets the thickness of the column resize handle.
+    pub fn handle_thickness(mut self, thickness: impl Into<Px>) -> Self {
+        self = TableCustomStyle(self.0.set(HandleThickness, thickness));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A table of the numbers below `rows` in a viewport, with a fixed, a flexible and a wider
+    /// flexible column, sortable by number and by parity.
+    fn numbers(rows: usize, viewport: Rect) -> Table<usize> {
+        let mut table = table(
+            imbl::Vector::new,
+            [
+                TableColumn::new("Number", |n: &usize| *n)
+                    .width(100.)
+                    .sort_by_key(|n: &usize| *n),
+                TableColumn::new("Parity", |n: &usize| n % 2)
+                    .sort_by(|a: &usize, b: &usize| (a % 2).cmp(&(b % 2))),
+                TableColumn::new("Square", |n: &usize| n * n).flex(3.),
+            ],
+        );
+        table.rows = (0..rows).collect();
+        table.viewport = viewport;
+        table.resolve_widths();
+        table
+    }
+
+    #[cfg(feature = "testing")]
+    fn press(table: &mut Table<usize>, key: NamedKey, modifiers: Modifiers) -> bool {
+        table.handle_key(&crate::testing::key_event(
+            Key::Named(key),
+            modifiers,
+            KeyState::Down,
+        ))
+    }
+
+    fn selection(table: &Table<usize>) -> Vec<usize> {
+        table.selection.get_untracked().into_iter().collect()
+    }
+
+    #[test]
+    fn widths_share_the_free_space() {
+        let mut table = numbers(10, Rect::new(0., 0., 500., 200.));
+        assert_eq!(table.widths, vec![100., 100., 300.]);
+        assert_eq!(table.column_x, vec![0., 100., 200., 500.]);
+
+        // Flexible columns don't shrink below their minimum width
+        table.viewport = Rect::new(0., 0., 150., 200.);
+        table.resolve_widths();
+        assert_eq!(table.widths, vec![100., 30., 37.5]);
+    }
+
+    #[test]
+    fn visible_rows_follow_the_viewport() {
+        // The header is 32px and rows are 28px high
+        let mut table = numbers(100, Rect::new(0., 0., 500., 200.));
+        assert_eq!(table.visible_rows(), (0..6).collect::<Vec<_>>());
+
+        table.viewport = Rect::new(0., 280., 500., 480.);
+        assert_eq!(table.visible_rows(), (10..16).collect::<Vec<_>>());
+
+        // Frozen rows stay visible and cover the rows scrolled under them
+        table.frozen_rows = 2;
+        assert_eq!(table.visible_rows(), vec![0, 1, 12, 13, 14, 15]);
+
+        table.rows = (0..14).collect();
+        assert_eq!(table.visible_rows(), vec![0, 1, 12, 13]);
+    }
+
+    #[test]
+    fn hit_finds_headers_and_cells() {
+        let mut table = numbers(10, Rect::new(0., 0., 500., 200.));
+        assert_eq!(table.hit(Point::new(50., 10.)), Some(Hit::Header(0)));
+        assert_eq!(table.hit(Point::new(150., 93.)), Some(Hit::Cell(2, 1)));
+        assert_eq!(table.hit(Point::new(450., 32.)), Some(Hit::Cell(0, 2)));
+        assert_eq!(table.hit(Point::new(50., 313.)), None);
+        assert_eq!(table.hit(Point::new(550., 50.)), None);
+
+        // A frozen column scrolled horizontally covers the columns under it
+        table.frozen_columns = 1;
+        table.viewport = Rect::new(50., 0., 550., 200.);
+        assert_eq!(table.hit(Point::new(60., 50.)), Some(Hit::Cell(0, 0)));
+        assert_eq!(table.hit(Point::new(120., 50.)), Some(Hit::Cell(0, 0)));
+        assert_eq!(table.hit(Point::new(160., 50.)), Some(Hit::Cell(0, 1)));
+    }
+
+    #[test]
+    fn sorting_cycles_through_the_directions() {
+        let mut table = numbers(6, Rect::new(0., 0., 500., 200.));
+        table.toggle_sort(0);
+        assert_eq!(
+            table.sort.get_untracked(),
+            Some((0, SortDirection::Ascending))
+        );
+        table.toggle_sort(0);
+        assert_eq!(
+            table.sort.get_untracked(),
+            Some((0, SortDirection::Descending))
+        );
+        table.sort_rows();
+        assert_eq!(table.order, Some(vec![5, 4, 3, 2, 1, 0]));
+        table.toggle_sort(0);
+        assert_eq!(table.sort.get_untracked(), None);
+        table.sort_rows();
+        assert_eq!(table.order, None);
+
+        // Columns without a comparator aren't sortable
+        table.toggle_sort(2);
+        assert_eq!(table.sort.get_untracked(), None);
+
+        // Sorting is stable in both directions
+        table.toggle_sort(1);
+        table.sort_rows();
+        assert_eq!(table.order, Some(vec![0, 2, 4, 1, 3, 5]));
+        table.toggle_sort(1);
+        table.sort_rows();
+        assert_eq!(table.order, Some(vec![1, 3, 5, 0, 2, 4]));
+    }
+
+    #[test]
+    fn rows_are_selected_in_ranges_and_toggled() {
+        let mut table = numbers(10, Rect::new(0., 0., 500., 200.));
+        table.selection_mode = TableSelectionMode::Multiple;
+        table.select(2, false, false);
+        table.select(5, true, false);
+        assert_eq!(selection(&table), vec![2, 3, 4, 5]);
+
+        table.select(7, false, true);
+        table.select(9, true, true);
+        assert_eq!(selection(&table), vec![2, 3, 4, 5, 7, 8, 9]);
+        table.select(3, false, true);
+        assert_eq!(selection(&table), vec![2, 4, 5, 7, 8, 9]);
+
+        // Display rows are selected as the data rows they show
+        table.sort.set(Some((0, SortDirection::Descending)));
+        table.sort_rows();
+        table.select(0, false, false);
+        assert_eq!(selection(&table), vec![9]);
+
+        table.selection_mode = TableSelectionMode::Single;
+        table.select(1, false, false);
+        table.select(4, true, true);
+        assert_eq!(selection(&table), vec![5]);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn keys_move_the_focused_cell() {
+        let mut table = numbers(20, Rect::new(0., 0., 500., 200.));
+        table.selection_mode = TableSelectionMode::Multiple;
+        assert!(press(&mut table, NamedKey::ArrowDown, Modifiers::empty()));
+        assert_eq!(table.focused, Some((1, 0)));
+        assert_eq!(selection(&table), vec![1]);
+
+        assert!(press(&mut table, NamedKey::ArrowRight, Modifiers::empty()));
+        assert_eq!(table.focused, Some((1, 1)));
+
+        // A page is the number of rows that fit below the header
+        assert!(press(&mut table, NamedKey::PageDown, Modifiers::empty()));
+        assert_eq!(table.focused, Some((7, 1)));
+        assert!(press(&mut table, NamedKey::ArrowDown, Modifiers::SHIFT));
+        assert_eq!(selection(&table), vec![7, 8]);
+
+        assert!(press(&mut table, NamedKey::End, Modifiers::empty()));
+        assert_eq!(table.focused, Some((8, 2)));
+        assert!(press(&mut table, NamedKey::Home, Modifiers::empty()));
+        assert_eq!(table.focused, Some((8, 0)));
+        assert!(!press(&mut table, NamedKey::Tab, Modifiers::empty()));
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn paging_without_a_row_height_moves_one_row() {
+        let mut table = numbers(20, Rect::new(0., 0., 500., 200.));
+        let style = Style::new().set(TableRowHeight, Px(0.));
+        table
+            .style
+            .read_explicit(&style, &style, &std::time::Instant::now(), &mut false);
+        assert_eq!(table.row_height(), 0.);
+        assert!(press(&mut table, NamedKey::PageDown, Modifiers::empty()));
+        assert_eq!(table.focused, Some((1, 0)));
+        assert!(press(&mut table, NamedKey::PageUp, Modifiers::empty()));
+        assert_eq!(table.focused, Some((0, 0)));
+    }
+}
@@ -0,0 +1,1168 @@
+#![deny(missing_docs)]
+//! A multi-line text input backed by the editor core's [`Buffer`] and [`Selection`].
+
+use std::{any::Any, ops::Range};
+
+use floem_editor_core::{
+    buffer::{Buffer, rope_text::RopeText},
+    cursor::{ColPosition, CursorAffinity, CursorMode},
+    editor::EditType,
+    mode::Mode,
+    selection::{InsertDrift, SelRegion, Selection},
+    xi_rope::Rope,
+};
+use floem_reactive::{SignalGet, SignalUpdate, SignalWith, create_effect, create_rw_signal};
+use floem_renderer::Renderer;
+use peniko::{
+    Brush,
+    color::palette,
+    kurbo::{Point, Rect, Size},
+};
+use taffy::tree::NodeId;
+use ui_events::{
+    keyboard::{Code, Key, KeyState, KeyboardEvent, Modifiers, NamedKey},
+    pointer::{PointerButton, PointerButtonEvent, PointerEvent},
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
+
+use crate::{
+    Clipboard,
+    accessibility::{Accessibility, Action, ActionData, Role},
+    action::{exec_after, set_ime_allowed, set_ime_cursor_area},
+    context::{ComputeLayoutCx, EventCx, LayoutCx, PaintCx, StyleCx, UpdateCx},
+    event::{Event, EventListener, EventPropagation},
+    id::ViewId,
+    prop_extractor,
+    reactive::RwSignal,
+    style::{
+        FontFamily, FontProps, FontStyle, FontWeight, LetterSpacing, LineHeight, SelectionStyle,
+        Style, TextAlignProp, TextColor, TextDecorationProp, TextShadowProp, WordSpacing,
+    },
+    style_class,
+    text::{Affinity, Attrs, AttrsList, Cursor, FamilyOwned, TextLayout},
+    view::View,
+    views::{Decorators, PlaceholderTextClass, TextCommand, editor::text::Preedit},
+};
+
+style_class!(
+    /// The style class that is applied to all [`TextArea`] views.
+    pub TextAreaClass
+);
+
+prop_extractor! {
+    Extractor {
+        color: TextColor,
+        line_height: LineHeight,
+        text_align: TextAlignProp,
+        text_decoration: TextDecorationProp,
+        letter_spacing: LetterSpacing,
+        word_spacing: WordSpacing,
+        text_shadow: TextShadowProp,
+    }
+}
+
+prop_extractor! {
+    PlaceholderStyle {
+        color: TextColor,
+        font_weight: FontWeight,
+        font_style: FontStyle,
+        font_family: FontFamily,
+    }
+}
+
+const DEFAULT_FONT_SIZE: f32 = 14.0;
+const CURSOR_BLINK_INTERVAL_MS: u64 = 500;
+
+/// A multi-line text input with soft wrapping, undo/redo and IME support.
+///
+/// See [`text_area`].
+pub struct TextArea {
+    id: ViewId,
+    value: RwSignal<String>,
+    // The last text read from or written to `value`, used to tell external changes apart.
+    last_value: String,
+    doc: Buffer,
+    // The text of `doc`, kept around for layout and length checks.
+    text: String,
+    selection: Selection,
+    placeholder_text: Option<String>,
+    max_length: Option<usize>,
+    min_rows: usize,
+    max_rows: Option<usize>,
+    preedit: Option<Preedit>,
+    text_layout: TextLayout,
+    text_node: Option<NodeId>,
+    wrap_width: Option<f32>,
+    row_height: f64,
+    content_height: f64,
+    scroll_y: f64,
+    scroll_to_caret: bool,
+    style: Extractor,
+    font: FontProps,
+    placeholder_style: PlaceholderStyle,
+    selection_style: SelectionStyle,
+    is_focused: bool,
+    last_cursor_action_on: Instant,
+    window_origin: Option<Point>,
+    last_ime_cursor_area: Option<(Point, Size)>,
+}
+
+/// Creates a [`TextArea`], a multi-line text input editing the string in `value`.
+///
+/// Long lines are soft wrapped to the width of the view and the view grows with its content,
+/// between [`min_rows`](TextArea::min_rows) and [`max_rows`](TextArea::max_rows) lines, after
+/// which the text scrolls. Edits can be undone and redone with the usual shortcuts.
+/// ### Examples
+/// ```rust
+/// # use floem::prelude::*;
+/// let comment = RwSignal::new(String::new());
+/// text_area(comment)
+///     .placeholder("Leave a comment")
+///     .max_length(500)
+///     .min_rows(2)
+///     .max_rows(8)
+///     .style(|s| s.width(300.));
+/// ```
+/// ### Reactivity
+/// The view is reactive and will track updates on the `value` signal.
+pub fn text_area(value: RwSignal<String>) -> TextArea {
+    let id = ViewId::new();
+    let is_focused = create_rw_signal(false);
+
+    create_effect(move |_| {
+        // subscribe to changes without cloning string
+        value.with(|_| {});
+        id.update_state(is_focused.get());
+    });
+
+    let last_value = value.get_untracked();
+    let doc = Buffer::new(last_value.as_str());
+    let text = doc.to_string();
+
+    TextArea {
+        id,
+        value,
+        last_value,
+        doc,
+        text,
+        selection: Selection::caret(0, CursorAffinity::Forward),
+        placeholder_text: None,
+        max_length: None,
+        min_rows: 3,
+        max_rows: None,
+        preedit: None,
+        text_layout: TextLayout::new(),
+        text_node: None,
+        wrap_width: None,
+        row_height: 0.0,
+        content_height: 0.0,
+        scroll_y: 0.0,
+        scroll_to_caret: false,
+        style: Default::default(),
+        font: FontProps::default(),
+        placeholder_style: Default::default(),
+        selection_style: Default::default(),
+        is_focused: false,
+        last_cursor_action_on: Instant::now(),
+        window_origin: None,
+        last_ime_cursor_area: None,
+    }
+    .on_event_stop(EventListener::FocusGained, move |_| {
+        is_focused.set(true);
+        set_ime_allowed(true);
+    })
+    .on_event_stop(EventListener::FocusLost, move |_| {
+        is_focused.set(false);
+        set_ime_allowed(false);
+    })
+    .class(TextAreaClass)
+}
+
+impl TextArea {
+    /// Add placeholder text visible when the text area is empty.
+    ///
+    /// The placeholder is styled with [`PlaceholderTextClass`].
+    /// ### Reactivity
+    /// This method is not reactive.
+    pub fn placeholder(mut self, text: impl Into<String>) -> Self {
+        self.placeholder_text = Some(text.into());
+        self
+    }
+
+    /// Limit the text to at most `max_length` characters.
+    ///
+    /// Typed and pasted text that would go over the limit is truncated.
+    /// Changes made directly to the signal are not limited.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Sets the number of lines the text area is tall when it has less content.
+    ///
+    /// Defaults to 3.
+    pub fn min_rows(mut self, rows: usize) -> Self {
+        self.min_rows = rows;
+        self
+    }
+
+    /// Sets the number of lines the text area grows to before it starts to scroll.
+    ///
+    /// By default the text area keeps growing with its content.
+    pub fn max_rows(mut self, rows: usize) -> Self {
+        self.max_rows = Some(rows);
+        self
+    }
+}
+
+/// Where the caret for an offset into a [`TextLayout`] is drawn.
+#[derive(Clone, Copy)]
+struct CaretPosition {
+    x: f64,
+    top: f64,
+    height: f64,
+    /// The index of the visual, possibly wrapped, line.
+    run: usize,
+}
+
+/// Splits an offset into the text of `layout` into a line index and the offset within that line.
+fn line_col(layout: &TextLayout, offset: usize) -> (usize, usize) {
+    let lines = layout.lines_range();
+    let line = lines
+        .iter()
+        .rposition(|range| range.start <= offset)
+        .unwrap_or(0);
+    let start = lines.get(line).map_or(0, |range| range.start);
+    (line, offset - start)
+}
+
+fn layout_cursor(layout: &TextLayout, offset: usize) -> Cursor {
+    let (line, index) = line_col(layout, offset);
+    Cursor::new_with_affinity(line, index, Affinity::Before)
+}
+
+fn caret_position(layout: &TextLayout, offset: usize) -> CaretPosition {
+    let (line, col) = line_col(layout, offset);
+    let mut position = CaretPosition {
+        x: 0.0,
+        top: 0.0,
+        height: 0.0,
+        run: 0,
+    };
+    for (i, run) in layout.layout_runs().enumerate() {
+        if run.line_i > line {
+            break;
+        }
+        let mut run_position = CaretPosition {
+            x: 0.0,
+            top: run.line_top as f64,
+            height: run.line_height as f64,
+            run: i,
+        };
+        if run.line_i < line {
+            position = run_position;
+            continue;
+        }
+        for glyph in run.glyphs {
+            if (glyph.start..glyph.end).contains(&col) {
+                let fraction = (col - glyph.start) as f32 / (glyph.end - glyph.start) as f32;
+                let fraction = if glyph.level.is_rtl() {
+                    1.0 - fraction
+                } else {
+                    fraction
+                };
+                run_position.x = (glyph.x + glyph.w * fraction) as f64;
+                return run_position;
+            }
+        }
+        // Past the last glyph of this run, unless a following wrapped run contains the offset.
+        if run.glyphs.first().is_none_or(|glyph| glyph.start <= col) {
+            if let Some(last) = run.glyphs.last() {
+                run_position.x = if last.level.is_rtl() {
+                    last.x
+                } else {
+                    last.x + last.w
+                } as f64;
+            }
+            position = run_position;
+        }
+    }
+    position
+}
+
+fn offset_at_point(layout: &TextLayout, point: Point) -> usize {
+    layout
+        .hit(point.x as f32, point.y as f32)
+        .map_or(0, |cursor| {
+            layout
+                .lines_range()
+                .get(cursor.line)
+                .map_or(0, |range| range.start)
+                + cursor.index
+        })
+}
+
+/// The top and height of a visual line.
+fn run_bounds(layout: &TextLayout, run: usize) -> Option<(f64, f64)> {
+    layout
+        .layout_runs()
+        .nth(run)
+        .map(|run| (run.line_top as f64, run.line_height as f64))
+}
+
+/// The range of text shown on a visual line.
+fn run_range(layout: &TextLayout, run: usize) -> Option<Range<usize>> {
+    let mut runs = layout.layout_runs().skip(run);
+    let run = runs.next()?;
+    let line_start = layout.lines_range().get(run.line_i)?.start;
+    let wraps = runs.next().is_some_and(|next| next.line_i == run.line_i);
+    let start = run
+        .glyphs
+        .iter()
+        .map(|glyph| glyph.start)
+        .min()
+        .unwrap_or(0);
+    let mut end = run.glyphs.iter().map(|glyph| glyph.end).max().unwrap_or(0);
+    if wraps {
+        // Stay in front of the whitespace the line was wrapped at, which
+        // would otherwise put the caret at the start of the next line.
+        if let Some(last) = run.glyphs.iter().max_by_key(|glyph| glyph.end) {
+            if run.text[last.start..last.end]
+                .chars()
+                .all(char::is_whitespace)
+            {
+                end = last.start;
+            }
+        }
+    }
+    Some(line_start + start..line_start + end)
+}
+
+impl TextArea {
+    fn region(&self) -> SelRegion {
+        self.selection
+            .first()
+            .copied()
+            .unwrap_or_else(|| SelRegion::caret(0, CursorAffinity::Forward))
+    }
+
+    fn caret(&self) -> usize {
+        self.region().end
+    }
+
+    fn selected_range(&self) -> Range<usize> {
+        let region = self.region();
+        region.min()..region.max()
+    }
+
+    /// Moves the caret to `offset`, extending the selection from its anchor if `extend` is set.
+    fn move_caret(&mut self, offset: usize, extend: bool, horiz: Option<ColPosition>) {
+        let start = if extend { self.region().start } else { offset };
+        self.selection = Selection::sel_region(SelRegion::new(
+            start,
+            offset,
+            CursorAffinity::Forward,
+            horiz,
+        ));
+        // typing after moving the caret starts a new undo group
+        self.doc.reset_edit_type();
+        self.scroll_to_caret = true;
+    }
+
+    fn caret_display_offset(&self) -> usize {
+        let caret = self.caret();
+        match &self.preedit {
+            Some(preedit) => caret + preedit.cursor.map_or(preedit.text.len(), |c| c.0),
+            None => caret,
+        }
+    }
+
+    fn text_origin(&self) -> Point {
+        let location = self
+            .text_node
+            .and_then(|node| self.id.taffy().borrow().layout(node).ok().cloned())
+            .unwrap_or_default()
+            .location;
+        Point::new(location.x as f64, location.y as f64 - self.scroll_y)
+    }
+
+    fn offset_at(&self, point: Point) -> usize {
+        let origin = self.text_origin();
+        let point = Point::new(
+            (point.x - origin.x).max(0.0),
+            (point.y - origin.y).clamp(0.0, (self.content_height - 1.0).max(0.0)),
+        );
+        offset_at_point(&self.text_layout, point).min(self.text.len())
+    }
+
+    fn visible_height(&self) -> f64 {
+        let height = self
+            .content_height
+            .max(self.row_height * self.min_rows as f64);
+        match self.max_rows {
+            Some(max_rows) => height.min(self.row_height * max_rows.max(self.min_rows) as f64),
+            None => height,
+        }
+    }
+
+    fn max_scroll(&self) -> f64 {
+        (self.content_height - self.visible_height()).max(0.0)
+    }
+
+    fn ensure_caret_visible(&mut self) {
+        let caret = caret_position(&self.text_layout, self.caret_display_offset());
+        let visible_height = self.visible_height();
+        if caret.top < self.scroll_y {
+            self.scroll_y = caret.top;
+        } else if caret.top + caret.height > self.scroll_y + visible_height {
+            self.scroll_y = caret.top + caret.height - visible_height;
+        }
+        self.scroll_y = self.scroll_y.clamp(0.0, self.max_scroll());
+    }
+
+    /// Truncates `text` so that replacing `range` with it stays within the max length.
+    fn truncate_to_max_length<'a>(&self, range: Range<usize>, text: &'a str) -> &'a str {
+        let Some(max_length) = self.max_length else {
+            return text;
+        };
+        let kept = self.text.chars().count() - self.text[range].chars().count();
+        let allowed = max_length.saturating_sub(kept);
+        text.char_indices()
+            .nth(allowed)
+            .map_or(text, |(idx, _)| &text[..idx])
+    }
+
+    fn replace_range(&mut self, range: Range<usize>, text: &str, edit_type: EditType) -> bool {
+        let text = self.truncate_to_max_length(range.clone(), text);
+        if range.is_empty() && text.is_empty() {
+            return false;
+        }
+
+        let before = self.selection.clone();
+        let region = Selection::region(range.start, range.end, CursorAffinity::Forward);
+        let (_, delta, _) = self.doc.edit([(&region, text)], edit_type);
+        self.selection = Selection::caret(range.end, CursorAffinity::Forward).apply_delta(
+            &delta,
+            true,
+            InsertDrift::Default,
+        );
+        self.doc.set_cursor_before(CursorMode::Insert(before));
+        self.doc
+            .set_cursor_after(CursorMode::Insert(self.selection.clone()));
+
+        self.sync_value();
+        true
+    }
+
+    fn insert(&mut self, text: &str, edit_type: EditType) -> bool {
+        self.replace_range(self.selected_range(), text, edit_type)
+    }
+
+    fn delete(&mut self, backward: bool, by_word: bool) -> bool {
+        let range = self.selected_range();
+        if !range.is_empty() {
+            return self.replace_range(range, "", EditType::DeleteSelection);
+        }
+
+        let caret = range.start;
+        let range = match (backward, by_word) {
+            (true, true) => self.doc.move_word_backward_deletion(caret)..caret,
+            (true, false) => self.doc.prev_grapheme_offset(caret, 1, 0)..caret,
+            (false, true) => caret..self.doc.move_n_wordends_forward(caret, 1, true),
+            (false, false) => caret..self.doc.next_grapheme_offset(caret, 1, self.doc.len()),
+        };
+        let edit_type = if by_word {
+            EditType::DeleteWord
+        } else {
+            EditType::Delete
+        };
+        self.replace_range(range, "", edit_type)
+    }
+
+    fn undo(&mut self, redo: bool) -> bool {
+        let result = if redo {
+            self.doc.do_redo()
+        } else {
+            self.doc.do_undo()
+        };
+        let Some((_, delta, _, cursor)) = result else {
+            return false;
+        };
+
+        self.selection = match cursor {
+            Some(CursorMode::Insert(selection)) => selection,
+            _ => self
+                .selection
+                .apply_delta(&delta, true, InsertDrift::Default),
+        };
+        self.sync_value();
+        true
+    }
+
+    /// Publishes the text of the buffer to the signal after an edit.
+    fn sync_value(&mut self) {
+        self.text = self.doc.to_string();
+        self.last_value.clone_from(&self.text);
+        self.value.set(self.text.clone());
+        self.scroll_to_caret = true;
+    }
+
+    fn commit_preedit(&mut self) -> bool {
+        let Some(preedit) = self.preedit.take() else {
+            return false;
+        };
+        self.insert(&preedit.text, EditType::InsertChars);
+
+        if self.is_focused {
+            // toggle IME to flush external preedit state
+            set_ime_allowed(false);
+            set_ime_allowed(true);
+        }
+        true
+    }
+
+    fn copy_selection(&self) {
+        let range = self.selected_range();
+        if !range.is_empty() {
+            let _ = Clipboard::set_contents(self.text[range].to_string());
+        }
+    }
+
+    fn vertical_offset(&self, down: bool) -> (usize, f64) {
+        let caret = caret_position(&self.text_layout, self.caret());
+        let x = match self.region().horiz {
+            Some(ColPosition::Col(x)) => x,
+            _ => caret.x,
+        };
+        let run = if down {
+            Some(caret.run + 1)
+        } else {
+            caret.run.checked_sub(1)
+        };
+        let offset = match run.and_then(|run| run_bounds(&self.text_layout, run)) {
+            Some((top, height)) => {
+                offset_at_point(&self.text_layout, Point::new(x, top + height / 2.0))
+                    .min(self.text.len())
+            }
+            None if down => self.text.len(),
+            None => 0,
+        };
+        (offset, x)
+    }
+
+    fn handle_key_down(&mut self, cx: &mut EventCx, event: &KeyboardEvent) -> bool {
+        let shift = event.modifiers.contains(Modifiers::SHIFT);
+        let (by_word, by_command) = if cfg!(target_os = "macos") {
+            (
+                event.modifiers.contains(Modifiers::ALT),
+                event.modifiers.contains(Modifiers::META),
+            )
+        } else {
+            (
+                event.modifiers.contains(Modifiers::CONTROL),
+                event.modifiers.contains(Modifiers::CONTROL),
+            )
+        };
+        let len = self.text.len();
+        let caret = self.caret();
+        let range = self.selected_range();
+
+        match event.key {
+            Key::Named(NamedKey::Enter) => return self.insert("\n", EditType::InsertNewline),
+            Key::Named(NamedKey::Backspace) => return self.delete(true, by_word),
+            Key::Named(NamedKey::Delete) => return self.delete(false, by_word),
+            Key::Named(NamedKey::Escape) => {
+                cx.window_state.clear_focus();
+                return true;
+            }
+            Key::Named(NamedKey::ArrowLeft) => {
+                let offset = if cfg!(target_os = "macos") && by_command {
+                    let run = caret_position(&self.text_layout, caret).run;
+                    run_range(&self.text_layout, run).map_or(0, |range| range.start)
+                } else if by_word {
+                    self.doc.move_word_backward(caret, Mode::Insert)
+                } else if !shift && !range.is_empty() {
+                    range.start
+                } else {
+                    self.doc.prev_grapheme_offset(caret, 1, 0)
+                };
+                self.move_caret(offset, shift, None);
+                return true;
+            }
+            Key::Named(NamedKey::ArrowRight) => {
+                let offset = if cfg!(target_os = "macos") && by_command {
+                    let run = caret_position(&self.text_layout, caret).run;
+                    run_range(&self.text_layout, run).map_or(len, |range| range.end)
+                } else if by_word {
+                    self.doc.move_n_wordends_forward(caret, 1, true)
+                } else if !shift && !range.is_empty() {
+                    range.end
+                } else {
+                    self.doc.next_grapheme_offset(caret, 1, len)
+                };
+                self.move_caret(offset.min(len), shift, None);
+                return true;
+            }
+            Key::Named(NamedKey::ArrowUp) | Key::Named(NamedKey::ArrowDown) => {
+                let down = event.key == Key::Named(NamedKey::ArrowDown);
+                if cfg!(target_os = "macos") && by_command {
+                    self.move_caret(if down { len } else { 0 }, shift, None);
+                } else {
+                    let (offset, x) = self.vertical_offset(down);
+                    self.move_caret(offset, shift, Some(ColPosition::Col(x)));
+                }
+                return true;
+            }
+            Key::Named(NamedKey::Home) | Key::Named(NamedKey::End) => {
+                let end = event.key == Key::Named(NamedKey::End);
+                let offset = if by_command {
+                    if end { len } else { 0 }
+                } else {
+                    let run = caret_position(&self.text_layout, caret).run;
+                    run_range(&self.text_layout, run)
+                        .map_or(caret, |range| if end { range.end } else { range.start })
+                };
+                self.move_caret(offset.min(len), shift, None);
+                return true;
+            }
+            _ => {}
+        }
+
+        if by_command {
+            match event.code {
+                Code::KeyZ => return self.undo(shift),
+                Code::KeyY if !cfg!(target_os = "macos") => return self.undo(true),
+                _ => {}
+            }
+        }
+
+        match TextCommand::from(event) {
+            TextCommand::SelectAll => {
+                self.selection = Selection::region(0, len, CursorAffinity::Forward);
+                return true;
+            }
+            TextCommand::Copy => {
+                self.copy_selection();
+                return true;
+            }
+            TextCommand::Cut => {
+                self.copy_selection();
+                return self.replace_range(range, "", EditType::Cut);
+            }
+            TextCommand::Paste => {
+                return match Clipboard::get_contents() {
+                    Ok(content) => self.insert(&content, EditType::Paste),
+                    Err(_) => false,
+                };
+            }
+            TextCommand::None => {}
+        }
+
+        match event.key {
+            Key::Character(ref ch) => {
+                let non_shift_mask = Modifiers::all().difference(Modifiers::SHIFT);
+                if event.modifiers.intersects(non_shift_mask) {
+                    return false;
+                }
+                self.insert(ch, EditType::InsertChars)
+            }
+            _ => false,
+        }
+    }
+
+    fn update_text_layout(&mut self) {
+        let attrs_list = self.get_text_attrs();
+        let align = self.style.text_align();
+
+        let mut row = TextLayout::new();
+        row.set_text("W", attrs_list.clone(), align);
+        self.row_height = row.size().height;
+
+        let show_placeholder =
+            self.text.is_empty() && self.preedit.as_ref().is_none_or(|p| p.text.is_empty());
+
+        match (&self.placeholder_text, show_placeholder) {
+            (Some(placeholder), true) => {
+                let attrs_list = self.get_placeholder_text_attrs();
+                self.text_layout.set_text(placeholder, attrs_list, align);
+            }
+            _ => {
+                let mut display_text = match &self.preedit {
+                    Some(preedit) => {
+                        let caret = self.caret().min(self.text.len());
+                        [&self.text[..caret], &preedit.text, &self.text[caret..]].concat()
+                    }
+                    None => self.text.clone(),
+                };
+                // give a trailing newline a line of its own to put the caret on
+                if display_text.ends_with('\n') {
+                    display_text.push(' ');
+                }
+                self.text_layout.set_text(&display_text, attrs_list, align);
+            }
+        }
+
+        self.content_height = self.text_layout.size().height.max(self.row_height);
+    }
+
+    fn font_size(&self) -> f32 {
+        self.font.size().unwrap_or(DEFAULT_FONT_SIZE)
+    }
+
+    fn font_family(family: &Option<String>) -> Option<Vec<FamilyOwned>> {
+        family
+            .as_ref()
+            .map(|family| FamilyOwned::parse_list(family).collect())
+    }
+
+    /// Retrieve attributes for the placeholder text.
+    fn get_placeholder_text_attrs(&self) -> AttrsList {
+        let mut attrs = Attrs::new()
+            .color(
+                self.placeholder_style
+                    .color()
+                    .unwrap_or(palette::css::BLACK),
+            )
+            .font_size(self.font_size());
+
+        if let Some(font_style) = self.placeholder_style.font_style().or(self.font.style()) {
+            attrs = attrs.style(font_style);
+        }
+        if let Some(font_weight) = self.placeholder_style.font_weight().or(self.font.weight()) {
+            attrs = attrs.weight(font_weight);
+        }
+        if let Some(line_height) = self.style.line_height() {
+            attrs = attrs.line_height(line_height);
+        }
+        // Inherit the font family of the text area unless overridden by the placeholder
+        let font_family = Self::font_family(&self.placeholder_style.font_family())
+            .or_else(|| Self::font_family(&self.font.family()));
+        if let Some(font_family) = font_family.as_ref() {
+            attrs = attrs.family(font_family);
+        }
+
+        AttrsList::new(attrs)
+    }
+
+    /// Retrieve attributes for the text.
+    fn get_text_attrs(&self) -> AttrsList {
+        let mut attrs = Attrs::new()
+            .color(self.style.color().unwrap_or(palette::css::BLACK))
+            .font_size(self.font_size());
+
+        if let Some(font_style) = self.font.style() {
+            attrs = attrs.style(font_style);
+        }
+        let font_family = Self::font_family(&self.font.family());
+        if let Some(font_family) = font_family.as_ref() {
+            attrs = attrs.family(font_family);
+        }
+        if let Some(font_weight) = self.font.weight() {
+            attrs = attrs.weight(font_weight);
+        }
+        if let Some(line_height) = self.style.line_height() {
+            attrs = attrs.line_height(line_height);
+        }
+        if let Some(decoration) = self.style.text_decoration() {
+            attrs = attrs.decoration(decoration);
+        }
+        if let Some(shadow) = self.style.text_shadow() {
+            attrs = attrs.shadow(shadow);
+        }
+        attrs = attrs
+            .letter_spacing(self.style.letter_spacing())
+            .word_spacing(self.style.word_spacing());
+        AttrsList::new(attrs)
+    }
+
+    fn update_ime_cursor_area(&mut self) {
+        if !self.is_focused {
+            return;
+        }
+        let Some(window_origin) = self.window_origin else {
+            return;
+        };
+
+        let caret = caret_position(&self.text_layout, self.caret_display_offset());
+        let origin = self.text_origin();
+        let pos = Point::new(
+            window_origin.x + origin.x + caret.x,
+            window_origin.y + origin.y + caret.top,
+        );
+        let size = Size::new(0.0, caret.height.max(self.row_height));
+
+        if self.last_ime_cursor_area != Some((pos, size)) {
+            set_ime_cursor_area(pos, size);
+            self.last_ime_cursor_area = Some((pos, size));
+        }
+    }
+
+    fn paint_highlight(&self, cx: &mut PaintCx, range: Range<usize>, origin: Point) {
+        let start = layout_cursor(&self.text_layout, range.start);
+        let end = layout_cursor(&self.text_layout, range.end);
+        let color = self.selection_style.selection_color();
+        let radius = self.selection_style.corner_radius();
+
+        for run in self.text_layout.layout_runs() {
+            if let Some((x, width)) = run.highlight(start, end) {
+                let rect = Rect::new(
+                    origin.x + x as f64,
+                    origin.y + run.line_top as f64,
+                    origin.x + (x + width) as f64,
+                    origin.y + (run.line_top + run.line_height) as f64,
+                );
+                cx.fill(&rect.to_rounded_rect(radius), &color, 0.0);
+            }
+        }
+    }
+}
+
+impl View for TextArea {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        format!("TextArea: {:?}", self.text).into()
+    }
+
+    fn accessibility(&self) -> Option<Accessibility> {
+        let mut accessibility = Accessibility::new(Role::MultilineTextInput)
+            .value(self.text.clone())
+            .action(Action::SetValue)
+            .action(Action::ReplaceSelectedText);
+        if let Some(placeholder) = &self.placeholder_text {
+            accessibility = accessibility.placeholder(placeholder);
+        }
+        Some(accessibility)
+    }
+
+    fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn Any>) {
+        let Ok(is_focused) = state.downcast::<bool>() else {
+            return;
+        };
+        let is_focused = *is_focused;
+
+        if self.is_focused != is_focused {
+            self.is_focused = is_focused;
+            self.last_ime_cursor_area = None;
+
+            self.commit_preedit();
+            self.update_ime_cursor_area();
+
+            if is_focused && !cx.window_state.is_active(&self.id) {
+                self.move_caret(self.text.len(), false, None);
+            }
+        }
+
+        // Only reload the buffer if the text was changed from outside
+        if self.value.with_untracked(|value| *value != self.last_value) {
+            self.last_value = self.value.get_untracked();
+            let (_, delta, _) = self.doc.reload(Rope::from(self.last_value.as_str()), false);
+            self.text = self.doc.to_string();
+            self.selection = self
+                .selection
+                .apply_delta(&delta, true, InsertDrift::Default);
+            self.preedit = None;
+            self.scroll_to_caret = true;
+        }
+
+        self.update_text_layout();
+        self.id.request_layout();
+    }
+
+    fn event_before_children(&mut self, cx: &mut EventCx, event: &Event) -> EventPropagation {
+        let is_handled = match &event {
+            Event::Pointer(PointerEvent::Down(PointerButtonEvent {
+                button: Some(PointerButton::Primary),
+                state,
+                ..
+            })) => {
+                cx.update_active(self.id);
+                self.commit_preedit();
+
+                let offset = self.offset_at(state.logical_point());
+                match state.count {
+                    2 => {
+                        let (start, end) = self.doc.select_word(offset);
+                        self.selection = Selection::region(start, end, CursorAffinity::Forward);
+                    }
+                    3 => {
+                        let line = self.doc.line_of_offset(offset);
+                        self.selection = Selection::region(
+                            self.doc.offset_of_line(line),
+                            self.doc.offset_of_line(line + 1),
+                            CursorAffinity::Forward,
+                        );
+                    }
+                    _ => {
+                        let extend = state.modifiers.contains(Modifiers::SHIFT);
+                        self.move_caret(offset, extend, None);
+                    }
+                }
+                true
+            }
+            Event::Pointer(PointerEvent::Move(pu)) => {
+                if cx.is_active(self.id) {
+                    let offset = self.offset_at(pu.current.logical_point());
+                    if offset != self.caret() {
+                        self.move_caret(offset, true, None);
+                        self.ensure_caret_visible();
+                        self.id.request_paint();
+                    }
+                }
+                false
+            }
+            Event::Pointer(PointerEvent::Scroll(_)) => {
+                let max_scroll = self.max_scroll();
+                if let (Some(delta), true) = (event.pixel_scroll_delta_vec2(), max_scroll > 0.0) {
+                    let scroll_y = (self.scroll_y - delta.y).clamp(0.0, max_scroll);
+                    if scroll_y != self.scroll_y {
+                        self.scroll_y = scroll_y;
+                        self.last_ime_cursor_area = None;
+                        self.update_ime_cursor_area();
+                        self.id.request_paint();
+                        return EventPropagation::Stop;
+                    }
+                }
+                false
+            }
+            Event::Key(
+                ke @ KeyboardEvent {
+                    state: KeyState::Down,
+                    ..
+                },
+            ) => self.handle_key_down(cx, ke),
+            Event::ImePreedit { text, cursor } => {
+                if self.is_focused && !text.is_empty() {
+                    let range = self.selected_range();
+                    if !range.is_empty() {
+                        self.replace_range(range, "", EditType::DeleteSelection);
+                    }
+                    self.preedit = Some(Preedit {
+                        text: text.clone(),
+                        cursor: *cursor,
+                        offset: self.caret(),
+                    });
+                    self.scroll_to_caret = true;
+                    true
+                } else {
+                    // clear preedit and queue UI update
+                    self.preedit.take().is_some()
+                }
+            }
+            Event::ImeCommit(text) => {
+                if self.is_focused {
+                    self.preedit = None;
+                    self.insert(text, EditType::InsertChars);
+                    true
+                } else {
+                    false
+                }
+            }
+            Event::Accessibility(request) => match (request.action, &request.data) {
+                (Action::SetValue, Some(ActionData::Value(value))) => {
+                    self.commit_preedit();
+                    self.replace_range(0..self.text.len(), value, EditType::Other)
+                }
+                (Action::ReplaceSelectedText, Some(ActionData::Value(text))) => {
+                    self.commit_preedit();
+                    self.insert(text, EditType::InsertChars)
+                }
+                _ => false,
+            },
+            _ => false,
+        };
+
+        if is_handled {
+            self.update_text_layout();
+            self.id.request_layout();
+            self.last_cursor_action_on = Instant::now();
+            EventPropagation::Stop
+        } else {
+            EventPropagation::Continue
+        }
+    }
+
+    fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
+        let style = cx.style();
+
+        let placeholder_style = style.clone().apply_class(PlaceholderTextClass);
+        let placeholder_changed = self.placeholder_style.read_style(cx, &placeholder_style);
+
+        let font_changed = self.font.read(cx);
+        let style_changed = self.style.read(cx);
+        if font_changed || style_changed || placeholder_changed {
+            self.update_text_layout();
+            self.id.request_layout();
+        }
+
+        self.selection_style.read_style(cx, &style);
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> NodeId {
+        cx.layout_node(self.id(), true, |_cx| {
+            if self.text_node.is_none() {
+                self.text_node = Some(
+                    self.id
+                        .taffy()
+                        .borrow_mut()
+                        .new_leaf(taffy::style::Style::DEFAULT)
+                        .unwrap(),
+                );
+            }
+            let text_node = self.text_node.unwrap();
+
+            let style = Style::new()
+                .width_full()
+                .height(self.visible_height())
+                .to_taffy_style();
+            let _ = self.id.taffy().borrow_mut().set_style(text_node, style);
+
+            vec![text_node]
+        })
+    }
+
+    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
+        let width = self.id.get_content_rect().width() as f32;
+        if self.wrap_width != Some(width) {
+            let visible_height = self.visible_height();
+            self.wrap_width = Some(width);
+            self.text_layout.set_size(width, f32::MAX);
+            self.content_height = self.text_layout.size().height.max(self.row_height);
+            if self.visible_height() != visible_height {
+                self.id.request_layout();
+            }
+        }
+
+        if self.scroll_to_caret {
+            self.scroll_to_caret = false;
+            self.ensure_caret_visible();
+        } else {
+            self.scroll_y = self.scroll_y.clamp(0.0, self.max_scroll());
+        }
+
+        self.window_origin = Some(cx.window_origin);
+        self.update_ime_cursor_area();
+
+        None
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let origin = self.text_origin();
+        let is_focused = cx.window_state.is_focused(&self.id);
+
+        cx.save();
+        cx.clip(&self.id.get_content_rect());
+
+        let selection = self.selected_range();
+        let has_selection = !selection.is_empty();
+        if is_focused && has_selection {
+            self.paint_highlight(cx, selection, origin);
+        } else if let Some(preedit) = &self.preedit {
+            if let Some((start, end)) = preedit.cursor.filter(|c| c.0 != c.1) {
+                let caret = self.caret();
+                self.paint_highlight(cx, caret + start..caret + end, origin);
+            }
+        }
+
+        cx.draw_text(&self.text_layout, origin);
+
+        // underline the preedit text
+        if let Some(preedit) = &self.preedit {
+            let caret = self.caret();
+            let start = layout_cursor(&self.text_layout, caret);
+            let end = layout_cursor(&self.text_layout, caret + preedit.text.len());
+            let color = Brush::Solid(self.style.color().unwrap_or(palette::css::BLACK));
+            for run in self.text_layout.layout_runs() {
+                if let Some((x, width)) = run.highlight(start, end) {
+                    let x = origin.x + x as f64;
+                    let y = origin.y + run.line_y as f64;
+                    cx.fill(&Rect::new(x, y, x + width as f64, y + 1.0), &color, 0.0);
+                }
+            }
+        }
+
+        // skip rendering the cursor if we don't have focus or there is a selection
+        if is_focused && !has_selection {
+            let is_cursor_visible = (self.last_cursor_action_on.elapsed().as_millis()
+                / CURSOR_BLINK_INTERVAL_MS as u128)
+                .is_multiple_of(2);
+
+            if is_cursor_visible {
+                let cursor_color = self
+                    .id
+                    .state()
+                    .borrow()
+                    .combined_style
+                    .builtin()
+                    .cursor_color();
+                let caret = caret_position(&self.text_layout, self.caret_display_offset());
+                let height = if caret.height > 0.0 {
+                    caret.height
+                } else {
+                    self.row_height
+                };
+                let cursor_rect = Rect::new(
+                    origin.x + caret.x,
+                    origin.y + caret.top,
+                    origin.x + caret.x + 1.0,
+                    origin.y + caret.top + height,
+                );
+                cx.fill(&cursor_rect, &cursor_color, 0.0);
+            }
+
+            // request paint either way if we're attempting draw a cursor
+            let id = self.id();
+            exec_after(Duration::from_millis(CURSOR_BLINK_INTERVAL_MS), move |_| {
+                id.request_paint();
+            });
+        }
+
+        cx.restore();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(text: &str) -> TextArea {
+        let value = RwSignal::new(text.to_string());
+        let mut area = text_area(value);
+        area.move_caret(text.len(), false, None);
+        area
+    }
+
+    #[test]
+    fn max_length_truncates_insert() {
+        let mut area = area("abc").max_length(5);
+        assert!(area.insert("defg", EditType::Paste));
+        assert_eq!(area.text, "abcde");
+        assert_eq!(area.value.get_untracked(), "abcde");
+        assert!(!area.insert("f", EditType::InsertChars));
+    }
+
+    #[test]
+    fn undo_restores_text_and_caret() {
+        let mut area = area("hello");
+        area.insert(" world", EditType::Paste);
+        assert_eq!(area.caret(), 11);
+        assert!(area.undo(false));
+        assert_eq!(area.text, "hello");
+        assert_eq!(area.caret(), 5);
+        assert!(area.undo(true));
+        assert_eq!(area.value.get_untracked(), "hello world");
+    }
+
+    #[test]
+    fn delete_word_backward() {
+        let mut area = area("one two");
+        assert!(area.delete(true, true));
+        assert_eq!(area.text, "one ");
+    }
+}
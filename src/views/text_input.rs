@@ -1,10 +1,14 @@
 #![deny(missing_docs)]
+use crate::accessibility::{Accessibility, Action, ActionData, Role};
 use crate::action::{exec_after, set_ime_allowed, set_ime_cursor_area};
 use crate::event::{EventListener, EventPropagation};
 use crate::id::ViewId;
 use crate::reactive::{RwSignal, create_effect};
 use crate::style::{FontFamily, FontProps, PaddingProp, SelectionStyle, TextAlignProp};
-use crate::style::{FontStyle, FontWeight, TextColor};
+use crate::style::{
+    FontStyle, FontWeight, LetterSpacing, TextColor, TextDecorationProp, TextShadowProp,
+    WordSpacing,
+};
 use crate::unit::{PxPct, PxPctAuto};
 use crate::views::editor::text::Preedit;
 use crate::{Clipboard, prop_extractor, style_class};
@@ -18,9 +22,9 @@ use unicode_segmentation::UnicodeSegmentation;
 
 use crate::{peniko::color::palette, style::Style, view::View};
 
-use std::{any::Any, ops::Range};
+use std::{any::Any, cell::RefCell, ops::Range, rc::Rc};
 
-use crate::text::{Attrs, AttrsList, FamilyOwned, TextLayout};
+use crate::text::{Attrs, AttrsList, FamilyOwned, HitPosition, TextLayout};
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::{Duration, Instant};
 #[cfg(target_arch = "wasm32")]
@@ -34,7 +38,7 @@ use crate::{
     event::Event,
 };
 
-use super::Decorators;
+use super::{Decorators, Stack, button, h_stack, label};
 
 style_class!(
     /// The style class that is applied to all `TextInput` views.
@@ -44,11 +48,19 @@ style_class!(
     /// The style class that is applied to the placeholder `TextInput` text.
     pub PlaceholderTextClass
 );
+style_class!(
+    /// The style class that is applied to the button of [`TextInput::secure_with_toggle`].
+    pub SecureToggleClass
+);
 
 prop_extractor! {
     Extractor {
         color: TextColor,
         text_align: TextAlignProp,
+        text_decoration: TextDecorationProp,
+        letter_spacing: LetterSpacing,
+        word_spacing: WordSpacing,
+        text_shadow: TextShadowProp,
     }
 }
 
@@ -86,10 +98,29 @@ impl BufferState {
     }
 }
 
+/// Checks the text of a [`TextInput`], returning an error message if it is invalid.
+type Validator = Box<dyn Fn(&str) -> Result<(), String>>;
+
+/// Sent to the view when a [`TextInput::secure`] text is hidden or revealed.
+struct SecureState(bool);
+
+/// The character shown in place of each character of a hidden text.
+const MASK_CHAR: char = '•';
+
 /// Text Input View.
 pub struct TextInput {
     id: ViewId,
     buffer: BufferState,
+    masked: bool,
+    // Byte offsets of the graphemes of the displayed text and its length, while it is masked.
+    mask_offsets: Option<Vec<usize>>,
+    char_filter: Option<Box<dyn Fn(char) -> bool>>,
+    input_mask: Option<String>,
+    max_length: Option<usize>,
+    validators: Rc<RefCell<Vec<Validator>>>,
+    error: RwSignal<Option<String>>,
+    parse: Option<Box<dyn Fn(&str) -> String>>,
+    format: Option<Box<dyn Fn(&str) -> String>>,
     /// Optional text shown when the text input buffer is empty.
     placeholder_text: Option<String>,
     on_enter: Option<Box<dyn Fn()>>,
@@ -197,6 +228,9 @@ pub enum TextDirection {
 pub fn text_input(buffer: RwSignal<String>) -> TextInput {
     let id = ViewId::new();
     let is_focused = create_rw_signal(false);
+    let touched = create_rw_signal(false);
+    let error = create_rw_signal(None);
+    let validators: Rc<RefCell<Vec<Validator>>> = Default::default();
 
     {
         create_effect(move |_| {
@@ -206,8 +240,27 @@ pub fn text_input(buffer: RwSignal<String>) -> TextInput {
         });
     }
 
+    {
+        let validators = validators.clone();
+        create_effect(move |_| {
+            let message = buffer.with(|buff| validate(&validators.borrow(), buff));
+            if error.with_untracked(|error| *error != message) {
+                error.set(message);
+            }
+        });
+    }
+
     TextInput {
         id,
+        masked: false,
+        mask_offsets: None,
+        char_filter: None,
+        input_mask: None,
+        max_length: None,
+        validators,
+        error,
+        parse: None,
+        format: None,
         cursor_glyph_idx: 0,
         placeholder_text: None,
         placeholder_style: Default::default(),
@@ -241,11 +294,19 @@ pub fn text_input(buffer: RwSignal<String>) -> TextInput {
     })
     .on_event_stop(EventListener::FocusLost, move |_| {
         is_focused.set(false);
+        touched.set(true);
         set_ime_allowed(false);
     })
+    .style(move |s| s.set_invalid(touched.get() && error.with(Option::is_some)))
     .class(TextInputClass)
 }
 
+fn validate(validators: &[Validator], text: &str) -> Option<String> {
+    validators
+        .iter()
+        .find_map(|validator| validator(text).err())
+}
+
 pub(crate) enum TextCommand {
     SelectAll,
     Copy,
@@ -334,10 +395,157 @@ impl TextInput {
         self.on_enter = Some(Box::new(action));
         self
     }
+
+    /// Hide the text behind bullets while `hidden` returns `true`, for password fields.
+    ///
+    /// While hidden, the text can't be copied and word-wise navigation moves to the start or
+    /// end of the text.
+    /// ```
+    /// # use floem::prelude::*;
+    /// let password = RwSignal::new(String::new());
+    /// let reveal = RwSignal::new(false);
+    /// (
+    ///     text_input(password).secure(move || !reveal.get()),
+    ///     checkbox(move || reveal.get()).on_update(move |checked| reveal.set(checked)),
+    /// );
+    /// ```
+    /// ### Reactivity
+    /// The text is hidden or revealed when the signals read in `hidden` change.
+    pub fn secure(self, hidden: impl Fn() -> bool + 'static) -> Self {
+        let id = self.id;
+        create_effect(move |_| {
+            id.update_state(SecureState(hidden()));
+        });
+        self
+    }
+
+    /// Hide the text behind bullets, with a button after the input to show or hide it.
+    ///
+    /// This wraps the input in a stack, so it has to be called after the other methods of the
+    /// input. The button has the [`SecureToggleClass`].
+    /// ```
+    /// # use floem::views::text_input;
+    /// # use floem_reactive::RwSignal;
+    /// let password = RwSignal::new(String::new());
+    /// text_input(password)
+    ///     .placeholder("Password")
+    ///     .secure_with_toggle();
+    /// ```
+    pub fn secure_with_toggle(self) -> Stack {
+        let revealed = create_rw_signal(false);
+        let toggle = button(label(move || if revealed.get() { "Hide" } else { "Show" }))
+            .class(SecureToggleClass)
+            .action(move || revealed.update(|revealed| *revealed = !*revealed));
+        h_stack((self.secure(move || !revealed.get()), toggle)).style(|s| s.items_center().gap(4))
+    }
+
+    /// Only accept typed, pasted and composed characters for which `filter` returns `true`.
+    /// ```
+    /// # use floem::views::text_input;
+    /// # use floem_reactive::RwSignal;
+    /// let amount = RwSignal::new(String::new());
+    /// text_input(amount).char_filter(|c| c.is_ascii_digit());
+    /// ```
+    pub fn char_filter(mut self, filter: impl Fn(char) -> bool + 'static) -> Self {
+        self.char_filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Format the text as it is typed to follow `mask`.
+    ///
+    /// In the mask, `#` stands for a digit, `A` for a letter and `*` for a letter or digit.
+    /// Any other character is inserted as is once the input reaches it.
+    /// ```
+    /// # use floem::views::text_input;
+    /// # use floem_reactive::RwSignal;
+    /// let phone = RwSignal::new(String::new());
+    /// // typing "5551234567" shows "(555) 123-4567"
+    /// text_input(phone).input_mask("(###) ###-####");
+    /// ```
+    pub fn input_mask(mut self, mask: impl Into<String>) -> Self {
+        self.input_mask = Some(mask.into());
+        self
+    }
+
+    /// Limit the text to at most `max_length` characters.
+    ///
+    /// Typed and pasted text that would go over the limit is truncated.
+    /// Changes made directly to the buffer signal are not limited.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Add a validator, returning an error message when the text is invalid.
+    ///
+    /// Validators run in the order they were added whenever the text changes and the message
+    /// of the first one failing is stored in [`validation_error`](Self::validation_error).
+    /// Once the input lost focus for the first time, it is styled with the
+    /// [`invalid`](crate::style::Style::invalid) selector while there is an error.
+    /// ```
+    /// # use floem::prelude::*;
+    /// let email = RwSignal::new(String::new());
+    /// let input = text_input(email).validate(|text| {
+    ///     if text.contains('@') {
+    ///         Ok(())
+    ///     } else {
+    ///         Err("Enter an email address".to_string())
+    ///     }
+    /// });
+    /// let error = input.validation_error();
+    /// (
+    ///     input,
+    ///     label(move || error.get().unwrap_or_default()),
+    /// );
+    /// ```
+    pub fn validate(self, validator: impl Fn(&str) -> Result<(), String> + 'static) -> Self {
+        self.validators.borrow_mut().push(Box::new(validator));
+        let message = self
+            .buffer
+            .with_untracked(|buff| validate(&self.validators.borrow(), buff));
+        self.error.set(message);
+        self
+    }
+
+    /// The error message of the first failing [`validate`](Self::validate) validator, if any.
+    pub fn validation_error(&self) -> RwSignal<Option<String>> {
+        self.error
+    }
+
+    /// Transform the text when the input gains focus, such as removing the thousands
+    /// separators added by [`format_on_blur`](Self::format_on_blur).
+    pub fn parse_on_focus(mut self, parse: impl Fn(&str) -> String + 'static) -> Self {
+        self.parse = Some(Box::new(parse));
+        self
+    }
+
+    /// Transform the text when the input loses focus, such as formatting a number for the
+    /// user's locale.
+    /// ```
+    /// # use floem::views::text_input;
+    /// # use floem_reactive::RwSignal;
+    /// let price = RwSignal::new(String::new());
+    /// text_input(price)
+    ///     .char_filter(|c| c.is_ascii_digit() || c == '.')
+    ///     .format_on_blur(|text| {
+    ///         text.parse::<f64>()
+    ///             .map(|price| format!("{price:.2}"))
+    ///             .unwrap_or_default()
+    ///     });
+    /// ```
+    pub fn format_on_blur(mut self, format: impl Fn(&str) -> String + 'static) -> Self {
+        self.format = Some(Box::new(format));
+        self
+    }
 }
 
 impl TextInput {
     fn move_cursor(&mut self, move_kind: Movement, direction: TextDirection) -> bool {
+        // don't reveal where the words of a hidden text are
+        let move_kind = match move_kind {
+            Movement::Word if self.masked => Movement::Line,
+            move_kind => move_kind,
+        };
         match (move_kind, direction) {
             (Movement::Glyph, TextDirection::Left) => {
                 let untracked_buffer = self.buffer.get_untracked();
@@ -403,6 +611,30 @@ impl TextInput {
         }
     }
 
+    /// Maps an index into the unmasked text to an index into the laid out text.
+    fn layout_idx(&self, idx: usize) -> usize {
+        match &self.mask_offsets {
+            Some(offsets) => offsets.partition_point(|&offset| offset < idx) * MASK_CHAR.len_utf8(),
+            None => idx,
+        }
+    }
+
+    /// Maps an index into the laid out text to an index into the unmasked text.
+    fn unmasked_idx(&self, idx: usize) -> usize {
+        match &self.mask_offsets {
+            Some(offsets) => offsets
+                .get(idx / MASK_CHAR.len_utf8())
+                .or(offsets.last())
+                .copied()
+                .unwrap_or_default(),
+            None => idx,
+        }
+    }
+
+    fn hit_position(&self, idx: usize) -> HitPosition {
+        self.text_buf.hit_position(self.layout_idx(idx))
+    }
+
     fn cursor_visual_idx(&self) -> usize {
         let Some(preedit) = &self.preedit else {
             return self.cursor_glyph_idx;
@@ -417,7 +649,7 @@ impl TextInput {
 
     fn calculate_clip_offset(&mut self, node_layout: &Layout) {
         let node_width = node_layout.size.width as f64;
-        let cursor_glyph_pos = self.text_buf.hit_position(self.cursor_visual_idx());
+        let cursor_glyph_pos = self.hit_position(self.cursor_visual_idx());
         let cursor_x = cursor_glyph_pos.point.x;
 
         let mut clip_start_x = self.clip_start_x;
@@ -462,6 +694,11 @@ impl TextInput {
     }
 
     fn handle_double_click(&mut self, pos_x: f64) {
+        if self.masked {
+            self.select_all();
+            return;
+        }
+
         let clicked_glyph_idx = self.get_box_position(pos_x);
 
         self.buffer.with_untracked(|buff| {
@@ -487,12 +724,14 @@ impl TextInput {
             PxPct::Px(padding) => padding as f32,
             PxPct::Pct(pct) => pct as f32 * layout.size.width,
         };
-        self.text_buf
+        let idx = self
+            .text_buf
             .hit_point(Point::new(
                 pos_x + self.clip_start_x - padding_left as f64,
                 0.0,
             ))
-            .index
+            .index;
+        self.unmasked_idx(idx)
     }
 
     fn get_selection_rect(&self, node_layout: &Layout, left_padding: f64) -> Rect {
@@ -506,12 +745,11 @@ impl TextInput {
 
         let text_height = self.height;
 
-        let selection_start_x =
-            self.text_buf.hit_position(selection.start).point.x - self.clip_start_x;
+        let selection_start_x = self.hit_position(selection.start).point.x - self.clip_start_x;
         let selection_start_x = selection_start_x.max(node_layout.location.x as f64 - left_padding);
 
         let selection_end_x =
-            self.text_buf.hit_position(selection.end).point.x + left_padding - self.clip_start_x;
+            self.hit_position(selection.end).point.x + left_padding - self.clip_start_x;
         let selection_end_x =
             selection_end_x.min(selection_start_x + self.width as f64 + left_padding);
 
@@ -575,8 +813,8 @@ impl TextInput {
                 let start_idx = preedit.offset;
                 let end_idx = start_idx + preedit.text.len();
 
-                let start_x = self.text_buf.hit_position(start_idx).point.x;
-                let end_x = self.text_buf.hit_position(end_idx).point.x;
+                let start_x = self.hit_position(start_idx).point.x;
+                let end_x = self.hit_position(end_idx).point.x;
 
                 (end_x - start_x).abs()
             })
@@ -592,8 +830,7 @@ impl TextInput {
 
     fn commit_preedit(&mut self) -> bool {
         if let Some(preedit) = self.preedit.take() {
-            self.buffer
-                .update(|buf| buf.insert_str(self.cursor_glyph_idx, &preedit.text));
+            self.insert_text(&preedit.text);
 
             if self.is_focused {
                 // toggle IME to flush external preedit state
@@ -619,6 +856,7 @@ impl TextInput {
         });
 
         if let (Some(placeholder_text), true) = (&self.placeholder_text, buffer_is_empty) {
+            self.mask_offsets = None;
             let attrs_list = self.get_placeholder_text_attrs();
             self.text_buf.set_text(
                 placeholder_text,
@@ -645,8 +883,22 @@ impl TextInput {
                     buff
                 };
 
-                self.text_buf
-                    .set_text(display_text, attrs_list.clone(), align);
+                if self.masked {
+                    let offsets: Vec<usize> = display_text
+                        .grapheme_indices(true)
+                        .map(|(idx, _)| idx)
+                        .chain([display_text.len()])
+                        .collect();
+                    let masked_text: String =
+                        std::iter::repeat_n(MASK_CHAR, offsets.len() - 1).collect();
+                    self.text_buf
+                        .set_text(&masked_text, attrs_list.clone(), align);
+                    self.mask_offsets = Some(offsets);
+                } else {
+                    self.text_buf
+                        .set_text(display_text, attrs_list.clone(), align);
+                    self.mask_offsets = None;
+                }
             });
         }
     }
@@ -724,6 +976,15 @@ impl TextInput {
         if let Some(font_weight) = self.font.weight() {
             attrs = attrs.weight(font_weight);
         }
+        if let Some(decoration) = self.style.text_decoration() {
+            attrs = attrs.decoration(decoration);
+        }
+        if let Some(shadow) = self.style.text_shadow() {
+            attrs = attrs.shadow(shadow);
+        }
+        attrs = attrs
+            .letter_spacing(self.style.letter_spacing())
+            .word_spacing(self.style.word_spacing());
         AttrsList::new(attrs)
     }
 
@@ -767,6 +1028,7 @@ impl TextInput {
                 self.select_all();
                 true
             }
+            TextCommand::Copy | TextCommand::Cut if self.masked => true,
             TextCommand::Copy => {
                 if let Some(selection) = &self.selection {
                     let selection_txt = self
@@ -800,32 +1062,10 @@ impl TextInput {
 
                 true
             }
-            TextCommand::Paste => {
-                let mut clipboard_content = match Clipboard::get_contents() {
-                    Ok(content) => content,
-                    Err(_) => return false,
-                };
-
-                clipboard_content.retain(|c| c != '\r' && c != '\n');
-
-                if clipboard_content.is_empty() {
-                    return false;
-                }
-
-                if let Some(selection) = self.selection.take() {
-                    self.buffer.update(|buf| {
-                        replace_range(buf, selection.clone(), Some(&clipboard_content))
-                    });
-
-                    self.cursor_glyph_idx = selection.start + clipboard_content.len();
-                } else {
-                    self.buffer
-                        .update(|buf| buf.insert_str(self.cursor_glyph_idx, &clipboard_content));
-                    self.cursor_glyph_idx += clipboard_content.len();
-                }
-
-                true
-            }
+            TextCommand::Paste => match Clipboard::get_contents() {
+                Ok(content) => self.paste(content),
+                Err(_) => false,
+            },
             TextCommand::None => {
                 self.selection = None;
                 false
@@ -835,18 +1075,7 @@ impl TextInput {
 
     fn handle_key_down(&mut self, cx: &mut EventCx, event: &KeyboardEvent) -> bool {
         let handled = match event.key {
-            Key::Character(ref c) if c == " " => {
-                if let Some(selection) = &self.selection {
-                    self.buffer
-                        .update(|buf| replace_range(buf, selection.clone(), None));
-                    self.cursor_glyph_idx = selection.start;
-                    self.selection = None;
-                } else {
-                    self.buffer
-                        .update(|buf| buf.insert(self.cursor_glyph_idx, ' '));
-                }
-                self.move_cursor(Movement::Glyph, TextDirection::Right)
-            }
+            Key::Character(ref c) if c == " " => self.insert_text(" "),
             Key::Named(NamedKey::Backspace) => {
                 let selection = self.selection.clone();
                 if let Some(selection) = selection {
@@ -995,18 +1224,72 @@ impl TextInput {
         }
     }
 
-    fn insert_text(&mut self, ch: &str) -> bool {
-        let selection = self.selection.clone();
-        if let Some(selection) = selection {
+    /// Inserts pasted `content` on a single line.
+    fn paste(&mut self, mut content: String) -> bool {
+        content.retain(|c| c != '\r' && c != '\n');
+        self.insert_text(&content)
+    }
+
+    fn insert_text(&mut self, text: &str) -> bool {
+        let text = self.filter_text(text);
+        if text.is_empty() {
+            return false;
+        }
+
+        if let Some(selection) = self.selection.take() {
             self.buffer
                 .update(|buf| replace_range(buf, selection.clone(), None));
             self.cursor_glyph_idx = selection.start;
-            self.selection = None;
         }
 
         self.buffer
-            .update(|buf| buf.insert_str(self.cursor_glyph_idx, ch));
-        self.move_cursor(Movement::Glyph, TextDirection::Right)
+            .update(|buf| buf.insert_str(self.cursor_glyph_idx, &text));
+        self.cursor_glyph_idx += text.len();
+        true
+    }
+
+    /// Drops the characters of `text` rejected by the character filter and truncates it to
+    /// stay within the max length once it replaces the selection.
+    fn filter_text(&self, text: &str) -> String {
+        let mut text: String = match &self.char_filter {
+            Some(filter) => text.chars().filter(|c| filter(*c)).collect(),
+            None => text.to_string(),
+        };
+
+        if let Some(max_length) = self.max_length {
+            let kept = self.buffer.with_untracked(|buff| {
+                let selected = self
+                    .selection
+                    .as_ref()
+                    .map_or(0, |selection| buff[selection.clone()].chars().count());
+                buff.chars().count() - selected
+            });
+            let allowed = max_length.saturating_sub(kept);
+            if let Some((idx, _)) = text.char_indices().nth(allowed) {
+                text.truncate(idx);
+            }
+        }
+
+        text
+    }
+
+    /// Reformats the text to follow the input mask after an edit.
+    fn apply_input_mask(&mut self) {
+        let Some(mask) = &self.input_mask else {
+            return;
+        };
+        if self.preedit.is_some() {
+            return;
+        }
+
+        let (formatted, cursor) = self
+            .buffer
+            .with_untracked(|buff| apply_input_mask(mask, buff, self.cursor_glyph_idx));
+        if self.buffer.with_untracked(|buff| *buff != formatted) {
+            self.buffer.update(|buf| *buf = formatted);
+            self.cursor_glyph_idx = cursor;
+            self.selection = None;
+        }
     }
 
     fn move_selection(&mut self, old_glyph_idx: usize, curr_glyph_idx: usize) {
@@ -1086,7 +1369,52 @@ fn replace_range(buff: &mut String, del_range: Range<usize>, replacement: Option
     buff.push_str(&after_del_range);
 }
 
-fn get_dbl_click_selection(glyph_idx: usize, buffer: &str) -> Range<usize> {
+/// Formats `text` to follow an input `mask`, returning the formatted text and where the cursor
+/// at byte index `cursor` ends up.
+///
+/// Literal characters of the mask are inserted in front of the next accepted character, and
+/// characters that don't fit the mask are dropped.
+fn apply_input_mask(mask: &str, text: &str, cursor: usize) -> (String, usize) {
+    let mut formatted = String::with_capacity(mask.len());
+    let mut new_cursor = (cursor == 0).then_some(0);
+    let mut mask_chars = mask.chars();
+
+    for (idx, c) in text.char_indices() {
+        let mut literals = String::new();
+        let mut lookahead = mask_chars.clone();
+        while let Some(mask_char) = lookahead.next() {
+            let accepted = match mask_char {
+                '#' => c.is_ascii_digit(),
+                'A' => c.is_alphabetic(),
+                '*' => c.is_alphanumeric(),
+                literal => {
+                    literals.push(literal);
+                    if literal != c {
+                        continue;
+                    }
+                    formatted.push_str(&literals);
+                    mask_chars = lookahead.clone();
+                    break;
+                }
+            };
+            if accepted {
+                formatted.push_str(&literals);
+                formatted.push(c);
+                mask_chars = lookahead.clone();
+            }
+            break;
+        }
+
+        if new_cursor.is_none() && idx + c.len_utf8() >= cursor {
+            new_cursor = Some(formatted.len());
+        }
+    }
+
+    let len = formatted.len();
+    (formatted, new_cursor.unwrap_or(len))
+}
+
+pub(crate) fn get_dbl_click_selection(glyph_idx: usize, buffer: &str) -> Range<usize> {
     let mut selectable_ranges: Vec<Range<usize>> = Vec::new();
     let glyph_idx = usize::min(glyph_idx, buffer.len().saturating_sub(1));
 
@@ -1132,7 +1460,31 @@ impl View for TextInput {
         format!("TextInput: {:?}", self.buffer.get_untracked()).into()
     }
 
+    fn accessibility(&self) -> Option<Accessibility> {
+        // a hidden text isn't read out either
+        let mut accessibility = if self.masked {
+            Accessibility::new(Role::PasswordInput)
+        } else {
+            Accessibility::new(Role::TextInput).value(self.buffer.get_untracked())
+        }
+        .action(Action::SetValue)
+        .action(Action::ReplaceSelectedText);
+        if let Some(placeholder) = &self.placeholder_text {
+            accessibility = accessibility.placeholder(placeholder);
+        }
+        Some(accessibility)
+    }
+
     fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn Any>) {
+        if let Some(SecureState(masked)) = state.downcast_ref::<SecureState>() {
+            if self.masked != *masked {
+                self.masked = *masked;
+                self.update_text_layout();
+                self.id.request_layout();
+            }
+            return;
+        }
+
         if let Ok(state) = state.downcast::<bool>() {
             let is_focused = *state;
 
@@ -1147,6 +1499,20 @@ impl View for TextInput {
                     self.selection = None;
                     self.cursor_glyph_idx = self.buffer.with_untracked(|buf| buf.len());
                 }
+
+                let hook = if is_focused {
+                    &self.parse
+                } else {
+                    &self.format
+                };
+                if let Some(hook) = hook {
+                    let text = self.buffer.with_untracked(|buf| hook(buf));
+                    self.buffer.update(|buf| *buf = text);
+                    self.selection = None;
+                    self.cursor_glyph_idx = self.buffer.with_untracked(|buf| buf.len());
+                    self.update_text_layout();
+                    self.id.request_layout();
+                }
             }
 
             // Only update recomputation if the state has actually changed
@@ -1253,20 +1619,34 @@ impl View for TextInput {
             }
             Event::ImeCommit(text) => {
                 if self.is_focused {
-                    self.buffer
-                        .update(|buf| buf.insert_str(self.cursor_glyph_idx, text));
-                    self.cursor_glyph_idx += text.len();
                     self.preedit = None;
+                    self.insert_text(text);
 
                     true
                 } else {
                     false
                 }
             }
+            Event::Accessibility(request) => match (request.action, &request.data) {
+                (Action::SetValue, Some(ActionData::Value(value))) => {
+                    self.commit_preedit();
+                    self.buffer.update(|buf| buf.clear());
+                    self.cursor_glyph_idx = 0;
+                    self.selection = None;
+                    self.insert_text(value);
+                    true
+                }
+                (Action::ReplaceSelectedText, Some(ActionData::Value(text))) => {
+                    self.commit_preedit();
+                    self.insert_text(text)
+                }
+                _ => false,
+            },
             _ => false,
         };
 
         if is_handled {
+            self.apply_input_mask();
             self.update_text_layout();
             self.id.request_layout();
             self.last_cursor_action_on = Instant::now();
@@ -1347,7 +1727,7 @@ impl View for TextInput {
             self.calculate_clip_offset(&node_layout);
         } else {
             self.clip_start_x = 0.0;
-            let hit_pos = self.text_buf.hit_position(self.cursor_visual_idx());
+            let hit_pos = self.hit_position(self.cursor_visual_idx());
             self.cursor_x = hit_pos.point.x;
         }
 
@@ -1382,10 +1762,9 @@ impl View for TextInput {
             let start_idx = self.cursor_glyph_idx;
             let end_idx = start_idx + preedit.text.len();
 
-            let start_hit = self.text_buf.hit_position(start_idx);
+            let start_hit = self.hit_position(start_idx);
             let start_x = location.x as f64 + start_hit.point.x - self.clip_start_x;
-            let end_x =
-                location.x as f64 + self.text_buf.hit_position(end_idx).point.x - self.clip_start_x;
+            let end_x = location.x as f64 + self.hit_position(end_idx).point.x - self.clip_start_x;
 
             let color = self.style.color().unwrap_or(palette::css::BLACK);
             let y = location.y as f64 + start_hit.glyph_ascent;
@@ -1446,7 +1825,9 @@ impl View for TextInput {
 mod tests {
     use crate::views::text_input::get_dbl_click_selection;
 
-    use super::replace_range;
+    use floem_reactive::{RwSignal, SignalGet};
+
+    use super::{apply_input_mask, replace_range, text_input};
 
     #[test]
     fn replace_range_start() {
@@ -1494,6 +1875,40 @@ mod tests {
         assert_eq!("Sample", s);
     }
 
+    #[test]
+    fn input_mask_inserts_literals() {
+        let mask = "(###) ###-####";
+        assert_eq!(
+            apply_input_mask(mask, "5551234567", 10),
+            ("(555) 123-4567".to_owned(), 14)
+        );
+        assert_eq!(apply_input_mask(mask, "555", 3), ("(555".to_owned(), 4));
+        assert_eq!(
+            apply_input_mask(mask, "(5551", 5),
+            ("(555) 1".to_owned(), 7)
+        );
+    }
+
+    #[test]
+    fn input_mask_drops_rejected_chars() {
+        let mask = "(###) ###-####";
+        assert_eq!(apply_input_mask(mask, "(555a", 5), ("(555".to_owned(), 4));
+        assert_eq!(apply_input_mask(mask, "x", 1), ("".to_owned(), 0));
+        assert_eq!(
+            apply_input_mask(mask, "(555) 123-45678", 15),
+            ("(555) 123-4567".to_owned(), 14)
+        );
+    }
+
+    #[test]
+    fn input_mask_keeps_cursor_in_the_middle() {
+        let mask = "##/##/####";
+        assert_eq!(
+            apply_input_mask(mask, "1203/2024", 2),
+            ("12/03/2024".to_owned(), 2)
+        );
+    }
+
     #[test]
     fn dbl_click_whitespace_before_word() {
         let s = "  select  ".to_owned();
@@ -1618,4 +2033,178 @@ mod tests {
 
         assert_eq!(range, 0..s.len());
     }
+
+    #[test]
+    fn max_length_truncates_typed_and_pasted_text() {
+        let text = RwSignal::new(String::new());
+        let mut input = text_input(text).max_length(5);
+
+        assert!(input.insert_text("abc"));
+        assert!(input.paste("de\nfgh".to_string()));
+        assert_eq!(text.get_untracked(), "abcde");
+        assert!(!input.insert_text("x"));
+
+        // the selected text makes room for what replaces it
+        input.selection = Some(0..2);
+        assert!(input.insert_text("xyz"));
+        assert_eq!(text.get_untracked(), "xycde");
+    }
+
+    #[test]
+    fn char_filter_drops_rejected_typed_and_pasted_chars() {
+        let text = RwSignal::new(String::new());
+        let mut input = text_input(text).char_filter(|c| c.is_ascii_digit());
+
+        assert!(input.insert_text("a1b2"));
+        assert!(input.paste("3 x\r\n4".to_string()));
+        assert_eq!(text.get_untracked(), "1234");
+        assert!(!input.insert_text("x"));
+        assert!(!input.paste("-".to_string()));
+        assert_eq!(text.get_untracked(), "1234");
+    }
+
+    #[cfg(feature = "testing")]
+    mod window {
+        use ui_events::keyboard::{Key, Modifiers, NamedKey};
+        use ui_events::pointer::PointerButton;
+
+        use super::*;
+        use crate::{
+            accessibility::{self, Role},
+            peniko::{Brush, color::palette},
+            style::Background,
+            testing::TestWindow,
+            text::{Attrs, AttrsList, TextLayout},
+            views::{Decorators, SecureToggleClass, TextInput},
+        };
+
+        fn background(window: &TestWindow, name: &str) -> Option<Brush> {
+            let id = window.find(name).unwrap();
+            id.state().borrow().combined_style.get(Background)
+        }
+
+        fn press(window: &mut TestWindow, key: NamedKey) {
+            window.key_press(Key::Named(key), Modifiers::empty());
+        }
+
+        /// An input without padding and border, so text positions start at its left edge.
+        fn input(text: RwSignal<String>) -> TextInput {
+            text_input(text)
+                .debug_name("input")
+                .style(|s| s.width(200.0).padding(0.0).border(0.0).font_size(14.0))
+        }
+
+        #[test]
+        fn invalid_style_applies_once_the_input_was_touched() {
+            let text = RwSignal::new(String::new());
+            let input = input(text)
+                .validate(|text| {
+                    if text.contains('@') {
+                        Ok(())
+                    } else {
+                        Err("Enter an email address".to_string())
+                    }
+                })
+                .style(|s| {
+                    s.background(palette::css::WHITE)
+                        .invalid(|s| s.background(palette::css::RED))
+                });
+            let error = input.validation_error();
+            let mut window = TestWindow::new((300.0, 100.0), move || input);
+            let (white, red) = (
+                Some(Brush::Solid(palette::css::WHITE)),
+                Some(Brush::Solid(palette::css::RED)),
+            );
+            assert_eq!(
+                error.get_untracked().as_deref(),
+                Some("Enter an email address")
+            );
+
+            let id = window.find("input").unwrap();
+            window.click_view(id);
+            window.type_text("me");
+            assert!(error.get_untracked().is_some());
+            assert_eq!(background(&window, "input"), white);
+
+            // leaving the input marks it as touched
+            press(&mut window, NamedKey::Escape);
+            assert_eq!(background(&window, "input"), red);
+
+            window.click_view(id);
+            window.type_text("@x");
+            assert_eq!(error.get_untracked(), None);
+            assert_eq!(background(&window, "input"), white);
+        }
+
+        #[test]
+        fn secure_text_is_hit_tested_on_its_bullets() {
+            // graphemes of one, two, four and one bytes
+            let text = RwSignal::new("aé👍b".to_string());
+            let mut window = TestWindow::new((300.0, 100.0), move || input(text).secure(|| true));
+            let mut bullet = TextLayout::new();
+            bullet.set_text("•", AttrsList::new(Attrs::new().font_size(14.0)), None);
+            let width = bullet.size().width;
+            let rect = window.rect(window.find("input").unwrap());
+            let at = |bullets: f64| (rect.x0 + bullets * width, rect.center().y);
+
+            // the cursor lands between the second and the third bullet
+            window.click(at(2.3));
+            window.type_text("x");
+            assert_eq!(text.get_untracked(), "aéx👍b");
+
+            // dragging over the second to fourth bullets selects "éx👍"
+            window.pointer_down(at(1.2), PointerButton::Primary, 1);
+            window.pointer_move(at(3.8));
+            window.pointer_up(at(3.8), PointerButton::Primary, 1);
+            window.type_text("y");
+            assert_eq!(text.get_untracked(), "ayb");
+        }
+
+        #[test]
+        fn toggle_shows_and_hides_the_secure_text() {
+            let text = RwSignal::new("secret".to_string());
+            let mut window =
+                TestWindow::new((300.0, 100.0), move || input(text).secure_with_toggle());
+            let node = accessibility::node_id(window.find("input").unwrap());
+            let role = |window: &mut TestWindow| {
+                let update = window.accessibility_update();
+                let (_, node) = update.nodes.iter().find(|(id, _)| *id == node).unwrap();
+                (node.role(), node.value().map(str::to_string))
+            };
+            assert_eq!(role(&mut window), (Role::PasswordInput, None));
+
+            let toggle = window.find_by_class::<SecureToggleClass>()[0];
+            window.click_view(toggle);
+            assert_eq!(
+                role(&mut window),
+                (Role::TextInput, Some("secret".to_string()))
+            );
+
+            window.click_view(toggle);
+            assert_eq!(role(&mut window), (Role::PasswordInput, None));
+        }
+
+        #[test]
+        fn text_is_parsed_on_focus_and_formatted_on_blur() {
+            let text = RwSignal::new("5 kg".to_string());
+            let mut window = TestWindow::new((300.0, 100.0), move || {
+                input(text)
+                    .parse_on_focus(|text| text.trim_end_matches(" kg").to_string())
+                    .format_on_blur(|text| format!("{text} kg"))
+            });
+            let id = window.find("input").unwrap();
+
+            window.click_view(id);
+            assert_eq!(text.get_untracked(), "5");
+            window.type_text("0");
+            assert_eq!(text.get_untracked(), "50");
+
+            press(&mut window, NamedKey::Escape);
+            assert_eq!(text.get_untracked(), "50 kg");
+
+            // focusing it again gives back the text to edit
+            window.click_view(id);
+            assert_eq!(text.get_untracked(), "50");
+        }
+    }
 }
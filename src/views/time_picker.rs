@@ -0,0 +1,304 @@
+#![deny(missing_docs)]
+
+//! An input for a time of day in the 24-hour or 12-hour clock. See [`time_picker`].
+
+use std::{fmt, str::FromStr};
+
+use floem_reactive::{RwSignal, SignalGet, SignalUpdate, create_effect};
+
+use super::{Decorators, NumberInput, label, number_input, text};
+use crate::{
+    id::ViewId,
+    style::Style,
+    style_class,
+    view::{IntoView, View},
+};
+
+style_class!(
+    /// The style class that is applied to all [`TimePicker`] views.
+    pub TimePickerClass
+);
+style_class!(
+    /// The style class that is applied to the `:` between the fields of a [`TimePicker`].
+    pub TimePickerSeparatorClass
+);
+style_class!(
+    /// The style class that is applied to the AM/PM toggle of a [`TimePicker`].
+    pub TimePickerPeriodClass
+);
+
+/// A time of day, without a time zone.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    hour: u8,
+    minute: u8,
+    second: u8,
+}
+
+/// The error returned when parsing a [`Time`] that isn't a valid `HH:MM` or `HH:MM:SS` time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseTimeError;
+
+impl fmt::Display for ParseTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid time, expected HH:MM or HH:MM:SS")
+    }
+}
+
+impl std::error::Error for ParseTimeError {}
+
+impl Time {
+    /// Midnight.
+    pub const MIDNIGHT: Time = Time {
+        hour: 0,
+        minute: 0,
+        second: 0,
+    };
+
+    /// The time at `hour` (`0` to `23`), `minute` and `second`, if it exists.
+    pub fn new(hour: u8, minute: u8, second: u8) -> Option<Time> {
+        (hour < 24 && minute < 60 && second < 60).then_some(Time {
+            hour,
+            minute,
+            second,
+        })
+    }
+
+    /// The hour in the 24-hour clock, from `0` to `23`.
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// The minute, from `0` to `59`.
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// The second, from `0` to `59`.
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// The hour in the 12-hour clock, from `1` to `12`.
+    pub fn hour12(&self) -> u8 {
+        match self.hour % 12 {
+            0 => 12,
+            hour => hour,
+        }
+    }
+
+    /// Whether the time is after noon.
+    pub fn is_pm(&self) -> bool {
+        self.hour >= 12
+    }
+
+    fn with_hour12(self, hour12: u8, pm: bool) -> Time {
+        Time {
+            hour: hour12 % 12 + if pm { 12 } else { 0 },
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for Time {
+    /// Formats the time as `HH:MM:SS`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)
+    }
+}
+
+impl FromStr for Time {
+    type Err = ParseTimeError;
+
+    /// Parses a `HH:MM` or `HH:MM:SS` time.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split(':');
+        let mut next = || {
+            parts.next().map(|part| {
+                if part.len() == 2 && part.bytes().all(|b| b.is_ascii_digit()) {
+                    part.parse().map_err(|_| ParseTimeError)
+                } else {
+                    Err(ParseTimeError)
+                }
+            })
+        };
+        let hour = next().ok_or(ParseTimeError)??;
+        let minute = next().ok_or(ParseTimeError)??;
+        let second = next().transpose()?.unwrap_or(0);
+        if next().is_some() {
+            return Err(ParseTimeError);
+        }
+        Time::new(hour, minute, second).ok_or(ParseTimeError)
+    }
+}
+
+/// An input for a [`Time`], with a [`NumberInput`] for the hour, the minute and optionally the
+/// second.
+///
+/// In the 12-hour clock, the hour goes from `1` to `12` and a toggle switches between AM and PM.
+///
+/// See [`time_picker`].
+pub struct TimePicker {
+    id: ViewId,
+    twelve_hour: RwSignal<bool>,
+    show_seconds: RwSignal<bool>,
+}
+
+/// Creates a [`TimePicker`] editing `time`.
+///
+/// The time is shown in the 24-hour clock without seconds by default.
+/// ```
+/// # use floem::prelude::*;
+/// let alarm = RwSignal::new(Time::new(7, 30, 0).unwrap());
+/// time_picker(alarm).twelve_hour(true);
+/// ```
+/// ### Reactivity
+/// The fields are updated whenever `time` changes.
+pub fn time_picker(time: RwSignal<Time>) -> TimePicker {
+    let id = ViewId::new();
+    let twelve_hour = RwSignal::new(false);
+    let show_seconds = RwSignal::new(false);
+
+    let hour = RwSignal::new(0.0);
+    let hour12 = RwSignal::new(12.0);
+    let minute = RwSignal::new(0.0);
+    let second = RwSignal::new(0.0);
+
+    // the fields only change when they are different, so that the effects don't loop
+    let set = |field: RwSignal<f64>, value: u8| {
+        if field.get_untracked() != f64::from(value) {
+            field.set(f64::from(value));
+        }
+    };
+    create_effect(move |_| {
+        let time = time.get();
+        set(hour, time.hour());
+        set(hour12, time.hour12());
+        set(minute, time.minute());
+        set(second, time.second());
+    });
+
+    let update = move |f: &dyn Fn(Time) -> Time| {
+        let current = time.get_untracked();
+        let new = f(current);
+        if new != current {
+            time.set(new);
+        }
+    };
+    create_effect(move |_| {
+        let hour = hour.get() as u8;
+        update(&|time| Time { hour, ..time });
+    });
+    create_effect(move |_| {
+        let hour12 = hour12.get() as u8;
+        update(&|time| time.with_hour12(hour12, time.is_pm()));
+    });
+    create_effect(move |_| {
+        let minute = minute.get() as u8;
+        update(&|time| Time { minute, ..time });
+    });
+    create_effect(move |_| {
+        let second = second.get() as u8;
+        update(&|time| Time { second, ..time });
+    });
+
+    let field = |value: RwSignal<f64>| -> NumberInput {
+        number_input(value)
+            .integer_digits(2)
+            .number_input_style(|s| s.show_buttons(false))
+    };
+    let separator = || text(":").class(TimePickerSeparatorClass);
+
+    let hour_input = field(hour)
+        .range(0.0..=23.0)
+        .style(move |s| s.apply_if(twelve_hour.get(), |s| s.hide()));
+    let hour12_input = field(hour12)
+        .range(1.0..=12.0)
+        .style(move |s| s.apply_if(!twelve_hour.get(), |s| s.hide()));
+    let minute_input = field(minute).range(0.0..=59.0);
+    let second_separator =
+        separator().style(move |s| s.apply_if(!show_seconds.get(), |s| s.hide()));
+    let second_input = field(second)
+        .range(0.0..=59.0)
+        .style(move |s| s.apply_if(!show_seconds.get(), |s| s.hide()));
+    let period = label(move || if time.get().is_pm() { "PM" } else { "AM" })
+        .class(TimePickerPeriodClass)
+        .on_click_stop(move |_| {
+            update(&|time| Time {
+                hour: (time.hour + 12) % 24,
+                ..time
+            })
+        })
+        .style(move |s| s.apply_if(!twelve_hour.get(), |s| s.hide()));
+
+    id.set_children([
+        hour_input.into_any(),
+        hour12_input.into_any(),
+        separator().into_any(),
+        minute_input.into_any(),
+        second_separator.into_any(),
+        second_input.into_any(),
+        period.into_any(),
+    ]);
+
+    TimePicker {
+        id,
+        twelve_hour,
+        show_seconds,
+    }
+    .class(TimePickerClass)
+}
+
+impl TimePicker {
+    /// Sets whether the time is shown in the 12-hour clock, with an AM/PM toggle.
+    pub fn twelve_hour(self, twelve_hour: bool) -> Self {
+        self.twelve_hour.set(twelve_hour);
+        self
+    }
+
+    /// Sets whether the seconds can be edited.
+    pub fn show_seconds(self, show_seconds: bool) -> Self {
+        self.show_seconds.set(show_seconds);
+        self
+    }
+}
+
+impl View for TimePicker {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Time Picker".into()
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::new().flex_row().items_center())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Time;
+
+    #[test]
+    fn parse_and_format() {
+        let time: Time = "07:05".parse().unwrap();
+        assert_eq!(time, Time::new(7, 5, 0).unwrap());
+        assert_eq!(time.to_string(), "07:05:00");
+        assert_eq!("23:59:59".parse(), Ok(Time::new(23, 59, 59).unwrap()));
+        assert!("24:00".parse::<Time>().is_err());
+        assert!("7:05".parse::<Time>().is_err());
+        assert!("07:05:00:00".parse::<Time>().is_err());
+    }
+
+    #[test]
+    fn twelve_hour_clock() {
+        let midnight = Time::MIDNIGHT;
+        assert_eq!((midnight.hour12(), midnight.is_pm()), (12, false));
+        let noon = Time::new(12, 0, 0).unwrap();
+        assert_eq!((noon.hour12(), noon.is_pm()), (12, true));
+        assert_eq!(noon.with_hour12(12, false), midnight);
+        assert_eq!(midnight.with_hour12(3, true).hour(), 15);
+    }
+}
@@ -0,0 +1,557 @@
+#![deny(missing_docs)]
+
+//! Notifications stacked in a corner of the window that dismiss themselves. See [`notify`].
+
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use floem_reactive::{RwSignal, Scope, SignalGet, SignalUpdate, SignalWith, with_scope};
+use web_time::{Duration, Instant};
+
+use super::{Decorators, button, dyn_stack, h_stack, h_stack_from_iter, label, text, v_stack};
+use crate::{
+    action::{TimerToken, add_overlay, exec_after},
+    animate,
+    event::EventListener,
+    id::ViewId,
+    style::{FlexDirection, Style, StyleClass, StyleClassRef},
+    style_class,
+    unit::UnitExt,
+    view::{IntoView, View},
+    window_handle::get_current_view,
+};
+
+style_class!(
+    /// The style class that is applied to every toast shown with [`notify`].
+    pub ToastClass
+);
+style_class!(
+    /// The style class that is applied to toasts of [`ToastKind::Info`].
+    pub ToastInfoClass
+);
+style_class!(
+    /// The style class that is applied to toasts of [`ToastKind::Success`].
+    pub ToastSuccessClass
+);
+style_class!(
+    /// The style class that is applied to toasts of [`ToastKind::Warning`].
+    pub ToastWarningClass
+);
+style_class!(
+    /// The style class that is applied to toasts of [`ToastKind::Danger`].
+    pub ToastDangerClass
+);
+style_class!(
+    /// The style class that is applied to the title of a toast.
+    pub ToastTitleClass
+);
+style_class!(
+    /// The style class that is applied to the button dismissing a toast.
+    pub ToastCloseClass
+);
+style_class!(
+    /// The style class that is applied to the count of the toasts waiting to be shown.
+    pub ToastCountClass
+);
+
+/// How long a toast is shown by default before it dismisses itself.
+pub const DEFAULT_TOAST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long toasts take to animate in and out.
+const TOAST_TRANSITION: Duration = Duration::from_millis(200);
+
+/// The distance kept between the toasts and the edges of the window.
+const TOAST_MARGIN: f64 = 16.0;
+
+/// The kind of a [`Toast`], deciding the theme color it is shown with.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ToastKind {
+    /// A neutral notification.
+    #[default]
+    Info,
+    /// A notification that something went well.
+    Success,
+    /// A notification that something might need attention.
+    Warning,
+    /// A notification that something went wrong.
+    Danger,
+}
+
+/// The corner of the window that toasts are stacked in. See [`set_toast_corner`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ToastCorner {
+    /// The top left corner.
+    TopLeft,
+    /// The top right corner.
+    TopRight,
+    /// The bottom left corner.
+    BottomLeft,
+    /// The bottom right corner.
+    #[default]
+    BottomRight,
+}
+
+impl ToastKind {
+    fn class_ref(self) -> StyleClassRef {
+        match self {
+            ToastKind::Info => ToastInfoClass::class_ref(),
+            ToastKind::Success => ToastSuccessClass::class_ref(),
+            ToastKind::Warning => ToastWarningClass::class_ref(),
+            ToastKind::Danger => ToastDangerClass::class_ref(),
+        }
+    }
+}
+
+impl ToastCorner {
+    fn is_top(self) -> bool {
+        matches!(self, ToastCorner::TopLeft | ToastCorner::TopRight)
+    }
+
+    fn is_left(self) -> bool {
+        matches!(self, ToastCorner::TopLeft | ToastCorner::BottomLeft)
+    }
+}
+
+/// A notification shown by [`notify`].
+/// ```
+/// # use floem::prelude::*;
+/// # use std::time::Duration;
+/// Toast::new("File deleted")
+///     .kind(ToastKind::Success)
+///     .timeout(Duration::from_secs(8))
+///     .action("Undo", || println!("restoring the file"));
+/// ```
+pub struct Toast {
+    title: Option<String>,
+    message: String,
+    kind: ToastKind,
+    timeout: Option<Duration>,
+    actions: Vec<(String, Rc<dyn Fn()>)>,
+}
+
+impl Toast {
+    /// Creates a toast showing `message`, of [`ToastKind::Info`], that dismisses itself after
+    /// [`DEFAULT_TOAST_TIMEOUT`].
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            title: None,
+            message: message.into(),
+            kind: ToastKind::default(),
+            timeout: Some(DEFAULT_TOAST_TIMEOUT),
+            actions: Vec::new(),
+        }
+    }
+
+    /// Shows `title` above the message.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the kind of the toast.
+    pub fn kind(mut self, kind: ToastKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Sets how long the toast is shown before it dismisses itself.
+    ///
+    /// The time doesn't run while the toast is waiting to be shown, or while it is hovered.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Keeps the toast shown until it is dismissed.
+    pub fn persistent(mut self) -> Self {
+        self.timeout = None;
+        self
+    }
+
+    /// Adds a button with `label` that calls `action` and dismisses the toast.
+    pub fn action(mut self, label: impl Into<String>, action: impl Fn() + 'static) -> Self {
+        self.actions.push((label.into(), Rc::new(action)));
+        self
+    }
+}
+
+/// Shows `toast` in the toasts of the current window.
+///
+/// Toasts are stacked in the [corner](set_toast_corner) of the window in the order they are
+/// shown. When more than the [maximum](set_max_visible_toasts) are shown, the others wait for
+/// their turn and are counted below the stack.
+/// ```no_run
+/// # use floem::prelude::*;
+/// button("Save").action(|| {
+///     notify(Toast::new("Saved").kind(ToastKind::Success));
+/// });
+/// ```
+pub fn notify(toast: Toast) -> ToastHandle {
+    let toaster = Toaster::current();
+    let id = toaster.next_id.get_untracked();
+    toaster.next_id.set(id + 1);
+    let entry = ToastEntry {
+        id,
+        toast: Rc::new(toast),
+        leaving: toaster.scope.create_rw_signal(false),
+    };
+    toaster.toasts.update(|toasts| toasts.push(entry));
+    ToastHandle { toaster, id }
+}
+
+/// Sets the corner of the current window that toasts are stacked in.
+///
+/// Toasts are stacked in the bottom right corner by default.
+pub fn set_toast_corner(corner: ToastCorner) {
+    Toaster::current().corner.set(corner);
+}
+
+/// Sets how many toasts are shown at once in the current window.
+///
+/// Three toasts are shown by default.
+pub fn set_max_visible_toasts(max: usize) {
+    Toaster::current().max_visible.set(max.max(1));
+}
+
+/// A handle to a toast shown by [`notify`], to dismiss it.
+#[derive(Clone, Copy)]
+pub struct ToastHandle {
+    toaster: Toaster,
+    id: u64,
+}
+
+impl ToastHandle {
+    /// Dismisses the toast, if it wasn't already.
+    pub fn dismiss(&self) {
+        self.toaster.dismiss(self.id);
+    }
+}
+
+#[derive(Clone)]
+struct ToastEntry {
+    id: u64,
+    toast: Rc<Toast>,
+    leaving: RwSignal<bool>,
+}
+
+/// The toasts of a window, held by the [`ToastStack`] overlay.
+#[derive(Clone, Copy)]
+struct Toaster {
+    scope: Scope,
+    toasts: RwSignal<Vec<ToastEntry>>,
+    corner: RwSignal<ToastCorner>,
+    max_visible: RwSignal<usize>,
+    next_id: RwSignal<u64>,
+}
+
+thread_local! {
+    static TOASTERS: RefCell<HashMap<ViewId, Toaster>> = RefCell::new(HashMap::new());
+}
+
+impl Toaster {
+    /// Returns the toaster of the current window, adding its overlay the first time.
+    fn current() -> Toaster {
+        let window = get_current_view();
+        if let Some(toaster) = TOASTERS.with_borrow(|toasters| toasters.get(&window).copied()) {
+            return toaster;
+        }
+
+        // the toasts outlive the view that showed them, so they get their own scope
+        let scope = Scope::new();
+        let toaster = Toaster {
+            scope,
+            toasts: scope.create_rw_signal(Vec::new()),
+            corner: scope.create_rw_signal(ToastCorner::default()),
+            max_visible: scope.create_rw_signal(3),
+            next_id: scope.create_rw_signal(0),
+        };
+        TOASTERS.with_borrow_mut(|toasters| toasters.insert(window, toaster));
+        add_overlay(with_scope(scope, || toast_stack(window, toaster)));
+        toaster
+    }
+
+    fn dismiss(&self, id: u64) {
+        // the signals are gone if the window was closed in the meantime
+        let Some((index, leaving)) = self.toasts.try_with_untracked(|toasts| {
+            let toasts = toasts?;
+            let index = toasts.iter().position(|entry| entry.id == id)?;
+            Some((index, toasts[index].leaving))
+        }) else {
+            return;
+        };
+        if index >= self.max_visible.get_untracked() {
+            // waiting to be shown, so there is nothing to animate
+            self.remove(id);
+        } else if !leaving.get_untracked() {
+            // hiding the toast plays its animation in reverse before it is removed
+            leaving.set(true);
+            let toaster = *self;
+            exec_after(TOAST_TRANSITION, move |_| toaster.remove(id));
+        }
+    }
+
+    fn remove(&self, id: u64) {
+        self.toasts
+            .try_update(|toasts| toasts.retain(|entry| entry.id != id));
+    }
+}
+
+/// The overlay holding the toasts of a window.
+struct ToastStack {
+    id: ViewId,
+    window: ViewId,
+    scope: Scope,
+}
+
+fn toast_stack(window: ViewId, toaster: Toaster) -> ToastStack {
+    let id = ViewId::new();
+    let Toaster {
+        toasts,
+        corner,
+        max_visible,
+        ..
+    } = toaster;
+
+    let direction = move || {
+        if corner.get().is_top() {
+            FlexDirection::Column
+        } else {
+            FlexDirection::ColumnReverse
+        }
+    };
+
+    let stack = dyn_stack(
+        move || {
+            let max_visible = max_visible.get();
+            toasts.with(|toasts| toasts.iter().take(max_visible).cloned().collect::<Vec<_>>())
+        },
+        |entry| entry.id,
+        move |entry| toast_view(toaster, entry),
+    )
+    .style(move |s| s.flex_direction(direction()).gap(8));
+
+    let waiting = move || toasts.with(Vec::len).saturating_sub(max_visible.get());
+    let count = label(move || format!("+{} more", waiting()))
+        .class(ToastCountClass)
+        .style(move |s| s.apply_if(waiting() == 0, |s| s.hide()));
+
+    id.set_children([stack.into_any(), count.into_any()]);
+
+    ToastStack {
+        id,
+        window,
+        scope: toaster.scope,
+    }
+    .style(move |s| {
+        let corner = corner.get();
+        s.flex_direction(direction())
+            .gap(8)
+            .apply_if(corner.is_top(), |s| s.inset_top(TOAST_MARGIN))
+            .apply_if(!corner.is_top(), |s| s.inset_bottom(TOAST_MARGIN))
+            .apply_if(corner.is_left(), |s| {
+                s.inset_left(TOAST_MARGIN).items_start()
+            })
+            .apply_if(!corner.is_left(), |s| {
+                s.inset_right(TOAST_MARGIN).items_end()
+            })
+    })
+}
+
+impl View for ToastStack {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Toast Stack".into()
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::new().flex_col())
+    }
+}
+
+impl Drop for ToastStack {
+    fn drop(&mut self) {
+        TOASTERS.with_borrow_mut(|toasters| toasters.remove(&self.window));
+        self.scope.dispose();
+    }
+}
+
+/// The timer dismissing a toast, paused while the toast is hovered.
+struct DismissTimer {
+    remaining: Duration,
+    started: Instant,
+    token: Option<TimerToken>,
+}
+
+impl DismissTimer {
+    fn start(timer: &Rc<RefCell<DismissTimer>>, dismiss: impl FnOnce() + 'static) {
+        let mut state = timer.borrow_mut();
+        if state.token.is_some() {
+            return;
+        }
+        state.started = animate::now();
+        state.token = Some(exec_after(state.remaining, move |_| dismiss()));
+    }
+
+    fn pause(&mut self) {
+        if let Some(token) = self.token.take() {
+            token.cancel();
+            self.remaining = self
+                .remaining
+                .saturating_sub(animate::now().saturating_duration_since(self.started));
+        }
+    }
+}
+
+fn toast_view(toaster: Toaster, entry: ToastEntry) -> impl IntoView {
+    let ToastEntry { id, toast, leaving } = entry;
+    let handle = ToastHandle { toaster, id };
+
+    let timer = toast.timeout.map(|remaining| {
+        let timer = Rc::new(RefCell::new(DismissTimer {
+            remaining,
+            started: animate::now(),
+            token: None,
+        }));
+        DismissTimer::start(&timer, move || handle.dismiss());
+        timer
+    });
+
+    let title = toast
+        .title
+        .clone()
+        .map(|title| text(title).class(ToastTitleClass).into_any());
+    let message = v_stack((title, text(toast.message.clone())))
+        .style(|s| s.flex_grow(1.0).flex_basis(0.0).min_width(0.0));
+    let actions = toast
+        .actions
+        .iter()
+        .map(|(label, action)| {
+            let action = action.clone();
+            button(label.clone()).action(move || {
+                action();
+                handle.dismiss();
+            })
+        })
+        .collect::<Vec<_>>();
+    let close = text("✕")
+        .class(ToastCloseClass)
+        .on_click_stop(move |_| handle.dismiss());
+
+    let view = h_stack((
+        message,
+        h_stack_from_iter(actions).style(|s| s.gap(4)),
+        close,
+    ))
+    .class(ToastClass);
+    view.id().add_class(toast.kind.class_ref());
+    view.on_event_cont(EventListener::PointerEnter, {
+        let timer = timer.clone();
+        move |_| {
+            if let Some(timer) = &timer {
+                timer.borrow_mut().pause();
+            }
+        }
+    })
+    .on_event_cont(EventListener::PointerLeave, move |_| {
+        if let Some(timer) = &timer {
+            DismissTimer::start(timer, move || handle.dismiss());
+        }
+    })
+    .style(move |s| s.apply_if(leaving.get(), |s| s.hide()))
+    .animation(|a| {
+        a.run_on_remove(true)
+            .duration(TOAST_TRANSITION)
+            .keyframe(0, |f| f.style(|s| s.opacity(0.).scale(90.pct())))
+            .keyframe(100, |f| f.computed_style().ease_out())
+    })
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::testing::TestWindow;
+
+    /// A window with a button showing the toast returned by `toast` for the number of
+    /// toasts shown so far, and the handles of the toasts it showed.
+    fn window(
+        toast: impl Fn(usize) -> Toast + 'static,
+    ) -> (TestWindow, RwSignal<Vec<ToastHandle>>) {
+        let handles = RwSignal::new(Vec::new());
+        let window = TestWindow::new((400.0, 300.0), move || {
+            button("Notify").debug_name("notify").action(move || {
+                let handle = notify(toast(handles.with_untracked(Vec::len)));
+                handles.update(|handles| handles.push(handle));
+            })
+        });
+        (window, handles)
+    }
+
+    fn notify_times(window: &mut TestWindow, times: usize) {
+        let button = window.find("notify").unwrap();
+        for _ in 0..times {
+            window.click_view(button);
+        }
+        window.frame();
+    }
+
+    /// The ids of the toasts that are shown or waiting to be shown, in order.
+    fn queued(handles: RwSignal<Vec<ToastHandle>>) -> Vec<u64> {
+        let toaster = handles.with_untracked(|handles| handles[0].toaster);
+        toaster
+            .toasts
+            .with_untracked(|toasts| toasts.iter().map(|entry| entry.id).collect())
+    }
+
+    #[test]
+    fn toasts_beyond_the_maximum_wait_for_their_turn() {
+        let (mut window, handles) = window(|n| Toast::new(format!("Toast {n}")).persistent());
+        notify_times(&mut window, 4);
+        assert_eq!(window.find_by_class::<ToastClass>().len(), 3);
+        assert_eq!(queued(handles), [0, 1, 2, 3]);
+
+        // dismissing a shown toast makes room for the first one waiting
+        handles.with_untracked(|handles| handles[1].dismiss());
+        window.advance(TOAST_TRANSITION);
+        assert_eq!(queued(handles), [0, 2, 3]);
+        // once the dismissed toast finished animating out
+        window.advance(TOAST_TRANSITION);
+        assert_eq!(window.find_by_class::<ToastClass>().len(), 3);
+    }
+
+    #[test]
+    fn dismissing_a_waiting_toast_removes_it_at_once() {
+        let (mut window, handles) = window(|n| Toast::new(format!("Toast {n}")).persistent());
+        notify_times(&mut window, 5);
+
+        handles.with_untracked(|handles| handles[4].dismiss());
+        assert_eq!(queued(handles), [0, 1, 2, 3]);
+
+        // a shown toast animates out before it is removed
+        handles.with_untracked(|handles| handles[0].dismiss());
+        window.frame();
+        assert_eq!(queued(handles), [0, 1, 2, 3]);
+        window.advance(TOAST_TRANSITION);
+        assert_eq!(queued(handles), [1, 2, 3]);
+    }
+
+    #[test]
+    fn hovering_a_toast_pauses_its_timeout() {
+        let (mut window, handles) = window(|_| Toast::new("Saved").timeout(Duration::from_secs(1)));
+        notify_times(&mut window, 1);
+        let toast = window.find_by_class::<ToastClass>()[0];
+
+        window.advance(Duration::from_millis(600));
+        window.pointer_move(window.rect(toast).center());
+        window.advance(Duration::from_secs(2));
+        assert_eq!(queued(handles), [0]);
+
+        // the time left when it was hovered runs once the pointer leaves
+        window.pointer_move((1.0, 299.0));
+        window.advance(Duration::from_millis(300));
+        assert_eq!(queued(handles), [0]);
+        window.advance(Duration::from_millis(100) + TOAST_TRANSITION);
+        assert_eq!(queued(handles), Vec::<u64>::new());
+    }
+}
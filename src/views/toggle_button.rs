@@ -10,6 +10,7 @@ use ui_events::pointer::PointerEvent;
 
 use crate::{
     Renderer,
+    accessibility::{Accessibility, Action, Role},
     event::EventPropagation,
     id::ViewId,
     prop, prop_extractor,
@@ -122,6 +123,14 @@ impl View for ToggleButton {
         "Toggle Button".into()
     }
 
+    fn accessibility(&self) -> Option<Accessibility> {
+        Some(
+            Accessibility::new(Role::Switch)
+                .toggled(self.state)
+                .action(Action::Click),
+        )
+    }
+
     fn update(&mut self, _cx: &mut crate::context::UpdateCx, state: Box<dyn std::any::Any>) {
         if let Ok(state) = state.downcast::<bool>() {
             if self.held == ToggleState::Nothing {
@@ -224,6 +233,12 @@ impl View for ToggleButton {
                     }
                 }
             }
+            crate::event::Event::Accessibility(request) if request.action == Action::Click => {
+                if let Some(ontoggle) = &self.ontoggle {
+                    ontoggle(!self.state);
+                }
+                return EventPropagation::Stop;
+            }
             _ => {}
         }
         EventPropagation::Continue
@@ -0,0 +1,1009 @@
+#![deny(missing_docs)]
+
+//! A virtualized hierarchical list. See [`tree_view`].
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    future::Future,
+    hash::Hash,
+    pin::Pin,
+    rc::Rc,
+};
+
+use floem_reactive::{
+    RwSignal, Scope, SignalGet, SignalTrack, SignalUpdate, SignalWith, create_effect, with_scope,
+};
+use peniko::kurbo::{Point, Rect};
+use ui_events::{
+    keyboard::{Key, KeyState, KeyboardEvent, Modifiers, NamedKey},
+    pointer::{PointerButton, PointerButtonEvent, PointerEvent},
+};
+
+use super::{Decorators, empty, h_stack_from_iter, text, virtual_stack};
+use crate::{
+    accessibility::{Accessibility, Role},
+    context::StyleCx,
+    event::{Event, EventListener, EventPropagation},
+    id::ViewId,
+    receiver_signal::Resource,
+    style::{Style, StyleClass},
+    style_class,
+    view::{IntoView, View},
+};
+
+style_class!(
+    /// The style class that is applied to all [`TreeView`] views.
+    pub TreeViewClass
+);
+style_class!(
+    /// The style class that is applied to the row of every node of a [`TreeView`].
+    pub TreeItemClass
+);
+style_class!(
+    /// The style class that is applied to the row of the focused node of a [`TreeView`].
+    pub TreeItemFocusedClass
+);
+style_class!(
+    /// The style class that is applied to each level of indentation of a [`TreeView`] row,
+    /// which draws the indentation guides.
+    pub TreeGuideClass
+);
+style_class!(
+    /// The style class that is applied to the disclosure arrow of a [`TreeView`] row.
+    pub TreeArrowClass
+);
+
+/// The children of a node of a [`TreeView`], returned by its `children_fn`.
+///
+/// `children_fn` is called for every visible node, so it should be cheap. The [`Lazy`](Self::Lazy)
+/// and [`Async`](Self::Async) variants only load the children once the node is expanded.
+pub enum TreeChildren<T> {
+    /// The node is a leaf and can't be expanded.
+    Leaf,
+    /// The children of the node.
+    Ready(Vec<T>),
+    /// A function loading the children when the node is expanded.
+    Lazy(Box<dyn FnOnce() -> Vec<T>>),
+    /// A future loading the children when the node is expanded, through a [`Resource`].
+    Async(Pin<Box<dyn Future<Output = Vec<T>>>>),
+}
+
+impl<T> TreeChildren<T> {
+    /// Loads the children with `load` when the node is expanded.
+    pub fn lazy(load: impl FnOnce() -> Vec<T> + 'static) -> Self {
+        Self::Lazy(Box::new(load))
+    }
+
+    /// Loads the children with the `load` future when the node is expanded.
+    pub fn future(load: impl Future<Output = Vec<T>> + 'static) -> Self {
+        Self::Async(Box::pin(load))
+    }
+}
+
+/// Where a node dragged in a [`TreeView`] was dropped, relative to the target node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeDropPosition {
+    /// Above the target node, as its previous sibling.
+    Before,
+    /// Below the target node, as its next sibling.
+    After,
+    /// Into the target node, as one of its children.
+    Inside,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum NodeState {
+    Leaf,
+    Collapsed,
+    Expanded,
+    Loading,
+}
+
+#[derive(Clone)]
+struct TreeRow<T, K> {
+    node: T,
+    key: K,
+    depth: usize,
+    state: NodeState,
+}
+
+enum LoadedChildren<T: 'static> {
+    Ready(Vec<T>),
+    Async(Resource<Option<Vec<T>>>, Scope),
+}
+
+/// Flattens the expanded part of a tree into rows, loading and caching the children of expanded nodes.
+struct Flattener<T: 'static, K> {
+    children_fn: Box<dyn Fn(&T) -> TreeChildren<T>>,
+    key_fn: Box<dyn Fn(&T) -> K>,
+    loaded: RefCell<HashMap<K, LoadedChildren<T>>>,
+    scope: Scope,
+}
+
+impl<T: Clone + 'static, K: Clone + Eq + Hash + 'static> Flattener<T, K> {
+    fn flatten(
+        &self,
+        node: T,
+        depth: usize,
+        expanded: &HashSet<K>,
+        rows: &mut imbl::Vector<TreeRow<T, K>>,
+        visited: &mut HashSet<K>,
+    ) {
+        let key = (self.key_fn)(&node);
+        let is_expanded = expanded.contains(&key);
+        let (state, children) = match (self.children_fn)(&node) {
+            TreeChildren::Leaf => (NodeState::Leaf, Vec::new()),
+            _ if !is_expanded => (NodeState::Collapsed, Vec::new()),
+            TreeChildren::Ready(children) => (NodeState::Expanded, children),
+            children => {
+                visited.insert(key.clone());
+                match self.load(&key, children) {
+                    Some(children) => (NodeState::Expanded, children),
+                    None => (NodeState::Loading, Vec::new()),
+                }
+            }
+        };
+        rows.push_back(TreeRow {
+            node,
+            key,
+            depth,
+            state,
+        });
+        for child in children {
+            self.flatten(child, depth + 1, expanded, rows, visited);
+        }
+    }
+
+    /// Returns the cached children of an expanded node, starting to load them if needed.
+    fn load(&self, key: &K, children: TreeChildren<T>) -> Option<Vec<T>> {
+        let mut loaded = self.loaded.borrow_mut();
+        let entry = loaded.entry(key.clone()).or_insert_with(|| match children {
+            TreeChildren::Lazy(load) => LoadedChildren::Ready(load()),
+            TreeChildren::Async(load) => {
+                let scope = self.scope.create_child();
+                let load = RefCell::new(Some(load));
+                let resource = with_scope(scope, || {
+                    Resource::new(
+                        || (),
+                        move |_| {
+                            let load = load.borrow_mut().take();
+                            async move {
+                                match load {
+                                    Some(load) => load.await,
+                                    None => Vec::new(),
+                                }
+                            }
+                        },
+                    )
+                });
+                LoadedChildren::Async(resource, scope)
+            }
+            TreeChildren::Leaf | TreeChildren::Ready(_) => unreachable!(),
+        });
+        match entry {
+            LoadedChildren::Ready(children) => Some(children.clone()),
+            LoadedChildren::Async(resource, _) => resource.get(),
+        }
+    }
+
+    /// Drops the children loaded for nodes that are no longer expanded, so they are
+    /// loaded again when the node is expanded the next time.
+    fn retain(&self, visited: &HashSet<K>) {
+        self.loaded.borrow_mut().retain(|key, loaded| {
+            let keep = visited.contains(key);
+            if let (false, LoadedChildren::Async(_, scope)) = (keep, loaded) {
+                scope.dispose();
+            }
+            keep
+        });
+    }
+}
+
+struct TreeState<T: 'static, K: 'static> {
+    tree: ViewId,
+    rows: RwSignal<imbl::Vector<TreeRow<T, K>>>,
+    expanded: RwSignal<HashSet<K>>,
+    selection: RwSignal<HashSet<K>>,
+    focused: RwSignal<Option<K>>,
+    anchor: RwSignal<Option<K>>,
+    multi_select: RwSignal<bool>,
+    row_height: RwSignal<f64>,
+}
+
+impl<T: 'static, K: 'static> Clone for TreeState<T, K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static, K: 'static> Copy for TreeState<T, K> {}
+
+impl<T: Clone + 'static, K: Clone + Eq + Hash + 'static> TreeState<T, K> {
+    fn index_of(&self, key: &K) -> Option<usize> {
+        self.rows
+            .with_untracked(|rows| rows.iter().position(|row| &row.key == key))
+    }
+
+    fn row(&self, idx: usize) -> Option<TreeRow<T, K>> {
+        self.rows.with_untracked(|rows| rows.get(idx).cloned())
+    }
+
+    fn set_expanded(&self, key: &K, expand: bool) {
+        self.expanded.update(|expanded| {
+            if expand {
+                expanded.insert(key.clone());
+            } else {
+                expanded.remove(key);
+            }
+        });
+    }
+
+    fn toggle_expanded(&self, key: &K) {
+        let expand = !self
+            .expanded
+            .with_untracked(|expanded| expanded.contains(key));
+        self.set_expanded(key, expand);
+    }
+
+    /// Selects a node. `extend` selects the range from the anchor node and `toggle` adds to or
+    /// removes from the current selection instead of replacing it.
+    fn select(&self, key: &K, extend: bool, toggle: bool) {
+        if !self.multi_select.get_untracked() {
+            self.anchor.set(Some(key.clone()));
+            self.selection.set(HashSet::from([key.clone()]));
+            return;
+        }
+        let range = self
+            .anchor
+            .get_untracked()
+            .filter(|_| extend)
+            .and_then(|anchor| Some((self.index_of(&anchor)?, self.index_of(key)?)));
+        if let Some((anchor, idx)) = range {
+            let keys = self.rows.with_untracked(|rows| {
+                rows.iter()
+                    .skip(anchor.min(idx))
+                    .take(anchor.abs_diff(idx) + 1)
+                    .map(|row| row.key.clone())
+                    .collect::<Vec<_>>()
+            });
+            self.selection.update(|selection| {
+                if !toggle {
+                    selection.clear();
+                }
+                selection.extend(keys);
+            });
+        } else if toggle {
+            self.anchor.set(Some(key.clone()));
+            self.selection.update(|selection| {
+                if !selection.remove(key) {
+                    selection.insert(key.clone());
+                }
+            });
+        } else {
+            self.anchor.set(Some(key.clone()));
+            self.selection.set(HashSet::from([key.clone()]));
+        }
+    }
+
+    /// Moves the focus to the row at `idx`, selecting it unless `command` is held.
+    fn focus_row(&self, idx: usize, extend: bool, command: bool) {
+        let Some(row) = self.row(idx) else {
+            return;
+        };
+        if extend {
+            if self.anchor.with_untracked(|anchor| anchor.is_none()) {
+                self.anchor.set(self.focused.get_untracked());
+            }
+            self.select(&row.key, true, false);
+        } else if !command {
+            self.select(&row.key, false, false);
+        }
+        self.focused.set(Some(row.key));
+        let height = self.row_height.get_untracked();
+        self.tree.scroll_to(Some(Rect::from_origin_size(
+            (0., idx as f64 * height),
+            (0., height),
+        )));
+    }
+
+    fn handle_key(&self, event: &KeyboardEvent) -> bool {
+        let len = self.rows.with_untracked(|rows| rows.len());
+        if len == 0 {
+            return false;
+        }
+        let command = if cfg!(target_os = "macos") {
+            event.modifiers.contains(Modifiers::META)
+        } else {
+            event.modifiers.contains(Modifiers::CONTROL)
+        };
+        let shift = event.modifiers.contains(Modifiers::SHIFT);
+        let idx = self
+            .focused
+            .get_untracked()
+            .and_then(|key| self.index_of(&key));
+        let Some(idx) = idx else {
+            if matches!(
+                event.key,
+                Key::Named(
+                    NamedKey::ArrowDown | NamedKey::ArrowUp | NamedKey::Home | NamedKey::End
+                )
+            ) {
+                self.focus_row(0, false, false);
+                return true;
+            }
+            return false;
+        };
+        let Some(row) = self.row(idx) else {
+            return false;
+        };
+
+        match &event.key {
+            Key::Named(NamedKey::ArrowUp) => self.focus_row(idx.saturating_sub(1), shift, command),
+            Key::Named(NamedKey::ArrowDown) => {
+                self.focus_row((idx + 1).min(len - 1), shift, command)
+            }
+            Key::Named(NamedKey::Home) => self.focus_row(0, shift, command),
+            Key::Named(NamedKey::End) => self.focus_row(len - 1, shift, command),
+            Key::Named(NamedKey::ArrowRight) => match row.state {
+                NodeState::Collapsed => self.set_expanded(&row.key, true),
+                NodeState::Expanded if idx + 1 < len => self.focus_row(idx + 1, false, false),
+                _ => {}
+            },
+            Key::Named(NamedKey::ArrowLeft) => match row.state {
+                NodeState::Expanded | NodeState::Loading => self.set_expanded(&row.key, false),
+                _ => {
+                    let parent = self.rows.with_untracked(|rows| {
+                        (0..idx)
+                            .rev()
+                            .find(|parent| rows[*parent].depth + 1 == row.depth)
+                    });
+                    if let Some(parent) = parent {
+                        self.focus_row(parent, false, false);
+                    }
+                }
+            },
+            Key::Named(NamedKey::Enter) => {
+                if row.state != NodeState::Leaf {
+                    self.toggle_expanded(&row.key);
+                }
+            }
+            Key::Character(c) if c == " " => self.select(&row.key, shift, command),
+            Key::Character(c) if command && c.eq_ignore_ascii_case("a") => {
+                if self.multi_select.get_untracked() {
+                    let keys = self
+                        .rows
+                        .with_untracked(|rows| rows.iter().map(|row| row.key.clone()).collect());
+                    self.selection.set(keys);
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[derive(Clone)]
+struct TreeDragPayload<K> {
+    tree: ViewId,
+    key: K,
+}
+
+type ReorderFn<K> = Rc<RefCell<Option<Box<dyn Fn(&K, &K, TreeDropPosition)>>>>;
+
+/// A virtualized hierarchical list of expandable nodes. See [`tree_view`].
+pub struct TreeView<T: 'static, K: 'static> {
+    id: ViewId,
+    state: TreeState<T, K>,
+    show_root: RwSignal<bool>,
+    on_reorder: ReorderFn<K>,
+}
+
+/// Creates a tree showing `root` and its descendants.
+///
+/// The children of a node are returned by `children_fn`, and can be loaded lazily or
+/// asynchronously when the node is first expanded (see [`TreeChildren`]). `key_fn` identifies
+/// a node across updates, and `view_fn` builds the view shown for it next to its indentation
+/// guides and disclosure arrow.
+///
+/// The visible nodes are flattened into a [`virtual_stack`], so put the tree in a
+/// [scroll view](super::scroll()) to show large trees. Every row has the same height, set with
+/// [`TreeView::row_height`].
+///
+/// The focused node moves with the up and down arrow keys, the right arrow expands it or moves
+/// to its first child and the left arrow collapses it or moves to its parent.
+///
+/// ## Example
+/// ```rust
+/// use floem::prelude::*;
+///
+/// #[derive(Clone)]
+/// struct Entry {
+///     path: String,
+///     depth: usize,
+/// }
+///
+/// tree_view(
+///     || Entry {
+///         path: "/".to_string(),
+///         depth: 0,
+///     },
+///     |entry: &Entry| {
+///         if entry.depth == 3 {
+///             return TreeChildren::Leaf;
+///         }
+///         let entry = entry.clone();
+///         TreeChildren::lazy(move || {
+///             (0..10)
+///                 .map(|i| Entry {
+///                     path: format!("{}{i}/", entry.path),
+///                     depth: entry.depth + 1,
+///                 })
+///                 .collect()
+///         })
+///     },
+///     |entry| entry.path.clone(),
+///     |entry| entry.path.clone(),
+/// )
+/// .scroll()
+/// .style(|s| s.size(300., 400.));
+/// ```
+pub fn tree_view<T, K, V>(
+    root: impl Fn() -> T + 'static,
+    children_fn: impl Fn(&T) -> TreeChildren<T> + 'static,
+    key_fn: impl Fn(&T) -> K + 'static,
+    view_fn: impl Fn(&T) -> V + 'static,
+) -> TreeView<T, K>
+where
+    T: Clone + 'static,
+    K: Clone + Eq + Hash + 'static,
+    V: IntoView + 'static,
+{
+    let id = ViewId::new();
+    let rows = RwSignal::new(imbl::Vector::new());
+    let expanded = RwSignal::new(HashSet::new());
+    let selection = RwSignal::new(HashSet::new());
+    let focused = RwSignal::new(None);
+    let row_height = RwSignal::new(24.);
+    let show_root = RwSignal::new(true);
+    let on_reorder: ReorderFn<K> = Rc::new(RefCell::new(None));
+
+    let flattener = Flattener {
+        children_fn: Box::new(children_fn),
+        key_fn: Box::new(key_fn),
+        loaded: RefCell::new(HashMap::new()),
+        scope: Scope::current(),
+    };
+    create_effect(move |_| {
+        let root = root();
+        let mut flat = imbl::Vector::new();
+        let mut visited = HashSet::new();
+        expanded.with(|expanded| {
+            flattener.flatten(root, 0, expanded, &mut flat, &mut visited);
+        });
+        flattener.retain(&visited);
+        if !show_root.get() {
+            let root = flat.pop_front();
+            flat = flat
+                .into_iter()
+                .map(|mut row| {
+                    row.depth -= 1;
+                    row
+                })
+                .collect();
+            // The root's children are always shown when the root is hidden
+            if let Some(root) = root.filter(|root| root.state == NodeState::Collapsed) {
+                expanded.update(|expanded| {
+                    expanded.insert(root.key);
+                });
+            }
+        }
+        rows.set(flat);
+    });
+
+    let state = TreeState {
+        tree: id,
+        rows,
+        expanded,
+        selection,
+        focused,
+        anchor: RwSignal::new(None),
+        multi_select: RwSignal::new(false),
+        row_height,
+    };
+    let stack = virtual_stack(
+        move || rows.get(),
+        |row: &TreeRow<T, K>| (row.key.clone(), row.depth, row.state),
+        {
+            let on_reorder = on_reorder.clone();
+            move |row: TreeRow<T, K>| tree_item(state, row, &view_fn, &on_reorder)
+        },
+    )
+    .item_size_fixed(move || row_height.get())
+    .style(|s| s.flex_col().width_full());
+    let stack_id = stack.id();
+    id.set_children([stack]);
+
+    create_effect(move |_| {
+        selection.track();
+        stack_id.request_style_recursive();
+    });
+
+    TreeView {
+        id,
+        state,
+        show_root,
+        on_reorder,
+    }
+    .keyboard_navigable()
+    .on_event(EventListener::KeyDown, move |e| {
+        if let Event::Key(
+            key_event @ KeyboardEvent {
+                state: KeyState::Down,
+                ..
+            },
+        ) = e
+        {
+            if state.handle_key(key_event) {
+                return EventPropagation::Stop;
+            }
+        }
+        EventPropagation::Continue
+    })
+}
+
+fn tree_item<T, K, V>(
+    state: TreeState<T, K>,
+    row: TreeRow<T, K>,
+    view_fn: &impl Fn(&T) -> V,
+    on_reorder: &ReorderFn<K>,
+) -> TreeItem<K>
+where
+    T: Clone + 'static,
+    K: Clone + Eq + Hash + 'static,
+    V: IntoView + 'static,
+{
+    let is_leaf = row.state == NodeState::Leaf;
+    let arrow = text(match row.state {
+        NodeState::Leaf => "",
+        NodeState::Collapsed => "▶",
+        NodeState::Expanded => "▼",
+        NodeState::Loading => "…",
+    })
+    .class(TreeArrowClass);
+    let arrow = if is_leaf {
+        arrow
+    } else {
+        let key = row.key.clone();
+        arrow.on_click_stop(move |_| state.toggle_expanded(&key))
+    };
+    let guides = (0..row.depth).map(|_| empty().class(TreeGuideClass).into_any());
+    let focused_key = row.key.clone();
+    let row_height = state.row_height;
+    let content =
+        h_stack_from_iter(guides.chain([arrow.into_any(), view_fn(&row.node).into_any()]))
+            .class(TreeItemClass)
+            .class_if(
+                move || {
+                    state
+                        .focused
+                        .with(|focused| focused.as_ref() == Some(&focused_key))
+                },
+                TreeItemFocusedClass,
+            )
+            .style(move |s| s.height(row_height.get()));
+
+    let id = ViewId::new();
+    let child = content.id();
+    id.set_children([content]);
+    let key = row.key.clone();
+    let item = TreeItem {
+        id,
+        key: row.key.clone(),
+        selection: state.selection,
+        child,
+    }
+    .on_event(EventListener::PointerDown, move |e| {
+        if let Event::Pointer(PointerEvent::Down(PointerButtonEvent {
+            button: Some(PointerButton::Primary),
+            state: pointer,
+            ..
+        })) = e
+        {
+            state.tree.request_focus();
+            if pointer.count == 2 && !is_leaf {
+                state.toggle_expanded(&key);
+            }
+            let command = if cfg!(target_os = "macos") {
+                pointer.modifiers.contains(Modifiers::META)
+            } else {
+                pointer.modifiers.contains(Modifiers::CONTROL)
+            };
+            state.focused.set(Some(key.clone()));
+            state.select(&key, pointer.modifiers.contains(Modifiers::SHIFT), command);
+        }
+        EventPropagation::Continue
+    });
+
+    if on_reorder.borrow().is_none() {
+        return item;
+    }
+    let payload = TreeDragPayload {
+        tree: state.tree,
+        key: row.key.clone(),
+    };
+    let target = row.key;
+    let on_reorder = on_reorder.clone();
+    item.draggable_with(move || payload.clone()).on_drop_if(
+        {
+            let target = target.clone();
+            move |payload: &TreeDragPayload<K>, _| {
+                payload.tree == state.tree && payload.key != target
+            }
+        },
+        move |payload: &TreeDragPayload<K>, point: Point| {
+            let height = row_height.get_untracked();
+            let position = if is_leaf {
+                if point.y < height / 2. {
+                    TreeDropPosition::Before
+                } else {
+                    TreeDropPosition::After
+                }
+            } else if point.y < height / 4. {
+                TreeDropPosition::Before
+            } else if point.y > height * 3. / 4. {
+                TreeDropPosition::After
+            } else {
+                TreeDropPosition::Inside
+            };
+            if let Some(on_reorder) = on_reorder.borrow().as_ref() {
+                on_reorder(&payload.key, &target, position);
+            }
+        },
+    )
+}
+
+impl<T: Clone + 'static, K: Clone + Eq + Hash + 'static> TreeView<T, K> {
+    /// Sets whether several nodes can be selected, using `Ctrl`/`Cmd` to toggle nodes
+    /// and `Shift` to select ranges. Only one node can be selected by default.
+    pub fn multi_select(self, multi_select: bool) -> Self {
+        self.state.multi_select.set(multi_select);
+        self
+    }
+
+    /// Sets the height of every row. Defaults to 24 pixels.
+    pub fn row_height(self, height: f64) -> Self {
+        self.state.row_height.set(height);
+        self
+    }
+
+    /// Sets whether the root node is shown. When it is hidden, its children are always expanded
+    /// and shown at the top level.
+    pub fn show_root(self, show_root: bool) -> Self {
+        self.show_root.set(show_root);
+        self
+    }
+
+    /// Allows nodes to be dragged onto other nodes, calling `on_reorder` with the keys of the
+    /// dragged node and the node it was dropped on, and where it was dropped.
+    ///
+    /// The tree doesn't move the node itself: `on_reorder` should update the data returned by
+    /// the tree's `children_fn`.
+    pub fn on_reorder(self, on_reorder: impl Fn(&K, &K, TreeDropPosition) + 'static) -> Self {
+        *self.on_reorder.borrow_mut() = Some(Box::new(on_reorder));
+        self
+    }
+
+    /// Returns the keys of the expanded nodes.
+    pub fn expanded(&self) -> RwSignal<HashSet<K>> {
+        self.state.expanded
+    }
+
+    /// Returns the keys of the selected nodes.
+    pub fn selection(&self) -> RwSignal<HashSet<K>> {
+        self.state.selection
+    }
+
+    /// Returns the key of the node focused with the keyboard.
+    pub fn focused(&self) -> RwSignal<Option<K>> {
+        self.state.focused
+    }
+}
+
+impl<T: 'static, K: 'static> View for TreeView<T, K> {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Tree View".into()
+    }
+
+    fn view_class(&self) -> Option<crate::style::StyleClassRef> {
+        Some(TreeViewClass::class_ref())
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::new().flex_col())
+    }
+
+    fn accessibility(&self) -> Option<Accessibility> {
+        Some(Accessibility::new(Role::Tree))
+    }
+}
+
+struct TreeItem<K: 'static> {
+    id: ViewId,
+    key: K,
+    selection: RwSignal<HashSet<K>>,
+    child: ViewId,
+}
+
+impl<K: Eq + Hash + 'static> TreeItem<K> {
+    fn is_selected(&self) -> bool {
+        self.selection
+            .with_untracked(|selection| selection.contains(&self.key))
+    }
+}
+
+impl<K: Eq + Hash + 'static> View for TreeItem<K> {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Tree Item".into()
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::new().flex_col())
+    }
+
+    fn accessibility(&self) -> Option<Accessibility> {
+        Some(Accessibility::new(Role::TreeItem).selected(self.is_selected()))
+    }
+
+    fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
+        if self.is_selected() {
+            cx.save();
+            cx.selected();
+            cx.style_view(self.child);
+            cx.restore();
+        } else {
+            cx.style_view(self.child);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::{testing::TestWindow, views::scroll};
+
+    const COMMAND: Modifiers = if cfg!(target_os = "macos") {
+        Modifiers::META
+    } else {
+        Modifiers::CONTROL
+    };
+
+    /// A tree of numbers where `1` has the children `2` and `3`, `2` has the children `4` and
+    /// `5`, and the child `6` of `3` is loaded lazily, counting the loads. The view of a node
+    /// is named after its number.
+    fn numbers() -> (TreeView<usize, usize>, Rc<Cell<usize>>) {
+        let loads = Rc::new(Cell::new(0));
+        let tree = tree_view(
+            || 1,
+            {
+                let loads = loads.clone();
+                move |node: &usize| match node {
+                    1 => TreeChildren::Ready(vec![2, 3]),
+                    2 => TreeChildren::Ready(vec![4, 5]),
+                    3 => {
+                        let loads = loads.clone();
+                        TreeChildren::lazy(move || {
+                            loads.set(loads.get() + 1);
+                            vec![6]
+                        })
+                    }
+                    _ => TreeChildren::Leaf,
+                }
+            },
+            |node| *node,
+            |node| text(node).debug_name(node.to_string()),
+        );
+        (tree, loads)
+    }
+
+    fn window(tree: TreeView<usize, usize>) -> TestWindow {
+        TestWindow::new((300.0, 400.0), move || {
+            scroll(tree).style(|s| s.size(300.0, 400.0))
+        })
+    }
+
+    /// The nodes shown in the window from top to bottom, with their indentation.
+    fn shown(window: &TestWindow) -> Vec<(usize, f64)> {
+        let mut shown = (1..=6)
+            .filter_map(|node| Some((node, window.rect(window.find(&node.to_string())?))))
+            .collect::<Vec<_>>();
+        shown.sort_by(|(_, a), (_, b)| a.y0.total_cmp(&b.y0));
+        let left = shown.first().map_or(0.0, |(_, rect)| rect.x0);
+        shown
+            .into_iter()
+            .map(|(node, rect)| (node, rect.x0 - left))
+            .collect()
+    }
+
+    fn nodes(window: &TestWindow) -> Vec<usize> {
+        shown(window).into_iter().map(|(node, _)| node).collect()
+    }
+
+    fn click(window: &mut TestWindow, node: usize, modifiers: Modifiers) {
+        window.set_modifiers(modifiers);
+        let node = window.find(&node.to_string()).unwrap();
+        window.click_view(node);
+        window.set_modifiers(Modifiers::empty());
+    }
+
+    fn press(window: &mut TestWindow, key: NamedKey) {
+        window.key_press(Key::Named(key), Modifiers::empty());
+    }
+
+    fn sorted(keys: RwSignal<HashSet<usize>>) -> Vec<usize> {
+        let mut keys = keys.get_untracked().into_iter().collect::<Vec<_>>();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn expanding_shows_the_children_below_their_parent() {
+        let (tree, loads) = numbers();
+        let focused = tree.focused();
+        let mut window = window(tree);
+        assert_eq!(nodes(&window), vec![1]);
+
+        click(&mut window, 1, Modifiers::empty());
+        press(&mut window, NamedKey::ArrowRight);
+        assert_eq!(nodes(&window), vec![1, 2, 3]);
+        // Lazy children are only loaded once their parent is expanded
+        assert_eq!(loads.get(), 0);
+
+        press(&mut window, NamedKey::End);
+        assert_eq!(focused.get_untracked(), Some(3));
+        press(&mut window, NamedKey::Enter);
+        assert_eq!(nodes(&window), vec![1, 2, 3, 6]);
+        assert_eq!(loads.get(), 1);
+
+        // A double click toggles a node too
+        let two = window.find("2").unwrap();
+        window.double_click(window.rect(two).center());
+        let shown = shown(&window);
+        assert_eq!(
+            shown.iter().map(|(node, _)| *node).collect::<Vec<_>>(),
+            vec![1, 2, 4, 5, 3, 6]
+        );
+        // Children are indented further than their parent
+        assert!(shown[0].1 < shown[1].1 && shown[1].1 < shown[2].1);
+        assert_eq!(shown[1].1, shown[4].1);
+    }
+
+    #[test]
+    fn collapsed_nodes_load_their_children_again() {
+        let (tree, loads) = numbers();
+        let expanded = tree.expanded();
+        let mut window = window(tree);
+        let set_expanded = |window: &mut TestWindow, node: usize, expand: bool| {
+            expanded.update(|expanded| {
+                if expand {
+                    expanded.insert(node);
+                } else {
+                    expanded.remove(&node);
+                }
+            });
+            window.frame();
+        };
+
+        set_expanded(&mut window, 1, true);
+        set_expanded(&mut window, 3, true);
+        assert_eq!(loads.get(), 1);
+
+        set_expanded(&mut window, 3, false);
+        assert_eq!(nodes(&window), vec![1, 2, 3]);
+        set_expanded(&mut window, 3, true);
+        assert_eq!(loads.get(), 2);
+
+        // Hiding an expanded node by collapsing its parent drops its children too
+        set_expanded(&mut window, 1, false);
+        assert_eq!(nodes(&window), vec![1]);
+        set_expanded(&mut window, 1, true);
+        assert_eq!(nodes(&window), vec![1, 2, 3, 6]);
+        assert_eq!(loads.get(), 3);
+    }
+
+    #[test]
+    fn clicks_select_ranges_and_toggle_nodes() {
+        let (tree, _) = numbers();
+        let tree = tree.multi_select(true);
+        let selection = tree.selection();
+        tree.expanded().set(HashSet::from([1, 2]));
+        let mut window = window(tree);
+
+        // Ranges follow the order of the rows: 1, 2, 4, 5, 3
+        click(&mut window, 2, Modifiers::empty());
+        click(&mut window, 5, Modifiers::SHIFT);
+        assert_eq!(sorted(selection), vec![2, 4, 5]);
+
+        click(&mut window, 3, COMMAND);
+        assert_eq!(sorted(selection), vec![2, 3, 4, 5]);
+        click(&mut window, 1, COMMAND | Modifiers::SHIFT);
+        assert_eq!(sorted(selection), vec![1, 2, 3, 4, 5]);
+        click(&mut window, 4, COMMAND);
+        assert_eq!(sorted(selection), vec![1, 2, 3, 5]);
+
+        click(&mut window, 3, Modifiers::empty());
+        assert_eq!(sorted(selection), vec![3]);
+    }
+
+    #[test]
+    fn single_selection_ignores_modifiers() {
+        let (tree, _) = numbers();
+        let selection = tree.selection();
+        tree.expanded().set(HashSet::from([1, 2]));
+        let mut window = window(tree);
+
+        click(&mut window, 2, Modifiers::empty());
+        click(&mut window, 5, COMMAND | Modifiers::SHIFT);
+        assert_eq!(sorted(selection), vec![5]);
+        window.key_press(Key::Named(NamedKey::ArrowUp), Modifiers::SHIFT);
+        assert_eq!(sorted(selection), vec![4]);
+    }
+
+    #[test]
+    fn arrow_left_collapses_or_moves_to_the_parent() {
+        let (tree, _) = numbers();
+        let (expanded, selection, focused) = (tree.expanded(), tree.selection(), tree.focused());
+        expanded.set(HashSet::from([1, 2]));
+        let mut window = window(tree);
+        click(&mut window, 5, Modifiers::empty());
+
+        press(&mut window, NamedKey::ArrowLeft);
+        assert_eq!(focused.get_untracked(), Some(2));
+        assert_eq!(sorted(selection), vec![2]);
+
+        press(&mut window, NamedKey::ArrowLeft);
+        assert_eq!(focused.get_untracked(), Some(2));
+        assert_eq!(nodes(&window), vec![1, 2, 3]);
+
+        press(&mut window, NamedKey::ArrowLeft);
+        assert_eq!(focused.get_untracked(), Some(1));
+        press(&mut window, NamedKey::ArrowLeft);
+        assert_eq!(nodes(&window), vec![1]);
+
+        // The root has no parent to move to
+        press(&mut window, NamedKey::ArrowLeft);
+        assert_eq!(focused.get_untracked(), Some(1));
+    }
+
+    #[test]
+    fn hidden_root_shows_its_children_at_the_top_level() {
+        let (tree, _) = numbers();
+        let tree = tree.show_root(false);
+        let focused = tree.focused();
+        let mut window = window(tree);
+        assert_eq!(shown(&window), vec![(2, 0.0), (3, 0.0)]);
+
+        click(&mut window, 2, Modifiers::empty());
+        press(&mut window, NamedKey::ArrowRight);
+        assert_eq!(nodes(&window), vec![2, 4, 5, 3]);
+
+        // Moving left from a top level node doesn't reach the hidden root
+        click(&mut window, 4, Modifiers::empty());
+        press(&mut window, NamedKey::ArrowLeft);
+        assert_eq!(focused.get_untracked(), Some(2));
+        press(&mut window, NamedKey::ArrowLeft);
+        assert_eq!(nodes(&window), vec![2, 3]);
+        press(&mut window, NamedKey::ArrowLeft);
+        assert_eq!(focused.get_untracked(), Some(2));
+    }
+}
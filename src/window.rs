@@ -11,6 +11,7 @@ pub use winit::window::WindowLevel;
 use crate::AnyView;
 use crate::app::{AppUpdateEvent, add_app_update_event};
 use crate::view::IntoView;
+use crate::window_handle::WindowHandle;
 
 pub struct WindowCreation {
     pub(crate) view_fn: Box<dyn FnOnce(WindowId) -> AnyView>,
@@ -639,3 +640,117 @@ pub fn new_window<V: IntoView + 'static>(
 pub fn close_window(window_id: WindowId) {
     add_app_update_event(AppUpdateEvent::CloseWindow { window_id });
 }
+
+/// Renders a view offscreen into an image, without opening a window or needing a GPU.
+///
+/// The view is mounted in a headless window of the given logical `size` with the default
+/// theme, styled, laid out and painted with tiny-skia at `scale` pixels per logical pixel.
+/// The image is in premultiplied RGBA, `size` times `scale` pixels large. Use
+/// [`ViewId::render_to_image`](crate::ViewId::render_to_image) to render a view that is
+/// already shown in a window.
+///
+/// ```rust,ignore
+/// let thumbnail = render_view_to_image(dashboard(), (800.0, 600.0), 0.25);
+/// ```
+pub fn render_view_to_image<V: IntoView + 'static>(
+    view: V,
+    size: impl Into<Size>,
+    scale: f64,
+) -> peniko::ImageBrush {
+    let mut handle = WindowHandle::new_headless(move || view.into_any(), size.into(), scale, true);
+    handle.process_update_no_paint();
+    let image = handle.render_to_image(handle.window_state.root_view_id, scale);
+    handle.destroy();
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use peniko::color::palette::css;
+
+    use super::render_view_to_image;
+    use crate::views::{Decorators, empty};
+
+    #[test]
+    fn renders_a_view_offscreen() {
+        let image = render_view_to_image(
+            empty().style(|s| s.size(10.0, 10.0).background(css::RED)),
+            (20.0, 10.0),
+            2.0,
+        );
+        let image = image.image;
+        assert_eq!((image.width, image.height), (40, 20));
+        let pixel = |x: usize, y: usize| {
+            let i = (y * image.width as usize + x) * 4;
+            image.data.data()[i..i + 4].to_vec()
+        };
+        assert_eq!(pixel(5, 5), [255, 0, 0, 255]);
+        assert_ne!(pixel(30, 5), [255, 0, 0, 255]);
+    }
+
+    #[cfg(feature = "testing")]
+    mod window {
+        use std::{cell::Cell, rc::Rc};
+
+        use floem_reactive::{RwSignal, SignalGet, SignalUpdate};
+
+        use crate::{
+            IntoView,
+            testing::TestWindow,
+            views::{Decorators, dyn_container, empty},
+        };
+
+        #[test]
+        fn renders_a_view_once_it_is_laid_out() {
+            let mut window = TestWindow::new((100.0, 100.0), empty);
+            let size = Rc::new(Cell::new(None));
+            let image_size = size.clone();
+            window.root().render_to_image(2.0, move |image| {
+                image_size.set(Some((image.image.width, image.image.height)));
+            });
+            window.frame();
+            assert_eq!(size.get(), Some((200, 200)));
+        }
+
+        #[test]
+        fn drops_the_callback_of_a_removed_view() {
+            let show = RwSignal::new(true);
+            let shown = Rc::new(Cell::new(None));
+            let shown_id = shown.clone();
+            let mut window = TestWindow::new((100.0, 100.0), move || {
+                dyn_container(
+                    move || show.get(),
+                    move |show| {
+                        let view = empty().style(|s| s.size(10.0, 10.0));
+                        if show {
+                            shown_id.set(Some(view.id()));
+                        }
+                        view.into_any()
+                    },
+                )
+            });
+            let called = Rc::new(Cell::new(false));
+            let on_image_called = called.clone();
+            shown.get().unwrap().render_to_image(1.0, move |_| {
+                on_image_called.set(true);
+            });
+            show.set(false);
+            window.frame();
+            assert!(!called.get());
+            assert_eq!(Rc::strong_count(&called), 1);
+        }
+
+        #[test]
+        fn drops_the_callback_when_the_window_closes() {
+            let window = TestWindow::new((100.0, 100.0), empty);
+            let called = Rc::new(Cell::new(false));
+            let on_image_called = called.clone();
+            window.root().render_to_image(1.0, move |_| {
+                on_image_called.set(true);
+            });
+            drop(window);
+            assert!(!called.get());
+            assert_eq!(Rc::strong_count(&called), 1);
+        }
+    }
+}
@@ -16,7 +16,7 @@ use floem_reactive::{RwSignal, Scope, SignalGet, SignalUpdate, with_scope};
 use floem_renderer::Renderer;
 use floem_renderer::gpu_resources::GpuResources;
 use peniko::color::palette;
-use peniko::kurbo::{Affine, Point, Size};
+use peniko::kurbo::{Affine, Point, Size, Vec2};
 use winit::{
     cursor::CursorIcon,
     dpi::{LogicalPosition, LogicalSize},
@@ -35,6 +35,7 @@ use crate::unit::UnitExt;
 use crate::views::{Decorators, container, stack};
 use crate::{
     Application,
+    accessibility::{AccessibilityRequest, AccessibilityTree, ActionRequest, Adapter, TreeUpdate},
     app::UserEvent,
     context::{
         ComputeLayoutCx, EventCx, FrameUpdate, LayoutCx, PaintCx, PaintState, StyleCx, UpdateCx,
@@ -48,12 +49,16 @@ use crate::{
     theme::default_theme,
     update::{
         CENTRAL_DEFERRED_UPDATE_MESSAGES, CENTRAL_UPDATE_MESSAGES, CURRENT_RUNNING_VIEW_HANDLE,
-        DEFERRED_UPDATE_MESSAGES, UPDATE_MESSAGES, UpdateMessage,
+        DEFERRED_UPDATE_MESSAGES, ImageCallback, UPDATE_MESSAGES, UpdateMessage,
     },
     view::{IntoView, View, view_tab_navigation},
     view_state::ChangeFlags,
+    view_storage::VIEW_STORAGE,
     window_state::WindowState,
-    window_tracking::{remove_window_id_mapping, store_window_id_mapping},
+    window_tracking::{
+        remove_headless_root, remove_window_id_mapping, store_headless_root,
+        store_window_id_mapping,
+    },
 };
 
 /// The top-level window handle that owns the winit `Window`.
@@ -63,8 +68,9 @@ use crate::{
 /// - processing all requests to update the animation state from the reactive system
 /// - requesting a new animation frame from the backend
 pub(crate) struct WindowHandle {
-    pub(crate) window: Arc<dyn winit::window::Window>,
-    window_id: WindowId,
+    /// The winit window, or `None` for a headless window
+    pub(crate) window: Option<Arc<dyn winit::window::Window>>,
+    window_id: Option<WindowId>,
     id: ViewId,
     main_view: ViewId,
     /// Reactive Scope for this `WindowHandle`
@@ -85,6 +91,12 @@ pub(crate) struct WindowHandle {
     pub(crate) window_menu_actions: HashMap<MenuId, Box<dyn Fn()>>,
     pub(crate) window_menu: Option<muda::Menu>,
     pub(crate) event_reducer: WindowEventReducer,
+    accessibility: AccessibilityTree,
+    /// Connects `accessibility` to the assistive technologies of the platform, or `None` for
+    /// headless windows.
+    accessibility_adapter: Option<Adapter>,
+    /// Views to render offscreen once style and layout are up to date
+    pending_images: Vec<(ViewId, f64, ImageCallback)>,
 }
 
 impl WindowHandle {
@@ -143,6 +155,8 @@ impl WindowHandle {
 
         let window: Arc<dyn Window> = window.into();
         store_window_id_mapping(id, window_id, &window);
+        // Created before the window is shown, as the platform adapters require
+        let accessibility_adapter = Adapter::new(window.as_ref());
 
         let paint_state = if let Some(resources) = gpu_resources.clone() {
             let surface = resources
@@ -179,8 +193,10 @@ impl WindowHandle {
         let window_state = WindowState::new(id, os_theme);
 
         let mut window_handle = Self {
-            window,
-            window_id,
+            window: Some(window),
+            window_id: Some(window_id),
+            accessibility: AccessibilityTree::default(),
+            accessibility_adapter,
             id,
             main_view: main_view_id,
             scope,
@@ -203,6 +219,7 @@ impl WindowHandle {
             window_menu_actions: HashMap::new(),
             window_menu: None,
             event_reducer: WindowEventReducer::default(),
+            pending_images: Vec::new(),
         };
         if paint_state_initialized {
             window_handle.init_renderer(gpu_resources);
@@ -221,6 +238,73 @@ impl WindowHandle {
         window_handle
     }
 
+    /// Creates a window handle that isn't backed by a winit window or a GPU surface.
+    ///
+    /// Events, style, layout and paint run as usual, but window requests such as changing
+    /// the title or the cursor are ignored.
+    pub(crate) fn new_headless(
+        view_fn: impl FnOnce() -> Box<dyn View> + 'static,
+        size: Size,
+        scale: f64,
+        apply_default_theme: bool,
+    ) -> Self {
+        let scope = Scope::new();
+        let id = ViewId::new();
+        let size = scope.create_rw_signal(size);
+        store_headless_root(id);
+
+        set_current_view(id);
+
+        let (main_view_id, widget) = with_scope(scope, move || {
+            let main_view = view_fn();
+            (main_view.id(), main_view)
+        });
+        id.set_children([widget]);
+
+        let view = WindowView { id };
+        id.set_view(view.into_any());
+
+        let window_state = WindowState::new(id, None);
+
+        let mut window_handle = Self {
+            window: None,
+            window_id: None,
+            accessibility: AccessibilityTree::default(),
+            accessibility_adapter: None,
+            id,
+            main_view: main_view_id,
+            scope,
+            paint_state: PaintState::new_headless(scale, size.get_untracked() * scale),
+            size,
+            default_theme: match apply_default_theme {
+                true => Some(default_theme(window_state.light_dark_theme)),
+                false => None,
+            },
+            window_state,
+            is_maximized: false,
+            transparent: false,
+            profile: None,
+            scale,
+            modifiers: Modifiers::default(),
+            cursor_position: Point::ZERO,
+            window_position: Point::ZERO,
+            #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+            context_menu: scope.create_rw_signal(None),
+            window_menu_actions: HashMap::new(),
+            window_menu: None,
+            event_reducer: WindowEventReducer::default(),
+            pending_images: Vec::new(),
+        };
+        window_handle
+            .window_state
+            .set_root_size(size.get_untracked());
+        window_handle.event(Event::ThemeChanged(
+            window_handle.window_state.light_dark_theme,
+        ));
+        window_handle.size(size.get_untracked());
+        window_handle
+    }
+
     pub(crate) fn init_renderer(&mut self, gpu_resources: Option<GpuResources>) {
         // On the web, we need to get the canvas size once. The size will be updated automatically
         // when the canvas element is resized subsequently. This is the correct place to do so
@@ -242,7 +326,9 @@ impl WindowHandle {
         }
         // Now that the renderer is initialized, draw the first frame
         self.render_frame(gpu_resources);
-        self.window.set_visible(true);
+        if let Some(window) = &self.window {
+            window.set_visible(true);
+        }
     }
 
     pub fn event(&mut self, event: Event) {
@@ -260,16 +346,35 @@ impl WindowHandle {
         } else {
             None
         };
-        let (was_hovered, was_dragging_over) = if is_pointer_move.is_some() {
+        let (was_hovered, was_dragging_over, was_drop_target) = if is_pointer_move.is_some() {
             cx.window_state.cursor = None;
             let was_hovered = std::mem::take(&mut cx.window_state.hovered);
             let was_dragging_over = std::mem::take(&mut cx.window_state.dragging_over);
+            let was_drop_target = cx.window_state.drop_target.take();
 
-            (Some(was_hovered), Some(was_dragging_over))
+            (Some(was_hovered), Some(was_dragging_over), was_drop_target)
         } else {
-            (None, None)
+            (None, None, None)
         };
 
+        if let Event::Key(KeyboardEvent {
+            key: Key::Named(NamedKey::Escape),
+            state: KeyState::Down,
+            ..
+        }) = &event
+        {
+            if cx.window_state.is_dragging() {
+                // Cancels the drag, animating the dragged view back to its place
+                let release_location = cx.window_state.last_cursor_location;
+                if let Some(id) = cx.window_state.end_drag(Some(release_location)) {
+                    cx.window_state.active = None;
+                    id.apply_event(&EventListener::DragEnd, &event);
+                }
+                self.process_update();
+                return;
+            }
+        }
+
         let was_file_hovered = if matches!(event, Event::FileDrag(FileDragEvent::DragMoved { .. }))
             || is_pointer_move.is_some()
         {
@@ -301,10 +406,13 @@ impl WindowHandle {
                         .is_processed();
                 }
 
+                // a focus trap gets the key events instead of the views outside of it
+                let focus_root = cx.window_state.focus_trap();
+
                 if !processed {
                     if let Some(listener) = event.listener() {
-                        processed |= self
-                            .main_view
+                        processed |= focus_root
+                            .unwrap_or(self.main_view)
                             .apply_event(&listener, &event)
                             .is_some_and(|prop| prop.is_processed());
                     }
@@ -322,7 +430,11 @@ impl WindowHandle {
                             && (modifiers.is_empty() || *modifiers == Modifiers::SHIFT)
                         {
                             let backwards = modifiers.contains(Modifiers::SHIFT);
-                            view_tab_navigation(self.id, cx.window_state, backwards);
+                            view_tab_navigation(
+                                focus_root.unwrap_or(self.id),
+                                cx.window_state,
+                                backwards,
+                            );
                             // view_debug_tree(&self.view);
                         } else if *modifiers == Modifiers::ALT {
                             if let Key::Named(
@@ -332,7 +444,11 @@ impl WindowHandle {
                                 | NamedKey::ArrowRight),
                             ) = key
                             {
-                                view_arrow_navigation(*name, cx.window_state, self.id);
+                                view_arrow_navigation(
+                                    *name,
+                                    cx.window_state,
+                                    focus_root.unwrap_or(self.id),
+                                );
                             }
                         }
                     }
@@ -417,6 +533,11 @@ impl WindowHandle {
                     );
                 }
             }
+            if was_drop_target != cx.window_state.drop_target {
+                for id in was_drop_target.iter().chain(&cx.window_state.drop_target) {
+                    id.request_style();
+                }
+            }
             let dragging_over = &cx.window_state.dragging_over.clone();
             for id in was_dragging_over
                 .unwrap()
@@ -508,7 +629,9 @@ impl WindowHandle {
             self.window_state.theme_overriden = false;
         }
         if !change_from_os {
-            self.window.set_theme(theme);
+            if let Some(window) = &self.window {
+                window.set_theme(theme);
+            }
         }
         self.id.request_all();
         request_recursive_changes(self.id, ChangeFlags::STYLE);
@@ -519,13 +642,16 @@ impl WindowHandle {
 
     pub(crate) fn size(&mut self, size: Size) {
         self.size.set(size);
+        if let (Some(adapter), Some(window)) = (&mut self.accessibility_adapter, &self.window) {
+            adapter.bounds_changed(window.as_ref());
+        }
         self.window_state.update_screen_size_bp(size);
         self.event(Event::WindowResized(size));
         let scale = self.scale * self.window_state.scale;
         self.paint_state.resize(scale, size * self.scale);
         self.window_state.set_root_size(size);
 
-        let is_maximized = self.window.is_maximized();
+        let is_maximized = self.window.as_ref().is_some_and(|w| w.is_maximized());
         if is_maximized != self.is_maximized {
             self.is_maximized = is_maximized;
             self.event(Event::WindowMaximizeChanged(is_maximized));
@@ -539,6 +665,9 @@ impl WindowHandle {
 
     pub(crate) fn position(&mut self, point: Point) {
         self.window_position = point;
+        if let (Some(adapter), Some(window)) = (&mut self.accessibility_adapter, &self.window) {
+            adapter.bounds_changed(window.as_ref());
+        }
         self.event(Event::WindowMoved(point));
     }
 
@@ -596,6 +725,9 @@ impl WindowHandle {
     }
 
     pub(crate) fn focused(&mut self, focused: bool) {
+        if let Some(adapter) = &mut self.accessibility_adapter {
+            adapter.focused(focused);
+        }
         if focused {
             #[cfg(target_os = "macos")]
             if let Some(window_menu) = &self.window_menu {
@@ -660,6 +792,7 @@ impl WindowHandle {
     }
 
     pub fn paint(&mut self, gpu_resources: Option<GpuResources>) -> Option<peniko::ImageBrush> {
+        let background = self.background();
         let mut cx = PaintCx {
             window_state: &mut self.window_state,
             paint_state: &mut self.paint_state,
@@ -679,13 +812,8 @@ impl WindowHandle {
         cx.paint_state
             .renderer_mut()
             .begin(cx.window_state.capture.is_some());
-        if !self.transparent {
+        if let Some(color) = background {
             let scale = cx.window_state.scale;
-            let color = self
-                .default_theme
-                .as_ref()
-                .and_then(|theme| theme.get(crate::style::Background))
-                .unwrap_or(peniko::Brush::Solid(palette::css::WHITE));
             // fill window with default white background if it's not transparent
             cx.fill(
                 &self
@@ -700,11 +828,72 @@ impl WindowHandle {
         }
         cx.paint_view(self.id);
         if cx.window_state.capture.is_none() {
-            self.window.pre_present_notify();
+            if let Some(window) = &self.window {
+                window.pre_present_notify();
+            }
         }
         cx.paint_state.renderer_mut().finish()
     }
 
+    /// The brush the window is filled with before painting, `None` if it's transparent.
+    fn background(&self) -> Option<peniko::Brush> {
+        if self.transparent {
+            return None;
+        }
+        let color = self
+            .default_theme
+            .as_ref()
+            .and_then(|theme| theme.get(crate::style::Background))
+            .unwrap_or(peniko::Brush::Solid(palette::css::WHITE));
+        Some(color)
+    }
+
+    /// Paints the view `id` and its children into an offscreen tiny-skia pixmap at `scale`
+    /// pixels per logical pixel, over the window background.
+    ///
+    /// The view is painted at its current layout, so style and layout have to be up to date.
+    pub(crate) fn render_to_image(&mut self, id: ViewId, scale: f64) -> peniko::ImageBrush {
+        let background = self.background();
+        let (location, size) = id
+            .get_layout()
+            .map(|layout| {
+                (
+                    Vec2::new(layout.location.x as f64, layout.location.y as f64),
+                    Size::new(layout.size.width as f64, layout.size.height as f64),
+                )
+            })
+            .unwrap_or_default();
+        let mut paint_state = PaintState::new_headless(scale, (size * scale).ceil());
+        // Undo the offset and transform `paint_view` applies, so the view is painted at the
+        // origin of the image
+        let transform = (Affine::translate(location) * id.state().borrow().transform).inverse();
+        let mut cx = PaintCx {
+            window_state: &mut self.window_state,
+            paint_state: &mut paint_state,
+            transform,
+            clip: None,
+            z_index: None,
+            saved_transforms: Vec::new(),
+            saved_clips: Vec::new(),
+            saved_z_indexes: Vec::new(),
+            gpu_resources: None,
+            window: self.window.clone(),
+            #[cfg(feature = "vello")]
+            saved_layer_counts: Vec::new(),
+            #[cfg(feature = "vello")]
+            layer_count: 0,
+        };
+        cx.paint_state.renderer_mut().begin(true);
+        if let Some(color) = background {
+            cx.fill(&size.to_rect().expand(), &color, 0.0);
+        }
+        cx.paint_view(id);
+        cx.paint_state
+            .renderer_mut()
+            .finish()
+            .expect("offscreen renderers return the captured frame")
+    }
+
     pub(crate) fn capture(&mut self, gpu_resources: Option<GpuResources>) -> Capture {
         // Capture the view before we run `style` and `layout` to catch missing `request_style`` or
         // `request_layout` flags.
@@ -810,12 +999,23 @@ impl WindowHandle {
                     self.compute_layout();
                 }
             }
+            if !self.pending_images.is_empty() {
+                // Rendered only now, so the views built or changed in this update are laid out
+                for (id, scale, on_image) in mem::take(&mut self.pending_images) {
+                    // The callback of a view removed in the meantime is dropped uncalled
+                    if VIEW_STORAGE.with_borrow(|s| s.view_ids.contains_key(id)) {
+                        on_image(self.render_to_image(id, scale));
+                    }
+                }
+                continue;
+            }
             if !self.has_deferred_update_messages() {
                 break;
             }
             self.process_deferred_update_messages();
         }
 
+        self.update_accessibility();
         self.set_cursor();
 
         // TODO: This should only use `self.window_state.request_paint)`
@@ -834,6 +1034,9 @@ impl WindowHandle {
                         if let Some(root) = id.root() {
                             let msgs = msgs.entry(root).or_default();
                             msgs.push(msg);
+                        } else if VIEW_STORAGE.with_borrow(|s| !s.view_ids.contains_key(id)) {
+                            // The view was removed, so nothing will handle its messages. They
+                            // are dropped, along with any callbacks they hold.
                         } else {
                             // Messages that are not for our root get put back - they may
                             // belong to another window, or may be construction-time messages
@@ -905,6 +1108,12 @@ impl WindowHandle {
                             cx.window_state.focus_changed(Some(id), None);
                         }
                     }
+                    UpdateMessage::TrapFocus(id) => {
+                        cx.window_state.trap_focus(id);
+                    }
+                    UpdateMessage::ReleaseFocus(id) => {
+                        cx.window_state.release_focus_traps(id);
+                    }
                     UpdateMessage::ClearAppFocus => {
                         let focus = cx.window_state.focus;
                         cx.window_state.clear_focus();
@@ -946,29 +1155,43 @@ impl WindowHandle {
                         view.borrow_mut().update(&mut cx, state);
                     }
                     UpdateMessage::DragWindow => {
-                        let _ = self.window.drag_window();
+                        if let Some(window) = &self.window {
+                            let _ = window.drag_window();
+                        }
                     }
                     UpdateMessage::FocusWindow => {
-                        self.window.focus_window();
+                        if let Some(window) = &self.window {
+                            window.focus_window();
+                        }
                     }
                     UpdateMessage::DragResizeWindow(direction) => {
-                        let _ = self.window.drag_resize_window(direction);
+                        if let Some(window) = &self.window {
+                            let _ = window.drag_resize_window(direction);
+                        }
                     }
                     UpdateMessage::ToggleWindowMaximized => {
-                        self.window.set_maximized(!self.window.is_maximized());
+                        if let Some(window) = &self.window {
+                            window.set_maximized(!window.is_maximized());
+                        }
                     }
                     UpdateMessage::SetWindowMaximized(maximized) => {
-                        self.window.set_maximized(maximized);
+                        if let Some(window) = &self.window {
+                            window.set_maximized(maximized);
+                        }
                     }
                     UpdateMessage::MinimizeWindow => {
-                        self.window.set_minimized(true);
+                        if let Some(window) = &self.window {
+                            window.set_minimized(true);
+                        }
                     }
                     UpdateMessage::SetWindowDelta(delta) => {
                         let pos = self.window_position + delta;
-                        self.window
-                            .set_outer_position(winit::dpi::Position::Logical(
-                                winit::dpi::LogicalPosition::new(pos.x, pos.y),
-                            ));
+                        let Some(window) = &self.window else {
+                            continue;
+                        };
+                        window.set_outer_position(winit::dpi::Position::Logical(
+                            winit::dpi::LogicalPosition::new(pos.x, pos.y),
+                        ));
                     }
                     UpdateMessage::WindowScale(scale) => {
                         cx.window_state.scale = scale;
@@ -1003,10 +1226,15 @@ impl WindowHandle {
                         self.window_menu = Some(menu);
                     }
                     UpdateMessage::SetWindowTitle { title } => {
-                        self.window.set_title(&title);
+                        if let Some(window) = &self.window {
+                            window.set_title(&title);
+                        }
                     }
                     UpdateMessage::SetImeAllowed { allowed } => {
-                        if self.window.ime_capabilities().is_some() != allowed {
+                        let Some(window) = &self.window else {
+                            continue;
+                        };
+                        if window.ime_capabilities().is_some() != allowed {
                             let ime = if allowed {
                                 let position = LogicalPosition::new(0, 0);
                                 let size = LogicalSize::new(0, 0);
@@ -1027,12 +1255,14 @@ impl WindowHandle {
                                 ImeRequest::Disable
                             };
 
-                            self.window.request_ime_update(ime).unwrap();
+                            window.request_ime_update(ime).unwrap();
                         }
                     }
                     UpdateMessage::SetImeCursorArea { position, size } => {
-                        if self
-                            .window
+                        let Some(window) = &self.window else {
+                            continue;
+                        };
+                        if window
                             .ime_capabilities()
                             .map(|caps| caps.cursor_area())
                             .unwrap_or(false)
@@ -1046,7 +1276,7 @@ impl WindowHandle {
                                 size.width * self.window_state.scale,
                                 size.height * self.window_state.scale,
                             ));
-                            self.window
+                            window
                                 .request_ime_update(ImeRequest::Update(
                                     ImeRequestData::default().with_cursor_area(position, size),
                                 ))
@@ -1054,24 +1284,32 @@ impl WindowHandle {
                         }
                     }
                     UpdateMessage::Inspect => {
-                        inspector::capture(self.window_id);
+                        if let Some(window_id) = self.window_id {
+                            inspector::capture(window_id);
+                        }
                     }
                     UpdateMessage::AddOverlay { view } => {
                         self.id.add_child(view);
                         self.id.request_all();
                     }
                     UpdateMessage::RemoveOverlay { id } => {
+                        cx.window_state.release_focus_traps(id);
                         cx.window_state.remove_view(id);
                         self.id.request_all();
                     }
                     UpdateMessage::WindowVisible(visible) => {
-                        self.window.set_visible(visible);
+                        if let Some(window) = &self.window {
+                            window.set_visible(visible);
+                        }
                     }
                     UpdateMessage::ViewTransitionAnimComplete(id) => {
                         let num_waiting =
                             id.state().borrow().num_waiting_animations.saturating_sub(1);
                         id.state().borrow_mut().num_waiting_animations = num_waiting;
                     }
+                    UpdateMessage::RenderToImage(id, scale, on_image) => {
+                        self.pending_images.push((id, scale, on_image));
+                    }
                     UpdateMessage::SetTheme(theme) => {
                         self.set_theme(theme, false);
 
@@ -1131,6 +1369,7 @@ impl WindowHandle {
             Some(CursorStyle::Move) => CursorIcon::Move,
             Some(CursorStyle::Grab) => CursorIcon::Grab,
             Some(CursorStyle::Grabbing) => CursorIcon::Grabbing,
+            Some(CursorStyle::NotAllowed) => CursorIcon::NotAllowed,
             Some(CursorStyle::ColResize) => CursorIcon::ColResize,
             Some(CursorStyle::RowResize) => CursorIcon::RowResize,
             Some(CursorStyle::WResize) => CursorIcon::WResize,
@@ -1146,19 +1385,31 @@ impl WindowHandle {
             None => CursorIcon::Default,
         };
         if cursor != self.window_state.last_cursor {
-            self.window.set_cursor(cursor.into());
+            if let Some(window) = &self.window {
+                window.set_cursor(cursor.into());
+            }
             self.window_state.last_cursor = cursor;
         }
     }
 
     fn schedule_repaint(&self) {
-        self.window.request_redraw();
+        if let Some(window) = &self.window {
+            window.request_redraw();
+        }
     }
 
     pub(crate) fn destroy(&mut self) {
         self.event(Event::WindowClosed);
         self.scope.dispose();
-        remove_window_id_mapping(&self.id, &self.window_id);
+        // Drop the messages that won't be handled anymore, along with any callbacks they hold
+        self.process_central_messages();
+        UPDATE_MESSAGES.with_borrow_mut(|msgs| msgs.remove(&self.id));
+        self.pending_images.clear();
+        if let Some(window_id) = &self.window_id {
+            remove_window_id_mapping(&self.id, window_id);
+        } else {
+            remove_headless_root(&self.id);
+        }
     }
 
     #[cfg(target_os = "macos")]
@@ -1170,7 +1421,10 @@ impl WindowHandle {
         use raw_window_handle::HasWindowHandle;
         use raw_window_handle::RawWindowHandle;
 
-        if let RawWindowHandle::AppKit(handle) = self.window.window_handle().unwrap().as_raw() {
+        let Some(window) = &self.window else {
+            return;
+        };
+        if let RawWindowHandle::AppKit(handle) = window.window_handle().unwrap().as_raw() {
             unsafe {
                 menu.show_context_menu_for_nsview(
                     handle.ns_view.as_ptr() as _,
@@ -1194,7 +1448,10 @@ impl WindowHandle {
         use raw_window_handle::HasWindowHandle;
         use raw_window_handle::RawWindowHandle;
 
-        if let RawWindowHandle::Win32(handle) = self.window.window_handle().unwrap().as_raw() {
+        let Some(window) = &self.window else {
+            return;
+        };
+        if let RawWindowHandle::Win32(handle) = window.window_handle().unwrap().as_raw() {
             unsafe {
                 menu.show_context_menu_for_hwnd(
                     isize::from(handle.hwnd),
@@ -1213,7 +1470,10 @@ impl WindowHandle {
     fn init_menu_for_windows(&self, menu: &muda::Menu) {
         use raw_window_handle::{HasWindowHandle, RawWindowHandle};
 
-        if let RawWindowHandle::Win32(handle) = self.window.window_handle().unwrap().as_raw() {
+        let Some(window) = &self.window else {
+            return;
+        };
+        if let RawWindowHandle::Win32(handle) = window.window_handle().unwrap().as_raw() {
             unsafe {
                 let menu_theme = match (
                     self.window_state.theme_overriden,
@@ -1234,7 +1494,10 @@ impl WindowHandle {
     pub(crate) fn set_menu_theme_for_windows(&self, theme: winit::window::Theme) {
         use raw_window_handle::{HasWindowHandle, RawWindowHandle};
 
-        if let RawWindowHandle::Win32(handle) = self.window.window_handle().unwrap().as_raw() {
+        let Some(window) = &self.window else {
+            return;
+        };
+        if let RawWindowHandle::Win32(handle) = window.window_handle().unwrap().as_raw() {
             if let Some(menu) = &self.window_menu {
                 unsafe {
                     let menu_theme = match theme {
@@ -1257,6 +1520,52 @@ impl WindowHandle {
         self.context_menu.set(Some((menu, pos, false)));
     }
 
+    /// The changes to the accessibility tree since the last call, or the whole tree on the
+    /// first call.
+    pub(crate) fn accessibility_update(&mut self) -> TreeUpdate {
+        self.accessibility.update(
+            self.id,
+            self.scale * self.window_state.scale,
+            self.window_state.focus,
+        )
+    }
+
+    /// Sends the changes to the accessibility tree to the platform, if an assistive technology
+    /// follows the window.
+    fn update_accessibility(&mut self) {
+        if let Some(mut adapter) = self.accessibility_adapter.take() {
+            adapter.update_if_active(|| self.accessibility_update());
+            self.accessibility_adapter = Some(adapter);
+        }
+    }
+
+    pub(crate) fn accessibility_request(&mut self, request: AccessibilityRequest) {
+        match request {
+            AccessibilityRequest::InitialTree => {
+                // Starts over so that the whole tree is sent
+                self.accessibility = AccessibilityTree::default();
+                self.update_accessibility();
+            }
+            AccessibilityRequest::Action(request) => self.accessibility_action(request),
+            AccessibilityRequest::Deactivate => {
+                self.accessibility = AccessibilityTree::default();
+            }
+        }
+    }
+
+    /// Dispatches an action requested by an assistive technology to its target view.
+    pub(crate) fn accessibility_action(&mut self, request: ActionRequest) {
+        let Some(id) = self.accessibility.view_for_node(request.target) else {
+            return;
+        };
+        set_current_view(self.id);
+        let mut cx = EventCx {
+            window_state: &mut self.window_state,
+        };
+        cx.unconditional_view_event(id, Event::Accessibility(request), true);
+        self.process_update();
+    }
+
     pub(crate) fn menu_action(&mut self, id: &MenuId) {
         set_current_view(self.id);
         if let Some(action) = self.window_state.context_menu.get(id) {
@@ -7,6 +7,7 @@ use winit::cursor::CursorIcon;
 use winit::window::Theme;
 
 use crate::{
+    action::remove_overlay,
     context::{DragState, FrameUpdate},
     event::{Event, EventListener},
     id::ViewId,
@@ -21,6 +22,9 @@ pub struct WindowState {
     /// keyboard focus
     pub(crate) focus: Option<ViewId>,
     pub(crate) prev_focus: Option<ViewId>,
+    /// The views keeping the focus inside of them, the innermost last, with the view focused
+    /// before each of them
+    pub(crate) focus_traps: Vec<(ViewId, Option<ViewId>)>,
     /// when a view is active, it gets mouse event even when the mouse is
     /// not on it
     pub(crate) active: Option<ViewId>,
@@ -34,6 +38,8 @@ pub struct WindowState {
     pub(crate) dragging: Option<DragState>,
     pub(crate) drag_start: Option<(ViewId, Point)>,
     pub(crate) dragging_over: HashSet<ViewId>,
+    /// The innermost view under the pointer accepting the dragged payload
+    pub(crate) drop_target: Option<ViewId>,
     pub(crate) screen_size_bp: ScreenSizeBp,
     pub(crate) grid_bps: GridBreakpoints,
     pub(crate) clicking: HashSet<ViewId>,
@@ -63,6 +69,7 @@ impl WindowState {
             root_view_id,
             focus: None,
             prev_focus: None,
+            focus_traps: Vec::new(),
             active: None,
             scale: 1.0,
             root_size: Size::ZERO,
@@ -72,6 +79,7 @@ impl WindowState {
             request_compute_layout: false,
             dragging: None,
             drag_start: None,
+            drop_target: None,
             dragging_over: HashSet::new(),
             clicking: HashSet::new(),
             hovered: HashSet::new(),
@@ -120,6 +128,9 @@ impl WindowState {
         let _ = taffy.remove(node);
         id.remove();
         self.dragging_over.remove(&id);
+        if self.drop_target == Some(id) {
+            self.drop_target = None;
+        }
         self.clicking.remove(&id);
         self.hovered.remove(&id);
         self.file_hovered.remove(&id);
@@ -131,6 +142,7 @@ impl WindowState {
         if self.prev_focus == Some(id) {
             self.prev_focus = None;
         }
+        self.focus_traps.retain(|(trap, _)| *trap != id);
 
         if self.active == Some(id) {
             self.active = None;
@@ -168,6 +180,38 @@ impl WindowState {
             .unwrap_or(false)
     }
 
+    /// Whether a dragged payload the view accepts is over it.
+    pub fn is_drag_over(&self, id: &ViewId) -> bool {
+        self.drop_target == Some(*id)
+    }
+
+    /// Ends the current drag and returns the dragged view.
+    ///
+    /// With a `release_location`, the dragged view animates back to its place from there,
+    /// otherwise it is dropped in place. A drag preview is removed right away.
+    pub(crate) fn end_drag(&mut self, release_location: Option<Point>) -> Option<ViewId> {
+        let dragging = self.dragging.as_mut()?;
+        let id = dragging.id;
+        let preview = dragging.preview.take();
+        match release_location {
+            Some(release_location) if preview.is_none() => {
+                dragging.released_at = Some(crate::animate::now());
+                dragging.release_location = Some(release_location);
+            }
+            _ => self.dragging = None,
+        }
+        if let Some(preview) = preview {
+            remove_overlay(preview.overlay);
+            preview.scope.dispose();
+        }
+        if let Some(target) = self.drop_target.take() {
+            target.request_style();
+        }
+        self.drag_start = None;
+        self.request_paint(id);
+        Some(id)
+    }
+
     pub fn set_root_size(&mut self, size: Size) {
         self.root_size = size;
         self.compute_layout();
@@ -277,6 +321,38 @@ impl WindowState {
         self.context_menu = actions;
     }
 
+    /// The view the keyboard focus is kept inside of, if any.
+    pub(crate) fn focus_trap(&self) -> Option<ViewId> {
+        self.focus_traps.last().map(|(trap, _)| *trap)
+    }
+
+    pub(crate) fn trap_focus(&mut self, id: ViewId) {
+        self.focus_traps.retain(|(trap, _)| *trap != id);
+        self.focus_traps.push((id, self.focus));
+    }
+
+    /// Releases the focus traps inside of `id`, and gives the focus back to the view that had
+    /// it before them if it is still in the window and the focus is still inside of them.
+    pub(crate) fn release_focus_traps(&mut self, id: ViewId) {
+        let Some(idx) = self
+            .focus_traps
+            .iter()
+            .position(|(trap, _)| trap.is_descendant_of(id))
+        else {
+            return;
+        };
+        let restore = self.focus_traps[idx].1;
+        self.focus_traps
+            .retain(|(trap, _)| !trap.is_descendant_of(id));
+
+        let focus_in_trap = self.focus.is_none_or(|focus| focus.is_descendant_of(id));
+        if let Some(restore) = restore.filter(|restore| focus_in_trap && restore.root().is_some()) {
+            let old = self.focus;
+            self.focus = Some(restore);
+            self.focus_changed(old, self.focus);
+        }
+    }
+
     pub(crate) fn focus_changed(&mut self, old: Option<ViewId>, new: Option<ViewId>) {
         if let Some(old_id) = old {
             // To remove the styles applied by the Focus selector
@@ -6,7 +6,8 @@
 use crate::ViewId;
 use peniko::kurbo::{Point, Rect};
 use std::{
-    collections::HashMap,
+    cell::RefCell,
+    collections::{HashMap, HashSet},
     sync::{Arc, OnceLock, RwLock},
 };
 use winit::{
@@ -17,6 +18,11 @@ use winit::{
 
 static WINDOW_FOR_WINDOW_AND_ROOT_IDS: OnceLock<RwLock<WindowMapping>> = OnceLock::new();
 
+thread_local! {
+    /// The roots of headless windows, which have no platform window to map them to.
+    static HEADLESS_ROOTS: RefCell<HashSet<ViewId>> = RefCell::new(HashSet::new());
+}
+
 /// Add a mapping from `root_id` -> `window_id` -> `window` for the given triple.
 pub fn store_window_id_mapping(
     root_id: ViewId,
@@ -31,6 +37,17 @@ pub fn remove_window_id_mapping(root_id: &ViewId, window_id: &WindowId) {
     with_window_map_mut(move |m| m.remove(root_id, window_id));
 }
 
+/// Add `root_id` as the root of a headless window, so the update messages of its views
+/// are delivered to it.
+pub(crate) fn store_headless_root(root_id: ViewId) {
+    HEADLESS_ROOTS.with_borrow_mut(|roots| roots.insert(root_id));
+}
+
+/// Remove `root_id` from the roots of headless windows.
+pub(crate) fn remove_headless_root(root_id: &ViewId) {
+    HEADLESS_ROOTS.with_borrow_mut(|roots| roots.remove(root_id));
+}
+
 /// Maps root-id:window-id:window triples, so a view can get its root and
 /// from that locate the window-id (if any) that it belongs to.
 #[derive(Default)]
@@ -100,7 +117,8 @@ pub fn with_window_id_and_window<F: FnOnce(&WindowId, &Arc<dyn Window>) -> T, T>
 }
 
 pub fn is_known_root(id: &ViewId) -> bool {
-    with_window_map(|map| map.window_id_for_root_view_id.contains_key(id)).unwrap_or(false)
+    HEADLESS_ROOTS.with_borrow(|roots| roots.contains(id))
+        || with_window_map(|map| map.window_id_for_root_view_id.contains_key(id)).unwrap_or(false)
 }
 
 fn with_window_map_mut<F: FnMut(&mut WindowMapping)>(mut f: F) -> bool {
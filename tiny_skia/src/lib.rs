@@ -1,25 +1,27 @@
 use anyhow::{anyhow, Result};
 use floem_renderer::swash::SwashScaler;
-use floem_renderer::text::{CacheKey, LayoutRun, SwashContent};
+use floem_renderer::text::{CacheKey, LayoutGlyph, LayoutRun, SwashContent};
 use floem_renderer::tiny_skia::{
-    self, FillRule, FilterQuality, GradientStop, LinearGradient, Mask, MaskType, Paint, Path,
-    PathBuilder, Pattern, Pixmap, RadialGradient, Shader, SpreadMode, Stroke, Transform,
+    self, FillRule, FilterQuality, GradientStop, IntRect, LinearGradient, Mask, MaskType, Paint,
+    Path, PathBuilder, Pattern, Pixmap, PremultipliedColorU8, RadialGradient, Shader, SpreadMode,
+    Stroke, Transform,
 };
-use floem_renderer::Img;
 use floem_renderer::Renderer;
+use floem_renderer::{Filter, Img};
 use peniko::kurbo::{PathEl, Size};
 use peniko::{
     color::palette,
     kurbo::{Affine, Point, Rect, Shape},
     BrushRef, Color, GradientKind,
 };
-use peniko::{BlendMode, Compose, Mix, RadialGradientPosition};
+use peniko::{BlendMode, Blob, Compose, ImageData, Mix, RadialGradientPosition};
 use resvg::tiny_skia::StrokeDash;
 use softbuffer::{Context, Surface};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::num::NonZeroU32;
 use std::rc::Rc;
+use std::sync::Arc;
 use tiny_skia::{LineCap, LineJoin};
 
 thread_local! {
@@ -110,8 +112,12 @@ struct Layer {
     transform: Affine,
     // the transform that the layer was pushed with that will be used when applying the layer
     combine_transform: Affine,
+    /// where the pixmap starts, in the coordinates the layer was pushed in
+    origin: Point,
     blend_mode: BlendMode,
     alpha: f32,
+    /// applied to the pixmap when the layer is popped
+    filter: Filter,
     window_scale: f64,
     cache_color: CacheColor,
 }
@@ -215,6 +221,175 @@ impl Layer {
     fn skia_transform(&self) -> Transform {
         skia_transform(self.transform, self.window_scale as f32)
     }
+
+    /// Draws glyphs of a line at `pos`, which already includes the translation of the current
+    /// transform but not the window scale, in `color` if it is set.
+    fn draw_glyphs(
+        &mut self,
+        glyphs: &[LayoutGlyph],
+        color: Option<Color>,
+        pos: (f32, f32),
+        scaled_clip: Option<Rect>,
+    ) {
+        // we manually handle the offset so that the glyph_x and y can be scaled by the window_scale
+        let offset = self.transform.translation();
+        let transform = self.transform * Affine::translate((-offset.x, -offset.y));
+
+        for glyph_run in glyphs {
+            let x = glyph_run.x + pos.0;
+            let y = pos.1;
+            if let Some(rect) = scaled_clip {
+                if ((x + glyph_run.w) as f64) < rect.x0 {
+                    continue;
+                } else if x as f64 > rect.x1 {
+                    break;
+                }
+            }
+
+            let glyph_x = x * self.window_scale as f32;
+            let glyph_y = y * self.window_scale as f32;
+            let font_size = glyph_run.font_size * self.window_scale as f32;
+
+            let (cache_key, new_x, new_y) = CacheKey::new(
+                glyph_run.font_id,
+                glyph_run.glyph_id,
+                font_size,
+                (glyph_x, glyph_y),
+                glyph_run.cache_key_flags,
+            );
+
+            let color = color.unwrap_or_else(|| {
+                glyph_run.color_opt.map_or(palette::css::BLACK, |c| {
+                    Color::from_rgba8(c.r(), c.g(), c.b(), c.a())
+                })
+            });
+
+            let glyph = cache_glyph(self.cache_color, cache_key, color);
+            if let Some(glyph) = glyph {
+                self.render_pixmap_direct(
+                    &glyph.pixmap,
+                    new_x as f32 + glyph.left,
+                    new_y as f32 - glyph.top,
+                    transform,
+                );
+            }
+        }
+    }
+
+    /// Draws the glyphs in `color` on a separate pixmap, which is blurred with a standard
+    /// deviation of `blur` pixels before being drawn on the layer.
+    fn draw_blurred_glyphs(
+        &mut self,
+        glyphs: &[LayoutGlyph],
+        color: Color,
+        pos: (f32, f32),
+        line_height: f32,
+        blur_radius: f32,
+    ) {
+        let (Some(first), Some(last)) = (glyphs.first(), glyphs.last()) else {
+            return;
+        };
+        let sigma = blur_radius as f64 * self.window_scale;
+        let bounds = Rect::new(
+            (first.x + pos.0) as f64,
+            (pos.1 - line_height) as f64,
+            (last.x + last.w + pos.0) as f64,
+            (pos.1 + line_height) as f64,
+        );
+        // Glyphs are drawn with the current transform without its translation, see `draw_glyphs`
+        let offset = self.transform.translation();
+        let transform = self.transform * Affine::translate((-offset.x, -offset.y));
+        let bounds = transform
+            .transform_rect_bbox(Affine::scale(self.window_scale).transform_rect_bbox(bounds));
+        // Pixels up to three standard deviations away contribute to the blur
+        let area = bounds
+            .inflate(sigma * 3.0, sigma * 3.0)
+            .round_out()
+            .intersect(Rect::new(
+                0.0,
+                0.0,
+                self.pixmap.width() as f64,
+                self.pixmap.height() as f64,
+            ));
+        let area = try_ret!(IntRect::from_ltrb(
+            area.x0 as i32,
+            area.y0 as i32,
+            area.x1 as i32,
+            area.y1 as i32,
+        ));
+
+        let pixmap = try_ret!(Pixmap::new(self.pixmap.width(), self.pixmap.height()));
+        let pixmap = std::mem::replace(&mut self.pixmap, pixmap);
+        let clip = self.clip.take();
+        self.draw_glyphs(glyphs, Some(color), pos, None);
+        let shadow = std::mem::replace(&mut self.pixmap, pixmap);
+        self.clip = clip;
+
+        let mut shadow = try_ret!(shadow.clone_rect(area));
+        blur(&mut shadow, sigma);
+        self.pixmap.draw_pixmap(
+            area.x(),
+            area.y(),
+            shadow.as_ref(),
+            &tiny_skia::PixmapPaint::default(),
+            Transform::identity(),
+            self.clip.is_some().then_some(&self.mask),
+        );
+    }
+
+    /// Replaces what was drawn inside `shape` with a blurred copy.
+    fn blur_backdrop(&mut self, shape: &impl Shape, radius: f64) {
+        if radius <= 0.0 {
+            return;
+        }
+        let sigma = radius * self.window_scale;
+        let path = try_ret!(shape_to_path(shape).and_then(|p| p.transform(self.skia_transform())));
+        let bounds = path.bounds();
+        let bounds = Rect::new(
+            bounds.left() as f64,
+            bounds.top() as f64,
+            bounds.right() as f64,
+            bounds.bottom() as f64,
+        );
+        // Pixels up to three standard deviations away contribute to the blur
+        let area = bounds
+            .inflate(sigma * 3.0, sigma * 3.0)
+            .round_out()
+            .intersect(Rect::new(
+                0.0,
+                0.0,
+                self.pixmap.width() as f64,
+                self.pixmap.height() as f64,
+            ));
+        let area = try_ret!(IntRect::from_ltrb(
+            area.x0 as i32,
+            area.y0 as i32,
+            area.x1 as i32,
+            area.y1 as i32,
+        ));
+        let mut backdrop = try_ret!(self.pixmap.clone_rect(area));
+        blur(&mut backdrop, sigma);
+
+        let paint = Paint {
+            shader: Pattern::new(
+                backdrop.as_ref(),
+                SpreadMode::Pad,
+                FilterQuality::Nearest,
+                1.0,
+                Transform::from_translate(area.x() as f32, area.y() as f32),
+            ),
+            blend_mode: tiny_skia::BlendMode::Source,
+            anti_alias: true,
+            ..Default::default()
+        };
+        self.pixmap.fill_path(
+            &path,
+            &paint,
+            FillRule::Winding,
+            Transform::identity(),
+            self.clip.is_some().then_some(&self.mask),
+        );
+    }
 }
 impl Layer {
     /// The combine transform should be the transform that the layer is pushed with without combining with the previous transform. It will be used when combining layers to offset/transform this layer into the parent with the parent transform
@@ -226,26 +401,30 @@ impl Layer {
         window_scale: f64,
         cache_color: CacheColor,
     ) -> Result<Self, anyhow::Error> {
-        let transform = Affine::IDENTITY;
         let bbox = clip.bounding_box();
         let scaled_box = Affine::scale(window_scale).transform_rect_bbox(bbox);
-        let width = scaled_box.width() as u32;
-        let height = scaled_box.height() as u32;
+        let width = scaled_box.width().ceil() as u32;
+        let height = scaled_box.height().ceil() as u32;
+        // The pixmap only covers the clip, so drawing is offset to start at its origin
+        let origin = bbox.origin();
+        let transform = Affine::translate(-origin.to_vec2());
         let mut mask = Mask::new(width, height).ok_or_else(|| anyhow!("unable to create mask"))?;
         mask.fill_path(
             &shape_to_path(clip).ok_or_else(|| anyhow!("unable to create clip shape"))?,
             FillRule::Winding,
             false,
-            Transform::from_scale(window_scale as f32, window_scale as f32),
+            skia_transform(transform, window_scale as f32),
         );
         Ok(Self {
             pixmap: Pixmap::new(width, height).ok_or_else(|| anyhow!("unable to create pixmap"))?,
             mask,
-            clip: Some(bbox),
+            clip: Some(Rect::from_origin_size(Point::ZERO, scaled_box.size())),
             transform,
-            combine_transform,
+            combine_transform: combine_transform * Affine::translate(origin.to_vec2()),
+            origin,
             blend_mode: blend.into(),
             alpha,
+            filter: Filter::default(),
             window_scale,
             cache_color,
         })
@@ -349,10 +528,7 @@ impl Layer {
                 .transform_rect_bbox(r)
         };
         let scaled_clip = clip.map(undo_transform);
-
-        // we manually handle the offset so that the glyph_x and y can be scaled by the window_scale
         let offset = self.transform.translation();
-        let transform = self.transform * Affine::translate((-offset.x, -offset.y));
 
         for line in layout {
             if let Some(rect) = scaled_clip {
@@ -364,42 +540,22 @@ impl Layer {
                     break;
                 }
             }
-            'line_loop: for glyph_run in line.glyphs {
-                let x = glyph_run.x + pos.x as f32 + offset.x as f32;
-                let y = line.line_y + pos.y as f32 + offset.y as f32;
-                if let Some(rect) = scaled_clip {
-                    if ((x + glyph_run.w) as f64) < rect.x0 {
-                        continue;
-                    } else if x as f64 > rect.x1 {
-                        break 'line_loop;
-                    }
+            let x = pos.x as f32 + offset.x as f32;
+            let y = line.line_y + pos.y as f32 + offset.y as f32;
+            for (shadow, glyphs) in line.shadows() {
+                let (x, y) = (x + shadow.offset_x, y + shadow.offset_y);
+                if shadow.blur > 0.0 {
+                    let height = line.line_height;
+                    self.draw_blurred_glyphs(glyphs, shadow.color, (x, y), height, shadow.blur);
+                } else {
+                    self.draw_glyphs(glyphs, Some(shadow.color), (x, y), scaled_clip);
                 }
+            }
+            self.draw_glyphs(line.glyphs, None, (x, y), scaled_clip);
 
-                let glyph_x = x * self.window_scale as f32;
-                let glyph_y = y * self.window_scale as f32;
-                let font_size = glyph_run.font_size * self.window_scale as f32;
-
-                let (cache_key, new_x, new_y) = CacheKey::new(
-                    glyph_run.font_id,
-                    glyph_run.glyph_id,
-                    font_size,
-                    (glyph_x, glyph_y),
-                    glyph_run.cache_key_flags,
-                );
-
-                let color = glyph_run.color_opt.map_or(palette::css::BLACK, |c| {
-                    Color::from_rgba8(c.r(), c.g(), c.b(), c.a())
-                });
-
-                let glyph = cache_glyph(self.cache_color, cache_key, color);
-                if let Some(glyph) = glyph {
-                    self.render_pixmap_direct(
-                        &glyph.pixmap,
-                        new_x as f32 + glyph.left,
-                        new_y as f32 - glyph.top,
-                        transform,
-                    );
-                }
+            for decoration in line.decorations() {
+                let path = Affine::translate(pos.to_vec2()) * decoration.path();
+                self.stroke(&path, decoration.color, &decoration.stroke());
             }
         }
     }
@@ -488,12 +644,15 @@ impl Layer {
 
 pub struct TinySkiaRenderer<W> {
     #[allow(unused)]
-    context: Context<W>,
-    surface: Surface<W, W>,
+    context: Option<Context<W>>,
+    /// The window surface the pixmap is copied to, `None` when rendering offscreen
+    surface: Option<Surface<W, W>>,
     cache_color: CacheColor,
     transform: Affine,
     window_scale: f64,
     layers: Vec<Layer>,
+    /// Whether the current frame is returned as an image by `finish` instead of presented
+    capture: bool,
 }
 
 impl<W: raw_window_handle::HasWindowHandle + raw_window_handle::HasDisplayHandle>
@@ -514,6 +673,35 @@ impl<W: raw_window_handle::HasWindowHandle + raw_window_handle::HasDisplayHandle
             )
             .map_err(|_| anyhow!("failed to resize surface"))?;
 
+        let mut renderer = Self::new_offscreen(width, height, scale, font_embolden)?;
+        renderer.context = Some(context);
+        renderer.surface = Some(surface);
+        Ok(renderer)
+    }
+
+    pub fn resize(&mut self, width: u32, height: u32, scale: f64) {
+        if width != self.layers[0].pixmap.width() || height != self.layers[0].pixmap.height() {
+            if let Some(surface) = &mut self.surface {
+                surface
+                    .resize(
+                        NonZeroU32::new(width).unwrap_or(NonZeroU32::new(1).unwrap()),
+                        NonZeroU32::new(height).unwrap_or(NonZeroU32::new(1).unwrap()),
+                    )
+                    .expect("failed to resize surface");
+            }
+            self.layers[0].pixmap = Pixmap::new(width, height).expect("unable to create pixmap");
+            self.layers[0].mask = Mask::new(width, height).expect("unable to create mask");
+        }
+        self.layers[0].window_scale = scale;
+        self.window_scale = scale;
+    }
+}
+
+impl<W> TinySkiaRenderer<W> {
+    /// Creates a renderer that paints into a [`Pixmap`] without presenting it to a window.
+    ///
+    /// The result of the last frame is available from [`TinySkiaRenderer::pixmap`].
+    pub fn new_offscreen(width: u32, height: u32, scale: f64, font_embolden: f32) -> Result<Self> {
         let pixmap =
             Pixmap::new(width, height).ok_or_else(|| anyhow!("unable to create pixmap"))?;
 
@@ -529,33 +717,26 @@ impl<W: raw_window_handle::HasWindowHandle + raw_window_handle::HasDisplayHandle
             alpha: 1.,
             transform: Affine::IDENTITY,
             combine_transform: Affine::IDENTITY,
+            origin: Point::ZERO,
             blend_mode: Mix::Normal.into(),
+            filter: Filter::default(),
             window_scale: scale,
             cache_color: CacheColor(false),
         };
         Ok(Self {
-            context,
-            surface,
+            context: None,
+            surface: None,
             transform: Affine::IDENTITY,
             window_scale: scale,
             cache_color: CacheColor(false),
             layers: vec![main_layer],
+            capture: false,
         })
     }
 
-    pub fn resize(&mut self, width: u32, height: u32, scale: f64) {
-        if width != self.layers[0].pixmap.width() || height != self.layers[0].pixmap.width() {
-            self.surface
-                .resize(
-                    NonZeroU32::new(width).unwrap_or(NonZeroU32::new(1).unwrap()),
-                    NonZeroU32::new(height).unwrap_or(NonZeroU32::new(1).unwrap()),
-                )
-                .expect("failed to resize surface");
-            self.layers[0].pixmap = Pixmap::new(width, height).expect("unable to create pixmap");
-            self.layers[0].mask = Mask::new(width, height).expect("unable to create mask");
-        }
-        self.layers[0].window_scale = scale;
-        self.window_scale = scale;
+    /// The pixels of the last finished frame, premultiplied.
+    pub fn pixmap(&self) -> &Pixmap {
+        &self.layers[0].pixmap
     }
 
     pub fn set_scale(&mut self, scale: f64) {
@@ -587,8 +768,9 @@ fn to_point(point: Point) -> tiny_skia::Point {
 impl<W: raw_window_handle::HasWindowHandle + raw_window_handle::HasDisplayHandle> Renderer
     for TinySkiaRenderer<W>
 {
-    fn begin(&mut self, _capture: bool) {
+    fn begin(&mut self, capture: bool) {
         assert!(self.layers.len() == 1);
+        self.capture = capture;
         let first_layer = self.layers.last_mut().unwrap();
         first_layer.pixmap.fill(tiny_skia::Color::WHITE);
         first_layer.clip = None;
@@ -661,8 +843,21 @@ impl<W: raw_window_handle::HasWindowHandle + raw_window_handle::HasDisplayHandle
         // Swap the cache color.
         self.cache_color = CacheColor(!self.cache_color.0);
 
-        let mut buffer = self
-            .surface
+        if self.capture {
+            let pixmap = &self.layers[0].pixmap;
+            return Some(peniko::ImageBrush::new(ImageData {
+                data: Blob::new(Arc::new(pixmap.data().to_vec())),
+                format: peniko::ImageFormat::Rgba8,
+                alpha_type: peniko::ImageAlphaType::AlphaPremultiplied,
+                width: pixmap.width(),
+                height: pixmap.height(),
+            }));
+        }
+
+        let Some(surface) = &mut self.surface else {
+            return None;
+        };
+        let mut buffer = surface
             .buffer_mut()
             .expect("failed to get the surface buffer");
 
@@ -701,18 +896,39 @@ impl<W: raw_window_handle::HasWindowHandle + raw_window_handle::HasDisplayHandle
         }
     }
 
+    fn push_filter_layer(&mut self, alpha: f32, filter: Filter, clip: &impl Shape) {
+        if let Ok(mut layer) = Layer::new(
+            Mix::Normal,
+            alpha,
+            Affine::IDENTITY,
+            clip,
+            self.window_scale,
+            self.cache_color,
+        ) {
+            layer.filter = filter;
+            self.layers.push(layer);
+        }
+    }
+
     fn pop_layer(&mut self) {
         if self.layers.len() <= 1 {
             // Don't pop the main layer
             return;
         }
 
-        let layer = self.layers.pop().unwrap();
+        let mut layer = self.layers.pop().unwrap();
         let parent = self.layers.last_mut().unwrap();
 
+        if !layer.filter.is_identity() {
+            apply_filter(&mut layer.pixmap, layer.filter, layer.window_scale);
+        }
         apply_layer(&layer, parent);
     }
 
+    fn blur_backdrop(&mut self, shape: &impl Shape, radius: f64) {
+        self.layers.last_mut().unwrap().blur_backdrop(shape, radius);
+    }
+
     fn debug_info(&self) -> String {
         "name: tiny_skia".into()
     }
@@ -974,7 +1190,7 @@ fn apply_layer(layer: &Layer, parent: &mut Layer) {
             )
         }
     }
-    parent.transform *= layer.transform;
+    parent.transform *= Affine::translate(layer.origin.to_vec2()) * layer.transform;
 }
 
 fn skia_transform(affine: Affine, window_scale: f32) -> Transform {
@@ -1006,3 +1222,327 @@ fn skia_transform_with_scaled_translation(
     )
     .post_scale(render_scale, render_scale)
 }
+
+/// Applies `filter` to premultiplied pixels. The blur is scaled by `window_scale`.
+fn apply_filter(pixmap: &mut Pixmap, filter: Filter, window_scale: f64) {
+    if filter.blur > 0.0 {
+        blur(pixmap, filter.blur * window_scale);
+    }
+    let brightness = filter.brightness.max(0.0) as f32;
+    let grayscale = filter.grayscale.clamp(0.0, 1.0) as f32;
+    if brightness == 1.0 && grayscale == 0.0 {
+        return;
+    }
+    for pixel in pixmap.pixels_mut() {
+        let a = pixel.alpha();
+        if a == 0 {
+            continue;
+        }
+        let r = pixel.red() as f32 * brightness;
+        let g = pixel.green() as f32 * brightness;
+        let b = pixel.blue() as f32 * brightness;
+        // The luminance weights of the CSS `grayscale()` filter
+        let luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        let channel = |c: f32| {
+            (c + (luminance - c) * grayscale)
+                .round()
+                .clamp(0.0, a as f32) as u8
+        };
+        if let Some(color) = PremultipliedColorU8::from_rgba(channel(r), channel(g), channel(b), a)
+        {
+            *pixel = color;
+        }
+    }
+}
+
+/// Approximates a gaussian blur with a standard deviation of `sigma` pixels with three box
+/// blurs. Pixels outside of the pixmap are transparent.
+fn blur(pixmap: &mut Pixmap, sigma: f64) {
+    let width = pixmap.width() as usize;
+    let height = pixmap.height() as usize;
+    for size in box_sizes(sigma, 3) {
+        let radius = size / 2;
+        if radius == 0 {
+            continue;
+        }
+        box_blur(pixmap.data_mut(), width, height, radius, true);
+        box_blur(pixmap.data_mut(), width, height, radius, false);
+    }
+}
+
+/// The odd widths of `n` box blurs that together approximate a gaussian blur.
+fn box_sizes(sigma: f64, n: usize) -> Vec<usize> {
+    let n_f = n as f64;
+    let ideal = (12.0 * sigma * sigma / n_f + 1.0).sqrt();
+    let mut lower = ideal.floor() as usize;
+    if lower % 2 == 0 {
+        lower = lower.saturating_sub(1);
+    }
+    let lower_f = lower as f64;
+    let m = (12.0 * sigma * sigma - n_f * lower_f * lower_f - 4.0 * n_f * lower_f - 3.0 * n_f)
+        / (-4.0 * lower_f - 4.0);
+    let m = m.round().max(0.0) as usize;
+    (0..n)
+        .map(|i| if i < m { lower } else { lower + 2 })
+        .collect()
+}
+
+/// Averages each RGBA pixel with the `radius` pixels on each side of it, along the rows or
+/// the columns.
+fn box_blur(data: &mut [u8], width: usize, height: usize, radius: usize, horizontal: bool) {
+    let (lines, len, step, line_step) = if horizontal {
+        (height, width, 4, width * 4)
+    } else {
+        (width, height, width * 4, 4)
+    };
+    let size = (2 * radius + 1) as u32;
+    let mut line = vec![[0u8; 4]; len];
+    for l in 0..lines {
+        let start = l * line_step;
+        for (i, pixel) in line.iter_mut().enumerate() {
+            let at = start + i * step;
+            pixel.copy_from_slice(&data[at..at + 4]);
+        }
+        let mut sum = [0u32; 4];
+        let add = |sum: &mut [u32; 4], pixel: [u8; 4], sign: bool| {
+            for (s, c) in sum.iter_mut().zip(pixel) {
+                if sign {
+                    *s += c as u32;
+                } else {
+                    *s -= c as u32;
+                }
+            }
+        };
+        for pixel in line.iter().take(radius) {
+            add(&mut sum, *pixel, true);
+        }
+        for i in 0..len {
+            if i + radius < len {
+                add(&mut sum, line[i + radius], true);
+            }
+            if i > radius {
+                add(&mut sum, line[i - radius - 1], false);
+            }
+            let at = start + i * step;
+            for (out, s) in data[at..at + 4].iter_mut().zip(sum) {
+                *out = ((s + size / 2) / size) as u8;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A renderer target for tests, which only render offscreen.
+    struct NoWindow;
+
+    impl raw_window_handle::HasWindowHandle for NoWindow {
+        fn window_handle(
+            &self,
+        ) -> Result<raw_window_handle::WindowHandle<'_>, raw_window_handle::HandleError> {
+            Err(raw_window_handle::HandleError::Unavailable)
+        }
+    }
+
+    impl raw_window_handle::HasDisplayHandle for NoWindow {
+        fn display_handle(
+            &self,
+        ) -> Result<raw_window_handle::DisplayHandle<'_>, raw_window_handle::HandleError> {
+            Err(raw_window_handle::HandleError::Unavailable)
+        }
+    }
+
+    /// Paints a frame of 40x40 logical pixels at `scale` and returns its pixels.
+    fn render(scale: f64, paint: impl FnOnce(&mut TinySkiaRenderer<NoWindow>)) -> Pixmap {
+        let size = (40.0 * scale) as u32;
+        let mut renderer = TinySkiaRenderer::new_offscreen(size, size, scale, 0.0).unwrap();
+        renderer.begin(false);
+        paint(&mut renderer);
+        renderer.finish();
+        renderer.pixmap().clone()
+    }
+
+    fn rgb(pixmap: &Pixmap, x: u32, y: u32) -> (u8, u8, u8) {
+        let pixel = pixmap.pixel(x, y).unwrap();
+        (pixel.red(), pixel.green(), pixel.blue())
+    }
+
+    #[test]
+    fn clip_layers_render_like_a_clip() {
+        let clip = Rect::new(0.0, 0.0, 20.0, 20.0);
+        let square = Rect::new(10.0, 10.0, 30.0, 30.0);
+        for scale in [1.0, 2.0] {
+            let clipped = render(scale, |r| {
+                r.clip(&clip);
+                r.fill(&square, palette::css::RED, 0.0);
+            });
+            let layered = render(scale, |r| {
+                r.push_layer(Mix::Normal, 1.0, Affine::IDENTITY, &clip);
+                r.fill(&square, palette::css::RED, 0.0);
+                r.pop_layer();
+            });
+            assert!(clipped.data() == layered.data(), "scale {scale}");
+        }
+    }
+
+    #[test]
+    fn layers_with_an_offset_clip_follow_the_transform() {
+        let clip = Rect::new(5.0, 5.0, 15.0, 15.0);
+        let paint = |r: &mut TinySkiaRenderer<NoWindow>| {
+            r.fill(&Rect::new(0.0, 0.0, 30.0, 30.0), palette::css::RED, 0.0);
+            r.set_transform(Affine::translate((12.0, 12.0)));
+            r.fill(&Rect::new(8.0, 8.0, 12.0, 12.0), palette::css::BLUE, 0.0);
+        };
+        for scale in [1.0, 2.0] {
+            let clipped = render(scale, |r| {
+                r.set_transform(Affine::translate((10.0, 10.0)));
+                r.clip(&clip);
+                paint(r);
+                r.clear_clip();
+                r.fill(&Rect::new(0.0, 0.0, 2.0, 2.0), palette::css::LIME, 0.0);
+            });
+            let layered = render(scale, |r| {
+                r.set_transform(Affine::translate((10.0, 10.0)));
+                r.push_layer(Mix::Normal, 1.0, Affine::IDENTITY, &clip);
+                paint(r);
+                r.pop_layer();
+                // the transform set in the layer carries on after it
+                r.fill(&Rect::new(0.0, 0.0, 2.0, 2.0), palette::css::LIME, 0.0);
+            });
+            assert!(clipped.data() == layered.data(), "scale {scale}");
+
+            // the clip covers 15..25 in the window
+            let at = |x: f64, y: f64| rgb(&layered, (x * scale) as u32, (y * scale) as u32);
+            assert_eq!(at(16.0, 16.0), (255, 0, 0));
+            assert_eq!(at(22.0, 22.0), (0, 0, 255));
+            assert_eq!(at(14.0, 20.0), (255, 255, 255));
+            assert_eq!(at(26.0, 20.0), (255, 255, 255));
+            assert_eq!(at(12.5, 12.5), (0, 255, 0));
+        }
+    }
+
+    #[test]
+    fn blended_layers_mix_with_the_pixels_below() {
+        let pixmap = render(1.0, |r| {
+            r.fill(&Rect::new(0.0, 0.0, 40.0, 40.0), palette::css::RED, 0.0);
+            r.push_layer(
+                Mix::Multiply,
+                1.0,
+                Affine::IDENTITY,
+                &Rect::new(10.0, 10.0, 30.0, 30.0),
+            );
+            r.fill(
+                &Rect::new(0.0, 0.0, 40.0, 40.0),
+                Color::from_rgb8(128, 128, 128),
+                0.0,
+            );
+            r.pop_layer();
+        });
+        let (red, green, blue) = rgb(&pixmap, 20, 20);
+        assert!(red.abs_diff(128) <= 1 && green == 0 && blue == 0);
+        assert_eq!(rgb(&pixmap, 5, 5), (255, 0, 0));
+        assert_eq!(rgb(&pixmap, 35, 20), (255, 0, 0));
+    }
+
+    fn filled(width: u32, height: u32, color: tiny_skia::Color) -> Pixmap {
+        let mut pixmap = Pixmap::new(width, height).unwrap();
+        pixmap.fill(color);
+        pixmap
+    }
+
+    #[test]
+    fn box_sizes_approximate_the_gaussian_variance() {
+        assert_eq!(box_sizes(0.0, 3), vec![1, 1, 1]);
+        for sigma in [1.0, 2.0, 5.0, 10.0] {
+            let sizes = box_sizes(sigma, 3);
+            assert_eq!(sizes.len(), 3);
+            assert!(sizes.iter().all(|size| size % 2 == 1), "{sizes:?}");
+            // The variance of a box blur of width `w` is `(w² - 1) / 12`
+            let variance: f64 = sizes
+                .iter()
+                .map(|&size| (size * size - 1) as f64 / 12.0)
+                .sum();
+            let expected = sigma * sigma;
+            assert!(
+                (variance - expected).abs() <= expected * 0.35,
+                "sigma {sigma}: {sizes:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn box_blur_averages_neighbors_along_one_axis() {
+        let line = |value: u8| [value; 4];
+        let mut row: Vec<u8> = [0, 0, 30, 0, 0].into_iter().flat_map(line).collect();
+        box_blur(&mut row, 5, 1, 1, true);
+        let expected: Vec<u8> = [0, 10, 10, 10, 0].into_iter().flat_map(line).collect();
+        assert_eq!(row, expected);
+
+        let mut column: Vec<u8> = [0, 0, 30, 0, 0].into_iter().flat_map(line).collect();
+        box_blur(&mut column, 1, 5, 1, false);
+        assert_eq!(column, expected);
+
+        // Pixels outside of the pixmap are transparent
+        let mut row: Vec<u8> = [30, 30].into_iter().flat_map(line).collect();
+        box_blur(&mut row, 2, 1, 1, true);
+        assert_eq!(row, [20; 8]);
+    }
+
+    #[test]
+    fn apply_filter_changes_brightness_and_saturation() {
+        let color = tiny_skia::Color::from_rgba8(200, 100, 50, 255);
+
+        let mut pixmap = filled(1, 1, color);
+        apply_filter(&mut pixmap, Filter::default(), 1.0);
+        assert_eq!(pixmap.data(), filled(1, 1, color).data());
+
+        let mut pixmap = filled(1, 1, color);
+        let filter = Filter {
+            brightness: 0.5,
+            ..Filter::default()
+        };
+        apply_filter(&mut pixmap, filter, 1.0);
+        let pixel = pixmap.pixels()[0];
+        assert_eq!(
+            (pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()),
+            (100, 50, 25, 255)
+        );
+
+        let mut pixmap = filled(1, 1, color);
+        let filter = Filter {
+            grayscale: 1.0,
+            ..Filter::default()
+        };
+        apply_filter(&mut pixmap, filter, 1.0);
+        let pixel = pixmap.pixels()[0];
+        assert_eq!((pixel.red(), pixel.green(), pixel.blue()), (118, 118, 118));
+    }
+
+    #[test]
+    fn apply_filter_blurs_by_the_window_scale() {
+        let dot = || {
+            let mut pixmap = Pixmap::new(9, 9).unwrap();
+            pixmap.pixels_mut()[4 * 9 + 4] =
+                PremultipliedColorU8::from_rgba(255, 255, 255, 255).unwrap();
+            pixmap
+        };
+        let filter = Filter {
+            blur: 1.0,
+            ..Filter::default()
+        };
+
+        let mut pixmap = dot();
+        apply_filter(&mut pixmap, filter, 1.0);
+        let center = pixmap.pixels()[4 * 9 + 4].alpha();
+        assert!(center < 255);
+        assert!(pixmap.pixels()[4 * 9 + 5].alpha() > 0);
+        assert_eq!(pixmap.pixels()[0].alpha(), 0);
+
+        // A larger window scale spreads the dot further
+        let mut scaled = dot();
+        apply_filter(&mut scaled, filter, 2.0);
+        assert!(scaled.pixels()[4 * 9 + 4].alpha() < center);
+    }
+}
@@ -8,14 +8,14 @@ use anyhow::Result;
 use floem_renderer::gpu_resources::GpuResources;
 use floem_renderer::text::fontdb::ID;
 use floem_renderer::text::{LayoutGlyph, LayoutRun, FONT_SYSTEM};
-use floem_renderer::{Img, Renderer};
-use peniko::kurbo::Size;
+use floem_renderer::{Filter, Img, Renderer};
+use peniko::kurbo::{BezPath, Size};
 use peniko::{
     color::palette,
     kurbo::{Affine, Point, Rect, Shape},
     Blob, BrushRef, Color,
 };
-use peniko::{Compose, Fill, ImageAlphaType, ImageData, Mix};
+use peniko::{BlendMode, Compose, Fill, ImageAlphaType, ImageData, Mix};
 use vello::kurbo::Stroke;
 use vello::util::RenderSurface;
 use vello::wgpu::Device;
@@ -36,6 +36,15 @@ pub struct VelloRenderer {
     capture: bool,
     font_cache: HashMap<ID, vello::peniko::FontData>,
     adapter: Adapter,
+    /// The filters of the pushed layers, `None` for layers without a filter.
+    layer_filters: Vec<Option<LayerFilter>>,
+}
+
+/// A filter applied when its layer is popped.
+struct LayerFilter {
+    filter: Filter,
+    clip: BezPath,
+    transform: Affine,
 }
 
 impl VelloRenderer {
@@ -142,6 +151,7 @@ impl VelloRenderer {
             capture: false,
             font_cache: HashMap::new(),
             adapter,
+            layer_filters: Vec::new(),
         })
     }
 
@@ -191,6 +201,7 @@ impl VelloRenderer {
 
 impl Renderer for VelloRenderer {
     fn begin(&mut self, capture: bool) {
+        self.layer_filters.clear();
         if self.capture == capture {
             self.scene.reset();
         } else {
@@ -279,12 +290,50 @@ impl Renderer for VelloRenderer {
             self.transform.then_scale(self.window_scale) * transform,
             clip,
         );
+        self.layer_filters.push(None);
+    }
+
+    fn push_filter_layer(&mut self, alpha: f32, filter: Filter, clip: &impl Shape) {
+        let transform = self.transform.then_scale(self.window_scale);
+        self.scene.push_layer(Mix::Normal, alpha, transform, clip);
+        // Vello can't blur the contents of a layer, so the blur of the filter is ignored
+        self.layer_filters.push(Some(LayerFilter {
+            filter,
+            clip: clip.to_path(0.1),
+            transform,
+        }));
     }
 
     fn pop_layer(&mut self) {
+        if let Some(Some(LayerFilter {
+            filter,
+            clip,
+            transform,
+        })) = self.layer_filters.pop()
+        {
+            // The color filters are blended over the contents of the layer, keeping its alpha.
+            // Multiplying by a gray of `brightness` darkens, and color dodge with a gray of
+            // `1 - 1 / brightness` divides by `1 / brightness`, which brightens.
+            let brightness = filter.brightness.max(0.0);
+            if brightness < 1.0 {
+                self.fill_over_layer(Mix::Multiply, 1.0, gray(brightness), &clip, transform);
+            } else if brightness > 1.0 {
+                let dodge = gray(1.0 - 1.0 / brightness);
+                self.fill_over_layer(Mix::ColorDodge, 1.0, dodge, &clip, transform);
+            }
+            // Taking the saturation of a gray removes the colors
+            if filter.grayscale > 0.0 {
+                let amount = filter.grayscale.min(1.0) as f32;
+                self.fill_over_layer(Mix::Saturation, amount, gray(0.5), &clip, transform);
+            }
+        }
         self.scene.pop_layer();
     }
 
+    fn blur_backdrop(&mut self, _shape: &impl Shape, _radius: f64) {
+        // Vello can't read back what was drawn while building the scene
+    }
+
     fn draw_text_with_layout<'b>(
         &mut self,
         layout: impl Iterator<Item = LayoutRun<'b>>,
@@ -297,46 +346,23 @@ impl Renderer for VelloRenderer {
             .then_scale(self.window_scale);
 
         for line in layout {
-            let mut current_run: Option<GlyphRun> = None;
-
-            for glyph in line.glyphs {
-                let color = glyph.color_opt.map_or(palette::css::BLACK, |c| {
-                    Color::from_rgba8(c.r(), c.g(), c.b(), c.a())
-                });
-                let font_size = glyph.font_size;
-                let font_id = glyph.font_id;
-                let metadata = glyph.metadata;
-
-                if current_run.as_ref().is_none_or(|run| {
-                    run.color != color
-                        || run.font_size != font_size
-                        || run.font_id != font_id
-                        || run.metadata != metadata
-                }) {
-                    if let Some(run) = current_run.take() {
-                        self.draw_glyph_run(
-                            run,
-                            transform.pre_translate((0., line.line_y.into()).into()),
-                        );
-                    }
-                    current_run = Some(GlyphRun {
-                        color,
-                        font_size,
-                        font_id,
-                        metadata,
-                        glyphs: Vec::new(),
-                    });
-                }
-
-                if let Some(run) = &mut current_run {
-                    run.glyphs.push(glyph);
-                }
+            let line_transform = transform.pre_translate((0., line.line_y.into()).into());
+            for (shadow, glyphs) in line.shadows() {
+                let offset = (shadow.offset_x as f64, shadow.offset_y as f64);
+                self.draw_glyphs(
+                    glyphs,
+                    Some(shadow.color),
+                    line_transform.pre_translate(offset.into()),
+                );
             }
-
-            if let Some(run) = current_run.take() {
-                self.draw_glyph_run(
-                    run,
-                    transform.pre_translate((0., line.line_y.into()).into()),
+            self.draw_glyphs(line.glyphs, None, line_transform);
+            for decoration in line.decorations() {
+                self.scene.stroke(
+                    &decoration.stroke(),
+                    transform,
+                    decoration.color,
+                    None,
+                    &decoration.path(),
                 );
             }
         }
@@ -599,6 +625,31 @@ impl VelloRenderer {
             height,
         }))
     }
+
+    /// Fills `clip` with `color` over the contents of the current layer, blending with `mix`
+    /// and keeping the alpha of the layer.
+    fn fill_over_layer(
+        &mut self,
+        mix: Mix,
+        alpha: f32,
+        color: Color,
+        clip: &BezPath,
+        transform: Affine,
+    ) {
+        self.scene.push_layer(
+            BlendMode::new(mix, Compose::SrcAtop),
+            alpha,
+            transform,
+            clip,
+        );
+        self.scene.fill(Fill::NonZero, transform, color, None, clip);
+        self.scene.pop_layer();
+    }
+}
+
+fn gray(value: f64) -> Color {
+    let value = value.clamp(0.0, 1.0) as f32;
+    Color::new([value, value, value, 1.0])
 }
 
 fn common_alpha_mask_scene(
@@ -674,6 +725,48 @@ impl VelloRenderer {
         })
     }
 
+    /// Draws glyphs grouped in runs of the same color and font, in `color` if it is set.
+    fn draw_glyphs(&mut self, glyphs: &[LayoutGlyph], color: Option<Color>, transform: Affine) {
+        let mut current_run: Option<GlyphRun> = None;
+
+        for glyph in glyphs {
+            let color = color.unwrap_or_else(|| {
+                glyph.color_opt.map_or(palette::css::BLACK, |c| {
+                    Color::from_rgba8(c.r(), c.g(), c.b(), c.a())
+                })
+            });
+            let font_size = glyph.font_size;
+            let font_id = glyph.font_id;
+            let metadata = glyph.metadata;
+
+            if current_run.as_ref().is_none_or(|run| {
+                run.color != color
+                    || run.font_size != font_size
+                    || run.font_id != font_id
+                    || run.metadata != metadata
+            }) {
+                if let Some(run) = current_run.take() {
+                    self.draw_glyph_run(run, transform);
+                }
+                current_run = Some(GlyphRun {
+                    color,
+                    font_size,
+                    font_id,
+                    metadata,
+                    glyphs: Vec::new(),
+                });
+            }
+
+            if let Some(run) = &mut current_run {
+                run.glyphs.push(glyph);
+            }
+        }
+
+        if let Some(run) = current_run.take() {
+            self.draw_glyph_run(run, transform);
+        }
+    }
+
     fn draw_glyph_run(&mut self, run: GlyphRun, transform: Affine) {
         let font = self.get_font(run.font_id);
         self.scene
@@ -5,11 +5,11 @@ use std::sync::Arc;
 use anyhow::Result;
 use floem_renderer::gpu_resources::GpuResources;
 use floem_renderer::swash::SwashScaler;
-use floem_renderer::text::{CacheKey, LayoutRun};
-use floem_renderer::{tiny_skia, Img, Renderer};
+use floem_renderer::text::{CacheKey, LayoutGlyph, LayoutRun};
+use floem_renderer::{tiny_skia, Filter, Img, Renderer};
 use floem_vger_rs::{Image, PaintIndex, PixelFormat, Vger};
 use image::EncodableLayout;
-use peniko::kurbo::{Size, Stroke};
+use peniko::kurbo::{dash, BezPath, Size, Stroke};
 use peniko::{
     color::palette,
     kurbo::{Affine, Point, Rect, Shape},
@@ -31,6 +31,8 @@ pub struct VgerRenderer {
     scale: f64,
     transform: Affine,
     clip: Option<Rect>,
+    /// The alpha of each pushed layer, applied to the colors drawn in them.
+    layer_alphas: Vec<f32>,
     capture: bool,
     swash_scaler: SwashScaler,
     adapter: Adapter,
@@ -103,6 +105,7 @@ impl VgerRenderer {
             config,
             transform: Affine::IDENTITY,
             clip: None,
+            layer_alphas: Vec::new(),
             capture: false,
             swash_scaler: SwashScaler::new(font_embolden),
             adapter,
@@ -132,16 +135,25 @@ impl VgerRenderer {
 }
 
 impl VgerRenderer {
+    /// The product of the alphas of the pushed layers.
+    fn alpha(&self) -> f32 {
+        self.layer_alphas.iter().product()
+    }
+
     fn brush_to_paint<'b>(&mut self, brush: impl Into<BrushRef<'b>>) -> Option<PaintIndex> {
+        // Vger can't composite layers, so their alpha is applied to each color instead. This
+        // is only exact when the shapes drawn in a layer don't overlap.
+        let alpha = self.alpha();
+        let faded = |color: Color| vger_color(color.multiply_alpha(alpha));
         let paint = match brush.into() {
-            BrushRef::Solid(color) => self.vger.color_paint(vger_color(color)),
+            BrushRef::Solid(color) => self.vger.color_paint(faded(color)),
             BrushRef::Gradient(g) => match g.kind {
                 GradientKind::Linear(LinearGradientPosition { start, end }) => {
                     let mut stops = g.stops.iter();
                     let first_stop = stops.next()?;
                     let second_stop = stops.next()?;
-                    let inner_color = vger_color(first_stop.color.to_alpha_color());
-                    let outer_color = vger_color(second_stop.color.to_alpha_color());
+                    let inner_color = faded(first_stop.color.to_alpha_color());
+                    let outer_color = faded(second_stop.color.to_alpha_color());
                     let start = floem_vger_rs::defs::LocalPoint::new(
                         start.x as f32 * first_stop.offset,
                         start.y as f32 * first_stop.offset,
@@ -281,6 +293,68 @@ impl VgerRenderer {
         }))
         // RgbaImage::from_raw(self.config.width, height, cropped_buffer).map(DynamicImage::ImageRgba8)
     }
+
+    /// Draws glyphs of a line at `pos` in the final coordinate space, in `color` if it is set.
+    fn draw_glyphs(
+        &mut self,
+        glyphs: &[LayoutGlyph],
+        color: Option<Color>,
+        pos: Point,
+        scale: f64,
+    ) {
+        let clip = self.clip;
+        for glyph_run in glyphs {
+            let x = pos.x + (glyph_run.x as f64) * scale;
+            let y = pos.y;
+
+            if let Some(rect) = clip {
+                let w = (glyph_run.w as f64) * scale;
+                if x + w < rect.x0 {
+                    continue;
+                }
+                if x > rect.x1 {
+                    break;
+                }
+            }
+
+            // if glyph_run.is_tab {
+            //     continue;
+            // }
+
+            let color = color.unwrap_or_else(|| match glyph_run.color_opt {
+                Some(c) => Color::from_rgba8(c.r(), c.g(), c.b(), c.a()),
+                None => palette::css::BLACK,
+            });
+            if let Some(paint) = self.brush_to_paint(color) {
+                let glyph_x = x as f32;
+                let glyph_y = y.round() as f32;
+                let font_size = (glyph_run.font_size * (scale as f32)).round() as u32;
+                let (cache_key, new_x, new_y) = CacheKey::new(
+                    glyph_run.font_id,
+                    glyph_run.glyph_id,
+                    font_size as f32,
+                    (glyph_x, glyph_y),
+                    glyph_run.cache_key_flags,
+                );
+
+                let glyph_x = new_x as f32;
+                let glyph_y = new_y as f32;
+                self.vger.render_glyph(
+                    glyph_x,
+                    glyph_y,
+                    glyph_run.font_id,
+                    glyph_run.glyph_id,
+                    font_size,
+                    (cache_key.x_bin, cache_key.y_bin),
+                    || {
+                        let image = self.swash_scaler.get_image(cache_key);
+                        image.unwrap_or_default()
+                    },
+                    paint,
+                );
+            }
+        }
+    }
 }
 
 impl Renderer for VgerRenderer {
@@ -299,6 +373,7 @@ impl Renderer for VgerRenderer {
         }
 
         self.transform = Affine::IDENTITY;
+        self.layer_alphas.clear();
         self.vger.begin(
             self.config.width as f32,
             self.config.height as f32,
@@ -488,8 +563,8 @@ impl Renderer for VgerRenderer {
         // Drawing text happens in the final coordinate space,
         // i.e. with all transforms and the window scale factor (self.scale) being applied.
         let coeffs = self.transform.as_coeffs();
-        let pos: Point = pos.into();
-        let pos = Affine::scale(self.scale) * self.transform * pos;
+        let origin: Point = pos.into();
+        let pos = Affine::scale(self.scale) * self.transform * origin;
         // This assumes that the text is axis-aligned.
         // We currently make this assumption in the entirety of this module.
         let scale = (coeffs[0] + coeffs[3]) / 2. * self.scale;
@@ -510,56 +585,25 @@ impl Renderer for VgerRenderer {
                 }
             }
 
-            'line_loop: for glyph_run in line.glyphs {
-                let x = pos.x + (glyph_run.x as f64) * scale;
-                let y = pos.y + (line.line_y as f64) * scale;
-
-                if let Some(rect) = clip {
-                    let w = (glyph_run.w as f64) * scale;
-                    if x + w < rect.x0 {
-                        continue;
-                    }
-                    if x > rect.x1 {
-                        break 'line_loop;
-                    }
-                }
-
-                // if glyph_run.is_tab {
-                //     continue;
-                // }
-
-                let color = match glyph_run.color_opt {
-                    Some(c) => Color::from_rgba8(c.r(), c.g(), c.b(), c.a()),
-                    None => palette::css::BLACK,
+            let y = pos.y + (line.line_y as f64) * scale;
+            for (shadow, glyphs) in line.shadows() {
+                let x = pos.x + shadow.offset_x as f64 * scale;
+                let y = y + shadow.offset_y as f64 * scale;
+                self.draw_glyphs(glyphs, Some(shadow.color), Point::new(x, y), scale);
+            }
+            self.draw_glyphs(line.glyphs, None, Point::new(pos.x, y), scale);
+
+            for decoration in line.decorations() {
+                // Vger strokes can't be dashed, so the dashes are stroked as separate segments
+                let stroke = decoration.stroke();
+                let path = decoration.path();
+                let path: BezPath = if stroke.dash_pattern.is_empty() {
+                    path
+                } else {
+                    dash(path.into_iter(), stroke.dash_offset, &stroke.dash_pattern).collect()
                 };
-                if let Some(paint) = self.brush_to_paint(color) {
-                    let glyph_x = x as f32;
-                    let glyph_y = y.round() as f32;
-                    let font_size = (glyph_run.font_size * (scale as f32)).round() as u32;
-                    let (cache_key, new_x, new_y) = CacheKey::new(
-                        glyph_run.font_id,
-                        glyph_run.glyph_id,
-                        font_size as f32,
-                        (glyph_x, glyph_y),
-                        glyph_run.cache_key_flags,
-                    );
-
-                    let glyph_x = new_x as f32;
-                    let glyph_y = new_y as f32;
-                    self.vger.render_glyph(
-                        glyph_x,
-                        glyph_y,
-                        glyph_run.font_id,
-                        glyph_run.glyph_id,
-                        font_size,
-                        (cache_key.x_bin, cache_key.y_bin),
-                        || {
-                            let image = self.swash_scaler.get_image(cache_key);
-                            image.unwrap_or_default()
-                        },
-                        paint,
-                    );
-                }
+                let path = Affine::translate(origin.to_vec2()) * path;
+                self.stroke(&path, decoration.color, &stroke);
             }
         }
     }
@@ -717,13 +761,20 @@ impl Renderer for VgerRenderer {
     fn push_layer(
         &mut self,
         _blend: impl Into<peniko::BlendMode>,
-        _alpha: f32,
+        alpha: f32,
         _transform: Affine,
         _clip: &impl Shape,
     ) {
+        self.layer_alphas.push(alpha.clamp(0.0, 1.0));
+    }
+
+    fn push_filter_layer(&mut self, alpha: f32, _filter: Filter, clip: &impl Shape) {
+        self.push_layer(peniko::BlendMode::default(), alpha, self.transform, clip);
     }
 
-    fn pop_layer(&mut self) {}
+    fn pop_layer(&mut self) {
+        self.layer_alphas.pop();
+    }
 
     fn debug_info(&self) -> String {
         use std::fmt::Write;